@@ -1,9 +1,11 @@
 // crates/ycg_cli/src/main.rs
 use anyhow::{Context, Result, anyhow};
-use clap::{Parser, Subcommand};
+use clap::{Args, Parser, Subcommand};
 use std::path::PathBuf;
 use std::process::Command;
-use ycg_core::{LevelOfDetail, YcgConfig, run_scip_conversion};
+use ycg_core::event_log::EventLog;
+use ycg_core::model::{NameFilterConfig, SignatureStyle, TestFilesMode, YamlStyle};
+use ycg_core::{LevelOfDetail, YcgConfig, run_dry_run, run_scip_conversion};
 
 #[derive(Parser)]
 #[command(author, version, about = "YAML Code Graph Transcoder")]
@@ -26,91 +28,430 @@ enum Commands {
     },
 
     /// Generate YAML graph from existing SCIP index
-    Generate {
-        /// Caminho para o arquivo de índice SCIP (Input)
+    Generate(Box<GenerateArgs>),
+
+    /// Split a generated YAML graph into self-contained chunks under a token limit (for RAG)
+    Chunk {
+        /// YAML graph produced by `generate` (flat or compact)
         #[arg(short, long)]
         input: PathBuf,
 
-        /// Caminho para o arquivo YAML de saída (Output)
+        /// Maximum tokens per chunk
+        #[arg(long, default_value_t = 2000)]
+        max_tokens: usize,
+
+        /// Directory receiving chunk_0000.yaml, chunk_0001.yaml, ...
+        #[arg(short, long, default_value = "chunks")]
+        output_dir: PathBuf,
+    },
+
+    /// Check that a YAML graph (flat or compact) conforms to the YCG model
+    Validate {
+        /// YAML graph to validate
         #[arg(short, long)]
-        output: Option<PathBuf>,
+        input: PathBuf,
+    },
 
-        /// Caminho raiz do projeto fonte
+    /// Print a summary of a YAML graph: definitions per kind, edges per type,
+    /// fan-in / fan-out, most referenced symbols, files and estimated tokens
+    Stats {
+        /// YAML graph produced by `generate` (flat or compact)
         #[arg(short, long)]
-        root: Option<PathBuf>,
+        input: PathBuf,
+    },
 
-        /// Nível de Detalhe (0=Low, 1=Medium, 2=High)
-        #[arg(short, long, default_value_t = 1)]
-        lod: u8,
+    /// Report structural problems found in a SCIP index
+    Analyze {
+        /// SCIP index to analyze
+        #[arg(short, long)]
+        input: PathBuf,
 
-        /// Ativa modo compacto (Lista de Adjacência)
-        #[arg(short, long, default_value_t = false)]
-        compact: bool,
+        /// Report circular imports between files, with the import statements behind each hop
+        #[arg(long)]
+        import_cycles: bool,
 
-        /// Remove framework boilerplate patterns (DI constructors, decorators)
+        /// Report imports whose symbol is never used in the importing file
         #[arg(long)]
-        ignore_framework_noise: bool,
+        unused_imports: bool,
 
-        /// Output format: yaml (default) or adhoc (compact pipe-separated format)
-        #[arg(long, value_name = "FORMAT")]
-        output_format: Option<String>,
+        /// Report edges crossing into a layer their layer may not depend on
+        /// (`layerRules` and `layerDependencies` in ycg.config.json)
+        #[arg(long)]
+        layer_violations: bool,
 
-        /// Include only files matching this glob pattern (can be repeated)
-        #[arg(long, value_name = "PATTERN")]
-        include: Vec<String>,
+        /// List the TODO / FIXME / HACK comments of each definition (body and leading comments)
+        #[arg(long)]
+        todos: bool,
 
-        /// Exclude files matching this glob pattern (can be repeated)
-        #[arg(long, value_name = "PATTERN")]
-        exclude: Vec<String>,
+        /// Project root holding the sources and ycg.config.json (defaults to the index's directory)
+        #[arg(short, long)]
+        root: Option<PathBuf>,
+    },
 
-        /// Disable automatic gitignore processing
-        #[arg(long)]
-        no_gitignore: bool,
+    /// Turn a binary graph (`--output-format bincode`) back into YAML or JSON
+    #[cfg(feature = "bincode")]
+    Decode {
+        /// Binary graph produced by `generate --output-format bincode`
+        #[arg(short, long)]
+        input: PathBuf,
 
-        /// Enable inline signatures in ad-hoc format (Level 1: ID|Signature(args):Return|Type)
-        /// Requires --output-format adhoc
-        #[arg(long)]
-        adhoc_inline_signatures: bool,
+        /// Text format: yaml or json
+        #[arg(long, default_value = "yaml")]
+        format: String,
 
-        /// Enable inline logic in ad-hoc format (Level 2: ID|Signature|Type|logic:steps)
-        /// Requires --output-format adhoc. Implicitly enables --adhoc-inline-signatures
-        #[arg(long)]
-        adhoc_inline_logic: bool,
+        /// Write the graph here instead of stdout
+        #[arg(short, long)]
+        output: Option<PathBuf>,
     },
 }
 
+#[derive(Args)]
+struct GenerateArgs {
+    /// Caminho para o arquivo de índice SCIP (Input)
+    #[arg(short, long)]
+    input: PathBuf,
+
+    /// Caminho para o arquivo YAML de saída (Output)
+    #[arg(short, long)]
+    output: Option<PathBuf>,
+
+    /// Caminho raiz do projeto fonte
+    #[arg(short, long)]
+    root: Option<PathBuf>,
+
+    /// Nível de Detalhe (0=Low, 1=Medium, 2=High)
+    #[arg(short, long, default_value_t = 1)]
+    lod: u8,
+
+    /// Ativa modo compacto (Lista de Adjacência)
+    #[arg(short, long, default_value_t = false)]
+    compact: bool,
+
+    /// Remove framework boilerplate patterns (DI constructors, decorators)
+    #[arg(long)]
+    ignore_framework_noise: bool,
+
+    /// Decorator kept as node metadata by --ignore-framework-noise instead of only stripped:
+    /// route verbs (Get, Post...) set `route`, deprecated and Injectable set their flags
+    /// (repeatable; overrides output.keepDecorators)
+    #[arg(long = "keep-decorator", value_name = "NAME")]
+    keep_decorators: Vec<String>,
+
+    /// Output format: yaml (default), json (the same document as JSON), adhoc (compact pipe-separated format), ndjson (one JSON object per line), plantuml (class diagram) or bincode (binary, `bincode` feature)
+    #[arg(long, value_name = "FORMAT")]
+    output_format: Option<String>,
+
+    /// Include only files matching this glob pattern (can be repeated)
+    #[arg(long, value_name = "PATTERN")]
+    include: Vec<String>,
+
+    /// Exclude files matching this glob pattern (can be repeated)
+    #[arg(long, value_name = "PATTERN")]
+    exclude: Vec<String>,
+
+    /// Disable automatic gitignore processing
+    #[arg(long)]
+    no_gitignore: bool,
+
+    /// Enable inline signatures in ad-hoc format (Level 1: ID|Signature(args):Return|Type)
+    /// Requires --output-format adhoc
+    #[arg(long)]
+    adhoc_inline_signatures: bool,
+
+    /// Enable inline logic in ad-hoc format (Level 2: ID|Signature|Type|logic:steps)
+    /// Requires --output-format adhoc. Implicitly enables --adhoc-inline-signatures
+    #[arg(long)]
+    adhoc_inline_logic: bool,
+
+    /// Signature style for inline signatures: named (default) or types-only
+    /// (drops parameter names, e.g. findOne(num):User). Applies to ad-hoc Level 1 and 2
+    #[arg(long, value_name = "STYLE", default_value = "named")]
+    signature_style: SignatureStyle,
+
+    /// Also write a JSON index mapping each file to its symbol anchors and each anchor to its line
+    #[arg(long, value_name = "PATH")]
+    emit_index: Option<PathBuf>,
+
+    /// Keep only symbols whose name matches this regex (can be repeated)
+    #[arg(long, value_name = "REGEX")]
+    include_name: Vec<String>,
+
+    /// Drop symbols whose name matches this regex, with their edges (can be repeated)
+    #[arg(long, value_name = "REGEX")]
+    exclude_name: Vec<String>,
+
+    /// Fail on SCIP indexes whose protocol version is outside the tested range, or that are
+    /// partly corrupt (default: warn, and keep the documents that still decode)
+    #[arg(long)]
+    strict: bool,
+
+    /// Abbreviate common name suffixes (Controller→Ctrl, Service→Svc, ...) in names, signatures and edges
+    #[arg(long)]
+    compact_names: bool,
+
+    /// Also write one graph file per top-level module into DIR, with cross-module edges in _cross.yaml
+    #[arg(long, value_name = "DIR")]
+    split_by_module: Option<PathBuf>,
+
+    /// Mask secret-looking literals (API keys, passwords, JWTs) in signatures and documentation
+    #[arg(long)]
+    redact: bool,
+
+    /// Report what the file, semantic and framework filters would remove (and the token savings)
+    /// without writing any output
+    #[arg(long)]
+    dry_run: bool,
+
+    /// Store repeated signatures/docs once in a _strings table and reference them as $N (YAML format)
+    #[arg(long)]
+    intern_strings: bool,
+
+    /// Replace hash anchors with sequential integer IDs in definitions and edges (YAML format);
+    /// the ID -> anchor mapping is written to _meta.anchors
+    #[arg(long)]
+    numeric_ids: bool,
+
+    /// Remove this namespace prefix (e.g. com.acme.platform) from every symbol name and anchor
+    #[arg(long, value_name = "PREFIX")]
+    strip_prefix: Option<String>,
+
+    /// Store the source text of each call expression on its `calls` edge (slower: reads every source file)
+    #[arg(long)]
+    with_call_text: bool,
+
+    /// Maximum characters kept from a call expression captured by --with-call-text
+    #[arg(long, value_name = "N", default_value_t = 80)]
+    call_text_max_len: usize,
+
+    /// Attach the top git authors of each file as `owners` on File nodes (skipped outside a git repository)
+    #[arg(long)]
+    with_ownership: bool,
+
+    /// Keep only classes, interfaces and their public methods (drops constructors, fields and private members)
+    #[arg(long)]
+    api_surface: bool,
+
+    /// Text prepended to the output as `#` comment lines (e.g. instructions for an LLM)
+    #[arg(long, value_name = "TEXT")]
+    header_comment: Option<String>,
+
+    /// Prepend a built-in legend explaining the output layout as `#` comment lines
+    #[arg(long)]
+    explain_format: bool,
+
+    /// Drop parameter default values (`limit: number = 100`) from signatures to save tokens
+    #[arg(long)]
+    no_defaults: bool,
+
+    /// Leading path stripped from SCIP document paths before joining them with --root
+    /// (monorepo indexed at the repo root, run from a package); detected when omitted
+    #[arg(long, value_name = "PREFIX")]
+    path_prefix: Option<String>,
+
+    /// Further source root tried, in order, when a document is not found under --root (can be repeated)
+    #[arg(long = "source-root", value_name = "PATH")]
+    source_roots: Vec<PathBuf>,
+
+    /// Group flat-format edges into one section per type (`calls: [{from, to}]`),
+    /// so `type` is not repeated on every edge (ignored with --compact or --numeric-ids)
+    #[arg(long)]
+    edges_by_type: bool,
+
+    /// Write each definition's outgoing edges inline (`calls: [ids]`, `imports: [ids]`)
+    /// instead of a separate `graph` section (ignored with --numeric-ids)
+    #[arg(long)]
+    inline_edges: bool,
+
+    /// Source files larger than this many bytes are not parsed (no signatures/logic),
+    /// so one huge generated file cannot stall the run
+    #[arg(long, value_name = "BYTES", default_value_t = ycg_core::enricher::DEFAULT_MAX_FILE_BYTES)]
+    max_file_bytes: u64,
+
+    /// Keep at most this many preconditions per definition (Level 2 logic);
+    /// the rest are summarized as `(+N more)`
+    #[arg(long, value_name = "N")]
+    max_preconditions: Option<usize>,
+
+    /// Guard clauses count as preconditions up to this many statement blocks deep
+    /// (1 = the function's top-level statements); guards of nested closures never do
+    #[arg(long, value_name = "N", default_value_t = ycg_core::enricher::DEFAULT_MAX_GUARD_DEPTH)]
+    max_guard_depth: usize,
+
+    /// Cut documentation longer than this many characters at a sentence or word boundary,
+    /// ending it with `…`
+    #[arg(long, value_name = "CHARS")]
+    max_doc_length: Option<usize>,
+
+    /// Drop documentation lines that carry nothing: `@param id` / `@returns` without a
+    /// description, `eslint-disable`, `@ts-ignore`, "auto-generated" notices
+    #[arg(long)]
+    strip_doc_boilerplate: bool,
+
+    /// Baseline SCIP index (e.g. from the target branch): emit only the definitions added,
+    /// removed or modified since it, with their edges, under a `_delta` marker
+    #[arg(long, value_name = "SCIP")]
+    baseline: Option<PathBuf>,
+
+    /// YAML layout: block (default, one key per line) or flow (nested maps and lists
+    /// written inline, fewer tokens for the adjacency section)
+    #[arg(long, value_name = "STYLE", default_value = "block")]
+    style: YamlStyle,
+
+    /// Abbreviate wrappers in ad-hoc inline signatures: Promise<T> becomes MARKER + T
+    /// (`~T` when no marker is given) and Result<T, E> becomes T?
+    #[arg(long, value_name = "MARKER", num_args = 0..=1, default_missing_value = "~")]
+    abbreviate_wrappers: Option<String>,
+
+    /// Also write a JSON file mapping each source file to its LSP DocumentSymbol outline
+    /// (classes with their methods nested), for editor outline views
+    #[arg(long, value_name = "PATH")]
+    emit_document_symbols: Option<PathBuf>,
+
+    /// Collapse pass-through methods (`return this.service.find(id)`): their logic becomes
+    /// a `delegates_to` marker and their callers call the delegated method directly
+    #[arg(long)]
+    collapse_delegates: bool,
+
+    /// Test files (*.spec.ts, *.test.ts, *_test.rs, __tests__/): include (default), exclude,
+    /// or separate (production graph in --output, test graph in <output>.tests.yaml)
+    #[arg(long, value_name = "MODE", default_value = "include")]
+    tests: TestFilesMode,
+
+    /// Skip (with a warning) source files reached through a symbolic link, e.g.
+    /// symlinked packages under node_modules, instead of reading them through the link
+    #[arg(long)]
+    no_follow_symlinks: bool,
+
+    /// Skip (with a warning) source files whose leading comment marks them as generated:
+    /// `@generated`, `Code generated ... DO NOT EDIT.` or `<auto-generated>`
+    #[arg(long)]
+    skip_generated: bool,
+
+    /// Give File nodes a `path` (their relative path) and nest them under one node per
+    /// directory, whose id is the directory path, instead of anchor-only parents
+    #[arg(long)]
+    file_paths: bool,
+
+    /// Deduplicate edges one document at a time and merge the sorted runs at the end,
+    /// instead of holding every edge in one set; lowers peak memory on very large indexes
+    /// without changing the output
+    #[arg(long)]
+    streaming_edges: bool,
+
+    /// Prefix definition anchors with a short kind tag (`cls:User_4702`, `fn:getUser_3f4a`,
+    /// `meth:`, `var:`, `iface:`, `enum:`, `mod:`, `file:`), in edges and parents alike
+    #[arg(long)]
+    kind_prefixed_anchors: bool,
+
+    /// Keep one edge per reference occurrence instead of one per (from, to, type),
+    /// so repeated calls between the same pair are preserved
+    #[arg(long)]
+    no_dedupe_edges: bool,
+
+    /// Write repeated identical edges once with their count: `count: 5` in the flat edge
+    /// list, `target (x5)` in --compact and --inline-edges adjacency lists
+    #[arg(long)]
+    weighted_edges: bool,
+
+    /// Rewrite method/function signatures in a language-neutral form (`find_one(id: int) -> User`)
+    #[arg(long)]
+    neutral_signatures: bool,
+
+    /// Mark optional parameters on their names in ad-hoc inline signatures: `id?: number`,
+    /// `id: number | undefined` and Rust `id: Option<u32>` all become `id?:num` / `id?:u32`
+    #[arg(long)]
+    optional_param_markers: bool,
+
+    /// Summarize QueryBuilder, fetch/axios and Rust iterator chains longer than N characters
+    /// to their result type in ad-hoc inline signatures (`users: Promise<Json>`) [default: 100]
+    #[arg(long, value_name = "N")]
+    chain_summary_threshold: Option<usize>,
+
+    /// Also write the graph into DIR as defs.yaml (metadata + definitions) and edges.yaml
+    /// (metadata + edges), each a valid graph on its own
+    #[arg(long, value_name = "DIR")]
+    split_defs_edges: Option<PathBuf>,
+
+    /// Write diagnostics (files read, symbols skipped by --lod, rejected signatures,
+    /// resolved variable names...) to PATH as JSON lines instead of stderr
+    #[arg(long, value_name = "PATH")]
+    log_json: Option<PathBuf>,
+
+    /// Order definitions by dependency: callees and imported definitions come before
+    /// the definitions using them (mutually recursive ones keep their relative order)
+    #[arg(long)]
+    topo_sort: bool,
+
+    /// Keep only edges between definitions of different files (drops a method calling
+    /// its sibling), for architecture-level views
+    #[arg(long)]
+    cross_file_edges_only: bool,
+
+    /// Store the original SCIP symbol of each definition as `scip_symbol`, to join the
+    /// output back to the index or other SCIP tools (costs tokens)
+    #[arg(long)]
+    keep_scip_symbols: bool,
+
+    /// Only emit the definitions of this file (SCIP document path, or a trailing part of it)
+    /// and their direct neighbors: callers and callees one edge away
+    #[arg(long, value_name = "PATH")]
+    focus_file: Option<String>,
+
+    /// Print, per file, how many methods/functions got a signature instead of falling back
+    /// to their name (low coverage usually means a --root / --path-prefix mismatch)
+    #[arg(long)]
+    signature_coverage: bool,
+
+    /// Attach the full source text (`source`) to definitions whose name matches this glob
+    /// (`UserService#find*`); may be repeated. Bodies above 16 KiB are cut
+    #[arg(long, value_name = "NAME_GLOB")]
+    inline_source: Vec<String>,
+
+    /// Merge an interface implemented by exactly one class into that class: edges to the
+    /// interface move to the class, which lists it under `merged_interfaces`
+    #[arg(long)]
+    merge_single_impl: bool,
+
+    /// Annotate methods/functions with their cyclomatic complexity (`complexity`: 1 plus
+    /// one per if, loop, case, catch, ternary, && / || / ??)
+    #[arg(long)]
+    complexity: bool,
+}
+
 fn main() -> Result<()> {
     let cli = Cli::parse();
 
     match cli.command {
         Commands::Index { directory, output } => handle_index_command(directory, output),
-        Commands::Generate {
+        Commands::Generate(args) => handle_generate_command(*args),
+        Commands::Chunk {
             input,
-            output,
+            max_tokens,
+            output_dir,
+        } => handle_chunk_command(input, max_tokens, output_dir),
+        Commands::Validate { input } => handle_validate_command(input),
+        Commands::Stats { input } => handle_stats_command(input),
+        Commands::Analyze {
+            input,
+            import_cycles,
+            unused_imports,
+            layer_violations,
+            todos,
             root,
-            lod,
-            compact,
-            ignore_framework_noise,
-            output_format,
-            include,
-            exclude,
-            no_gitignore,
-            adhoc_inline_signatures,
-            adhoc_inline_logic,
-        } => handle_generate_command(
+        } => handle_analyze_command(
             input,
-            output,
+            import_cycles,
+            unused_imports,
+            layer_violations,
+            todos,
             root,
-            lod,
-            compact,
-            ignore_framework_noise,
-            output_format,
-            include,
-            exclude,
-            no_gitignore,
-            adhoc_inline_signatures,
-            adhoc_inline_logic,
         ),
+        #[cfg(feature = "bincode")]
+        Commands::Decode {
+            input,
+            format,
+            output,
+        } => handle_decode_command(input, &format, output),
     }
 }
 
@@ -241,24 +582,217 @@ fn run_typescript_indexer(directory: &PathBuf, output: &PathBuf) -> Result<()> {
     Ok(())
 }
 
-fn handle_generate_command(
+fn handle_chunk_command(input: PathBuf, max_tokens: usize, output_dir: PathBuf) -> Result<()> {
+    use ycg_core::chunker::GraphChunker;
+
+    let yaml = std::fs::read_to_string(&input)
+        .with_context(|| format!("Failed to read graph: {:?}", input))?;
+    let graph = GraphChunker::load_graph_yaml(&yaml)?;
+    let chunks = GraphChunker::new(max_tokens).chunk(&graph)?;
+
+    GraphChunker::write_chunks(&chunks, &output_dir)?;
+
+    println!(
+        "✓ {} definitions split into {} chunks (max {} tokens) in {:?}",
+        graph.definitions.len(),
+        chunks.len(),
+        max_tokens,
+        output_dir
+    );
+    Ok(())
+}
+
+fn handle_validate_command(input: PathBuf) -> Result<()> {
+    let yaml = std::fs::read_to_string(&input)
+        .with_context(|| format!("Failed to read graph: {:?}", input))?;
+    ycg_core::validators::validate_graph_yaml(&yaml)
+        .with_context(|| format!("{:?} is not a valid YCG graph", input))?;
+
+    println!("✓ {:?} is a valid YCG graph", input);
+    Ok(())
+}
+
+fn handle_stats_command(input: PathBuf) -> Result<()> {
+    use ycg_core::chunker::GraphChunker;
+    use ycg_core::stats::GraphStats;
+
+    let yaml = std::fs::read_to_string(&input)
+        .with_context(|| format!("Failed to read graph: {:?}", input))?;
+    let graph = GraphChunker::load_graph_yaml(&yaml)?;
+    println!("{}", GraphStats::from_graph(&graph, &yaml));
+    Ok(())
+}
+
+fn handle_analyze_command(
     input: PathBuf,
-    output: Option<PathBuf>,
+    import_cycles: bool,
+    unused_imports: bool,
+    layer_violations: bool,
+    todos: bool,
     root: Option<PathBuf>,
-    lod: u8,
-    compact: bool,
-    ignore_framework_noise: bool,
-    output_format: Option<String>,
-    include: Vec<String>,
-    exclude: Vec<String>,
-    no_gitignore: bool,
-    adhoc_inline_signatures: bool,
-    adhoc_inline_logic: bool,
 ) -> Result<()> {
     use ycg_core::config::ConfigLoader;
+
+    if !import_cycles && !unused_imports && !layer_violations && !todos {
+        return Err(anyhow!(
+            "No analysis selected. Use --import-cycles, --unused-imports, --layer-violations or --todos"
+        ));
+    }
+
+    if import_cycles {
+        let report = ycg_core::run_import_cycles(&input)?;
+        println!("\n{}", report);
+    }
+
+    if unused_imports {
+        let report = ycg_core::run_unused_imports(&input)?;
+        println!("\n{}", report);
+    }
+
+    let project_root = root.unwrap_or_else(|| {
+        input
+            .parent()
+            .unwrap_or(&std::path::PathBuf::from("."))
+            .to_path_buf()
+    });
+
+    if layer_violations {
+        let file_config = ConfigLoader::load_from_file(&project_root.join("ycg.config.json"))?;
+        let merged = ConfigLoader::merge_with_cli(
+            file_config,
+            None,
+            None,
+            None,
+            vec![],
+            vec![],
+            false,
+            None,
+        )?;
+        ConfigLoader::validate(&merged)?;
+
+        let config = YcgConfig {
+            project_root: project_root.clone(),
+            file_filter: merged.file_filter,
+            layer_rules: merged.layer_rules,
+            layer_dependencies: merged.layer_dependencies,
+            ..Default::default()
+        };
+        let report = ycg_core::run_layer_violations(&input, &config)?;
+        println!("\n{}", report);
+    }
+
+    if todos {
+        let config = YcgConfig {
+            project_root,
+            ..Default::default()
+        };
+        let report = ycg_core::run_todos(&input, &config)?;
+        println!("\n{}", report);
+    }
+    Ok(())
+}
+
+#[cfg(feature = "bincode")]
+fn handle_decode_command(input: PathBuf, format: &str, output: Option<PathBuf>) -> Result<()> {
+    let bytes =
+        std::fs::read(&input).with_context(|| format!("Failed to read graph: {:?}", input))?;
+    let text = match format.to_lowercase().as_str() {
+        "yaml" => ycg_core::binary_graph::decode_to_yaml(&bytes),
+        "json" => ycg_core::binary_graph::decode_to_json(&bytes),
+        other => {
+            return Err(anyhow!(
+                "Invalid decode format: '{}'. Valid options are: 'yaml', 'json'",
+                other
+            ));
+        }
+    }
+    .with_context(|| format!("{:?} is not a valid binary graph", input))?;
+
+    match output {
+        Some(path) => {
+            std::fs::write(&path, text)?;
+            println!("Sucesso! Grafo salvo em: {:?}", path);
+        }
+        None => print!("{}", text),
+    }
+    Ok(())
+}
+
+fn handle_generate_command(args: GenerateArgs) -> Result<()> {
+    use ycg_core::config::ConfigLoader;
     use ycg_core::errors::GranularityError;
     use ycg_core::model::AdHocGranularity;
 
+    let GenerateArgs {
+        input,
+        output,
+        root,
+        lod,
+        compact,
+        ignore_framework_noise,
+        keep_decorators,
+        output_format,
+        include,
+        exclude,
+        no_gitignore,
+        adhoc_inline_signatures,
+        adhoc_inline_logic,
+        signature_style,
+        emit_index,
+        include_name,
+        exclude_name,
+        strict,
+        compact_names,
+        split_by_module,
+        redact,
+        dry_run,
+        intern_strings,
+        numeric_ids,
+        strip_prefix,
+        with_call_text,
+        call_text_max_len,
+        with_ownership,
+        api_surface,
+        header_comment,
+        explain_format,
+        no_defaults,
+        path_prefix,
+        source_roots,
+        edges_by_type,
+        inline_edges,
+        max_file_bytes,
+        max_preconditions,
+        max_guard_depth,
+        max_doc_length,
+        strip_doc_boilerplate,
+        baseline,
+        style,
+        abbreviate_wrappers,
+        emit_document_symbols,
+        collapse_delegates,
+        tests,
+        no_follow_symlinks,
+        skip_generated,
+        file_paths,
+        streaming_edges,
+        kind_prefixed_anchors,
+        no_dedupe_edges,
+        weighted_edges,
+        neutral_signatures,
+        optional_param_markers,
+        chain_summary_threshold,
+        split_defs_edges,
+        log_json,
+        topo_sort,
+        cross_file_edges_only,
+        keep_scip_symbols,
+        focus_file,
+        signature_coverage,
+        inline_source,
+        merge_single_impl,
+        complexity,
+    } = args;
+
     let lod = match lod {
         0 => LevelOfDetail::Low,
         1 => LevelOfDetail::Medium,
@@ -316,7 +850,11 @@ fn handle_generate_command(
 
     // Validate that granularity flags require adhoc format
     // Requirement 6.5: Granularity flags require --output-format adhoc
-    if merged.adhoc_granularity != AdHocGranularity::Default
+    let detailed_kinds = merged
+        .adhoc_granularity_by_kind
+        .values()
+        .any(|level| *level != AdHocGranularity::Default);
+    if (merged.adhoc_granularity != AdHocGranularity::Default || detailed_kinds)
         && merged.output_format != ycg_core::model::OutputFormat::AdHoc
     {
         return Err(GranularityError::requires_adhoc_format().into());
@@ -328,10 +866,102 @@ fn handle_generate_command(
         compact: merged.compact,
         output_format: merged.output_format,
         ignore_framework_noise: merged.ignore_framework_noise,
+        keep_decorators: if keep_decorators.is_empty() {
+            merged.keep_decorators
+        } else {
+            keep_decorators
+        },
         file_filter: merged.file_filter,
         adhoc_granularity: merged.adhoc_granularity,
+        adhoc_granularity_by_kind: merged.adhoc_granularity_by_kind,
+        signature_style,
+        emit_index,
+        name_filter: NameFilterConfig {
+            include_patterns: include_name,
+            exclude_patterns: exclude_name,
+        },
+        strict,
+        compact_names: compact_names || merged.compact_names,
+        name_abbreviations: merged.name_abbreviations,
+        edge_rules: merged.edge_rules,
+        layer_rules: merged.layer_rules,
+        layer_dependencies: merged.layer_dependencies,
+        split_by_module,
+        redact,
+        intern_strings,
+        numeric_ids,
+        strip_prefix,
+        with_call_text,
+        call_text_max_len,
+        with_ownership,
+        api_surface,
+        header_comment,
+        explain_format,
+        no_defaults,
+        path_prefix,
+        source_roots,
+        edges_by_type,
+        inline_edges,
+        max_file_bytes,
+        max_preconditions,
+        max_guard_depth,
+        max_doc_length,
+        strip_doc_boilerplate,
+        baseline,
+        yaml_style: style,
+        abbreviate_wrappers,
+        emit_document_symbols,
+        collapse_delegates,
+        tests,
+        tests_output: output
+            .as_ref()
+            .map(|path| path.with_extension("tests.yaml")),
+        follow_symlinks: !no_follow_symlinks,
+        skip_generated,
+        file_paths,
+        streaming_edges,
+        kind_prefixed_anchors,
+        dedupe_edges: !no_dedupe_edges,
+        weighted_edges,
+        neutral_signatures,
+        languages: Vec::new(),
+        optional_param_markers,
+        chain_summary_threshold,
+        split_defs_edges,
+        event_log: match &log_json {
+            Some(path) => EventLog::create(path)?,
+            None => EventLog::default(),
+        },
+        topo_sort,
+        cross_file_edges_only,
+        keep_scip_symbols,
+        focus_file,
+        signature_coverage,
+        inline_source,
+        merge_single_impl,
+        complexity,
     };
 
+    if dry_run {
+        let report = run_dry_run(&input, &config)?;
+        println!("\n{}", report);
+        return Ok(());
+    }
+
+    #[cfg(feature = "bincode")]
+    if config.output_format == ycg_core::model::OutputFormat::Bincode {
+        let Some(path) = output else {
+            return Err(anyhow!(
+                "--output-format bincode writes binary data, set --output <PATH>"
+            ));
+        };
+        println!("--- YCG: Processando {:?} ---", input);
+        let bytes = ycg_core::run_binary_conversion(&input, config)?;
+        std::fs::write(&path, bytes)?;
+        println!("Sucesso! Grafo salvo em: {:?}", path);
+        return Ok(());
+    }
+
     println!("--- YCG: Processando {:?} ---", input);
 
     let yaml_output = run_scip_conversion(&input, config)?;
@@ -245,3 +245,33 @@ fn test_existing_flags_with_new_flags() {
     // Verify output file was created
     assert!(output_path.exists());
 }
+
+/// Test that a valid index with zero documents warns and still writes a valid graph
+#[test]
+fn test_empty_index_warns_and_writes_empty_graph() {
+    let temp_dir = TempDir::new().unwrap();
+    let scip_path = temp_dir.path().join("test.scip");
+    let output_path = temp_dir.path().join("output.yaml");
+
+    // An empty protobuf message is an Index with no documents
+    let empty_scip = vec![];
+    fs::write(&scip_path, empty_scip).unwrap();
+
+    let mut cmd = Command::cargo_bin("ycg_cli").unwrap();
+
+    cmd.arg("generate")
+        .arg("--input")
+        .arg(&scip_path)
+        .arg("--output")
+        .arg(&output_path);
+
+    cmd.assert()
+        .success()
+        .stderr(predicate::str::contains("SCIP index contains no documents"))
+        .stdout(predicate::str::contains("Taxa de Compressão").not())
+        .stdout(predicate::str::contains("Input Total Tokens").not());
+
+    let output = fs::read_to_string(&output_path).unwrap();
+    assert!(output.contains("_defs: []"), "{}", output);
+    ycg_core::validators::validate_graph_yaml(&output).unwrap();
+}
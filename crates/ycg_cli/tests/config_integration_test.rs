@@ -60,12 +60,16 @@ fn test_cli_precedence_over_config() {
             compact: Some(false),
             ignore_framework_noise: Some(false),
             adhoc_granularity: None,
+            ..Default::default()
         },
         ignore: IgnoreConfig {
             use_gitignore: Some(true),
             custom_patterns: Some(vec!["**/node_modules/**".to_string()]),
         },
         include: vec!["**/*.ts".to_string()],
+        edge_rules: Vec::new(),
+        layer_rules: Vec::new(),
+        layer_dependencies: Default::default(),
     };
 
     // Merge with CLI args that override some settings
@@ -110,6 +114,7 @@ fn test_config_validation_detects_conflicts() {
             use_gitignore: true,
         },
         adhoc_granularity: ycg_core::model::AdHocGranularity::default(),
+        ..Default::default()
     };
 
     // Validation should fail
@@ -164,12 +169,16 @@ fn test_invalid_output_format() {
             compact: None,
             ignore_framework_noise: None,
             adhoc_granularity: None,
+            ..Default::default()
         },
         ignore: IgnoreConfig {
             use_gitignore: None,
             custom_patterns: None,
         },
         include: vec![],
+        edge_rules: Vec::new(),
+        layer_rules: Vec::new(),
+        layer_dependencies: Default::default(),
     };
 
     // Try to merge with invalid output format
@@ -207,12 +216,16 @@ fn test_cli_granularity_precedence_over_config() {
             compact: None,
             ignore_framework_noise: None,
             adhoc_granularity: Some("default".to_string()),
+            ..Default::default()
         },
         ignore: IgnoreConfig {
             use_gitignore: None,
             custom_patterns: None,
         },
         include: vec![],
+        edge_rules: Vec::new(),
+        layer_rules: Vec::new(),
+        layer_dependencies: Default::default(),
     };
 
     // Merge with CLI granularity = InlineLogic (should override file's "default")
@@ -245,12 +258,16 @@ fn test_config_file_granularity_when_no_cli() {
             compact: None,
             ignore_framework_noise: None,
             adhoc_granularity: Some("signatures".to_string()),
+            ..Default::default()
         },
         ignore: IgnoreConfig {
             use_gitignore: None,
             custom_patterns: None,
         },
         include: vec![],
+        edge_rules: Vec::new(),
+        layer_rules: Vec::new(),
+        layer_dependencies: Default::default(),
     };
 
     // Merge with no CLI granularity (None)
@@ -283,12 +300,16 @@ fn test_invalid_granularity_in_config_file() {
             compact: None,
             ignore_framework_noise: None,
             adhoc_granularity: Some("invalid_level".to_string()),
+            ..Default::default()
         },
         ignore: IgnoreConfig {
             use_gitignore: None,
             custom_patterns: None,
         },
         include: vec![],
+        edge_rules: Vec::new(),
+        layer_rules: Vec::new(),
+        layer_dependencies: Default::default(),
     };
 
     // Merge should fail with clear error
@@ -311,3 +332,74 @@ fn test_invalid_granularity_in_config_file() {
     assert!(err_msg.contains("signatures"));
     assert!(err_msg.contains("logic"));
 }
+
+#[test]
+fn test_edge_rules_loaded_and_validated() {
+    use ycg_core::config::ConfigLoader;
+
+    let temp_dir = TempDir::new().unwrap();
+    let config_path = temp_dir.path().join("ycg.config.json");
+    fs::write(
+        &config_path,
+        r#"{ "edgeRules": [{ "pattern": "\\.emit\\(", "edgeType": "emits" }] }"#,
+    )
+    .unwrap();
+
+    let file_config = ConfigLoader::load_from_file(&config_path).unwrap();
+    let merged =
+        ConfigLoader::merge_with_cli(file_config, None, None, None, vec![], vec![], false, None)
+            .unwrap();
+    assert_eq!(merged.edge_rules.len(), 1);
+    assert_eq!(merged.edge_rules[0].pattern, r"\.emit\(");
+    assert_eq!(merged.edge_rules[0].edge_type, "emits");
+    assert!(ConfigLoader::validate(&merged).is_ok());
+
+    let mut invalid = merged.clone();
+    invalid.edge_rules[0].pattern = "(unclosed".to_string();
+    let err = ConfigLoader::validate(&invalid).unwrap_err().to_string();
+    assert!(err.contains("Invalid edge rule pattern"), "{}", err);
+}
+
+#[test]
+fn test_adhoc_granularity_by_kind_loaded() {
+    use ycg_core::config::ConfigLoader;
+    use ycg_core::model::{AdHocGranularity, ScipSymbolKind};
+
+    let temp_dir = TempDir::new().unwrap();
+    let config_path = temp_dir.path().join("ycg.config.json");
+    fs::write(
+        &config_path,
+        r#"{ "output": { "format": "adhoc", "adhocGranularityByKind": { "method": "signatures", "Variable": "default" } } }"#,
+    )
+    .unwrap();
+
+    let file_config = ConfigLoader::load_from_file(&config_path).unwrap();
+    let merged =
+        ConfigLoader::merge_with_cli(file_config, None, None, None, vec![], vec![], false, None)
+            .unwrap();
+    assert_eq!(merged.adhoc_granularity, AdHocGranularity::Default);
+    assert_eq!(
+        merged
+            .adhoc_granularity_by_kind
+            .get(&ScipSymbolKind::Method),
+        Some(&AdHocGranularity::InlineSignatures)
+    );
+    assert_eq!(
+        merged
+            .adhoc_granularity_by_kind
+            .get(&ScipSymbolKind::Variable),
+        Some(&AdHocGranularity::Default)
+    );
+
+    fs::write(
+        &config_path,
+        r#"{ "output": { "adhocGranularityByKind": { "struct": "signatures" } } }"#,
+    )
+    .unwrap();
+    let file_config = ConfigLoader::load_from_file(&config_path).unwrap();
+    let err =
+        ConfigLoader::merge_with_cli(file_config, None, None, None, vec![], vec![], false, None)
+            .unwrap_err()
+            .to_string();
+    assert!(err.contains("Unknown symbol kind: 'struct'"), "{}", err);
+}
@@ -0,0 +1,77 @@
+// Integration tests for the `validate` subcommand
+
+use assert_cmd::cargo::cargo_bin_cmd;
+use predicates::prelude::*;
+use std::fs;
+use tempfile::TempDir;
+
+const VALID_GRAPH: &str = "_meta:
+  name: demo
+  version: 1.0.0
+_defs:
+- id: UserService_a1b2
+  n: UserService
+  t: class
+- id: findOne_c3d4
+  n: UserService.findOne
+  t: method
+  parent_id: UserService_a1b2
+graph:
+  findOne_c3d4:
+    references:
+    - UserService_a1b2
+";
+
+fn validate(yaml: &str) -> assert_cmd::assert::Assert {
+    let temp_dir = TempDir::new().unwrap();
+    let input = temp_dir.path().join("graph.yaml");
+    fs::write(&input, yaml).unwrap();
+
+    cargo_bin_cmd!("ycg_cli")
+        .arg("validate")
+        .arg("--input")
+        .arg(&input)
+        .assert()
+}
+
+#[test]
+fn test_validate_accepts_valid_graph() {
+    validate(VALID_GRAPH)
+        .success()
+        .stdout(predicate::str::contains("is a valid YCG graph"));
+}
+
+#[test]
+fn test_validate_rejects_dangling_edge() {
+    let yaml = VALID_GRAPH.replace("    - UserService_a1b2", "    - Missing_ffff");
+    validate(&yaml)
+        .failure()
+        .stderr(predicate::str::contains("target ID not found"))
+        .stderr(predicate::str::contains("(line 15)"));
+}
+
+#[test]
+fn test_validate_rejects_unknown_parent() {
+    let yaml = VALID_GRAPH.replace("parent_id: UserService_a1b2", "parent_id: Gone_0000");
+    validate(&yaml)
+        .failure()
+        .stderr(predicate::str::contains("parent 'Gone_0000' not found"));
+}
+
+#[test]
+fn test_validate_rejects_non_graph_yaml() {
+    validate("- just\n- a list\n")
+        .failure()
+        .stderr(predicate::str::contains("Not a YCG graph"));
+}
+
+#[test]
+fn test_validate_missing_file_fails() {
+    cargo_bin_cmd!("ycg_cli")
+        .arg("validate")
+        .arg("--input")
+        .arg("does-not-exist.yaml")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Failed to read graph"));
+}
@@ -29,6 +29,7 @@ fn create_config(granularity: AdHocGranularity, project: &str) -> YcgConfig {
             use_gitignore: false,
         },
         adhoc_granularity: granularity,
+        ..Default::default()
     }
 }
 
@@ -53,7 +53,9 @@ impl AdHocSerializer {
                 .entry(edge.from.clone())
                 .or_insert_with(std::collections::BTreeMap::new);
 
-            let targets = node_edges.entry(edge.edge_type).or_insert_with(Vec::new);
+            let targets = node_edges
+                .entry(edge.edge_type.clone())
+                .or_insert_with(Vec::new);
 
             targets.push(edge.to.clone());
         }
@@ -106,10 +108,7 @@ impl AdHocParser {
             id,
             name,
             kind,
-            parent_id: None,
-            documentation: None,
-            signature: None,
-            logic: None,
+            ..Default::default()
         })
     }
 
@@ -135,7 +134,8 @@ impl AdHocParser {
                     references.push(crate::model::ReferenceEdge {
                         from: from.clone(),
                         to: to.clone(),
-                        edge_type: *edge_type,
+                        edge_type: edge_type.clone(),
+                        call_text: None,
                     });
                 }
             }
@@ -203,7 +203,7 @@ fn split_escaped(s: &str) -> Result<Vec<String>> {
 }
 
 /// Convert ScipSymbolKind to lowercase string
-fn kind_to_string(kind: &ScipSymbolKind) -> String {
+pub(crate) fn kind_to_string(kind: &ScipSymbolKind) -> String {
     match kind {
         ScipSymbolKind::File => "file",
         ScipSymbolKind::Module => "module",
@@ -212,22 +212,14 @@ fn kind_to_string(kind: &ScipSymbolKind) -> String {
         ScipSymbolKind::Function => "function",
         ScipSymbolKind::Variable => "variable",
         ScipSymbolKind::Interface => "interface",
+        ScipSymbolKind::Enum => "enum",
     }
     .to_string()
 }
 
 /// Convert lowercase string to ScipSymbolKind
 fn string_to_kind(s: &str) -> Result<ScipSymbolKind> {
-    match s.to_lowercase().as_str() {
-        "file" => Ok(ScipSymbolKind::File),
-        "module" => Ok(ScipSymbolKind::Module),
-        "class" => Ok(ScipSymbolKind::Class),
-        "method" => Ok(ScipSymbolKind::Method),
-        "function" => Ok(ScipSymbolKind::Function),
-        "variable" => Ok(ScipSymbolKind::Variable),
-        "interface" => Ok(ScipSymbolKind::Interface),
-        _ => Err(anyhow!("Unknown symbol kind: '{}'", s)),
-    }
+    s.parse().map_err(|e: String| anyhow!(e))
 }
 
 #[cfg(test)]
@@ -241,10 +233,7 @@ mod tests {
             id: "User_b8c1".to_string(),
             name: "User".to_string(),
             kind: ScipSymbolKind::Class,
-            parent_id: None,
-            documentation: None,
-            signature: None,
-            logic: None,
+            ..Default::default()
         };
 
         let result = AdHocSerializer::serialize_node(&node);
@@ -257,10 +246,7 @@ mod tests {
             id: "weird|id".to_string(),
             name: "name|with|pipes".to_string(),
             kind: ScipSymbolKind::Function,
-            parent_id: None,
-            documentation: None,
-            signature: None,
-            logic: None,
+            ..Default::default()
         };
 
         let result = AdHocSerializer::serialize_node(&node);
@@ -319,10 +305,7 @@ mod tests {
             id: "Test_1234".to_string(),
             name: "TestClass".to_string(),
             kind: ScipSymbolKind::Class,
-            parent_id: None,
-            documentation: None,
-            signature: None,
-            logic: None,
+            ..Default::default()
         };
 
         let serialized = AdHocSerializer::serialize_node(&original);
@@ -339,31 +322,28 @@ mod tests {
             metadata: ProjectMetadata {
                 name: "test".to_string(),
                 version: "1.0".to_string(),
+                ..Default::default()
             },
             definitions: vec![
                 SymbolNode {
                     id: "A_0001".to_string(),
                     name: "ClassA".to_string(),
                     kind: ScipSymbolKind::Class,
-                    parent_id: None,
-                    documentation: None,
-                    signature: None,
-                    logic: None,
+                    ..Default::default()
                 },
                 SymbolNode {
                     id: "B_0002".to_string(),
                     name: "methodB".to_string(),
                     kind: ScipSymbolKind::Method,
                     parent_id: Some("A_0001".to_string()),
-                    documentation: None,
-                    signature: None,
-                    logic: None,
+                    ..Default::default()
                 },
             ],
             references: vec![ReferenceEdge {
                 from: "B_0002".to_string(),
                 to: "A_0001".to_string(),
                 edge_type: EdgeType::Calls,
+                call_text: None,
             }],
         };
 
@@ -381,31 +361,27 @@ mod tests {
             metadata: ProjectMetadata {
                 name: "test".to_string(),
                 version: "1.0".to_string(),
+                ..Default::default()
             },
             definitions: vec![
                 SymbolNode {
                     id: "User_b8c1".to_string(),
                     name: "User".to_string(),
                     kind: ScipSymbolKind::Class,
-                    parent_id: None,
-                    documentation: None,
-                    signature: None,
-                    logic: None,
+                    ..Default::default()
                 },
                 SymbolNode {
                     id: "greet_a3f2".to_string(),
                     name: "greet".to_string(),
                     kind: ScipSymbolKind::Function,
-                    parent_id: None,
-                    documentation: None,
-                    signature: None,
-                    logic: None,
+                    ..Default::default()
                 },
             ],
             references: vec![ReferenceEdge {
                 from: "greet_a3f2".to_string(),
                 to: "User_b8c1".to_string(),
                 edge_type: EdgeType::References,
+                call_text: None,
             }],
         };
 
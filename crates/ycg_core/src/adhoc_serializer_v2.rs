@@ -12,10 +12,13 @@
 
 use crate::ast_cache::AstCache;
 use crate::logic_extractor::LogicExtractor;
-use crate::model::{AdHocGranularity, ScipSymbolKind, SymbolNode, YcgGraph, YcgGraphAdHoc};
-use crate::signature_extractor::SignatureExtractor;
+use crate::model::{
+    AdHocGranularity, ScipSymbolKind, SignatureStyle, SymbolNode, YcgGraph, YcgGraphAdHoc,
+};
+use crate::signature_extractor::{DEFAULT_CHAIN_SUMMARY_THRESHOLD, SignatureExtractor};
+use crate::type_abbreviator::TypeAbbreviator;
 use rayon::prelude::*;
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, HashMap};
 use std::sync::Mutex;
 
 /// Enhanced ad-hoc serializer with granularity level support
@@ -26,6 +29,15 @@ use std::sync::Mutex;
 /// **Validates: Requirements 1.1, 1.2, 1.3, 1.4**
 pub struct AdHocSerializerV2 {
     granularity: AdHocGranularity,
+    signature_style: SignatureStyle,
+    /// Promise marker when `Promise`/`Result` wrappers are abbreviated
+    wrapper_marker: Option<String>,
+    /// Render optional parameters as `name?:type` in inline signatures
+    optional_param_markers: bool,
+    /// Length above which builder / fluent chains are summarized
+    chain_summary_threshold: usize,
+    /// Kinds serialized at their own level instead of `granularity`
+    kind_granularity: HashMap<ScipSymbolKind, AdHocGranularity>,
 }
 
 impl AdHocSerializerV2 {
@@ -42,7 +54,130 @@ impl AdHocSerializerV2 {
     /// let serializer = AdHocSerializerV2::new(AdHocGranularity::InlineSignatures);
     /// ```
     pub fn new(granularity: AdHocGranularity) -> Self {
-        Self { granularity }
+        Self {
+            granularity,
+            signature_style: SignatureStyle::default(),
+            wrapper_marker: None,
+            optional_param_markers: false,
+            chain_summary_threshold: DEFAULT_CHAIN_SUMMARY_THRESHOLD,
+            kind_granularity: HashMap::new(),
+        }
+    }
+
+    /// Set how parameters are rendered in inline signatures (Level 1 and 2)
+    ///
+    /// # Examples
+    /// ```
+    /// use ycg_core::adhoc_serializer_v2::AdHocSerializerV2;
+    /// use ycg_core::model::{AdHocGranularity, SignatureStyle};
+    ///
+    /// let serializer = AdHocSerializerV2::new(AdHocGranularity::InlineSignatures)
+    ///     .with_signature_style(SignatureStyle::TypesOnly);
+    /// ```
+    pub fn with_signature_style(mut self, style: SignatureStyle) -> Self {
+        self.signature_style = style;
+        self
+    }
+
+    /// Abbreviate `Promise<T>` to `{marker}T` and `Result<T, E>` to `T?` in
+    /// inline signatures (Level 1 and 2); `None` keeps the wrappers
+    ///
+    /// # Examples
+    /// ```
+    /// use ycg_core::adhoc_serializer_v2::AdHocSerializerV2;
+    /// use ycg_core::model::AdHocGranularity;
+    ///
+    /// let serializer = AdHocSerializerV2::new(AdHocGranularity::InlineSignatures)
+    ///     .with_wrapper_abbreviation(Some("~".to_string()));
+    /// ```
+    pub fn with_wrapper_abbreviation(mut self, marker: Option<String>) -> Self {
+        self.wrapper_marker = marker;
+        self
+    }
+
+    /// Render optional parameters as `name?:type` in inline signatures,
+    /// whether written `id?: T`, `id: T | undefined` or `id: Option<T>`
+    ///
+    /// # Examples
+    /// ```
+    /// use ycg_core::adhoc_serializer_v2::AdHocSerializerV2;
+    /// use ycg_core::model::AdHocGranularity;
+    ///
+    /// let serializer = AdHocSerializerV2::new(AdHocGranularity::InlineSignatures)
+    ///     .with_optional_param_markers(true);
+    /// ```
+    pub fn with_optional_param_markers(mut self, enabled: bool) -> Self {
+        self.optional_param_markers = enabled;
+        self
+    }
+
+    /// Summarize QueryBuilder, fetch/axios and iterator chains longer than
+    /// `threshold` characters to their result type in inline signatures
+    ///
+    /// # Examples
+    /// ```
+    /// use ycg_core::adhoc_serializer_v2::AdHocSerializerV2;
+    /// use ycg_core::model::AdHocGranularity;
+    ///
+    /// let serializer = AdHocSerializerV2::new(AdHocGranularity::InlineSignatures)
+    ///     .with_chain_summary_threshold(60);
+    /// ```
+    pub fn with_chain_summary_threshold(mut self, threshold: usize) -> Self {
+        self.chain_summary_threshold = threshold;
+        self
+    }
+
+    /// Override the granularity level for specific symbol kinds, e.g.
+    /// signatures for methods while variables stay name-only; kinds not in
+    /// the map use the level given to [`AdHocSerializerV2::new`]
+    ///
+    /// # Examples
+    /// ```
+    /// use std::collections::HashMap;
+    /// use ycg_core::adhoc_serializer_v2::AdHocSerializerV2;
+    /// use ycg_core::model::{AdHocGranularity, ScipSymbolKind};
+    ///
+    /// let serializer = AdHocSerializerV2::new(AdHocGranularity::Default).with_kind_granularity(
+    ///     HashMap::from([(ScipSymbolKind::Method, AdHocGranularity::InlineSignatures)]),
+    /// );
+    /// ```
+    pub fn with_kind_granularity(
+        mut self,
+        kind_granularity: HashMap<ScipSymbolKind, AdHocGranularity>,
+    ) -> Self {
+        self.kind_granularity = kind_granularity;
+        self
+    }
+
+    /// Granularity level applied to symbols of `kind`
+    fn granularity_for(&self, kind: ScipSymbolKind) -> AdHocGranularity {
+        self.kind_granularity
+            .get(&kind)
+            .copied()
+            .unwrap_or(self.granularity)
+    }
+
+    /// True when every kind is serialized at Level 0 (no extraction needed)
+    fn is_structural_only(&self) -> bool {
+        self.granularity == AdHocGranularity::Default
+            && self
+                .kind_granularity
+                .values()
+                .all(|level| *level == AdHocGranularity::Default)
+    }
+
+    /// Compact signature in the configured style, wrappers abbreviated if enabled
+    fn inline_signature(&self, node: &SymbolNode) -> Option<String> {
+        let sig = SignatureExtractor::extract_signature_with_chain_threshold(
+            node,
+            self.signature_style,
+            self.optional_param_markers,
+            self.chain_summary_threshold,
+        )?;
+        Some(match &self.wrapper_marker {
+            Some(marker) => TypeAbbreviator::abbreviate_wrappers(&sig, marker),
+            None => sig,
+        })
     }
 
     /// Serialize a symbol node based on the configured granularity level
@@ -61,7 +196,7 @@ impl AdHocSerializerV2 {
     ///
     /// **Validates: Requirements 1.1, 1.2, 1.3, 1.4, 2.1, 2.8, 3.1, 3.2**
     pub fn serialize_node(&self, node: &SymbolNode, source: &str) -> String {
-        match self.granularity {
+        match self.granularity_for(node.kind) {
             AdHocGranularity::Default => self.serialize_default(node),
             AdHocGranularity::InlineSignatures => self.serialize_with_signature(node, source),
             AdHocGranularity::InlineLogic => self.serialize_with_logic(node, source),
@@ -118,7 +253,7 @@ impl AdHocSerializerV2 {
         let kind = Self::kind_to_string(&node.kind);
 
         // Try to extract signature
-        let name_or_sig = if let Some(sig) = SignatureExtractor::extract_signature(node) {
+        let name_or_sig = if let Some(sig) = self.inline_signature(node) {
             Self::escape_pipes(&sig)
         } else {
             // Fallback to simple name (Requirement 2.7)
@@ -152,7 +287,7 @@ impl AdHocSerializerV2 {
         let kind = Self::kind_to_string(&node.kind);
 
         // Extract signature (always included at Level 2)
-        let name_or_sig = if let Some(sig) = SignatureExtractor::extract_signature(node) {
+        let name_or_sig = if let Some(sig) = self.inline_signature(node) {
             Self::escape_pipes(&sig)
         } else {
             Self::escape_pipes(&node.name)
@@ -221,10 +356,7 @@ impl AdHocSerializerV2 {
                 // Cache the AST for this file if we're using Level 1 or Level 2
                 // This allows multiple symbols from the same file to reuse the parsed AST
                 if !source.is_empty()
-                    && matches!(
-                        self.granularity,
-                        AdHocGranularity::InlineSignatures | AdHocGranularity::InlineLogic
-                    )
+                    && self.granularity_for(node.kind) != AdHocGranularity::Default
                 {
                     // Pre-populate cache for this file
                     // The actual parsing will happen in the extractors when tree-sitter is integrated
@@ -244,7 +376,9 @@ impl AdHocSerializerV2 {
                 .entry(edge.from.clone())
                 .or_insert_with(BTreeMap::new);
 
-            let targets = node_edges.entry(edge.edge_type).or_insert_with(Vec::new);
+            let targets = node_edges
+                .entry(edge.edge_type.clone())
+                .or_insert_with(Vec::new);
 
             targets.push(edge.to.clone());
         }
@@ -293,7 +427,7 @@ impl AdHocSerializerV2 {
     ) -> YcgGraphAdHoc {
         // For Level 0 (Default), no extraction is needed, so parallel processing
         // would add overhead without benefit. Use sequential processing.
-        if matches!(self.granularity, AdHocGranularity::Default) {
+        if self.is_structural_only() {
             return self.serialize_graph(graph, sources);
         }
 
@@ -320,7 +454,9 @@ impl AdHocSerializerV2 {
                 .entry(edge.from.clone())
                 .or_insert_with(BTreeMap::new);
 
-            let targets = node_edges.entry(edge.edge_type).or_insert_with(Vec::new);
+            let targets = node_edges
+                .entry(edge.edge_type.clone())
+                .or_insert_with(Vec::new);
 
             targets.push(edge.to.clone());
         }
@@ -371,7 +507,7 @@ impl AdHocSerializerV2 {
         cache: &Mutex<AstCache>,
     ) -> YcgGraphAdHoc {
         // For Level 0 (Default), no extraction is needed
-        if matches!(self.granularity, AdHocGranularity::Default) {
+        if self.is_structural_only() {
             return self.serialize_graph(graph, sources);
         }
 
@@ -385,10 +521,7 @@ impl AdHocSerializerV2 {
 
                 // Pre-populate cache for this file if we're using Level 1 or Level 2
                 if !source.is_empty()
-                    && matches!(
-                        self.granularity,
-                        AdHocGranularity::InlineSignatures | AdHocGranularity::InlineLogic
-                    )
+                    && self.granularity_for(node.kind) != AdHocGranularity::Default
                 {
                     // Lock the cache for this operation
                     if let Ok(mut cache_guard) = cache.lock() {
@@ -409,7 +542,9 @@ impl AdHocSerializerV2 {
                 .entry(edge.from.clone())
                 .or_insert_with(BTreeMap::new);
 
-            let targets = node_edges.entry(edge.edge_type).or_insert_with(Vec::new);
+            let targets = node_edges
+                .entry(edge.edge_type.clone())
+                .or_insert_with(Vec::new);
 
             targets.push(edge.to.clone());
         }
@@ -446,6 +581,7 @@ impl AdHocSerializerV2 {
     /// - Function → "function"
     /// - Variable → "variable"
     /// - Interface → "interface"
+    /// - Enum → "enum"
     /// - Module → "module"
     /// - File → "file"
     fn kind_to_string(kind: &ScipSymbolKind) -> &'static str {
@@ -455,6 +591,7 @@ impl AdHocSerializerV2 {
             ScipSymbolKind::Function => "function",
             ScipSymbolKind::Variable => "variable",
             ScipSymbolKind::Interface => "interface",
+            ScipSymbolKind::Enum => "enum",
             ScipSymbolKind::Module => "module",
             ScipSymbolKind::File => "file",
         }
@@ -476,10 +613,8 @@ mod tests {
             id: id.to_string(),
             name: name.to_string(),
             kind,
-            parent_id: None,
-            documentation: None,
             signature,
-            logic: None,
+            ..Default::default()
         }
     }
 
@@ -529,6 +664,7 @@ mod tests {
             (ScipSymbolKind::Function, "function"),
             (ScipSymbolKind::Variable, "variable"),
             (ScipSymbolKind::Interface, "interface"),
+            (ScipSymbolKind::Enum, "enum"),
         ];
 
         for (kind, expected_str) in kinds {
@@ -563,6 +699,54 @@ mod tests {
         assert!(result.ends_with("|method"));
     }
 
+    #[test]
+    fn test_serialize_level_1_types_only_signature_style() {
+        let node = create_test_node(
+            "update_91ab",
+            "update",
+            ScipSymbolKind::Method,
+            Some("update(id: number, dto: UpdateUserDto): Promise<User>".to_string()),
+        );
+
+        let named = AdHocSerializerV2::new(AdHocGranularity::InlineSignatures);
+        let types_only = AdHocSerializerV2::new(AdHocGranularity::InlineSignatures)
+            .with_signature_style(SignatureStyle::TypesOnly);
+
+        assert_eq!(
+            named.serialize_node(&node, ""),
+            "update_91ab|update(id:num,dto:UpdateUserDto):Promise<User>|method"
+        );
+        assert_eq!(
+            types_only.serialize_node(&node, ""),
+            "update_91ab|update(num,UpdateUserDto):Promise<User>|method"
+        );
+    }
+
+    #[test]
+    fn test_serialize_level_1_abbreviated_wrappers() {
+        let node = create_test_node(
+            "update_91ab",
+            "update",
+            ScipSymbolKind::Method,
+            Some("update(id: number, dto: UpdateUserDto): Promise<User>".to_string()),
+        );
+
+        let abbreviated = AdHocSerializerV2::new(AdHocGranularity::InlineSignatures)
+            .with_wrapper_abbreviation(Some("~".to_string()));
+        assert_eq!(
+            abbreviated.serialize_node(&node, ""),
+            "update_91ab|update(id:num,dto:UpdateUserDto):~User|method"
+        );
+
+        // Opt-out (default): wrappers kept
+        let kept = AdHocSerializerV2::new(AdHocGranularity::InlineSignatures)
+            .with_wrapper_abbreviation(None);
+        assert_eq!(
+            kept.serialize_node(&node, ""),
+            "update_91ab|update(id:num,dto:UpdateUserDto):Promise<User>|method"
+        );
+    }
+
     #[test]
     fn test_serialize_level_1_without_signature_fallback() {
         // Requirement 2.7: Fall back to simple name if no signature
@@ -723,6 +907,7 @@ mod tests {
             metadata: ProjectMetadata {
                 name: "test".to_string(),
                 version: "1.0".to_string(),
+                ..Default::default()
             },
             definitions: vec![
                 create_test_node("A_0001", "ClassA", ScipSymbolKind::Class, None),
@@ -732,6 +917,7 @@ mod tests {
                 from: "B_0002".to_string(),
                 to: "A_0001".to_string(),
                 edge_type: EdgeType::Calls,
+                call_text: None,
             }],
         };
 
@@ -752,6 +938,7 @@ mod tests {
             metadata: ProjectMetadata {
                 name: "test".to_string(),
                 version: "1.0".to_string(),
+                ..Default::default()
             },
             definitions: vec![
                 create_test_node("A_0001", "ClassA", ScipSymbolKind::Class, None),
@@ -775,6 +962,49 @@ mod tests {
         assert!(adhoc.definitions[1].contains("str")); // abbreviated
     }
 
+    #[test]
+    fn test_serialize_graph_per_kind_granularity() {
+        let serializer = AdHocSerializerV2::new(AdHocGranularity::Default).with_kind_granularity(
+            HashMap::from([(ScipSymbolKind::Method, AdHocGranularity::InlineSignatures)]),
+        );
+
+        let graph = YcgGraph {
+            metadata: ProjectMetadata::default(),
+            definitions: vec![
+                create_test_node(
+                    "B_0002",
+                    "methodB",
+                    ScipSymbolKind::Method,
+                    Some("methodB(id: string): User".to_string()),
+                ),
+                create_test_node(
+                    "C_0003",
+                    "timeout",
+                    ScipSymbolKind::Variable,
+                    Some("const timeout: number".to_string()),
+                ),
+                create_test_node(
+                    "D_0004",
+                    "helper",
+                    ScipSymbolKind::Function,
+                    Some("helper(x: number): number".to_string()),
+                ),
+            ],
+            references: vec![],
+        };
+
+        let sources = HashMap::new();
+        for adhoc in [
+            serializer.serialize_graph(&graph, &sources),
+            serializer.serialize_graph_parallel(&graph, &sources),
+        ] {
+            assert_eq!(adhoc.definitions[0], "B_0002|methodB(id:str):User|method");
+            // Kinds outside the map keep the uniform level: name only
+            assert_eq!(adhoc.definitions[1], "C_0003|timeout|variable");
+            assert_eq!(adhoc.definitions[2], "D_0004|helper|function");
+        }
+    }
+
     #[test]
     fn test_serialize_graph_adjacency_list() {
         let serializer = AdHocSerializerV2::new(AdHocGranularity::Default);
@@ -783,6 +1013,7 @@ mod tests {
             metadata: ProjectMetadata {
                 name: "test".to_string(),
                 version: "1.0".to_string(),
+                ..Default::default()
             },
             definitions: vec![
                 create_test_node("A_0001", "ClassA", ScipSymbolKind::Class, None),
@@ -794,11 +1025,13 @@ mod tests {
                     from: "B_0002".to_string(),
                     to: "A_0001".to_string(),
                     edge_type: EdgeType::Calls,
+                    call_text: None,
                 },
                 ReferenceEdge {
                     from: "B_0002".to_string(),
                     to: "C_0003".to_string(),
                     edge_type: EdgeType::Calls,
+                    call_text: None,
                 },
             ],
         };
@@ -818,6 +1051,46 @@ mod tests {
         assert_eq!(calls[1], "C_0003");
     }
 
+    #[test]
+    fn test_mixed_edge_types_use_single_letter_keys() {
+        let serializer = AdHocSerializerV2::new(AdHocGranularity::Default);
+        let edge = |to: &str, edge_type: EdgeType| ReferenceEdge {
+            from: "B_0002".to_string(),
+            to: to.to_string(),
+            edge_type,
+            call_text: None,
+        };
+
+        let graph = YcgGraph {
+            metadata: ProjectMetadata {
+                name: "test".to_string(),
+                version: "1.0".to_string(),
+                ..Default::default()
+            },
+            definitions: vec![
+                create_test_node("A_0001", "Base", ScipSymbolKind::Class, None),
+                create_test_node("B_0002", "Users", ScipSymbolKind::Class, None),
+                create_test_node("C_0003", "save", ScipSymbolKind::Function, None),
+                create_test_node("D_0004", "User", ScipSymbolKind::Interface, None),
+            ],
+            references: vec![
+                edge("D_0004", EdgeType::References),
+                edge("A_0001", EdgeType::Implements),
+                edge("C_0003", EdgeType::Calls),
+                edge("C_0003", EdgeType::Imports),
+                edge("A_0001", EdgeType::Custom("emits".to_string())),
+            ],
+        };
+
+        let adhoc = serializer.serialize_graph(&graph, &std::collections::HashMap::new());
+        let yaml = serde_yaml::to_string(&adhoc).unwrap();
+        let graph_section = &yaml[yaml.find("graph:").unwrap()..];
+        assert_eq!(
+            graph_section,
+            "graph:\n  B_0002:\n    c:\n    - C_0003\n    r:\n    - D_0004\n    i:\n    - C_0003\n    e:\n    - A_0001\n    emits:\n    - A_0001\n"
+        );
+    }
+
     #[test]
     fn test_serialize_graph_empty() {
         let serializer = AdHocSerializerV2::new(AdHocGranularity::Default);
@@ -826,6 +1099,7 @@ mod tests {
             metadata: ProjectMetadata {
                 name: "empty".to_string(),
                 version: "1.0".to_string(),
+                ..Default::default()
             },
             definitions: vec![],
             references: vec![],
@@ -898,6 +1172,7 @@ mod tests {
             metadata: ProjectMetadata {
                 name: "test".to_string(),
                 version: "1.0".to_string(),
+                ..Default::default()
             },
             definitions: vec![
                 create_test_node("A_0001", "ClassA", ScipSymbolKind::Class, None),
@@ -926,6 +1201,7 @@ mod tests {
             metadata: ProjectMetadata {
                 name: "test".to_string(),
                 version: "1.0".to_string(),
+                ..Default::default()
             },
             definitions: vec![
                 create_test_node("A_0001", "ClassA", ScipSymbolKind::Class, None),
@@ -966,6 +1242,7 @@ mod tests {
             metadata: ProjectMetadata {
                 name: "test".to_string(),
                 version: "1.0".to_string(),
+                ..Default::default()
             },
             definitions: vec![
                 create_test_node("A_0001", "ClassA", ScipSymbolKind::Class, None),
@@ -1015,6 +1292,7 @@ mod tests {
             metadata: ProjectMetadata {
                 name: "test".to_string(),
                 version: "1.0".to_string(),
+                ..Default::default()
             },
             definitions,
             references: vec![],
@@ -1045,6 +1323,7 @@ mod tests {
             metadata: ProjectMetadata {
                 name: "test".to_string(),
                 version: "1.0".to_string(),
+                ..Default::default()
             },
             definitions: vec![
                 create_test_node(
@@ -1084,6 +1363,7 @@ mod tests {
             metadata: ProjectMetadata {
                 name: "test".to_string(),
                 version: "1.0".to_string(),
+                ..Default::default()
             },
             definitions: vec![
                 create_test_node("A_0001", "ClassA", ScipSymbolKind::Class, None),
@@ -1111,6 +1391,7 @@ mod tests {
             metadata: ProjectMetadata {
                 name: "empty".to_string(),
                 version: "1.0".to_string(),
+                ..Default::default()
             },
             definitions: vec![],
             references: vec![],
@@ -1132,6 +1413,7 @@ mod tests {
             metadata: ProjectMetadata {
                 name: "test".to_string(),
                 version: "1.0".to_string(),
+                ..Default::default()
             },
             definitions: vec![
                 create_test_node("A_0001", "ClassA", ScipSymbolKind::Class, None),
@@ -1153,11 +1435,13 @@ mod tests {
                     from: "B_0002".to_string(),
                     to: "A_0001".to_string(),
                     edge_type: EdgeType::Calls,
+                    call_text: None,
                 },
                 ReferenceEdge {
                     from: "B_0002".to_string(),
                     to: "C_0003".to_string(),
                     edge_type: EdgeType::Calls,
+                    call_text: None,
                 },
             ],
         };
@@ -0,0 +1,79 @@
+// crates/ycg_core/src/anchor_prefix.rs
+//! Kind-tagged anchors (`--kind-prefixed-anchors`).
+//!
+//! Anchors are the sanitized name plus a hash suffix (`User_4702`), which
+//! says nothing about what the definition is. In this mode every definition
+//! anchor gets a short tag of its kind (`cls:User_4702`, `fn:getUser_3f4a`),
+//! and the rename is applied to parent references, edges, `delegates_to`
+//! and the symbol index alike, so everything still resolves. Anchors that
+//! are not definitions (file scopes, `ext_` targets) keep their name.
+
+use crate::model::{ScipSymbolKind, SymbolIndex, YcgGraph};
+use std::collections::HashMap;
+
+/// Short tag of a kind, as used in anchors
+pub fn kind_tag(kind: &ScipSymbolKind) -> &'static str {
+    match kind {
+        ScipSymbolKind::File => "file",
+        ScipSymbolKind::Module => "mod",
+        ScipSymbolKind::Class => "cls",
+        ScipSymbolKind::Method => "meth",
+        ScipSymbolKind::Function => "fn",
+        ScipSymbolKind::Variable => "var",
+        ScipSymbolKind::Interface => "iface",
+        ScipSymbolKind::Enum => "enum",
+    }
+}
+
+/// Prefixes every definition anchor with its [`kind_tag`]
+pub fn prefix_anchors(graph: &mut YcgGraph, symbol_index: &mut SymbolIndex) {
+    // An anchor shared by several definitions is renamed after the first
+    let mut renamed: HashMap<String, String> = HashMap::new();
+    for node in &graph.definitions {
+        renamed
+            .entry(node.id.clone())
+            .or_insert_with(|| format!("{}:{}", kind_tag(&node.kind), node.id));
+    }
+    let rename = |anchor: &mut String| {
+        if let Some(new) = renamed.get(anchor.as_str()) {
+            *anchor = new.clone();
+        }
+    };
+
+    for node in &mut graph.definitions {
+        rename(&mut node.id);
+        if let Some(parent) = node.parent_id.as_mut() {
+            rename(parent);
+        }
+        if let Some(target) = node
+            .logic
+            .as_mut()
+            .and_then(|logic| logic.delegates_to.as_mut())
+        {
+            rename(target);
+        }
+    }
+    for edge in &mut graph.references {
+        rename(&mut edge.from);
+        rename(&mut edge.to);
+    }
+    graph.references.sort();
+
+    symbol_index.lines = std::mem::take(&mut symbol_index.lines)
+        .into_iter()
+        .map(|(mut anchor, line)| {
+            rename(&mut anchor);
+            (anchor, line)
+        })
+        .collect();
+    for anchors in symbol_index.files.values_mut() {
+        anchors.iter_mut().for_each(rename);
+    }
+    symbol_index.fingerprints = std::mem::take(&mut symbol_index.fingerprints)
+        .into_iter()
+        .map(|(mut anchor, fingerprint)| {
+            rename(&mut anchor);
+            (anchor, fingerprint)
+        })
+        .collect();
+}
@@ -0,0 +1,159 @@
+// crates/ycg_core/src/api_surface.rs
+
+use crate::model::{ScipSymbolKind, YcgGraph};
+use crate::visibility::{self, Visibility};
+use std::collections::HashSet;
+
+/// Reduces a graph to the public API of its types (`--api-surface`)
+///
+/// Keeps Classes, Interfaces and their public Method children. Constructors,
+/// fields, private/protected methods and everything outside a type are pruned
+/// together with every edge that touches them.
+pub struct ApiSurfaceFilter;
+
+impl ApiSurfaceFilter {
+    pub fn filter_graph(graph: &mut YcgGraph) {
+        let types: HashSet<String> = graph
+            .definitions
+            .iter()
+            .filter(|node| matches!(node.kind, ScipSymbolKind::Class | ScipSymbolKind::Interface))
+            .map(|node| node.id.clone())
+            .collect();
+
+        let kept: HashSet<String> = graph
+            .definitions
+            .iter()
+            .filter(|node| {
+                types.contains(&node.id)
+                    || (node.kind == ScipSymbolKind::Method
+                        && node.parent_id.as_ref().is_some_and(|p| types.contains(p))
+                        && !Self::is_constructor(&node.name)
+                        && visibility::detect(node) == Visibility::Public)
+            })
+            .map(|node| node.id.clone())
+            .collect();
+
+        graph.definitions.retain(|node| kept.contains(&node.id));
+
+        // Types keep their parent only if it survived (nested types)
+        for node in &mut graph.definitions {
+            if node.parent_id.as_ref().is_some_and(|p| !kept.contains(p)) {
+                node.parent_id = None;
+            }
+        }
+
+        graph
+            .references
+            .retain(|edge| kept.contains(&edge.from) && kept.contains(&edge.to));
+    }
+
+    fn is_constructor(name: &str) -> bool {
+        let short_name = name.rsplit(['#', '.', ':']).next().unwrap_or(name);
+        matches!(short_name, "constructor" | "<constructor>")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{EdgeType, ProjectMetadata, ReferenceEdge, SymbolNode};
+
+    fn node(
+        id: &str,
+        name: &str,
+        kind: ScipSymbolKind,
+        parent: Option<&str>,
+        sig: Option<&str>,
+    ) -> SymbolNode {
+        SymbolNode {
+            id: id.to_string(),
+            name: name.to_string(),
+            kind,
+            parent_id: parent.map(|p| p.to_string()),
+            signature: sig.map(|s| s.to_string()),
+            ..Default::default()
+        }
+    }
+
+    fn edge(from: &str, to: &str) -> ReferenceEdge {
+        ReferenceEdge {
+            from: from.to_string(),
+            to: to.to_string(),
+            edge_type: EdgeType::Calls,
+            call_text: None,
+        }
+    }
+
+    #[test]
+    fn test_keeps_class_and_public_methods_only() {
+        let mut graph = YcgGraph {
+            metadata: ProjectMetadata::default(),
+            definitions: vec![
+                node(
+                    "file_0001",
+                    "users.service.ts",
+                    ScipSymbolKind::File,
+                    None,
+                    None,
+                ),
+                node(
+                    "UsersService_a1b2",
+                    "UsersService",
+                    ScipSymbolKind::Class,
+                    Some("file_0001"),
+                    None,
+                ),
+                node(
+                    "constructor_c3d4",
+                    "constructor",
+                    ScipSymbolKind::Method,
+                    Some("UsersService_a1b2"),
+                    Some("constructor(private readonly repo: UsersRepository)"),
+                ),
+                node(
+                    "findOne_e5f6",
+                    "UsersService#findOne",
+                    ScipSymbolKind::Method,
+                    Some("UsersService_a1b2"),
+                    Some("async findOne(id: number): Promise<User>"),
+                ),
+                node(
+                    "hash_0a0b",
+                    "UsersService#hash",
+                    ScipSymbolKind::Method,
+                    Some("UsersService_a1b2"),
+                    Some("private hash(password: string): string"),
+                ),
+                node(
+                    "repo_0c0d",
+                    "UsersService#repo",
+                    ScipSymbolKind::Variable,
+                    Some("UsersService_a1b2"),
+                    None,
+                ),
+                node(
+                    "bootstrap_0e0f",
+                    "bootstrap",
+                    ScipSymbolKind::Function,
+                    Some("file_0001"),
+                    None,
+                ),
+            ],
+            references: vec![
+                edge("findOne_e5f6", "hash_0a0b"),
+                edge("findOne_e5f6", "UsersService_a1b2"),
+                edge("bootstrap_0e0f", "findOne_e5f6"),
+            ],
+        };
+
+        ApiSurfaceFilter::filter_graph(&mut graph);
+
+        let ids: Vec<&str> = graph.definitions.iter().map(|n| n.id.as_str()).collect();
+        assert_eq!(ids, vec!["UsersService_a1b2", "findOne_e5f6"]);
+        assert_eq!(graph.definitions[0].parent_id, None);
+        assert_eq!(
+            graph.references,
+            vec![edge("findOne_e5f6", "UsersService_a1b2")]
+        );
+    }
+}
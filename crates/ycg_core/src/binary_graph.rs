@@ -0,0 +1,355 @@
+// crates/ycg_core/src/binary_graph.rs
+//! Binary graph encoding (`--output-format bincode`, `bincode` feature).
+//!
+//! Meant for storing and shipping many graphs, not for LLM prompts. bincode
+//! writes struct fields positionally, so it cannot honor the
+//! `skip_serializing_if` fields of the YAML model: the graph is encoded
+//! through mirror structs that always write every field, in model order.
+//! The conversions destructure the model exhaustively, so a new model field
+//! does not compile until it is mirrored here. Integers and lengths use
+//! varint encoding. A `YCGB` magic and a format version byte precede the
+//! payload so `decode` can reject other files.
+
+use crate::model::{
+    EdgeType, LogicMetadata, ProjectMetadata, ReferenceEdge, ScipSymbolKind, SymbolNode, YcgGraph,
+};
+use anyhow::{Context, Result, bail};
+use bincode::Options;
+use serde::{Deserialize, Serialize};
+
+const MAGIC: &[u8; 4] = b"YCGB";
+const FORMAT_VERSION: u8 = 1;
+
+/// `YcgGraph` without its section renames and skipped empty sections
+#[derive(Serialize, Deserialize)]
+struct BinaryGraph {
+    metadata: BinaryMetadata,
+    definitions: Vec<BinaryNode>,
+    references: Vec<BinaryEdge>,
+}
+
+/// [`ProjectMetadata`], every field written
+#[derive(Serialize, Deserialize)]
+struct BinaryMetadata {
+    name: String,
+    version: String,
+    stripped_prefix: Option<String>,
+}
+
+/// [`SymbolNode`], every field written
+#[derive(Serialize, Deserialize)]
+struct BinaryNode {
+    id: String,
+    name: String,
+    kind: ScipSymbolKind,
+    parent_id: Option<String>,
+    documentation: Option<String>,
+    signature: Option<String>,
+    logic: Option<BinaryLogic>,
+    throws: Vec<String>,
+    deprecated: bool,
+    owners: Vec<String>,
+    generator: bool,
+    ref_count: u32,
+    route: Option<String>,
+    env_access: Vec<String>,
+    layer: Option<String>,
+    source: Option<String>,
+    merged_interfaces: Vec<String>,
+    complexity: Option<u32>,
+    path: Option<String>,
+    annotations: Vec<String>,
+    pure: bool,
+    queries: Vec<String>,
+    injectable: bool,
+    scip_symbol: Option<String>,
+}
+
+/// [`LogicMetadata`], every field written
+#[derive(Serialize, Deserialize)]
+struct BinaryLogic {
+    preconditions: Vec<String>,
+    steps: Vec<String>,
+    delegates_to: Option<String>,
+}
+
+/// [`ReferenceEdge`], every field written
+#[derive(Serialize, Deserialize)]
+struct BinaryEdge {
+    from: String,
+    to: String,
+    edge_type: EdgeType,
+    call_text: Option<String>,
+}
+
+impl From<&YcgGraph> for BinaryGraph {
+    fn from(graph: &YcgGraph) -> Self {
+        let ProjectMetadata {
+            name,
+            version,
+            stripped_prefix,
+        } = graph.metadata.clone();
+        Self {
+            metadata: BinaryMetadata {
+                name,
+                version,
+                stripped_prefix,
+            },
+            definitions: graph
+                .definitions
+                .iter()
+                .cloned()
+                .map(BinaryNode::from)
+                .collect(),
+            references: graph
+                .references
+                .iter()
+                .cloned()
+                .map(|edge| {
+                    let ReferenceEdge {
+                        from,
+                        to,
+                        edge_type,
+                        call_text,
+                    } = edge;
+                    BinaryEdge {
+                        from,
+                        to,
+                        edge_type,
+                        call_text,
+                    }
+                })
+                .collect(),
+        }
+    }
+}
+
+impl From<BinaryGraph> for YcgGraph {
+    fn from(binary: BinaryGraph) -> Self {
+        let BinaryMetadata {
+            name,
+            version,
+            stripped_prefix,
+        } = binary.metadata;
+        Self {
+            metadata: ProjectMetadata {
+                name,
+                version,
+                stripped_prefix,
+            },
+            definitions: binary
+                .definitions
+                .into_iter()
+                .map(SymbolNode::from)
+                .collect(),
+            references: binary
+                .references
+                .into_iter()
+                .map(|edge| ReferenceEdge {
+                    from: edge.from,
+                    to: edge.to,
+                    edge_type: edge.edge_type,
+                    call_text: edge.call_text,
+                })
+                .collect(),
+        }
+    }
+}
+
+impl From<SymbolNode> for BinaryNode {
+    fn from(node: SymbolNode) -> Self {
+        let SymbolNode {
+            id,
+            name,
+            kind,
+            parent_id,
+            documentation,
+            signature,
+            logic,
+            throws,
+            deprecated,
+            owners,
+            generator,
+            ref_count,
+            route,
+            env_access,
+            layer,
+            source,
+            merged_interfaces,
+            complexity,
+            path,
+            annotations,
+            pure,
+            queries,
+            injectable,
+            scip_symbol,
+        } = node;
+        Self {
+            id,
+            name,
+            kind,
+            parent_id,
+            documentation,
+            signature,
+            logic: logic.map(|logic| {
+                let LogicMetadata {
+                    preconditions,
+                    steps,
+                    delegates_to,
+                } = logic;
+                BinaryLogic {
+                    preconditions,
+                    steps,
+                    delegates_to,
+                }
+            }),
+            throws,
+            deprecated,
+            owners,
+            generator,
+            ref_count,
+            route,
+            env_access,
+            layer,
+            source,
+            merged_interfaces,
+            complexity,
+            path,
+            annotations,
+            pure,
+            queries,
+            injectable,
+            scip_symbol,
+        }
+    }
+}
+
+impl From<BinaryNode> for SymbolNode {
+    fn from(node: BinaryNode) -> Self {
+        Self {
+            id: node.id,
+            name: node.name,
+            kind: node.kind,
+            parent_id: node.parent_id,
+            documentation: node.documentation,
+            signature: node.signature,
+            logic: node.logic.map(|logic| LogicMetadata {
+                preconditions: logic.preconditions,
+                steps: logic.steps,
+                delegates_to: logic.delegates_to,
+            }),
+            throws: node.throws,
+            deprecated: node.deprecated,
+            owners: node.owners,
+            generator: node.generator,
+            ref_count: node.ref_count,
+            route: node.route,
+            env_access: node.env_access,
+            layer: node.layer,
+            source: node.source,
+            merged_interfaces: node.merged_interfaces,
+            complexity: node.complexity,
+            path: node.path,
+            annotations: node.annotations,
+            pure: node.pure,
+            queries: node.queries,
+            injectable: node.injectable,
+            scip_symbol: node.scip_symbol,
+        }
+    }
+}
+
+/// Encodes a graph as `YCGB` + format version + bincode payload
+pub fn encode(graph: &YcgGraph) -> Result<Vec<u8>> {
+    let mut bytes = MAGIC.to_vec();
+    bytes.push(FORMAT_VERSION);
+    bincode::DefaultOptions::new()
+        .serialize_into(&mut bytes, &BinaryGraph::from(graph))
+        .context("Failed to encode graph as bincode")?;
+    Ok(bytes)
+}
+
+/// Decodes bytes written by [`encode`]
+pub fn decode(bytes: &[u8]) -> Result<YcgGraph> {
+    let Some(payload) = bytes.strip_prefix(MAGIC.as_slice()) else {
+        bail!("Not a YCG binary graph (missing YCGB header)");
+    };
+    let Some((&version, payload)) = payload.split_first() else {
+        bail!("Truncated YCG binary graph");
+    };
+    if version != FORMAT_VERSION {
+        bail!(
+            "Unsupported YCG binary graph version {} (expected {})",
+            version,
+            FORMAT_VERSION
+        );
+    }
+
+    let binary: BinaryGraph = bincode::DefaultOptions::new()
+        .deserialize(payload)
+        .context("Failed to decode bincode graph")?;
+    Ok(binary.into())
+}
+
+/// Decodes to the flat YAML layout `generate` writes by default
+pub fn decode_to_yaml(bytes: &[u8]) -> Result<String> {
+    Ok(serde_yaml::to_string(&decode(bytes)?)?)
+}
+
+/// Decodes to pretty-printed JSON
+pub fn decode_to_json(bytes: &[u8]) -> Result<String> {
+    Ok(serde_json::to_string_pretty(&decode(bytes)?)? + "\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GRAPH: &str = r#"_meta:
+  name: ycg
+  version: 1.0.0
+_defs:
+- id: UserService_a1b2
+  n: UserService
+  t: class
+- id: find_c3d4
+  n: UserService#find
+  t: method
+  parent_id: UserService_a1b2
+  sig: 'find(id: number): User'
+  logic:
+    pre:
+    - must(id > 0)
+  throws:
+  - NotFoundException
+  ref_count: 1
+  env_access:
+  - DATABASE_URL
+graph:
+- from: find_c3d4
+  to: UserService_a1b2
+  type: references
+- from: find_c3d4
+  to: ext_e5f6
+  type: emits
+  call: this.bus.emit(evt)
+"#;
+
+    #[test]
+    fn test_yaml_to_bincode_to_yaml_round_trip() {
+        let graph: YcgGraph = serde_yaml::from_str(GRAPH).unwrap();
+        let bytes = encode(&graph).unwrap();
+        assert!(bytes.starts_with(b"YCGB"));
+        assert!(bytes.len() < GRAPH.len(), "{} bytes", bytes.len());
+
+        let decoded = decode(&bytes).unwrap();
+        assert_eq!(serde_yaml::to_string(&decoded).unwrap(), GRAPH);
+    }
+
+    #[test]
+    fn test_decode_rejects_other_files() {
+        assert!(decode(b"_meta:\n  name: ycg\n").is_err());
+        assert!(decode(b"YCGB").is_err());
+        assert!(decode(&[b'Y', b'C', b'G', b'B', FORMAT_VERSION + 1]).is_err());
+    }
+}
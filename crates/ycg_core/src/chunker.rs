@@ -0,0 +1,316 @@
+// crates/ycg_core/src/chunker.rs
+
+use crate::count_tokens;
+use crate::model::{ReferenceEdge, ScipSymbolKind, SymbolNode, YcgGraph, YcgGraphOptimized};
+use anyhow::{Context, Result, bail};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+/// Splits a graph into self-contained chunks for RAG ingestion (`ycg_cli chunk`)
+///
+/// Each chunk is a complete `YcgGraph` (same `_meta`) whose serialized YAML
+/// stays under the token limit. A class travels with its methods and fields
+/// whenever the group fits in one chunk; a single definition is never split.
+/// Every edge is emitted once, in the chunk holding its source node.
+pub struct GraphChunker {
+    max_tokens: usize,
+}
+
+impl GraphChunker {
+    pub fn new(max_tokens: usize) -> Self {
+        Self { max_tokens }
+    }
+
+    /// Parse a flat or compact (adjacency list) YAML graph
+    pub fn load_graph_yaml(yaml: &str) -> Result<YcgGraph> {
+        if let Ok(graph) = serde_yaml::from_str::<YcgGraph>(yaml) {
+            return Ok(graph);
+        }
+        let optimized: YcgGraphOptimized =
+            serde_yaml::from_str(yaml).context("Input is not a YCG YAML graph")?;
+        let references = optimized
+            .adjacency
+            .into_iter()
+            .flat_map(|(from, by_type)| {
+                by_type.into_iter().flat_map(move |(edge_type, targets)| {
+                    let from = from.clone();
+                    targets.into_iter().map(move |to| ReferenceEdge {
+                        from: from.clone(),
+                        to,
+                        edge_type: edge_type.clone(),
+                        call_text: None,
+                    })
+                })
+            })
+            .collect();
+        Ok(YcgGraph {
+            metadata: optimized.metadata,
+            definitions: optimized.definitions,
+            references,
+        })
+    }
+
+    /// Split `graph` into chunks whose YAML is at most `max_tokens` tokens
+    ///
+    /// # Errors
+    /// Fails if a single definition (with its outgoing edges) cannot fit
+    /// in a chunk on its own.
+    pub fn chunk(&self, graph: &YcgGraph) -> Result<Vec<YcgGraph>> {
+        let mut edges_by_source: HashMap<&str, Vec<&ReferenceEdge>> = HashMap::new();
+        for edge in &graph.references {
+            edges_by_source
+                .entry(edge.from.as_str())
+                .or_default()
+                .push(edge);
+        }
+
+        let mut chunks: Vec<YcgGraph> = Vec::new();
+        let mut current = self.empty_chunk(graph);
+
+        for group in group_definitions(&graph.definitions) {
+            // Whole group (class + members) when it fits, one node at a time otherwise
+            let units: Vec<Vec<&SymbolNode>> =
+                if self.fits(&self.chunk_of(graph, &group, &edges_by_source)) {
+                    vec![group]
+                } else {
+                    group.into_iter().map(|node| vec![node]).collect()
+                };
+
+            for unit in units {
+                let mut candidate = current.clone();
+                push_unit(&mut candidate, &unit, &edges_by_source);
+                if self.fits(&candidate) {
+                    current = candidate;
+                    continue;
+                }
+
+                if !current.definitions.is_empty() {
+                    chunks.push(std::mem::replace(&mut current, self.empty_chunk(graph)));
+                }
+                push_unit(&mut current, &unit, &edges_by_source);
+                if !self.fits(&current) {
+                    bail!(
+                        "Definition '{}' needs more than {} tokens on its own",
+                        unit[0].id,
+                        self.max_tokens
+                    );
+                }
+            }
+        }
+
+        if !current.definitions.is_empty() {
+            chunks.push(current);
+        }
+        Ok(chunks)
+    }
+
+    /// Write chunks as `chunk_0000.yaml`, `chunk_0001.yaml`, ... into `dir`
+    pub fn write_chunks(chunks: &[YcgGraph], dir: &Path) -> Result<Vec<PathBuf>> {
+        std::fs::create_dir_all(dir)
+            .with_context(|| format!("Failed to create directory: {:?}", dir))?;
+
+        let mut paths = Vec::with_capacity(chunks.len());
+        for (i, chunk) in chunks.iter().enumerate() {
+            let path = dir.join(format!("chunk_{:04}.yaml", i));
+            std::fs::write(&path, serde_yaml::to_string(chunk)?)
+                .with_context(|| format!("Failed to write chunk: {:?}", path))?;
+            paths.push(path);
+        }
+        Ok(paths)
+    }
+
+    fn empty_chunk(&self, graph: &YcgGraph) -> YcgGraph {
+        YcgGraph {
+            metadata: graph.metadata.clone(),
+            definitions: Vec::new(),
+            references: Vec::new(),
+        }
+    }
+
+    fn chunk_of(
+        &self,
+        graph: &YcgGraph,
+        unit: &[&SymbolNode],
+        edges_by_source: &HashMap<&str, Vec<&ReferenceEdge>>,
+    ) -> YcgGraph {
+        let mut chunk = self.empty_chunk(graph);
+        push_unit(&mut chunk, unit, edges_by_source);
+        chunk
+    }
+
+    fn fits(&self, chunk: &YcgGraph) -> bool {
+        chunk_tokens(chunk) <= self.max_tokens
+    }
+}
+
+/// Tokens of a chunk as it will be written
+pub fn chunk_tokens(chunk: &YcgGraph) -> usize {
+    serde_yaml::to_string(chunk)
+        .map(|yaml| count_tokens(&yaml))
+        .unwrap_or(usize::MAX)
+}
+
+fn push_unit(
+    chunk: &mut YcgGraph,
+    unit: &[&SymbolNode],
+    edges_by_source: &HashMap<&str, Vec<&ReferenceEdge>>,
+) {
+    for node in unit {
+        chunk.definitions.push((*node).clone());
+        if let Some(edges) = edges_by_source.get(node.id.as_str()) {
+            chunk.references.extend(edges.iter().map(|e| (*e).clone()));
+        }
+    }
+}
+
+/// Groups each type-level definition with its descendants, in input order.
+///
+/// Files and modules are groups of their own so that a whole file is not
+/// forced into one chunk; their children start new groups.
+fn group_definitions(definitions: &[SymbolNode]) -> Vec<Vec<&SymbolNode>> {
+    let by_id: HashMap<&str, &SymbolNode> =
+        definitions.iter().map(|n| (n.id.as_str(), n)).collect();
+
+    let is_container =
+        |node: &SymbolNode| matches!(node.kind, ScipSymbolKind::File | ScipSymbolKind::Module);
+
+    // Topmost ancestor below any file/module node
+    let group_root = |node: &SymbolNode| -> String {
+        let mut current = node;
+        let mut seen = HashSet::new();
+        while !is_container(current) && seen.insert(current.id.as_str()) {
+            match current.parent_id.as_deref().and_then(|p| by_id.get(p)) {
+                Some(parent) if !is_container(parent) => current = parent,
+                _ => break,
+            }
+        }
+        current.id.clone()
+    };
+
+    let mut order: Vec<String> = Vec::new();
+    let mut groups: HashMap<String, Vec<&SymbolNode>> = HashMap::new();
+    for node in definitions {
+        let root = group_root(node);
+        if !groups.contains_key(&root) {
+            order.push(root.clone());
+        }
+        groups.entry(root).or_default().push(node);
+    }
+
+    order
+        .into_iter()
+        .filter_map(|root| groups.remove(&root))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{EdgeType, ProjectMetadata};
+
+    fn create_test_node(id: &str, kind: ScipSymbolKind, parent_id: Option<&str>) -> SymbolNode {
+        SymbolNode {
+            id: id.to_string(),
+            name: id.split('_').next().unwrap().to_string(),
+            kind,
+            parent_id: parent_id.map(|p| p.to_string()),
+            signature: Some(format!("{}(input: RequestDto): Promise<ResponseDto>", id)),
+            ..Default::default()
+        }
+    }
+
+    fn sample_graph() -> YcgGraph {
+        let mut definitions = vec![create_test_node("main_f001", ScipSymbolKind::File, None)];
+        let mut references = Vec::new();
+        for class in ["Users", "Orders", "Items", "Payments"] {
+            let class_id = format!("{}Service_{:04x}", class, class.len());
+            definitions.push(create_test_node(
+                &class_id,
+                ScipSymbolKind::Class,
+                Some("main_f001"),
+            ));
+            for method in ["create", "findAll", "findOne", "remove"] {
+                let method_id = format!("{}{}_{:04x}", class, method, method.len());
+                definitions.push(create_test_node(
+                    &method_id,
+                    ScipSymbolKind::Method,
+                    Some(&class_id),
+                ));
+                references.push(ReferenceEdge {
+                    from: method_id,
+                    to: "UsersService_0005".to_string(),
+                    edge_type: EdgeType::Calls,
+                    call_text: None,
+                });
+            }
+        }
+        YcgGraph {
+            metadata: ProjectMetadata {
+                name: "test".to_string(),
+                version: "1.0.0".to_string(),
+                ..Default::default()
+            },
+            definitions,
+            references,
+        }
+    }
+
+    #[test]
+    fn test_chunks_under_limit_and_each_definition_once() {
+        let graph = sample_graph();
+        let max_tokens = 400;
+        let chunks = GraphChunker::new(max_tokens).chunk(&graph).unwrap();
+
+        assert!(chunks.len() > 1);
+        for chunk in &chunks {
+            assert!(chunk_tokens(chunk) <= max_tokens);
+        }
+
+        let mut seen: HashMap<&str, usize> = HashMap::new();
+        for chunk in &chunks {
+            for node in &chunk.definitions {
+                *seen.entry(node.id.as_str()).or_insert(0) += 1;
+            }
+        }
+        assert_eq!(seen.len(), graph.definitions.len());
+        assert!(seen.values().all(|&count| count == 1));
+
+        let edge_total: usize = chunks.iter().map(|c| c.references.len()).sum();
+        assert_eq!(edge_total, graph.references.len());
+    }
+
+    #[test]
+    fn test_class_kept_with_its_methods() {
+        let graph = sample_graph();
+        let chunks = GraphChunker::new(400).chunk(&graph).unwrap();
+
+        let chunk_of = |id: &str| {
+            chunks
+                .iter()
+                .position(|c| c.definitions.iter().any(|n| n.id == id))
+                .unwrap()
+        };
+        for node in &graph.definitions {
+            if node.kind == ScipSymbolKind::Method {
+                assert_eq!(
+                    chunk_of(&node.id),
+                    chunk_of(node.parent_id.as_ref().unwrap())
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_oversized_definition_is_an_error() {
+        let graph = sample_graph();
+        assert!(GraphChunker::new(10).chunk(&graph).is_err());
+    }
+
+    #[test]
+    fn test_load_compact_graph() {
+        let yaml = "_meta:\n  name: test\n  version: 1.0.0\n_defs:\n- id: a_0001\n  n: a\n  t: function\ngraph:\n  a_0001:\n    calls:\n    - b_0002\n";
+        let graph = GraphChunker::load_graph_yaml(yaml).unwrap();
+        assert_eq!(graph.references.len(), 1);
+        assert_eq!(graph.references[0].to, "b_0002");
+    }
+}
@@ -0,0 +1,205 @@
+// crates/ycg_core/src/compiled_filters.rs
+//! User-supplied patterns, compiled once per run.
+//!
+//! A run may build several graphs from the same configuration (the main
+//! graph, the `--baseline` graph, the `--tests separate` graph), and the
+//! patterns they use are matched against every definition, call or file.
+//! They are compiled here, once, and the conversion and filter passes
+//! borrow them: nothing downstream compiles a pattern. Invalid edge rules
+//! and `--inline-source` globs are skipped with a warning (emitted once);
+//! invalid name regexes are errors.
+
+use crate::YcgConfig;
+use crate::event_log::Event;
+use crate::layers::LayerRules;
+use crate::model::EdgeType;
+use crate::name_filter::NameFilter;
+use anyhow::Result;
+use regex::Regex;
+
+#[cfg(test)]
+thread_local! {
+    /// Patterns compiled on this thread, read by the tests
+    static COMPILED_PATTERNS: std::cell::Cell<usize> = const { std::cell::Cell::new(0) };
+}
+
+/// Compiles a user-supplied regex (counted in test builds)
+pub(crate) fn compile_regex(pattern: &str) -> Result<Regex, regex::Error> {
+    #[cfg(test)]
+    COMPILED_PATTERNS.with(|count| count.set(count.get() + 1));
+    Regex::new(pattern)
+}
+
+/// Compiles a user-supplied glob (counted in test builds)
+pub(crate) fn compile_glob(pattern: &str) -> Result<glob::Pattern, glob::PatternError> {
+    #[cfg(test)]
+    COMPILED_PATTERNS.with(|count| count.set(count.get() + 1));
+    glob::Pattern::new(pattern)
+}
+
+pub struct CompiledFilters {
+    /// Symbol name filter (`--include-name` / `--exclude-name`), if any
+    pub name_filter: Option<NameFilter>,
+    /// Custom edge types of call expressions (`edgeRules`), first match wins
+    pub edge_rules: Vec<(Regex, EdgeType)>,
+    /// Architectural layer per document (`layerRules`)
+    pub layer_rules: LayerRules,
+    /// Definitions whose full source is inlined (`--inline-source`)
+    pub inline_source: Vec<glob::Pattern>,
+}
+
+impl CompiledFilters {
+    /// Compiles every pattern of `config`
+    pub fn new(config: &YcgConfig) -> Result<Self> {
+        let name_filter = if config.name_filter.is_empty() {
+            None
+        } else {
+            Some(NameFilter::new(&config.name_filter)?)
+        };
+        Ok(Self {
+            name_filter,
+            ..Self::for_conversion(config)
+        })
+    }
+
+    /// Compiles the patterns used while converting the index (no name filter)
+    pub fn for_conversion(config: &YcgConfig) -> Self {
+        let edge_rules = config
+            .edge_rules
+            .iter()
+            .filter_map(|rule| match compile_regex(&rule.pattern) {
+                Ok(pattern) => Some((pattern, EdgeType::from_name(&rule.edge_type))),
+                Err(err) => {
+                    config.event_log.emit(Event::warning(format!(
+                        "Ignoring edge rule '{}': {}",
+                        rule.pattern, err
+                    )));
+                    None
+                }
+            })
+            .collect();
+
+        let inline_source = config
+            .inline_source
+            .iter()
+            .filter_map(|pattern| match compile_glob(pattern) {
+                Ok(glob) => Some(glob),
+                Err(err) => {
+                    config.event_log.emit(Event::warning(format!(
+                        "Ignoring --inline-source '{}': {}",
+                        pattern, err
+                    )));
+                    None
+                }
+            })
+            .collect();
+
+        Self {
+            name_filter: None,
+            edge_rules,
+            layer_rules: LayerRules::new(&config.layer_rules, &config.event_log),
+            inline_source,
+        }
+    }
+
+    /// Number of compiled patterns, over all filters
+    pub fn pattern_count(&self) -> usize {
+        self.name_filter
+            .as_ref()
+            .map_or(0, NameFilter::pattern_count)
+            + self.edge_rules.len()
+            + self.layer_rules.pattern_count()
+            + self.inline_source.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{
+        EdgeRule, LayerRule, NameFilterConfig, ProjectMetadata, ScipSymbolKind, SymbolNode,
+        YcgGraph,
+    };
+    use crate::scip_proto;
+
+    fn graph(size: usize) -> YcgGraph {
+        let definitions = (0..size)
+            .map(|i| {
+                let name = if i % 2 == 0 {
+                    format!("UserRepo{}Mock", i)
+                } else {
+                    format!("find{}", i)
+                };
+                SymbolNode {
+                    id: format!("{}_{:04x}", name, i),
+                    name,
+                    kind: ScipSymbolKind::Method,
+                    ..Default::default()
+                }
+            })
+            .collect();
+        YcgGraph {
+            metadata: ProjectMetadata::default(),
+            definitions,
+            references: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_patterns_are_compiled_once_per_run() {
+        let config = YcgConfig {
+            name_filter: NameFilterConfig {
+                include_patterns: Vec::new(),
+                exclude_patterns: vec!["Mock$".to_string(), "^test".to_string()],
+            },
+            edge_rules: vec![EdgeRule {
+                pattern: r"emit\(".to_string(),
+                edge_type: "emits".to_string(),
+            }],
+            layer_rules: vec![LayerRule {
+                pattern: "src/controllers/**".to_string(),
+                layer: "controller".to_string(),
+            }],
+            // Invalid glob: skipped, not compiled again later
+            inline_source: vec!["[".to_string()],
+            ..Default::default()
+        };
+        COMPILED_PATTERNS.with(|count| count.set(0));
+        let filters = CompiledFilters::new(&config).unwrap();
+        assert_eq!(filters.pattern_count(), 4);
+        // The invalid glob was attempted once too
+        assert_eq!(COMPILED_PATTERNS.with(|count| count.get()), 5);
+
+        // Main, baseline and test graphs of one run share the compiled set
+        let name_filter = filters.name_filter.as_ref().unwrap();
+        for _ in 0..3 {
+            let mut graph = graph(5_000);
+            name_filter.filter_graph(&mut graph);
+            assert_eq!(graph.definitions.len(), 2_500);
+            assert!(graph.definitions.iter().all(|n| n.name.starts_with("find")));
+
+            let index = scip_proto::Index {
+                documents: vec![scip_proto::Document {
+                    relative_path: "src/controllers/users.controller.ts".to_string(),
+                    occurrences: vec![scip_proto::Occurrence {
+                        range: vec![0, 0, 10],
+                        symbol: "scip-typescript npm pkg 1.0.0 src/`users.controller.ts`/UsersController#".to_string(),
+                        symbol_roles: scip_proto::SymbolRole::Definition as i32,
+                        ..Default::default()
+                    }],
+                    ..Default::default()
+                }],
+                ..Default::default()
+            };
+            let (graph, _) = crate::build_graph(index, &config, &filters).unwrap();
+            assert_eq!(graph.definitions[0].layer.as_deref(), Some("controller"));
+        }
+        assert_eq!(COMPILED_PATTERNS.with(|count| count.get()), 5);
+
+        assert!(
+            CompiledFilters::for_conversion(&config)
+                .name_filter
+                .is_none()
+        );
+    }
+}
@@ -75,6 +75,26 @@ impl ConfigLoader {
                     crate::model::AdHocGranularity::from_str(&granularity_str)
                         .map_err(|e| anyhow::anyhow!("{}", e))?;
             }
+            if let Some(by_kind) = file_cfg.output.adhoc_granularity_by_kind {
+                for (kind, granularity) in by_kind {
+                    let kind = kind.parse::<crate::model::ScipSymbolKind>().map_err(|e| {
+                        anyhow::anyhow!("Invalid adhocGranularityByKind key: {}", e)
+                    })?;
+                    let granularity = crate::model::AdHocGranularity::from_str(&granularity)
+                        .map_err(|e| anyhow::anyhow!("{}", e))?;
+                    merged.adhoc_granularity_by_kind.insert(kind, granularity);
+                }
+            }
+
+            if let Some(compact_names) = file_cfg.output.compact_names {
+                merged.compact_names = compact_names;
+            }
+            if let Some(abbreviations) = file_cfg.output.name_abbreviations {
+                merged.name_abbreviations = abbreviations;
+            }
+            if let Some(keep_decorators) = file_cfg.output.keep_decorators {
+                merged.keep_decorators = keep_decorators;
+            }
 
             // File filter settings
             merged.file_filter.include_patterns = file_cfg.include;
@@ -84,6 +104,10 @@ impl ConfigLoader {
             if let Some(use_gitignore) = file_cfg.ignore.use_gitignore {
                 merged.file_filter.use_gitignore = use_gitignore;
             }
+
+            merged.edge_rules = file_cfg.edge_rules;
+            merged.layer_rules = file_cfg.layer_rules;
+            merged.layer_dependencies = file_cfg.layer_dependencies;
         }
 
         // CLI overrides file config
@@ -131,6 +155,22 @@ impl ConfigLoader {
             }
         }
 
+        for rule in &config.edge_rules {
+            regex::Regex::new(&rule.pattern)
+                .with_context(|| format!("Invalid edge rule pattern: '{}'", rule.pattern))?;
+            if rule.edge_type.trim().is_empty() {
+                anyhow::bail!("Edge rule '{}' has an empty edgeType", rule.pattern);
+            }
+        }
+
+        for rule in &config.layer_rules {
+            glob::Pattern::new(&rule.pattern)
+                .with_context(|| format!("Invalid layer rule pattern: '{}'", rule.pattern))?;
+            if rule.layer.trim().is_empty() {
+                anyhow::bail!("Layer rule '{}' has an empty layer", rule.pattern);
+            }
+        }
+
         Ok(())
     }
 
@@ -139,8 +179,15 @@ impl ConfigLoader {
         match format_str.to_lowercase().as_str() {
             "yaml" => Ok(OutputFormat::Yaml),
             "adhoc" => Ok(OutputFormat::AdHoc),
+            "ndjson" => Ok(OutputFormat::Ndjson),
+            "json" => Ok(OutputFormat::Json),
+            "plantuml" => Ok(OutputFormat::PlantUml),
+            #[cfg(feature = "bincode")]
+            "bincode" => Ok(OutputFormat::Bincode),
+            #[cfg(not(feature = "bincode"))]
+            "bincode" => anyhow::bail!("Output format 'bincode' requires the `bincode` feature"),
             _ => anyhow::bail!(
-                "Invalid output format: '{}'. Valid options are: 'yaml', 'adhoc'",
+                "Invalid output format: '{}'. Valid options are: 'yaml', 'json', 'adhoc', 'ndjson', 'plantuml'",
                 format_str
             ),
         }
@@ -155,6 +202,17 @@ pub struct MergedConfig {
     pub ignore_framework_noise: bool,
     pub file_filter: FileFilterConfig,
     pub adhoc_granularity: crate::model::AdHocGranularity,
+    /// Kinds serialized at their own level instead of `adhoc_granularity`
+    pub adhoc_granularity_by_kind:
+        std::collections::HashMap<crate::model::ScipSymbolKind, crate::model::AdHocGranularity>,
+    pub compact_names: bool,
+    pub name_abbreviations: std::collections::BTreeMap<String, String>,
+    pub edge_rules: Vec<crate::model::EdgeRule>,
+    pub layer_rules: Vec<crate::model::LayerRule>,
+    /// Layer -> layers it may depend on
+    pub layer_dependencies: std::collections::BTreeMap<String, Vec<String>>,
+    /// Decorators turned into node metadata by the framework noise filter
+    pub keep_decorators: Vec<String>,
 }
 
 impl Default for MergedConfig {
@@ -169,6 +227,13 @@ impl Default for MergedConfig {
                 use_gitignore: true, // Default to respecting gitignore
             },
             adhoc_granularity: crate::model::AdHocGranularity::default(),
+            adhoc_granularity_by_kind: std::collections::HashMap::new(),
+            compact_names: false,
+            name_abbreviations: std::collections::BTreeMap::new(),
+            edge_rules: Vec::new(),
+            layer_rules: Vec::new(),
+            layer_dependencies: std::collections::BTreeMap::new(),
+            keep_decorators: Vec::new(),
         }
     }
 }
@@ -0,0 +1,112 @@
+// crates/ycg_core/src/cross_file.rs
+//! Cross-file edges only (`--cross-file-edges-only`).
+//!
+//! Most `calls` edges stay within a file (a method calling a sibling), which
+//! says little about the architecture. In this mode an edge is kept only
+//! when its endpoints live in different files. The file of a definition is
+//! the one the [`SymbolIndex`] lists it under, else the file of its nearest
+//! indexed ancestor. Edges with an endpoint of unknown file (external
+//! symbols) always cross a file boundary and are kept.
+
+use crate::model::{SymbolIndex, YcgGraph};
+use std::collections::HashMap;
+
+/// Drops the edges whose `from` and `to` are defined in the same file
+///
+/// # Returns
+/// Number of edges removed
+pub fn retain_cross_file_edges(graph: &mut YcgGraph, symbol_index: &SymbolIndex) -> usize {
+    let mut file_of: HashMap<&str, &str> = HashMap::new();
+    for (path, anchors) in &symbol_index.files {
+        for anchor in anchors {
+            file_of.insert(anchor.as_str(), path.as_str());
+        }
+    }
+    let parents: HashMap<&str, &str> = graph
+        .definitions
+        .iter()
+        .filter_map(|node| Some((node.id.as_str(), node.parent_id.as_deref()?)))
+        .collect();
+
+    let file = |id: &str| -> Option<String> {
+        let mut current = id;
+        // Parent chains are short; the cap only guards against cycles
+        for _ in 0..32 {
+            if let Some(path) = file_of.get(current) {
+                return Some(path.to_string());
+            }
+            current = parents.get(current)?;
+        }
+        None
+    };
+
+    let before = graph.references.len();
+    let mut files: HashMap<String, Option<String>> = HashMap::new();
+    graph.references.retain(|edge| {
+        let from = files
+            .entry(edge.from.clone())
+            .or_insert_with(|| file(&edge.from))
+            .clone();
+        let to = files
+            .entry(edge.to.clone())
+            .or_insert_with(|| file(&edge.to))
+            .clone();
+        match (from, to) {
+            (Some(from), Some(to)) => from != to,
+            _ => true,
+        }
+    });
+    before - graph.references.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{EdgeType, ProjectMetadata, ReferenceEdge, ScipSymbolKind, SymbolNode};
+
+    fn node(id: &str, parent: Option<&str>) -> SymbolNode {
+        SymbolNode {
+            id: id.to_string(),
+            name: id.to_string(),
+            kind: ScipSymbolKind::Method,
+            parent_id: parent.map(str::to_string),
+            ..Default::default()
+        }
+    }
+
+    fn edge(from: &str, to: &str) -> ReferenceEdge {
+        ReferenceEdge {
+            from: from.to_string(),
+            to: to.to_string(),
+            edge_type: EdgeType::Calls,
+            call_text: None,
+        }
+    }
+
+    #[test]
+    fn test_only_cross_file_edges_survive() {
+        let mut graph = YcgGraph {
+            metadata: ProjectMetadata::default(),
+            definitions: vec![
+                node("UserService", None),
+                node("findOne", Some("UserService")),
+                node("save", Some("UserService")),
+                node("get", None),
+            ],
+            references: vec![
+                edge("findOne", "save"),
+                edge("findOne", "get"),
+                edge("save", "ext_lodash"),
+            ],
+        };
+        let mut symbol_index = SymbolIndex::default();
+        symbol_index.insert("src/users.ts", "UserService", 1);
+        symbol_index.insert("src/store.ts", "get", 1);
+
+        assert_eq!(retain_cross_file_edges(&mut graph, &symbol_index), 1);
+        assert_eq!(
+            graph.references,
+            vec![edge("findOne", "get"), edge("save", "ext_lodash")]
+        );
+    }
+}
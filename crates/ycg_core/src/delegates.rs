@@ -0,0 +1,156 @@
+// crates/ycg_core/src/delegates.rs
+//! Collapses pass-through methods (`--collapse-delegates`).
+//!
+//! A method whose body only forwards to another call
+//! (`return this.service.find(id)`) keeps its node, but its logic becomes a
+//! `delegates_to` marker naming the callee, its call edge to the callee is
+//! folded into that marker, and callers are rewired to call the callee
+//! directly. Service layers made of such wrappers then read as one hop.
+
+use crate::model::{EdgeType, LogicMetadata, YcgGraph};
+use std::collections::HashMap;
+
+/// Applies the collapse for every method whose forwarded call resolves.
+///
+/// `delegate_calls` maps a method ID to the callee name found in its body.
+/// The callee must be the target of exactly one outgoing `Calls` edge of
+/// the method whose definition has that short name; otherwise (external
+/// callee, ambiguous name) the method is left alone. Returns the number
+/// of collapsed methods.
+pub fn collapse(graph: &mut YcgGraph, delegate_calls: &HashMap<String, String>) -> usize {
+    let names: HashMap<&str, &str> = graph
+        .definitions
+        .iter()
+        .map(|node| (node.id.as_str(), crate::short_symbol_name(&node.name)))
+        .collect();
+
+    let mut targets: HashMap<String, String> = HashMap::new();
+    for (method, callee) in delegate_calls {
+        let matches: Vec<&str> = graph
+            .references
+            .iter()
+            .filter(|edge| {
+                edge.from == *method
+                    && edge.edge_type == EdgeType::Calls
+                    && edge.to != *method
+                    && names.get(edge.to.as_str()) == Some(&callee.as_str())
+            })
+            .map(|edge| edge.to.as_str())
+            .collect();
+        if let [target] = matches.as_slice() {
+            targets.insert(method.clone(), target.to_string());
+        }
+    }
+    if targets.is_empty() {
+        return 0;
+    }
+
+    // Follow chains of delegates (controller -> service -> repository)
+    let resolve = |id: &str| {
+        let mut current = id;
+        let mut hops = 0;
+        while let Some(next) = targets.get(current) {
+            current = next;
+            hops += 1;
+            if hops > targets.len() {
+                break;
+            }
+        }
+        current.to_string()
+    };
+
+    for node in &mut graph.definitions {
+        if let Some(target) = targets.get(&node.id) {
+            node.logic = Some(LogicMetadata {
+                preconditions: Vec::new(),
+                steps: Vec::new(),
+                delegates_to: Some(target.clone()),
+            });
+        }
+    }
+
+    graph.references.retain(|edge| {
+        !(edge.edge_type == EdgeType::Calls && targets.get(&edge.from) == Some(&edge.to))
+    });
+    for edge in &mut graph.references {
+        if edge.edge_type == EdgeType::Calls && targets.contains_key(&edge.to) {
+            let target = resolve(&edge.to);
+            if target != edge.from {
+                edge.to = target;
+            }
+        }
+    }
+    graph.references.sort();
+    graph.references.dedup();
+
+    targets.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{ProjectMetadata, ReferenceEdge, ScipSymbolKind, SymbolNode};
+
+    fn method(id: &str, name: &str) -> SymbolNode {
+        SymbolNode {
+            id: id.to_string(),
+            name: name.to_string(),
+            kind: ScipSymbolKind::Method,
+            ..Default::default()
+        }
+    }
+
+    fn edge(from: &str, to: &str, edge_type: EdgeType) -> ReferenceEdge {
+        ReferenceEdge {
+            from: from.to_string(),
+            to: to.to_string(),
+            edge_type,
+            call_text: None,
+        }
+    }
+
+    #[test]
+    fn test_chain_of_delegates_rewired_to_the_last_callee() {
+        let mut graph = YcgGraph {
+            metadata: ProjectMetadata::default(),
+            definitions: vec![
+                method("ctl_find", "UserController#find"),
+                method("svc_find", "UserService#find"),
+                method("repo_find", "UserRepo#find"),
+                method("app", "bootstrap"),
+            ],
+            references: vec![
+                edge("app", "ctl_find", EdgeType::Calls),
+                edge("ctl_find", "svc_find", EdgeType::Calls),
+                edge("ctl_find", "svc_field", EdgeType::References),
+                edge("svc_find", "repo_find", EdgeType::Calls),
+            ],
+        };
+        let delegate_calls = HashMap::from([
+            ("ctl_find".to_string(), "find".to_string()),
+            ("svc_find".to_string(), "find".to_string()),
+            // External callee: nothing to point at
+            ("app".to_string(), "listen".to_string()),
+        ]);
+
+        assert_eq!(collapse(&mut graph, &delegate_calls), 2);
+        let delegates_to = |id: &str| {
+            graph
+                .definitions
+                .iter()
+                .find(|n| n.id == id)
+                .and_then(|n| n.logic.as_ref())
+                .and_then(|l| l.delegates_to.clone())
+        };
+        assert_eq!(delegates_to("ctl_find").as_deref(), Some("svc_find"));
+        assert_eq!(delegates_to("svc_find").as_deref(), Some("repo_find"));
+        assert_eq!(delegates_to("app"), None);
+        assert_eq!(
+            graph.references,
+            vec![
+                edge("app", "repo_find", EdgeType::Calls),
+                edge("ctl_find", "svc_field", EdgeType::References),
+            ]
+        );
+    }
+}
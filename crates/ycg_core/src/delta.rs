@@ -0,0 +1,376 @@
+// crates/ycg_core/src/delta.rs
+//! Changed subgraph between a baseline and a current graph (`--baseline`).
+//!
+//! Both graphs come out of the same pipeline, so anchors are stable and
+//! definitions are matched by ID. A definition counts as modified when its
+//! SCIP-derived shape changed: name, kind, parent, outgoing edges, or the
+//! [fingerprint](definition_fingerprints) of its signature and body as the
+//! index records them. Source-derived fields (signature, docs, logic) are
+//! not compared, since only the current revision of the sources is on disk.
+
+use crate::model::{EdgeType, ReferenceEdge, SymbolIndex, SymbolNode, YcgGraph};
+use crate::scip_proto;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::fmt::Write;
+use xxhash_rust::xxh64::xxh64;
+
+/// The `_delta` marker: IDs of the definitions that changed
+#[derive(Debug, Serialize, Deserialize, Default, Clone, PartialEq)]
+pub struct GraphDelta {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub added: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub removed: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub modified: Vec<String>,
+}
+
+/// A graph prefixed by its `_delta` marker, when there is one
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DeltaGraph<G> {
+    #[serde(rename = "_delta", default, skip_serializing_if = "Option::is_none")]
+    pub delta: Option<GraphDelta>,
+    #[serde(flatten)]
+    pub graph: G,
+}
+
+impl GraphDelta {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.modified.is_empty()
+    }
+
+    /// Diffs two graphs and returns the summary plus the changed subgraph.
+    ///
+    /// The subgraph holds added and modified definitions (from `current`),
+    /// removed ones (from `baseline`) and every edge incident to them. The
+    /// symbol indexes of both graphs carry their definition fingerprints.
+    pub fn between(
+        baseline: YcgGraph,
+        baseline_index: &SymbolIndex,
+        current: YcgGraph,
+        current_index: &SymbolIndex,
+    ) -> (GraphDelta, YcgGraph) {
+        let base_nodes: HashMap<&str, &SymbolNode> = baseline
+            .definitions
+            .iter()
+            .map(|node| (node.id.as_str(), node))
+            .collect();
+        let current_ids: HashSet<&str> = current
+            .definitions
+            .iter()
+            .map(|node| node.id.as_str())
+            .collect();
+        let base_out = outgoing(&baseline.references);
+        let current_out = outgoing(&current.references);
+
+        let mut delta = GraphDelta::default();
+        for node in &current.definitions {
+            match base_nodes.get(node.id.as_str()) {
+                None => delta.added.push(node.id.clone()),
+                Some(old) => {
+                    let id = node.id.as_str();
+                    if old.name != node.name
+                        || old.kind != node.kind
+                        || old.parent_id != node.parent_id
+                        || base_out.get(id) != current_out.get(id)
+                        || baseline_index.fingerprints.get(id) != current_index.fingerprints.get(id)
+                    {
+                        delta.modified.push(node.id.clone());
+                    }
+                }
+            }
+        }
+        delta.removed = baseline
+            .definitions
+            .iter()
+            .filter(|node| !current_ids.contains(node.id.as_str()))
+            .map(|node| node.id.clone())
+            .collect();
+        delta.added.sort();
+        delta.removed.sort();
+        delta.modified.sort();
+
+        let changed: HashSet<&str> = delta
+            .added
+            .iter()
+            .chain(&delta.modified)
+            .map(String::as_str)
+            .collect();
+        let removed: HashSet<&str> = delta.removed.iter().map(String::as_str).collect();
+        let incident = |ids: &HashSet<&str>, edge: &ReferenceEdge| {
+            ids.contains(edge.from.as_str()) || ids.contains(edge.to.as_str())
+        };
+
+        let mut definitions: Vec<SymbolNode> = current
+            .definitions
+            .iter()
+            .filter(|node| changed.contains(node.id.as_str()))
+            .cloned()
+            .collect();
+        // The baseline was enriched against the current sources, so its
+        // source-derived fields may describe whatever now sits at that line
+        definitions.extend(
+            baseline
+                .definitions
+                .iter()
+                .filter(|node| removed.contains(node.id.as_str()))
+                .map(|node| SymbolNode {
+                    id: node.id.clone(),
+                    name: node.name.clone(),
+                    kind: node.kind,
+                    parent_id: node.parent_id.clone(),
+                    ref_count: node.ref_count,
+                    ..Default::default()
+                }),
+        );
+
+        let mut references: Vec<ReferenceEdge> = current
+            .references
+            .iter()
+            .filter(|edge| incident(&changed, edge))
+            .chain(
+                baseline
+                    .references
+                    .iter()
+                    .filter(|edge| incident(&removed, edge)),
+            )
+            .cloned()
+            .collect();
+        references.sort();
+        references.dedup();
+
+        let graph = YcgGraph {
+            metadata: current.metadata.clone(),
+            definitions,
+            references,
+        };
+        (delta, graph)
+    }
+}
+
+/// SCIP-side fingerprint of every definition of `doc`, by symbol
+///
+/// Hashes the `signature_documentation` of the symbol and every occurrence
+/// within the definition's extent, with lines taken relative to the start of
+/// the extent so that a definition pushed down the file keeps its
+/// fingerprint. The extent is the `enclosing_range` of the definition or,
+/// for indexers that do not emit one, the lines up to the next definition.
+/// Edits that leave no trace in the index (a changed string literal) go
+/// unnoticed.
+pub fn definition_fingerprints(doc: &scip_proto::Document) -> HashMap<String, u64> {
+    let is_definition = |occurrence: &&scip_proto::Occurrence| {
+        occurrence.symbol_roles & scip_proto::SymbolRole::Definition as i32 != 0
+    };
+    let signatures: HashMap<&str, &str> = doc
+        .symbols
+        .iter()
+        .filter_map(|info| {
+            let signature = info.signature_documentation.as_ref()?;
+            Some((info.symbol.as_str(), signature.text.as_str()))
+        })
+        .collect();
+    let mut definition_lines: Vec<i32> = doc
+        .occurrences
+        .iter()
+        .filter(is_definition)
+        .filter_map(|occurrence| occurrence.range.first().copied())
+        .collect();
+    definition_lines.sort_unstable();
+    definition_lines.dedup();
+
+    let mut fingerprints = HashMap::new();
+    for definition in doc.occurrences.iter().filter(is_definition) {
+        let Some(&line) = definition.range.first() else {
+            continue;
+        };
+        let (start, end) = match definition.enclosing_range.as_slice() {
+            [start, _, end, _] => (*start, *end),
+            [start, _, _] => (*start, *start),
+            _ => {
+                let next = definition_lines.iter().find(|&&next| next > line);
+                (line, next.map_or(i32::MAX, |next| next - 1))
+            }
+        };
+
+        let mut text = signatures
+            .get(definition.symbol.as_str())
+            .copied()
+            .unwrap_or_default()
+            .to_string();
+        for occurrence in &doc.occurrences {
+            let Some(&occurrence_line) = occurrence.range.first() else {
+                continue;
+            };
+            if !(start..=end).contains(&occurrence_line) {
+                continue;
+            }
+            // [line, start char, (end line,) end char], end line made relative too
+            let shape: Vec<i32> = match occurrence.range.as_slice() {
+                [_, start_char, end_line, end_char] => {
+                    vec![*start_char, end_line - occurrence_line, *end_char]
+                }
+                [_, rest @ ..] => rest.to_vec(),
+                [] => Vec::new(),
+            };
+            let _ = write!(
+                text,
+                "\n{} {} {:?} {}",
+                occurrence.symbol,
+                occurrence_line - start,
+                shape,
+                occurrence.symbol_roles
+            );
+        }
+        fingerprints.insert(definition.symbol.clone(), xxh64(text.as_bytes(), 0));
+    }
+    fingerprints
+}
+
+/// Outgoing `(target, type)` pairs per source ID
+fn outgoing(edges: &[ReferenceEdge]) -> HashMap<&str, BTreeSet<(&str, EdgeType)>> {
+    let mut out: HashMap<&str, BTreeSet<(&str, EdgeType)>> = HashMap::new();
+    for edge in edges {
+        out.entry(edge.from.as_str())
+            .or_default()
+            .insert((edge.to.as_str(), edge.edge_type.clone()));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{ProjectMetadata, ScipSymbolKind};
+
+    fn node(id: &str, signature: &str) -> SymbolNode {
+        SymbolNode {
+            id: id.to_string(),
+            name: id.to_string(),
+            kind: ScipSymbolKind::Method,
+            signature: Some(signature.to_string()),
+            ..Default::default()
+        }
+    }
+
+    fn calls(from: &str, to: &str) -> ReferenceEdge {
+        ReferenceEdge {
+            from: from.to_string(),
+            to: to.to_string(),
+            edge_type: EdgeType::Calls,
+            call_text: None,
+        }
+    }
+
+    fn graph(definitions: Vec<SymbolNode>, references: Vec<ReferenceEdge>) -> YcgGraph {
+        YcgGraph {
+            metadata: ProjectMetadata::default(),
+            definitions,
+            references,
+        }
+    }
+
+    #[test]
+    fn test_added_removed_and_modified_with_incident_edges() {
+        let baseline = graph(
+            vec![node("a", "a()"), node("b", "b()"), node("old", "old()")],
+            vec![calls("a", "b"), calls("old", "b")],
+        );
+        let current = graph(
+            vec![node("a", "a()"), node("b", "b()"), node("new", "new()")],
+            vec![calls("a", "b"), calls("b", "new")],
+        );
+
+        let (delta, changed) = GraphDelta::between(
+            baseline,
+            &SymbolIndex::default(),
+            current,
+            &SymbolIndex::default(),
+        );
+        assert_eq!(delta.added, vec!["new"]);
+        assert_eq!(delta.removed, vec!["old"]);
+        // b gained an outgoing edge
+        assert_eq!(delta.modified, vec!["b"]);
+
+        let ids: Vec<&str> = changed.definitions.iter().map(|n| n.id.as_str()).collect();
+        assert_eq!(ids, vec!["b", "new", "old"]);
+        // Removed definitions lose source-derived fields
+        assert_eq!(changed.definitions[2].signature, None);
+        assert_eq!(
+            changed.references,
+            vec![calls("a", "b"), calls("b", "new"), calls("old", "b")]
+        );
+    }
+
+    #[test]
+    fn test_identical_graphs_have_empty_delta() {
+        let make = || graph(vec![node("a", "a()")], vec![calls("a", "ext")]);
+        let (delta, changed) = GraphDelta::between(
+            make(),
+            &SymbolIndex::default(),
+            make(),
+            &SymbolIndex::default(),
+        );
+        assert!(delta.is_empty());
+        assert!(changed.definitions.is_empty());
+        assert!(changed.references.is_empty());
+    }
+
+    #[test]
+    fn test_fingerprint_change_marks_definition_modified() {
+        let make = || graph(vec![node("a", "a()"), node("b", "b()")], Vec::new());
+        let index = |fingerprint: u64| {
+            let mut index = SymbolIndex::default();
+            index.fingerprints.insert("a".to_string(), fingerprint);
+            index.fingerprints.insert("b".to_string(), 7);
+            index
+        };
+
+        let (delta, changed) = GraphDelta::between(make(), &index(1), make(), &index(2));
+        assert_eq!(delta.modified, vec!["a"]);
+        assert_eq!(changed.definitions.len(), 1);
+    }
+
+    #[test]
+    fn test_fingerprints_follow_signature_and_body_not_position() {
+        let find = "scip-typescript npm pkg 1.0.0 src/`users.ts`/UserService#find().";
+        let repo = "scip-typescript npm pkg 1.0.0 src/`users.ts`/UserService#repo.";
+        // `find` spans `start..=start + 2` and reads `this.repo` at `body` (line offset, column)
+        let fingerprint = |start: i32, body: (i32, i32), signature: &str| {
+            let doc = scip_proto::Document {
+                relative_path: "src/users.ts".to_string(),
+                occurrences: vec![
+                    scip_proto::Occurrence {
+                        range: vec![start, 2, 6],
+                        symbol: find.to_string(),
+                        symbol_roles: scip_proto::SymbolRole::Definition as i32,
+                        enclosing_range: vec![start, 2, start + 2, 3],
+                        ..Default::default()
+                    },
+                    scip_proto::Occurrence {
+                        range: vec![start + body.0, body.1, body.1 + 4],
+                        symbol: repo.to_string(),
+                        ..Default::default()
+                    },
+                ],
+                symbols: vec![scip_proto::SymbolInformation {
+                    symbol: find.to_string(),
+                    signature_documentation: Some(scip_proto::Document {
+                        text: signature.to_string(),
+                        ..Default::default()
+                    }),
+                    ..Default::default()
+                }],
+                ..Default::default()
+            };
+            definition_fingerprints(&doc)[find]
+        };
+
+        let original = fingerprint(1, (1, 16), "find(id: number): User");
+        // Lines inserted above the method
+        assert_eq!(fingerprint(9, (1, 16), "find(id: number): User"), original);
+        // Same references, different body
+        assert_ne!(fingerprint(1, (1, 23), "find(id: number): User"), original);
+        assert_ne!(fingerprint(1, (1, 16), "find(id: string): User"), original);
+    }
+}
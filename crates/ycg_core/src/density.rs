@@ -0,0 +1,108 @@
+// crates/ycg_core/src/density.rs
+//! Token density report printed at the end of a conversion.
+//!
+//! The compression ratio (input tokens / output tokens) alone cannot tell a
+//! compact but complete graph from one where filters dropped most of the
+//! code: both have a large ratio. The report therefore also shows how many
+//! of the converted definitions survived the graph filters (`--compact`,
+//! `--ignore-framework-noise`, `--api-surface`, name filters...).
+
+use std::fmt;
+
+/// Below this share of surviving definitions the ratio gets a warning
+const LOW_SURVIVAL_PERCENT: f64 = 50.0;
+
+/// Token counts and definition counts of one conversion
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct DensityReport {
+    /// Tokens of the source files read
+    pub input_tokens: usize,
+    /// Tokens of the serialized graph
+    pub output_tokens: usize,
+    /// Definitions produced by the SCIP conversion, before the graph filters
+    pub converted_symbols: usize,
+    /// Definitions left once the graph filters have run
+    pub output_symbols: usize,
+}
+
+impl DensityReport {
+    /// Input tokens per output token, when both are known
+    pub fn compression_ratio(&self) -> Option<f64> {
+        (self.input_tokens > 0 && self.output_tokens > 0)
+            .then(|| self.input_tokens as f64 / self.output_tokens as f64)
+    }
+
+    /// Share of the converted definitions present in the output, in percent
+    pub fn symbol_survival(&self) -> f64 {
+        if self.converted_symbols == 0 {
+            100.0
+        } else {
+            self.output_symbols.min(self.converted_symbols) as f64 / self.converted_symbols as f64
+                * 100.0
+        }
+    }
+}
+
+impl fmt::Display for DensityReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "--- Métrica de Densidade ---")?;
+        writeln!(
+            f,
+            "Input Total Tokens (Código Bruto): {}",
+            self.input_tokens
+        )?;
+        writeln!(
+            f,
+            "Output Total Tokens (Grafo YAML): {}",
+            self.output_tokens
+        )?;
+        if let Some(ratio) = self.compression_ratio() {
+            writeln!(f, "Taxa de Compressão: {:.2}x", ratio)?;
+        }
+        writeln!(
+            f,
+            "Símbolos Preservados: {}/{} ({:.1}%)",
+            self.output_symbols.min(self.converted_symbols),
+            self.converted_symbols,
+            self.symbol_survival()
+        )?;
+        if self.symbol_survival() < LOW_SURVIVAL_PERCENT {
+            writeln!(
+                f,
+                "Aviso: a maior parte dos símbolos foi filtrada; a taxa de compressão não indica um grafo completo"
+            )?;
+        }
+        write!(f, "--------------------------")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ratio_is_reported_with_symbol_survival() {
+        // A name filter kept 4 of 40 definitions: the 20x ratio comes
+        // mostly from dropped code
+        let report = DensityReport {
+            input_tokens: 1000,
+            output_tokens: 50,
+            converted_symbols: 40,
+            output_symbols: 4,
+        };
+        assert_eq!(report.compression_ratio(), Some(20.0));
+        assert_eq!(report.symbol_survival(), 10.0);
+
+        let text = report.to_string();
+        assert!(text.contains("Taxa de Compressão: 20.00x"));
+        assert!(text.contains("Símbolos Preservados: 4/40 (10.0%)"));
+        assert!(text.contains("Aviso:"));
+
+        let complete = DensityReport {
+            output_symbols: 40,
+            ..report
+        };
+        assert_eq!(complete.symbol_survival(), 100.0);
+        assert!(!complete.to_string().contains("Aviso:"));
+    }
+}
@@ -0,0 +1,158 @@
+// crates/ycg_core/src/descriptor_signature.rs
+
+use crate::scip_proto;
+use std::collections::{HashMap, HashSet};
+
+/// Signature hints carried by the SCIP index itself, used when the source
+/// file of a document cannot be read (so Tree-sitter has nothing to parse).
+///
+/// Sources, in order of preference:
+/// 1. `SymbolInformation.signature_documentation`
+/// 2. A fenced code block in `SymbolInformation.documentation`
+///    (scip-typescript emits ```` ```ts\n(method) find(id: number): User\n``` ````)
+/// 3. Parameter descriptors (`User#find().(id)`) reassembled as `find(id)`
+#[derive(Debug, Default)]
+pub struct DescriptorHints {
+    signatures: HashMap<String, String>,
+    parameters: HashMap<String, Vec<String>>,
+}
+
+impl DescriptorHints {
+    /// Collect hints from every document and external symbol in `index`
+    pub fn from_index(index: &scip_proto::Index) -> Self {
+        let mut hints = DescriptorHints::default();
+        let mut seen_params: HashSet<&str> = HashSet::new();
+
+        let infos = index
+            .external_symbols
+            .iter()
+            .chain(index.documents.iter().flat_map(|doc| doc.symbols.iter()));
+        for info in infos {
+            if let Some(sig) = signature_from_info(info) {
+                hints.signatures.entry(info.symbol.clone()).or_insert(sig);
+            }
+        }
+
+        let symbols = index.documents.iter().flat_map(|doc| {
+            doc.occurrences
+                .iter()
+                .map(|o| o.symbol.as_str())
+                .chain(doc.symbols.iter().map(|s| s.symbol.as_str()))
+        });
+        for symbol in symbols {
+            if !seen_params.insert(symbol) {
+                continue;
+            }
+            if let Some((method, param)) = split_parameter_descriptor(symbol) {
+                hints
+                    .parameters
+                    .entry(method.to_string())
+                    .or_default()
+                    .push(param.to_string());
+            }
+        }
+
+        hints
+    }
+
+    /// Best-effort signature for `symbol`, or `None` if the index has no hint
+    pub fn signature_for(&self, symbol: &str) -> Option<String> {
+        if let Some(sig) = self.signatures.get(symbol) {
+            return Some(sig.clone());
+        }
+
+        // Methods/functions only: `pkg src/`a.ts`/User#find().`
+        let method = symbol.strip_suffix('.')?;
+        let without_parens = method.strip_suffix(')')?;
+        let open = without_parens.rfind('(')?;
+        let name_part = &without_parens[..open];
+        let name = name_part
+            .rsplit(['#', '/', '.'])
+            .next()
+            .filter(|n| !n.is_empty())?
+            .trim_matches('`');
+
+        let params = self
+            .parameters
+            .get(method)
+            .map(|p| p.join(", "))
+            .unwrap_or_default();
+        Some(format!("{}({})", name, params))
+    }
+}
+
+fn signature_from_info(info: &scip_proto::SymbolInformation) -> Option<String> {
+    if let Some(doc) = &info.signature_documentation {
+        let text = doc.text.trim();
+        if !text.is_empty() {
+            return Some(strip_kind_marker(first_code_line(text)).to_string());
+        }
+    }
+
+    let fenced = info
+        .documentation
+        .iter()
+        .find(|d| d.trim_start().starts_with("```"))?;
+    let line = first_code_line(fenced);
+    if line.is_empty() {
+        return None;
+    }
+    Some(strip_kind_marker(line).to_string())
+}
+
+/// First non-fence line of a (possibly fenced) code block
+fn first_code_line(text: &str) -> &str {
+    text.lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty() && !line.starts_with("```"))
+        .unwrap_or("")
+}
+
+/// `(method) find(id: number): User` -> `find(id: number): User`
+fn strip_kind_marker(line: &str) -> &str {
+    if line.starts_with('(')
+        && let Some(end) = line.find(") ")
+    {
+        return line[end + 2..].trim_start();
+    }
+    line
+}
+
+/// `User#find().(id)` -> (`User#find()`, `id`)
+fn split_parameter_descriptor(symbol: &str) -> Option<(&str, &str)> {
+    let inner = symbol.strip_suffix(')')?;
+    let open = inner.rfind(".(")?;
+    let method = &inner[..open];
+    let param = &inner[open + 2..];
+    if !method.ends_with(')') || param.is_empty() {
+        return None;
+    }
+    Some((method, param))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_signature_from_fenced_documentation() {
+        let info = scip_proto::SymbolInformation {
+            symbol: "pkg src/`a.ts`/User#find().".to_string(),
+            documentation: vec!["```ts\n(method) find(id: number): User\n```".to_string()],
+            ..Default::default()
+        };
+        assert_eq!(
+            signature_from_info(&info).as_deref(),
+            Some("find(id: number): User")
+        );
+    }
+
+    #[test]
+    fn test_split_parameter_descriptor() {
+        assert_eq!(
+            split_parameter_descriptor("pkg src/`a.ts`/User#find().(id)"),
+            Some(("pkg src/`a.ts`/User#find()", "id"))
+        );
+        assert_eq!(split_parameter_descriptor("pkg src/`a.ts`/User#"), None);
+    }
+}
@@ -0,0 +1,230 @@
+// crates/ycg_core/src/document_symbols.rs
+//! LSP `textDocument/documentSymbol` export (`--emit-document-symbols`).
+//!
+//! Each file of the symbol index becomes a `DocumentSymbol[]` outline:
+//! definitions nest under their `parent_id` when the parent is defined in
+//! the same file, and sit at the top level otherwise. Only definition
+//! lines are known, so a symbol's range spans from its own line to the
+//! last line of its children; positions are 0-based, as LSP expects.
+
+use crate::model::{ScipSymbolKind, SymbolIndex, SymbolNode, YcgGraph};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+/// LSP `Position`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Position {
+    pub line: u32,
+    pub character: u32,
+}
+
+/// LSP `Range`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Range {
+    pub start: Position,
+    pub end: Position,
+}
+
+/// LSP `DocumentSymbol`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DocumentSymbol {
+    pub name: String,
+    /// Signature, when the definition has one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+    /// LSP `SymbolKind` number
+    pub kind: u8,
+    pub range: Range,
+    pub selection_range: Range,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub children: Vec<DocumentSymbol>,
+}
+
+/// Maps a YCG kind to its LSP `SymbolKind` number
+pub fn lsp_symbol_kind(kind: ScipSymbolKind) -> u8 {
+    match kind {
+        ScipSymbolKind::File => 1,
+        ScipSymbolKind::Module => 2,
+        ScipSymbolKind::Class => 5,
+        ScipSymbolKind::Method => 6,
+        ScipSymbolKind::Enum => 10,
+        ScipSymbolKind::Interface => 11,
+        ScipSymbolKind::Function => 12,
+        ScipSymbolKind::Variable => 13,
+    }
+}
+
+/// Outline per file (`relative_path` → top-level symbols, sorted by line)
+pub fn document_symbols(
+    graph: &YcgGraph,
+    index: &SymbolIndex,
+) -> BTreeMap<String, Vec<DocumentSymbol>> {
+    let nodes: HashMap<&str, &SymbolNode> = graph
+        .definitions
+        .iter()
+        .map(|node| (node.id.as_str(), node))
+        .collect();
+
+    let mut outline = BTreeMap::new();
+    for (path, anchors) in &index.files {
+        let in_file: HashSet<&str> = anchors
+            .iter()
+            .map(String::as_str)
+            .filter(|anchor| nodes.contains_key(anchor) && index.lines.contains_key(*anchor))
+            .collect();
+
+        let mut children: HashMap<&str, Vec<&str>> = HashMap::new();
+        let mut roots = Vec::new();
+        for anchor in anchors.iter().map(String::as_str) {
+            if !in_file.contains(anchor) {
+                continue;
+            }
+            match nodes[anchor].parent_id.as_deref() {
+                Some(parent) if parent != anchor && in_file.contains(parent) => {
+                    children.entry(parent).or_default().push(anchor)
+                }
+                _ => roots.push(anchor),
+            }
+        }
+
+        let mut visited = HashSet::new();
+        let symbols = Outline {
+            nodes: &nodes,
+            lines: &index.lines,
+            children: &children,
+        }
+        .build_all(&roots, &mut visited);
+        if !symbols.is_empty() {
+            outline.insert(path.clone(), symbols);
+        }
+    }
+    outline
+}
+
+struct Outline<'a> {
+    nodes: &'a HashMap<&'a str, &'a SymbolNode>,
+    lines: &'a BTreeMap<String, i32>,
+    children: &'a HashMap<&'a str, Vec<&'a str>>,
+}
+
+impl<'a> Outline<'a> {
+    fn build_all(
+        &self,
+        anchors: &[&'a str],
+        visited: &mut HashSet<&'a str>,
+    ) -> Vec<DocumentSymbol> {
+        let mut symbols: Vec<DocumentSymbol> = anchors
+            .iter()
+            .filter_map(|anchor| self.build(anchor, visited))
+            .collect();
+        symbols.sort_by_key(|symbol| symbol.selection_range.start.line);
+        symbols
+    }
+
+    fn build(&self, anchor: &'a str, visited: &mut HashSet<&'a str>) -> Option<DocumentSymbol> {
+        // parent_id chains come from SCIP descriptors; guard against loops anyway
+        if !visited.insert(anchor) {
+            return None;
+        }
+        let node = self.nodes[anchor];
+        let line = (self.lines[anchor] - 1).max(0) as u32;
+        let children = self.build_all(
+            self.children.get(anchor).map(Vec::as_slice).unwrap_or(&[]),
+            visited,
+        );
+        let end_line = children
+            .iter()
+            .map(|child| child.range.end.line)
+            .fold(line, u32::max);
+
+        let at = |line| Position { line, character: 0 };
+        Some(DocumentSymbol {
+            name: crate::short_symbol_name(&node.name).to_string(),
+            detail: node.signature.clone(),
+            kind: lsp_symbol_kind(node.kind),
+            range: Range {
+                start: at(line),
+                end: at(end_line),
+            },
+            selection_range: Range {
+                start: at(line),
+                end: at(line),
+            },
+            children,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::ProjectMetadata;
+
+    fn node(id: &str, name: &str, kind: ScipSymbolKind, parent_id: Option<&str>) -> SymbolNode {
+        SymbolNode {
+            id: id.to_string(),
+            name: name.to_string(),
+            kind,
+            parent_id: parent_id.map(|p| p.to_string()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_methods_nest_under_their_class() {
+        let graph = YcgGraph {
+            metadata: ProjectMetadata::default(),
+            definitions: vec![
+                node("helper_0a0b", "helper", ScipSymbolKind::Function, None),
+                node("Users_a1b2", "Users", ScipSymbolKind::Class, None),
+                SymbolNode {
+                    signature: Some("find(id: number): User".to_string()),
+                    ..node(
+                        "find_c3d4",
+                        "Users#find",
+                        ScipSymbolKind::Method,
+                        Some("Users_a1b2"),
+                    )
+                },
+                node(
+                    "save_e5f6",
+                    "Users#save",
+                    ScipSymbolKind::Method,
+                    Some("Users_a1b2"),
+                ),
+            ],
+            references: Vec::new(),
+        };
+        let mut index = SymbolIndex::default();
+        index.insert("src/users.ts", "Users_a1b2", 3);
+        index.insert("src/users.ts", "save_e5f6", 9);
+        index.insert("src/users.ts", "find_c3d4", 4);
+        index.insert("src/users.ts", "helper_0a0b", 1);
+
+        let outline = document_symbols(&graph, &index);
+        let symbols = &outline["src/users.ts"];
+        let names: Vec<&str> = symbols.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, vec!["helper", "Users"]);
+
+        let class = &symbols[1];
+        assert_eq!(class.kind, 5);
+        let methods: Vec<(&str, u8, u32)> = class
+            .children
+            .iter()
+            .map(|m| (m.name.as_str(), m.kind, m.range.start.line))
+            .collect();
+        assert_eq!(methods, vec![("find", 6, 3), ("save", 6, 8)]);
+        assert_eq!(
+            class.children[0].detail.as_deref(),
+            Some("find(id: number): User")
+        );
+        // The class range covers its methods
+        assert_eq!(class.range.start.line, 2);
+        assert_eq!(class.range.end.line, 8);
+
+        let json = serde_json::to_value(class).unwrap();
+        assert!(json.get("selectionRange").is_some(), "{}", json);
+        assert!(json["children"][0].get("children").is_none(), "{}", json);
+    }
+}
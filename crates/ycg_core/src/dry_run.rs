@@ -0,0 +1,209 @@
+// crates/ycg_core/src/dry_run.rs
+
+use crate::count_tokens;
+use crate::framework_filter::FrameworkNoiseFilter;
+use crate::model::{SymbolIndex, YcgGraph};
+use crate::semantic_filter::SemanticFilter;
+use std::collections::HashSet;
+use std::fmt;
+
+/// What a single filter would drop from the graph
+#[derive(Debug, Clone, Default)]
+pub struct FilterPreview {
+    /// Node IDs (or file paths, for the file filter) that would be dropped
+    pub removed: Vec<String>,
+    /// Output tokens saved by dropping them
+    pub token_delta: usize,
+}
+
+/// Report produced by `--dry-run`: each filter's removals, nothing applied
+#[derive(Debug, Clone, Default)]
+pub struct DryRunReport {
+    /// Output tokens of the unfiltered graph
+    pub total_tokens: usize,
+    /// File filter (`--include`, `--exclude`, gitignore); `removed` lists paths
+    pub file_filter: FilterPreview,
+    /// Semantic filter (`--compact`)
+    pub semantic_filter: FilterPreview,
+    /// Framework noise filter (`--ignore-framework-noise`)
+    pub framework_filter: FilterPreview,
+}
+
+impl DryRunReport {
+    /// Run every filter in reporting mode against `graph`.
+    ///
+    /// The graph is only borrowed: removals are collected from each filter's
+    /// `nodes_to_remove` and measured on a copy.
+    ///
+    /// # Arguments
+    /// * `graph` - The unfiltered graph
+    /// * `symbol_index` - File -> anchors index, to attribute nodes to excluded files
+    /// * `excluded_files` - Paths the file filter would drop
+    pub fn build(
+        graph: &YcgGraph,
+        symbol_index: &SymbolIndex,
+        excluded_files: Vec<String>,
+    ) -> Self {
+        let total_tokens = graph_tokens(graph);
+
+        let file_nodes: HashSet<String> = excluded_files
+            .iter()
+            .filter_map(|path| symbol_index.files.get(path))
+            .flatten()
+            .cloned()
+            .collect();
+
+        DryRunReport {
+            total_tokens,
+            file_filter: FilterPreview {
+                token_delta: token_delta(graph, &file_nodes, total_tokens),
+                removed: excluded_files,
+            },
+            semantic_filter: preview(graph, SemanticFilter::nodes_to_remove(graph), total_tokens),
+            framework_filter: preview(
+                graph,
+                FrameworkNoiseFilter::nodes_to_remove(graph),
+                total_tokens,
+            ),
+        }
+    }
+}
+
+impl fmt::Display for DryRunReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "--- Dry run (nothing written) ---")?;
+        writeln!(f, "Output tokens without filters: {}", self.total_tokens)?;
+
+        let sections = [
+            ("File filter", "files", &self.file_filter),
+            (
+                "Semantic filter (--compact)",
+                "nodes",
+                &self.semantic_filter,
+            ),
+            (
+                "Framework noise filter (--ignore-framework-noise)",
+                "nodes",
+                &self.framework_filter,
+            ),
+        ];
+        for (title, unit, preview) in sections {
+            writeln!(
+                f,
+                "\n{}: {} {} (-{} tokens)",
+                title,
+                preview.removed.len(),
+                unit,
+                preview.token_delta
+            )?;
+            for item in &preview.removed {
+                writeln!(f, "  - {}", item)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+fn preview(graph: &YcgGraph, removed: HashSet<String>, total_tokens: usize) -> FilterPreview {
+    let token_delta = token_delta(graph, &removed, total_tokens);
+    let mut removed: Vec<String> = removed.into_iter().collect();
+    removed.sort();
+    FilterPreview {
+        removed,
+        token_delta,
+    }
+}
+
+fn token_delta(graph: &YcgGraph, removed: &HashSet<String>, total_tokens: usize) -> usize {
+    if removed.is_empty() {
+        return 0;
+    }
+    let mut pruned = graph.clone();
+    pruned
+        .definitions
+        .retain(|node| !removed.contains(&node.id));
+    pruned
+        .references
+        .retain(|edge| !removed.contains(&edge.from) && !removed.contains(&edge.to));
+    total_tokens.saturating_sub(graph_tokens(&pruned))
+}
+
+fn graph_tokens(graph: &YcgGraph) -> usize {
+    serde_yaml::to_string(graph)
+        .map(|yaml| count_tokens(&yaml))
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{ProjectMetadata, ScipSymbolKind, SymbolNode};
+
+    fn node(id: &str, name: &str, kind: ScipSymbolKind, signature: Option<&str>) -> SymbolNode {
+        SymbolNode {
+            id: id.to_string(),
+            name: name.to_string(),
+            kind,
+            signature: signature.map(|s| s.to_string()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_dry_run_lists_di_constructor_without_removing_it() {
+        let graph = YcgGraph {
+            metadata: ProjectMetadata {
+                name: "test".to_string(),
+                version: "1.0.0".to_string(),
+                ..Default::default()
+            },
+            definitions: vec![
+                node(
+                    "UsersService_a1b2",
+                    "UsersService",
+                    ScipSymbolKind::Class,
+                    None,
+                ),
+                node(
+                    "constructor_c3d4",
+                    "constructor",
+                    ScipSymbolKind::Method,
+                    Some("constructor(private readonly repo: UsersRepository)"),
+                ),
+            ],
+            references: Vec::new(),
+        };
+
+        let mut index = SymbolIndex::default();
+        index.insert("src/users.service.ts", "UsersService_a1b2", 1);
+        index.insert("src/users.service.ts", "constructor_c3d4", 2);
+
+        let report = DryRunReport::build(&graph, &index, Vec::new());
+
+        assert_eq!(report.framework_filter.removed, vec!["constructor_c3d4"]);
+        assert!(report.framework_filter.token_delta > 0);
+        assert!(report.to_string().contains("  - constructor_c3d4"));
+        assert_eq!(graph.definitions.len(), 2);
+    }
+
+    #[test]
+    fn test_dry_run_attributes_nodes_to_excluded_files() {
+        let graph = YcgGraph {
+            metadata: ProjectMetadata::default(),
+            definitions: vec![node(
+                "helper_e5f6",
+                "helper",
+                ScipSymbolKind::Function,
+                None,
+            )],
+            references: Vec::new(),
+        };
+        let mut index = SymbolIndex::default();
+        index.insert("test/helper.spec.ts", "helper_e5f6", 1);
+
+        let report = DryRunReport::build(&graph, &index, vec!["test/helper.spec.ts".to_string()]);
+
+        assert_eq!(report.file_filter.removed, vec!["test/helper.spec.ts"]);
+        assert!(report.file_filter.token_delta > 0);
+    }
+}
@@ -0,0 +1,170 @@
+// crates/ycg_core/src/edge_dedup.rs
+//! Edge deduplication during the SCIP conversion.
+//!
+//! By default every edge goes into one `HashSet`, which is then collected
+//! into a `Vec` and sorted: on very large indexes the set alone is big, and
+//! for a moment the set and the vector coexist. In streaming mode
+//! (`--streaming-edges`) edges are deduplicated per document instead: the
+//! document's edges are held in a small ordered set, flushed as a sorted run
+//! when the document is done, and the runs are merged at the end, dropping
+//! the duplicates that span documents. Only one document's worth of edges is
+//! ever hashed, and the merged output is identical to the in-memory path.
+//! With `--no-dedupe-edges` every edge is kept, one per reference.
+
+use crate::model::ReferenceEdge;
+use std::cmp::Reverse;
+use std::collections::{BTreeSet, BinaryHeap, HashSet};
+
+/// Unique edges of a conversion, returned sorted by [`EdgeDedup::into_sorted`]
+pub enum EdgeDedup {
+    /// One set holding every edge
+    InMemory(HashSet<ReferenceEdge>),
+    /// Edges of the current document, plus the sorted runs already flushed
+    Streaming {
+        current: BTreeSet<ReferenceEdge>,
+        runs: Vec<Vec<ReferenceEdge>>,
+    },
+    /// No deduplication: identical edges are kept (`--no-dedupe-edges`)
+    KeepAll(Vec<ReferenceEdge>),
+}
+
+impl EdgeDedup {
+    pub fn new(streaming: bool) -> Self {
+        if streaming {
+            EdgeDedup::Streaming {
+                current: BTreeSet::new(),
+                runs: Vec::new(),
+            }
+        } else {
+            EdgeDedup::InMemory(HashSet::new())
+        }
+    }
+
+    pub fn insert(&mut self, edge: ReferenceEdge) {
+        match self {
+            EdgeDedup::InMemory(set) => {
+                set.insert(edge);
+            }
+            EdgeDedup::Streaming { current, .. } => {
+                current.insert(edge);
+            }
+            EdgeDedup::KeepAll(edges) => edges.push(edge),
+        }
+    }
+
+    /// Ends a document: its edges become a sorted run (no-op in memory)
+    pub fn flush(&mut self) {
+        if let EdgeDedup::Streaming { current, runs } = self
+            && !current.is_empty()
+        {
+            runs.push(std::mem::take(current).into_iter().collect());
+        }
+    }
+
+    /// Every unique edge (every edge for `KeepAll`), sorted, with `finish`
+    /// applied to each
+    ///
+    /// `finish` must not change the relative order of distinct edges (it is
+    /// applied before sorting in memory, during the merge when streaming).
+    pub fn into_sorted(
+        mut self,
+        mut finish: impl FnMut(ReferenceEdge) -> ReferenceEdge,
+    ) -> Vec<ReferenceEdge> {
+        self.flush();
+        match self {
+            EdgeDedup::InMemory(set) => {
+                let mut edges: Vec<ReferenceEdge> = set.into_iter().map(finish).collect();
+                edges.sort();
+                edges
+            }
+            EdgeDedup::KeepAll(edges) => {
+                let mut edges: Vec<ReferenceEdge> = edges.into_iter().map(finish).collect();
+                edges.sort();
+                edges
+            }
+            EdgeDedup::Streaming { runs, .. } => {
+                let mut runs: Vec<_> = runs.into_iter().map(Vec::into_iter).collect();
+                let mut heap = BinaryHeap::new();
+                for (i, run) in runs.iter_mut().enumerate() {
+                    if let Some(edge) = run.next() {
+                        heap.push(Reverse((edge, i)));
+                    }
+                }
+
+                let mut edges = Vec::new();
+                let mut last: Option<ReferenceEdge> = None;
+                while let Some(Reverse((edge, i))) = heap.pop() {
+                    if let Some(next) = runs[i].next() {
+                        heap.push(Reverse((next, i)));
+                    }
+                    if last.as_ref() == Some(&edge) {
+                        continue;
+                    }
+                    last = Some(edge.clone());
+                    edges.push(finish(edge));
+                }
+                edges
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::EdgeType;
+    use proptest::prelude::*;
+
+    fn edge(from: u8, to: u8, kind: u8) -> ReferenceEdge {
+        ReferenceEdge {
+            from: format!("f_{}", from),
+            to: format!("t_{}", to),
+            edge_type: match kind % 3 {
+                0 => EdgeType::Calls,
+                1 => EdgeType::Imports,
+                _ => EdgeType::References,
+            },
+            call_text: None,
+        }
+    }
+
+    fn dedup(streaming: bool, documents: &[Vec<(u8, u8, u8)>]) -> Vec<ReferenceEdge> {
+        let mut edges = EdgeDedup::new(streaming);
+        for document in documents {
+            for &(from, to, kind) in document {
+                edges.insert(edge(from, to, kind));
+            }
+            edges.flush();
+        }
+        edges.into_sorted(|mut edge| {
+            if edge.edge_type == EdgeType::Calls {
+                edge.call_text = Some(format!("{}()", edge.to));
+            }
+            edge
+        })
+    }
+
+    #[test]
+    fn test_duplicates_across_documents_are_merged() {
+        let documents = vec![
+            vec![(1, 2, 0), (1, 3, 0), (1, 2, 0)],
+            vec![(1, 2, 0), (0, 2, 1)],
+            vec![],
+        ];
+        let edges = dedup(true, &documents);
+        assert_eq!(edges.len(), 3);
+        assert_eq!(edges, dedup(false, &documents));
+    }
+
+    proptest! {
+        #[test]
+        fn test_streaming_matches_in_memory(
+            documents in proptest::collection::vec(
+                proptest::collection::vec((0u8..6, 0u8..6, 0u8..3), 0..20),
+                0..8,
+            )
+        ) {
+            prop_assert_eq!(dedup(true, &documents), dedup(false, &documents));
+        }
+    }
+}
@@ -1,16 +1,173 @@
 // crates/ycg_core/src/enricher.rs
-use std::collections::HashMap;
-use std::path::Path;
-use tree_sitter::{Language, Node, Parser, Query, QueryCursor};
+use crate::event_log::{Event, EventLog};
+use crate::logic_extractor::{EarlyExit, LogicExtractor};
+use regex::Regex;
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use tree_sitter::{Language, Node, Parser, Point, Query, QueryCursor, Tree};
+
+/// Files larger than this are not parsed (generated bundles, fixtures)
+pub const DEFAULT_MAX_FILE_BYTES: u64 = 4 * 1024 * 1024;
+
+/// Guard clauses are read from the function's top-level statements only
+pub const DEFAULT_MAX_GUARD_DEPTH: usize = 1;
 
 pub struct TreeSitterEnricher {
     parsers: HashMap<String, Language>,
+    max_file_bytes: u64,
+    /// Preconditions kept per function, `None` for all
+    max_preconditions: Option<usize>,
+    /// Statement blocks a guard may be nested in (1 = function body)
+    max_guard_depth: usize,
+    /// Characters of documentation kept per definition, `None` for all
+    max_doc_length: Option<usize>,
+    /// Drop tag-only and tooling lines (`@param id`, `eslint-disable`) from documentation
+    strip_doc_boilerplate: bool,
+    /// Oversized files already reported, so each is warned about once
+    skipped_files: HashSet<PathBuf>,
+    /// Extensions looked up without a registered grammar
+    unsupported_extensions: BTreeSet<String>,
+    log: EventLog,
 }
 
 pub struct EnrichmentResult {
     pub signature: Option<String>,
     pub documentation: Option<String>,
     pub preconditions: Vec<String>, // Novo campo
+    /// `return(<condition>)` steps of early returns in success branches
+    pub early_returns: Vec<String>,
+    pub throws: Vec<String>,
+    pub deprecated: bool,
+    pub generator: bool,
+    /// `yield(...)` / `yield*(...)` steps of a generator body
+    pub yields: Vec<String>,
+    /// `set(field)` / `call(callee)` steps of a constructor body, in order
+    pub init_steps: Vec<String>,
+    /// HTTP route served by a handler (`GET /users/:id`)
+    pub route: Option<String>,
+    /// Callee name when the body only forwards to another call
+    /// (`return this.repo.find(id)` -> `find`)
+    pub delegate_call: Option<String>,
+    /// Environment variables / config keys read in the body
+    pub env_access: Vec<String>,
+    /// Cyclomatic complexity of the body (1 + decision points)
+    pub complexity: u32,
+    /// `TODO` / `FIXME` / `HACK` comments of the body and leading comments
+    pub annotations: Vec<String>,
+    /// Best-effort: the body shows no side effect ([`is_pure`])
+    pub pure: bool,
+    /// SQL strings passed to query calls in the body
+    pub queries: Vec<String>,
+    pub impl_context: Option<ImplContext>,
+    /// Full text of the definition node (`--inline-source`)
+    pub source: String,
+}
+
+/// Unit of SCIP `character` offsets (`Document.position_encoding`).
+/// Tree-sitter columns are always bytes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColumnEncoding {
+    Utf8,
+    Utf16,
+    Utf32,
+}
+
+impl ColumnEncoding {
+    /// Converts a column in this encoding to a byte column within `line`
+    pub fn to_byte_column(self, line: &str, col: usize) -> usize {
+        if self == ColumnEncoding::Utf8 {
+            return col;
+        }
+        let mut units = 0;
+        for (byte, ch) in line.char_indices() {
+            if units >= col {
+                return byte;
+            }
+            units += match self {
+                ColumnEncoding::Utf16 => ch.len_utf16(),
+                _ => 1,
+            };
+        }
+        line.len()
+    }
+}
+
+/// A source file parsed once, for repeated position lookups (call text)
+pub struct ParsedSource {
+    source: String,
+    tree: Tree,
+}
+
+/// Node kinds that represent a call, with the field holding the callee
+const CALL_KINDS: &[(&str, &str)] = &[
+    ("call_expression", "function"),
+    ("new_expression", "constructor"),
+    ("macro_invocation", "macro"),
+];
+
+impl ParsedSource {
+    /// Source text of the call whose callee contains the zero-based position,
+    /// with whitespace collapsed and truncated to `max_len` characters.
+    ///
+    /// `col` is in `encoding` units. Returns `None` if the position is not the callee of a call (e.g. a
+    /// type annotation or an argument).
+    pub fn call_text(
+        &self,
+        line: usize,
+        col: usize,
+        encoding: ColumnEncoding,
+        max_len: usize,
+    ) -> Option<String> {
+        let line_text = self.source.lines().nth(line)?;
+        let position = Point::new(line, encoding.to_byte_column(line_text, col));
+        let mut node = innermost_node_at(self.tree.root_node(), position);
+        let call = loop {
+            if let Some((_, field)) = CALL_KINDS.iter().find(|(kind, _)| *kind == node.kind()) {
+                let callee = node.child_by_field_name(field)?;
+                if callee.start_position() <= position && position <= callee.end_position() {
+                    break node;
+                }
+                return None;
+            }
+            node = node.parent()?;
+        };
+
+        let text = self.source[call.start_byte()..call.end_byte()]
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ");
+        Some(truncate_chars(&text, max_len))
+    }
+}
+
+/// Smallest node whose half-open range `[start, end)` contains `position`
+fn innermost_node_at(node: Node, position: Point) -> Node {
+    let mut cursor = node.walk();
+    let child = node
+        .children(&mut cursor)
+        .find(|child| child.start_position() <= position && position < child.end_position());
+    match child {
+        Some(child) => innermost_node_at(child, position),
+        None => node,
+    }
+}
+
+fn truncate_chars(text: &str, max_len: usize) -> String {
+    if text.chars().count() <= max_len {
+        return text.to_string();
+    }
+    let mut truncated: String = text.chars().take(max_len.saturating_sub(1)).collect();
+    truncated.push('…');
+    truncated
+}
+
+/// Enclosing Rust `impl` block of a method (`impl Trait for Type { .. }`)
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImplContext {
+    /// Self type without generic arguments (`Dog`, `Wrapper` for `Wrapper<T>`)
+    pub self_type: String,
+    /// Implemented trait, last path segment without generics (`Display` for `fmt::Display`)
+    pub trait_name: Option<String>,
 }
 
 impl TreeSitterEnricher {
@@ -20,7 +177,101 @@ impl TreeSitterEnricher {
         parsers.insert("ts".into(), tree_sitter_typescript::language_typescript());
         parsers.insert("tsx".into(), tree_sitter_typescript::language_tsx());
         parsers.insert("js".into(), tree_sitter_javascript::language());
-        Self { parsers }
+        Self {
+            parsers,
+            max_file_bytes: DEFAULT_MAX_FILE_BYTES,
+            max_preconditions: None,
+            max_guard_depth: DEFAULT_MAX_GUARD_DEPTH,
+            max_doc_length: None,
+            strip_doc_boilerplate: false,
+            skipped_files: HashSet::new(),
+            unsupported_extensions: BTreeSet::new(),
+            log: EventLog::default(),
+        }
+    }
+
+    /// Parses files with extension `ext` (without the dot) using `language`,
+    /// replacing any grammar already registered for it
+    ///
+    /// Only Rust, TypeScript and JavaScript ship with the crate; other
+    /// grammars (`tree-sitter-scala`, `tree-sitter-dart`...) can be added by
+    /// library users. The extraction is tuned for the node kinds of the
+    /// built-in grammars, so enrichment of other languages is best-effort.
+    pub fn register_language(&mut self, ext: impl Into<String>, language: Language) {
+        self.parsers.insert(ext.into(), language);
+    }
+
+    /// Extensions of files that were looked up but have no grammar, sorted
+    pub fn unsupported_extensions(&self) -> &BTreeSet<String> {
+        &self.unsupported_extensions
+    }
+
+    /// Grammar registered for the file's extension
+    fn language_for(&mut self, file_path: &Path) -> Option<Language> {
+        let ext = file_path.extension()?.to_str()?;
+        let language = self.parsers.get(ext).copied();
+        if language.is_none() && !self.unsupported_extensions.contains(ext) {
+            self.unsupported_extensions.insert(ext.to_string());
+        }
+        language
+    }
+
+    /// Files above `max_file_bytes` are skipped (every lookup returns `None`)
+    pub fn with_max_file_bytes(mut self, max_file_bytes: u64) -> Self {
+        self.max_file_bytes = max_file_bytes;
+        self
+    }
+
+    /// Diagnostics (skipped files, truncated signatures) go to `log`
+    pub fn with_event_log(mut self, log: EventLog) -> Self {
+        self.log = log;
+        self
+    }
+
+    /// Guards nested deeper than `max_guard_depth` blocks (inside loops,
+    /// other ifs) are not preconditions of the function
+    pub fn with_max_guard_depth(mut self, max_guard_depth: usize) -> Self {
+        self.max_guard_depth = max_guard_depth;
+        self
+    }
+
+    /// Guard clauses beyond `max_preconditions` are summarized as `(+N more)`
+    pub fn with_max_preconditions(mut self, max_preconditions: Option<usize>) -> Self {
+        self.max_preconditions = max_preconditions;
+        self
+    }
+
+    /// Documentation longer than `max_doc_length` characters is cut at a
+    /// sentence or word boundary and ends with `…`
+    pub fn with_max_doc_length(mut self, max_doc_length: Option<usize>) -> Self {
+        self.max_doc_length = max_doc_length;
+        self
+    }
+
+    /// Documentation lines that say nothing (`@param id` without a
+    /// description, `@returns`, `eslint-disable`, `@ts-ignore`) are dropped
+    pub fn with_doc_boilerplate_stripped(mut self, strip: bool) -> Self {
+        self.strip_doc_boilerplate = strip;
+        self
+    }
+
+    /// Reads a source file unless it exceeds the size cap.
+    ///
+    /// Every lookup parses the whole file, so one huge generated file
+    /// would otherwise stall the run.
+    fn read_source(&mut self, file_path: &Path) -> Option<String> {
+        let size = std::fs::metadata(file_path).ok()?.len();
+        if size > self.max_file_bytes {
+            if self.skipped_files.insert(file_path.to_path_buf()) {
+                self.log.emit(Event::FileSkipped {
+                    path: file_path.display().to_string(),
+                    bytes: size,
+                    limit: self.max_file_bytes,
+                });
+            }
+            return None;
+        }
+        std::fs::read_to_string(file_path).ok()
     }
 
     /// Resolves a variable name from source code at a specific position.
@@ -31,7 +282,7 @@ impl TreeSitterEnricher {
     /// # Arguments
     /// * `file_path` - Path to the source file
     /// * `line` - Zero-based line number
-    /// * `col` - Zero-based column number
+    /// * `col` - Zero-based column number, in bytes
     ///
     /// # Returns
     /// * `Some(String)` - The resolved variable name from source
@@ -44,19 +295,30 @@ impl TreeSitterEnricher {
         line: usize,
         col: usize,
     ) -> Option<String> {
-        // Get language parser for file extension
-        let ext = file_path.extension()?.to_str()?;
-        let language = self.parsers.get(ext)?;
+        self.resolve_variable_name_with_encoding(file_path, line, col, ColumnEncoding::Utf8)
+    }
+
+    /// Like [`Self::resolve_variable_name`], with `col` counted in `encoding`
+    /// units (SCIP TypeScript indexes count UTF-16 code units, not bytes).
+    pub fn resolve_variable_name_with_encoding(
+        &mut self,
+        file_path: &Path,
+        line: usize,
+        col: usize,
+        encoding: ColumnEncoding,
+    ) -> Option<String> {
+        let language = self.language_for(file_path)?;
 
         // Read source code
-        let source_code = std::fs::read_to_string(file_path).ok()?;
+        let source_code = self.read_source(file_path)?;
 
         // Parse the file
         let mut parser = Parser::new();
-        parser.set_language(*language).ok()?;
+        parser.set_language(language).ok()?;
         let tree = parser.parse(&source_code, None)?;
 
-        // Find node at the specified position
+        // Find node at the specified position, in tree-sitter byte columns
+        let col = encoding.to_byte_column(source_code.lines().nth(line)?, col);
         let node = find_node_at_position(tree.root_node(), line, col)?;
 
         // Extract identifier text
@@ -80,30 +342,110 @@ impl TreeSitterEnricher {
         None
     }
 
+    /// Recovers the declared name of a TS/JS `export default` at a line.
+    ///
+    /// `export default class UserStore {}` yields `UserStore` and
+    /// `export default store;` yields `store`. Anonymous classes, functions
+    /// and arrows have no name to recover and yield `None`.
+    ///
+    /// # Arguments
+    /// * `file_path` - Path to the source file
+    /// * `line` - Zero-based line of the default-export symbol
+    pub fn resolve_default_export_name(&mut self, file_path: &Path, line: usize) -> Option<String> {
+        let language = self.language_for(file_path)?;
+        let source_code = self.read_source(file_path)?;
+
+        let mut parser = Parser::new();
+        parser.set_language(language).ok()?;
+        let tree = parser.parse(&source_code, None)?;
+        let root = tree.root_node();
+
+        let mut cursor = root.walk();
+        let export = root.children(&mut cursor).find(|node| {
+            node.kind() == "export_statement"
+                && node.start_position().row <= line
+                && line <= node.end_position().row
+                && node.child_by_field_name("source").is_none()
+                && (0..node.child_count())
+                    .filter_map(|i| node.child(i))
+                    .any(|child| child.kind() == "default")
+        })?;
+
+        let target = export
+            .child_by_field_name("declaration")
+            .or_else(|| export.child_by_field_name("value"))?;
+        let name_node = if target.kind() == "identifier" {
+            target
+        } else {
+            target.child_by_field_name("name")?
+        };
+
+        Some(source_code[name_node.start_byte()..name_node.end_byte()].to_string())
+    }
+
+    /// Whether an `enum` (TS `enum_declaration`, Rust `enum_item`) is named at `line`
+    pub fn is_enum_declaration(&mut self, file_path: &Path, line: usize) -> bool {
+        let Some(parsed) = self.parse_source(file_path) else {
+            return false;
+        };
+        let mut stack = vec![parsed.tree.root_node()];
+        while let Some(node) = stack.pop() {
+            if node.start_position().row > line || node.end_position().row < line {
+                continue;
+            }
+            if matches!(node.kind(), "enum_declaration" | "enum_item")
+                && node
+                    .child_by_field_name("name")
+                    .is_some_and(|name| name.start_position().row == line)
+            {
+                return true;
+            }
+            let mut cursor = node.walk();
+            stack.extend(node.children(&mut cursor));
+        }
+        false
+    }
+
+    /// Parses a whole file once so callers can look up many positions in it
+    pub fn parse_source(&mut self, file_path: &Path) -> Option<ParsedSource> {
+        let language = self.language_for(file_path)?;
+        let source = self.read_source(file_path)?;
+
+        let mut parser = Parser::new();
+        parser.set_language(language).ok()?;
+        let tree = parser.parse(&source, None)?;
+        Some(ParsedSource { source, tree })
+    }
+
     pub fn enrich(&mut self, file_path: &Path, start_line: usize) -> Option<EnrichmentResult> {
-        let ext = file_path.extension()?.to_str()?;
-        let language = self.parsers.get(ext)?;
-        let source_code = std::fs::read_to_string(file_path).ok()?;
+        let language = self.language_for(file_path)?;
+        let source_code = self.read_source(file_path)?;
 
         let mut parser = Parser::new();
-        parser.set_language(*language).ok()?;
+        parser.set_language(language).ok()?;
 
         let tree = parser.parse(&source_code, None)?;
         let root = tree.root_node();
 
-        let target_node = find_deepest_definition(root, start_line)?;
+        let is_declaration_file = is_declaration_file(file_path);
+        let target_node = if is_declaration_file {
+            find_deepest_declaration(root, start_line)?
+        } else {
+            find_deepest_definition(root, start_line)?
+        };
 
         // 1. Assinatura
         let raw_text = &source_code[target_node.start_byte()..target_node.end_byte()];
-        let signature = if let Some(idx) = find_body_start(raw_text) {
+        let signature = if is_declaration_file {
+            Some(declaration_signature(target_node, raw_text))
+        } else if let Some(idx) = find_body_start(raw_text) {
             let sig = raw_text[..idx].trim().to_string();
             // Validate signature is not truncated
             if is_truncated(&sig) {
-                eprintln!(
-                    "Warning: Truncated signature at {}:{} - falling back to symbol name",
-                    file_path.display(),
-                    start_line
-                );
+                self.log.emit(Event::SignatureTruncated {
+                    file: file_path.display().to_string(),
+                    line: start_line,
+                });
                 None
             } else {
                 Some(sig)
@@ -112,11 +454,10 @@ impl TreeSitterEnricher {
             let sig = raw_text.trim().to_string();
             // Validate signature is not truncated
             if is_truncated(&sig) {
-                eprintln!(
-                    "Warning: Truncated signature at {}:{} - falling back to symbol name",
-                    file_path.display(),
-                    start_line
-                );
+                self.log.emit(Event::SignatureTruncated {
+                    file: file_path.display().to_string(),
+                    line: start_line,
+                });
                 None
             } else {
                 Some(sig)
@@ -124,15 +465,83 @@ impl TreeSitterEnricher {
         };
 
         // 2. Documentação
-        let documentation = extract_comments(target_node, &source_code);
+        let documentation = extract_comments(target_node, &source_code)
+            .map(|doc| {
+                if self.strip_doc_boilerplate {
+                    strip_doc_boilerplate(&doc)
+                } else {
+                    doc
+                }
+            })
+            .filter(|doc| !doc.is_empty())
+            .map(|doc| truncate_documentation(doc, self.max_doc_length));
 
         // 3. Logic Lifting (Extração de Pré-condições)
-        let preconditions = extract_guard_clauses(target_node, &source_code, *language);
+        let (preconditions, early_returns) =
+            extract_guard_clauses(target_node, &source_code, language, self.max_guard_depth);
+        let preconditions =
+            LogicExtractor::cap_preconditions(preconditions, self.max_preconditions);
+
+        // 4. Throw sites (error flow), independent of guard clauses
+        let throws = extract_throw_sites(target_node, &source_code);
+
+        // 5. Deprecation markers (JSDoc `@deprecated`, Rust `#[deprecated]`)
+        let deprecated = is_deprecated(target_node, &source_code);
+
+        // 6. Rust impl context (self type + trait) for methods inside `impl` blocks
+        let impl_context = extract_impl_context(target_node, &source_code);
+
+        // 7. Generators and their yield points
+        let generator = is_generator(target_node);
+        let yields = if generator {
+            extract_yield_steps(target_node, &source_code)
+        } else {
+            Vec::new()
+        };
+
+        // 7b. Constructor initialization order (`this.x = ...`, `this.init()`)
+        let init_steps = extract_init_steps(target_node, &source_code);
+
+        // 8. HTTP route from handler decorators / attributes
+        let route = extract_route(target_node, &source_code);
+
+        // 9. Pass-through bodies (`return this.service.foo(args)`)
+        let delegate_call = extract_delegate_call(target_node, &source_code);
+
+        // 10. Environment / config reads (`process.env.X`, `std::env::var("X")`)
+        let env_access = extract_env_access(target_node, &source_code);
+
+        // 11. Cyclomatic complexity (decision points of the body)
+        let complexity = cyclomatic_complexity(target_node, &source_code);
+
+        // 12. Tech-debt markers (`// TODO: ...`, `FIXME`, `HACK`)
+        let annotations = extract_annotations(target_node, &source_code);
+
+        // 13. Purity heuristic (no await, field/parameter writes or I/O-looking calls)
+        let pure = is_pure(target_node, &source_code);
+
+        // 14. Embedded SQL (`db.query('SELECT ...')`, sql`...`)
+        let queries = extract_queries(target_node, &source_code);
 
         Some(EnrichmentResult {
             signature,
             documentation,
             preconditions,
+            early_returns,
+            throws,
+            deprecated,
+            generator,
+            yields,
+            init_steps,
+            route,
+            delegate_call,
+            env_access,
+            complexity,
+            annotations,
+            pure,
+            queries,
+            impl_context,
+            source: raw_text.to_string(),
         })
     }
 }
@@ -143,6 +552,7 @@ impl TreeSitterEnricher {
 
 const DEFINITION_KINDS: &[&str] = &[
     "function_declaration",
+    "generator_function_declaration",
     "class_declaration",
     "method_definition",
     "public_field_definition",
@@ -172,6 +582,80 @@ fn find_deepest_definition(node: Node, target_line: usize) -> Option<Node> {
     None
 }
 
+/// Declaration kinds in `.d.ts` files. Members have no body, so each one is
+/// its own signature instead of being swallowed by the enclosing type.
+const DECLARATION_KINDS: &[&str] = &[
+    "method_signature",
+    "abstract_method_signature",
+    "property_signature",
+    "public_field_definition",
+    "construct_signature",
+    "index_signature",
+    "function_signature",
+    "class_declaration",
+    "abstract_class_declaration",
+    "interface_declaration",
+    "enum_declaration",
+    "type_alias_declaration",
+    "lexical_declaration",
+    "variable_declaration",
+    "module",
+    "internal_module",
+];
+
+/// Kinds whose `.d.ts` signature stops at the `{` of their member list
+const DECLARATION_CONTAINER_KINDS: &[&str] = &[
+    "class_declaration",
+    "abstract_class_declaration",
+    "interface_declaration",
+    "enum_declaration",
+    "module",
+    "internal_module",
+];
+
+/// `types/index.d.ts`, `lib.d.mts`, `lib.d.cts`
+fn is_declaration_file(file_path: &Path) -> bool {
+    file_path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .is_some_and(|name| {
+            name.ends_with(".d.ts") || name.ends_with(".d.mts") || name.ends_with(".d.cts")
+        })
+}
+
+/// Like `find_deepest_definition`, but over `DECLARATION_KINDS`
+fn find_deepest_declaration(node: Node, target_line: usize) -> Option<Node> {
+    let start = node.start_position().row;
+    let end = node.end_position().row;
+    if target_line < start || target_line > end {
+        return None;
+    }
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        if let Some(deepest) = find_deepest_declaration(child, target_line) {
+            return Some(deepest);
+        }
+    }
+    if DECLARATION_KINDS.contains(&node.kind()) {
+        return Some(node);
+    }
+    None
+}
+
+/// Signature of a `.d.ts` declaration: the whole member without its trailing
+/// `;` (whitespace collapsed), or the header of a class/interface/namespace
+fn declaration_signature(node: Node, raw_text: &str) -> String {
+    let text = if DECLARATION_CONTAINER_KINDS.contains(&node.kind()) {
+        find_body_start(raw_text).map_or(raw_text, |idx| &raw_text[..idx])
+    } else {
+        raw_text
+    };
+    let collapsed = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    let trimmed = collapsed.trim_end_matches([';', ',']).trim_end();
+    // Parameters wrapped over several lines leave `( ` / ` )` behind
+    trimmed.replace("( ", "(").replace(" )", ")")
+}
+
 fn extract_comments(node: Node, source: &str) -> Option<String> {
     let mut comments = Vec::new();
     let mut cursor = node.prev_sibling();
@@ -200,10 +684,276 @@ fn extract_comments(node: Node, source: &str) -> Option<String> {
     }
 }
 
+lazy_static::lazy_static! {
+    /// A documentation line carrying no information of its own
+    static ref DOC_BOILERPLATE: Regex = Regex::new(
+        r"(?ix)^(?:
+            @(?:param|arg|argument)\s+(?:\{[^}]*\}\s*)?[\w.$\[\]]+\s*
+            | @returns?\s*(?:\{[^}]*\}\s*)?
+            | (?:eslint|tslint|prettier)-(?:disable|enable|ignore)\b.*
+            | @ts-(?:ignore|expect-error|nocheck)\b.*
+            | (?:todo:?\s*)?auto-?generated\b.*
+        )$"
+    )
+    .unwrap();
+}
+
+/// Drops the [`DOC_BOILERPLATE`] lines of a documentation string
+fn strip_doc_boilerplate(doc: &str) -> String {
+    doc.lines()
+        .filter(|line| {
+            let text = line.trim().trim_start_matches('/').trim_start();
+            !DOC_BOILERPLATE.is_match(text)
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+        .trim()
+        .to_string()
+}
+
+/// Cuts documentation to at most `max_length` characters plus `…`.
+///
+/// The cut falls after the last full sentence when that keeps at least
+/// half of the budget, otherwise before the last word that does not fit.
+fn truncate_documentation(doc: String, max_length: Option<usize>) -> String {
+    let Some(max_length) = max_length else {
+        return doc;
+    };
+    if doc.chars().count() <= max_length {
+        return doc;
+    }
+
+    let end = doc
+        .char_indices()
+        .nth(max_length)
+        .map_or(doc.len(), |(i, _)| i);
+    let head = &doc[..end];
+    let sentence_end = head
+        .rmatch_indices(['.', '!', '?'])
+        .map(|(i, _)| i + 1)
+        .find(|&i| doc[i..].starts_with(char::is_whitespace))
+        .filter(|&i| i >= end / 2);
+    let cut = match sentence_end {
+        Some(i) => &head[..i],
+        // A word cut in half is dropped; a single overlong word is kept cut
+        None if !doc[end..].starts_with(char::is_whitespace) => {
+            head.rfind(char::is_whitespace).map_or(head, |i| &head[..i])
+        }
+        None => head,
+    };
+    format!("{}…", cut.trim_end())
+}
+
+/// Checks the comments and attributes right above a definition for a
+/// JSDoc `@deprecated` tag or a Rust `#[deprecated]` attribute.
+///
+/// For `export function ...` the leading comment belongs to the
+/// `export_statement`, so its siblings are checked too.
+fn is_deprecated(node: Node, source: &str) -> bool {
+    let mut candidates = vec![node];
+    if let Some(parent) = node.parent().filter(|p| p.kind() == "export_statement") {
+        candidates.push(parent);
+    }
+
+    candidates.into_iter().any(|candidate| {
+        let mut cursor = candidate.prev_sibling();
+        while let Some(sibling) = cursor {
+            let text = &source[sibling.start_byte()..sibling.end_byte()];
+            match sibling.kind() {
+                "comment" | "line_comment" | "block_comment" => {
+                    if text.contains("@deprecated") {
+                        return true;
+                    }
+                }
+                "attribute_item" => {
+                    let attr = text.trim_start_matches("#[").trim_start();
+                    if attr.starts_with("deprecated") {
+                        return true;
+                    }
+                }
+                "decorator" => {}
+                _ => break,
+            }
+            cursor = sibling.prev_sibling();
+        }
+        false
+    })
+}
+
+/// HTTP verbs recognised in route decorators/attributes, by lowercase marker
+/// name: NestJS `@Get`, Spring `@GetMapping`, actix/rocket `#[get]`
+const ROUTE_METHODS: &[(&str, &str)] = &[
+    ("get", "GET"),
+    ("post", "POST"),
+    ("put", "PUT"),
+    ("patch", "PATCH"),
+    ("delete", "DELETE"),
+    ("head", "HEAD"),
+    ("options", "OPTIONS"),
+];
+
+/// Class-level markers whose path prefixes every handler route
+const ROUTE_PREFIX_MARKERS: &[&str] = &["controller", "requestmapping"];
+
+/// Builds `METHOD /path` from the decorators or attributes right above a
+/// handler, prefixed by the path of an enclosing `@Controller` class.
+///
+/// Method decorators are siblings of the `method_definition` in the class
+/// body; Rust attributes are siblings of the `function_item`.
+fn extract_route(node: Node, source: &str) -> Option<String> {
+    let mut cursor = node.prev_sibling();
+    let mut handler = None;
+    while let Some(sibling) = cursor {
+        match sibling.kind() {
+            "decorator" | "attribute_item" => {
+                let text = &source[sibling.start_byte()..sibling.end_byte()];
+                if let Some((name, path)) = parse_route_marker(text)
+                    && let Some(method) = route_method(&name)
+                {
+                    handler = Some((method, path));
+                    break;
+                }
+            }
+            "comment" | "line_comment" | "block_comment" => {}
+            _ => break,
+        }
+        cursor = sibling.prev_sibling();
+    }
+    let (method, path) = handler?;
+
+    let prefix = enclosing_class_decorators(node)
+        .into_iter()
+        .filter_map(|decorator| {
+            parse_route_marker(&source[decorator.start_byte()..decorator.end_byte()])
+        })
+        .find(|(name, _)| ROUTE_PREFIX_MARKERS.contains(&name.as_str()))
+        .map(|(_, path)| path)
+        .unwrap_or_default();
+
+    let segments: Vec<&str> = [prefix.as_str(), path.as_str()]
+        .into_iter()
+        .map(|part| part.trim_matches('/'))
+        .filter(|part| !part.is_empty())
+        .collect();
+    Some(format!("{} /{}", method, segments.join("/")))
+}
+
+/// HTTP verb of a lowercase route marker name (`get`, `getmapping` -> `GET`)
+pub(crate) fn route_method(name: &str) -> Option<&'static str> {
+    ROUTE_METHODS
+        .iter()
+        .find(|(marker, _)| name == *marker || name == format!("{}mapping", marker))
+        .map(|(_, method)| *method)
+}
+
+/// Splits `@Get(':id')` / `#[get("/users")]` into the lowercase marker name
+/// (last path segment) and the first string argument (empty when absent).
+pub(crate) fn parse_route_marker(text: &str) -> Option<(String, String)> {
+    let inner = match text.strip_prefix("#[") {
+        Some(attr) => attr.strip_suffix(']')?,
+        None => text.strip_prefix('@')?,
+    };
+    let name_end = inner.find('(').unwrap_or(inner.len());
+    let name = inner[..name_end].trim().rsplit([':', '.']).next()?;
+    if name.is_empty() {
+        return None;
+    }
+
+    let args = &inner[name_end..];
+    let path = args
+        .find(['\'', '"', '`'])
+        .and_then(|start| {
+            let quote = args[start..].chars().next()?;
+            let rest = &args[start + 1..];
+            rest.find(quote).map(|end| rest[..end].to_string())
+        })
+        .unwrap_or_default();
+    Some((name.to_lowercase(), path))
+}
+
+/// Decorators of the class enclosing a method, including those written
+/// before `export` (which belong to the `export_statement`).
+fn enclosing_class_decorators(node: Node) -> Vec<Node> {
+    let Some(class) = node
+        .parent()
+        .filter(|body| body.kind() == "class_body")
+        .and_then(|body| body.parent())
+    else {
+        return Vec::new();
+    };
+
+    let mut owners = vec![class];
+    if let Some(export) = class.parent().filter(|p| p.kind() == "export_statement") {
+        owners.push(export);
+    }
+
+    let mut decorators = Vec::new();
+    for owner in owners {
+        let mut cursor = owner.walk();
+        decorators.extend(
+            owner
+                .children(&mut cursor)
+                .filter(|child| child.kind() == "decorator"),
+        );
+    }
+    decorators
+}
+
+/// Finds the `impl_item` directly enclosing a Rust `function_item` and reads
+/// its `type` and `trait` fields.
+///
+/// Returns `None` for anything that is not a method of an impl block, so
+/// free functions and other languages are unaffected.
+fn extract_impl_context(node: Node, source: &str) -> Option<ImplContext> {
+    if node.kind() != "function_item" {
+        return None;
+    }
+
+    // function_item -> declaration_list -> impl_item
+    let impl_node = node.parent()?.parent()?;
+    if impl_node.kind() != "impl_item" {
+        return None;
+    }
+
+    let type_node = impl_node.child_by_field_name("type")?;
+    let self_type = strip_type_generics(&source[type_node.start_byte()..type_node.end_byte()]);
+
+    let trait_name = impl_node.child_by_field_name("trait").map(|trait_node| {
+        let text = strip_type_generics(&source[trait_node.start_byte()..trait_node.end_byte()]);
+        text.rsplit("::").next().unwrap_or(&text).to_string()
+    });
+
+    Some(ImplContext {
+        self_type,
+        trait_name,
+    })
+}
+
+/// `Wrapper<T>` -> `Wrapper`
+fn strip_type_generics(type_text: &str) -> String {
+    type_text
+        .split('<')
+        .next()
+        .unwrap_or(type_text)
+        .trim()
+        .to_string()
+}
+
 // --- LÓGICA NOVA: Logic Lifter ---
 
-fn extract_guard_clauses(node: Node, source: &str, lang: Language) -> Vec<String> {
+/// `if` blocks that leave the function early, at most `max_depth` statement
+/// blocks deep: error guards become `must avoid` preconditions, success
+/// returns become `return(cond)` steps.
+///
+/// Guards inside nested functions and closures belong to them, not to `node`.
+fn extract_guard_clauses(
+    node: Node,
+    source: &str,
+    lang: Language,
+    max_depth: usize,
+) -> (Vec<String>, Vec<String>) {
     let mut preconditions = Vec::new();
+    let mut early_returns = Vec::new();
 
     // Query para TypeScript/Rust: Procura IFs que tenham 'throw' ou 'return' dentro
     // Esta query é simplificada para demonstração
@@ -222,41 +972,754 @@ fn extract_guard_clauses(node: Node, source: &str, lang: Language) -> Vec<String
         for m in matches {
             // Verifica se o bloco do IF tem um 'throw' ou 'return' (indicando guard clause)
             let block_node = m.captures[1].node; // captura @block
+            if guard_depth(block_node, node).is_none_or(|depth| depth > max_depth) {
+                continue;
+            }
             let block_text = &source[block_node.start_byte()..block_node.end_byte()];
 
-            if block_text.contains("throw") || block_text.contains("return") {
-                // Captura a condição
-                let cond_node = m.captures[0].node; // captura @cond
-                let cond_text = &source[cond_node.start_byte()..cond_node.end_byte()];
+            let Some(exit) = LogicExtractor::classify_early_exit(block_text) else {
+                continue;
+            };
+
+            // Captura a condição
+            let cond_node = m.captures[0].node; // captura @cond
+            let cond_text = &source[cond_node.start_byte()..cond_node.end_byte()];
 
-                // Remove parenteses extras se houver
-                let clean_cond = cond_text.trim_matches(|c| c == '(' || c == ')').trim();
+            // Remove os parênteses externos do `if (...)`, mantendo os de chamadas
+            let clean_cond = cond_text
+                .strip_prefix('(')
+                .and_then(|c| c.strip_suffix(')'))
+                .unwrap_or(cond_text)
+                .trim();
 
+            match exit {
                 // Inverte a lógica (Human Readable): "Se x < 0 falha" vira "Requer x >= 0"
                 // Para o MVP, vamos apenas retornar a condição crua prefixada
-                preconditions.push(format!("must avoid: {}", clean_cond));
+                EarlyExit::ErrorGuard => preconditions.push(format!("must avoid: {}", clean_cond)),
+                EarlyExit::SuccessReturn => {
+                    early_returns.push(LogicExtractor::early_return_step(clean_cond))
+                }
             }
         }
     }
 
-    preconditions
+    (preconditions, early_returns)
+}
+
+/// Statement blocks enclosing the `if` of a guard block, up to `function`;
+/// `None` when a nested function lies in between
+fn guard_depth(block: Node, function: Node) -> Option<usize> {
+    let mut depth = 0;
+    // Start above the `if` itself
+    let mut current = block.parent()?.parent();
+    while let Some(ancestor) = current {
+        if ancestor.id() == function.id() {
+            return Some(depth);
+        }
+        if NESTED_FUNCTION_KINDS.contains(&ancestor.kind()) {
+            // `const f = () => {...}` / `handler = () => {...}`: the function is
+            // the value of the definition, so its guards are the definition's
+            return is_value_of(ancestor, function).then_some(depth);
+        }
+        if ancestor.kind() == "statement_block" {
+            depth += 1;
+        }
+        current = ancestor.parent();
+    }
+    None
+}
+
+/// Whether `value` is the initializer of `definition` (a field or a declarator of it)
+fn is_value_of(value: Node, definition: Node) -> bool {
+    let Some(parent) = value.parent() else {
+        return false;
+    };
+    parent.id() == definition.id()
+        || (parent.kind() == "variable_declarator"
+            && parent.parent().is_some_and(|p| p.id() == definition.id()))
+}
+
+/// Nested function kinds whose throw sites belong to themselves, not the enclosing function
+const NESTED_FUNCTION_KINDS: &[&str] = &[
+    "function_declaration",
+    "function_expression",
+    "generator_function_declaration",
+    "generator_function",
+    "arrow_function",
+    "method_definition",
+    "function_item",
+    "closure_expression",
+];
+
+/// Cyclomatic complexity of a function: 1 plus one per decision point in its
+/// body (`if`, loops, `case` / non-wildcard match arms, `catch`, `?:`, `&&`,
+/// `||`, `??`). Nested functions and closures have their own complexity;
+/// a function that is the value of the definition (`const f = () => {}`)
+/// is the body itself.
+fn cyclomatic_complexity(node: Node, source: &str) -> u32 {
+    1 + count_decision_points(node, node, source)
 }
+
+fn count_decision_points(node: Node, function: Node, source: &str) -> u32 {
+    if node.id() != function.id()
+        && NESTED_FUNCTION_KINDS.contains(&node.kind())
+        && !is_value_of(node, function)
+    {
+        return 0;
+    }
+
+    let decision = match node.kind() {
+        "if_statement" | "if_expression" | "for_statement" | "for_in_statement"
+        | "for_expression" | "while_statement" | "while_expression" | "do_statement"
+        | "switch_case" | "catch_clause" | "ternary_expression" => true,
+        "match_arm" => node
+            .child_by_field_name("pattern")
+            .is_some_and(|pattern| &source[pattern.start_byte()..pattern.end_byte()] != "_"),
+        "binary_expression" => node.child_by_field_name("operator").is_some_and(|op| {
+            matches!(&source[op.start_byte()..op.end_byte()], "&&" | "||" | "??")
+        }),
+        _ => false,
+    };
+
+    let mut cursor = node.walk();
+    let nested: u32 = node
+        .children(&mut cursor)
+        .map(|child| count_decision_points(child, function, source))
+        .sum();
+    u32::from(decision) + nested
+}
+
+/// Callee path segments that look like I/O or nondeterminism: logging,
+/// network, files, persistence, events, clocks and random numbers
+const IO_CALLEE_SEGMENTS: &[&str] = &[
+    "console",
+    "logger",
+    "log",
+    "fetch",
+    "axios",
+    "http",
+    "https",
+    "fs",
+    "file",
+    "process",
+    "stdin",
+    "stdout",
+    "stderr",
+    "repo",
+    "repository",
+    "db",
+    "database",
+    "prisma",
+    "client",
+    "socket",
+    "localstorage",
+    "sessionstorage",
+    "document",
+    "window",
+    "emit",
+    "send",
+    "save",
+    "insert",
+    "update",
+    "delete",
+    "remove",
+    "query",
+    "now",
+    "random",
+];
+
+/// Rust macros writing output
+const IO_MACROS: &[&str] = &[
+    "println", "print", "eprintln", "eprint", "write", "writeln", "dbg", "info", "warn", "error",
+    "debug", "trace",
+];
+
+/// Heuristic purity of a function: its body has no `await`, no assignment
+/// or `++`/`--` to a field, an element, a dereference or a parameter
+/// (`this.total = x`, `self.count += 1`, `items[0] = x`, `id = 0`), and no
+/// call whose callee looks like I/O ([`IO_CALLEE_SEGMENTS`], [`IO_MACROS`]).
+///
+/// This is a best-effort signal, not a proof: a call into an impure helper
+/// (`this.helper()`) or a mutating method on a parameter (`items.push(x)`)
+/// goes unnoticed, and a pure call with an I/O-looking name (`query(tree)`)
+/// marks the function impure. Effects inside closures count; nested named
+/// functions do not.
+fn is_pure(node: Node, source: &str) -> bool {
+    let parameters = function_parameters(node)
+        .map(|params| parameter_names(params, source))
+        .unwrap_or_default();
+    !has_side_effects(node, source, true, &parameters)
+}
+
+/// Parameter list of a function, or of the function a declaration holds
+/// (`const f = (a) => ...`)
+fn function_parameters(node: Node) -> Option<Node> {
+    if let Some(params) = node
+        .child_by_field_name("parameters")
+        .or_else(|| node.child_by_field_name("parameter"))
+    {
+        return Some(params);
+    }
+    let mut cursor = node.walk();
+    node.named_children(&mut cursor).find_map(|child| {
+        let value = if child.kind() == "variable_declarator" {
+            child.child_by_field_name("value")?
+        } else {
+            child
+        };
+        if NESTED_FUNCTION_KINDS.contains(&value.kind()) {
+            function_parameters(value)
+        } else {
+            None
+        }
+    })
+}
+
+fn parameter_names(params: Node, source: &str) -> HashSet<String> {
+    fn collect(node: Node, source: &str, names: &mut HashSet<String>) {
+        if matches!(
+            node.kind(),
+            "identifier" | "shorthand_property_identifier_pattern" | "self"
+        ) {
+            names.insert(source[node.start_byte()..node.end_byte()].to_string());
+            return;
+        }
+        let mut cursor = node.walk();
+        for child in node.named_children(&mut cursor) {
+            collect(child, source, names);
+        }
+    }
+
+    let mut names = HashSet::new();
+    if params.kind() == "identifier" {
+        collect(params, source, &mut names);
+        return names;
+    }
+    let mut cursor = params.walk();
+    for param in params.named_children(&mut cursor) {
+        let pattern = param.child_by_field_name("pattern").unwrap_or(param);
+        collect(pattern, source, &mut names);
+    }
+    names
+}
+
+fn has_side_effects(node: Node, source: &str, is_root: bool, parameters: &HashSet<String>) -> bool {
+    if !is_root && NESTED_DEFINITION_KINDS.contains(&node.kind()) {
+        return false;
+    }
+
+    let text = |n: Node| &source[n.start_byte()..n.end_byte()];
+    let is_mutation_target = |target: Node| match target.kind() {
+        "member_expression"
+        | "subscript_expression"
+        | "field_expression"
+        | "index_expression"
+        | "unary_expression" => true,
+        "identifier" => parameters.contains(text(target)),
+        _ => false,
+    };
+    let effect = match node.kind() {
+        "await_expression" => true,
+        "assignment_expression"
+        | "augmented_assignment_expression"
+        | "compound_assignment_expr" => node
+            .child_by_field_name("left")
+            .is_some_and(is_mutation_target),
+        "update_expression" => node
+            .child_by_field_name("argument")
+            .is_some_and(is_mutation_target),
+        "call_expression" => node.child_by_field_name("function").is_some_and(|callee| {
+            text(callee)
+                .split(['.', ':'])
+                .map(|segment| segment.to_lowercase())
+                .any(|segment| {
+                    IO_CALLEE_SEGMENTS.contains(&segment.as_str())
+                        || segment.starts_with("repo")
+                        || segment.starts_with("write")
+                })
+        }),
+        "macro_invocation" => node
+            .child_by_field_name("macro")
+            .is_some_and(|name| IO_MACROS.contains(&text(name))),
+        _ => false,
+    };
+    if effect {
+        return true;
+    }
+
+    let mut cursor = node.walk();
+    let children: Vec<Node> = node.children(&mut cursor).collect();
+    children
+        .into_iter()
+        .any(|child| has_side_effects(child, source, false, parameters))
+}
+
+/// Callee names (last path segment, `$` and case ignored) taking SQL as
+/// their first argument: `db.query`, `conn.execute`, `knex.raw`, Prisma
+/// `$queryRaw`, sqlx `query_as`, and the `sql` template tag
+const QUERY_CALLEES: &[&str] = &[
+    "query",
+    "execute",
+    "raw",
+    "sql",
+    "queryraw",
+    "executeraw",
+    "queryrawunsafe",
+    "executerawunsafe",
+    "query_as",
+    "query_scalar",
+];
+
+/// Captured SQL longer than this is cut
+const MAX_QUERY_LENGTH: usize = 200;
+
+lazy_static::lazy_static! {
+    /// A string that starts like a SQL statement
+    static ref SQL_STATEMENT: Regex = Regex::new(
+        r"(?i)^\(?\s*(SELECT|INSERT|UPDATE|DELETE|WITH|MERGE|UPSERT|REPLACE|CREATE|ALTER|DROP|TRUNCATE|CALL)\b"
+    )
+    .unwrap();
+}
+
+/// SQL strings passed to query calls ([`QUERY_CALLEES`]) in a body,
+/// tagged templates (sql`...`) included. Only string literals starting
+/// with a SQL keyword are kept, so `cache.query("users")` is ignored;
+/// interpolations (`${id}`) stay as written. Whitespace is collapsed, long
+/// queries are cut at [`MAX_QUERY_LENGTH`] characters, duplicates are
+/// removed. Closures count (queries inside `transaction(async () => ...)`);
+/// nested named functions do not.
+fn extract_queries(node: Node, source: &str) -> Vec<String> {
+    let mut queries = Vec::new();
+    collect_queries(node, source, true, &mut queries);
+    queries
+}
+
+fn collect_queries(node: Node, source: &str, is_root: bool, queries: &mut Vec<String>) {
+    if !is_root && NESTED_DEFINITION_KINDS.contains(&node.kind()) {
+        return;
+    }
+
+    let text = |n: Node| &source[n.start_byte()..n.end_byte()];
+    let is_query_callee = |callee: Node| {
+        let name = text(callee)
+            .rsplit(['.', ':'])
+            .next()
+            .unwrap_or_default()
+            .trim_start_matches('$')
+            .to_lowercase();
+        QUERY_CALLEES.contains(&name.as_str())
+    };
+    if node.kind() == "call_expression"
+        && node
+            .child_by_field_name("function")
+            .is_some_and(is_query_callee)
+        && let Some(arguments) = node.child_by_field_name("arguments")
+    {
+        // A tagged template's `arguments` is the template string itself
+        let literal = if arguments.kind() == "template_string" {
+            Some(arguments)
+        } else {
+            arguments.named_child(0)
+        };
+        if let Some(query) = literal.and_then(|literal| sql_literal(text(literal)))
+            && !queries.contains(&query)
+        {
+            queries.push(query);
+        }
+    }
+
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        collect_queries(child, source, false, queries);
+    }
+}
+
+/// The SQL inside a string literal (`'...'`, `"..."`, `` `...` ``, Rust
+/// `r#"..."#`), collapsed and cut; `None` when it is not a SQL statement
+fn sql_literal(literal: &str) -> Option<String> {
+    let unraw = literal
+        .strip_prefix('r')
+        .unwrap_or(literal)
+        .trim_matches('#');
+    let inner = ['"', '\'', '`'].iter().find_map(|quote| {
+        unraw
+            .strip_prefix(*quote)
+            .and_then(|rest| rest.strip_suffix(*quote))
+    })?;
+    let query = inner.split_whitespace().collect::<Vec<_>>().join(" ");
+    if !SQL_STATEMENT.is_match(&query) {
+        return None;
+    }
+    Some(if query.chars().count() > MAX_QUERY_LENGTH {
+        let cut: String = query.chars().take(MAX_QUERY_LENGTH).collect();
+        format!("{}…", cut.trim_end())
+    } else {
+        query
+    })
+}
+
+/// Rust macros that abort the current function
+const PANIC_MACROS: &[&str] = &["panic", "unreachable", "todo", "unimplemented"];
+
+/// Collects what a function throws, anywhere in its body (try/catch included).
+///
+/// TS/JS `throw new NotFoundException()` yields `NotFoundException`, a rethrow
+/// (`throw err`) yields the expression text; Rust panicking macros yield `panic!`.
+/// Nested functions and closures are skipped. Duplicates are removed, order is kept.
+fn extract_throw_sites(node: Node, source: &str) -> Vec<String> {
+    let mut throws = Vec::new();
+    collect_throw_sites(node, source, true, &mut throws);
+    throws
+}
+
+fn collect_throw_sites(node: Node, source: &str, is_root: bool, throws: &mut Vec<String>) {
+    if !is_root && NESTED_FUNCTION_KINDS.contains(&node.kind()) {
+        return;
+    }
+
+    let thrown = match node.kind() {
+        "throw_statement" => node.named_child(0).map(|expr| thrown_type(expr, source)),
+        "macro_invocation" => node
+            .child_by_field_name("macro")
+            .map(|m| &source[m.start_byte()..m.end_byte()])
+            .filter(|name| PANIC_MACROS.contains(name))
+            .map(|name| format!("{}!", name)),
+        _ => None,
+    };
+    if let Some(thrown) = thrown.filter(|t| !throws.contains(t)) {
+        throws.push(thrown);
+    }
+
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        collect_throw_sites(child, source, false, throws);
+    }
+}
+
+/// Name of the function called when the body is a single call statement,
+/// optionally returned and/or awaited: `return this.repo.find(id)`,
+/// `await save(x);`, Rust `self.inner.run(x)` / `run(x).await?`.
+fn extract_delegate_call(node: Node, source: &str) -> Option<String> {
+    let body = node.child_by_field_name("body")?;
+    if !matches!(body.kind(), "statement_block" | "block") {
+        return None;
+    }
+    let mut cursor = body.walk();
+    let statements: Vec<Node> = body
+        .named_children(&mut cursor)
+        .filter(|child| !matches!(child.kind(), "comment" | "line_comment" | "block_comment"))
+        .collect();
+    let [statement] = statements.as_slice() else {
+        return None;
+    };
+
+    let mut expr = *statement;
+    while matches!(
+        expr.kind(),
+        "return_statement"
+            | "expression_statement"
+            | "return_expression"
+            | "await_expression"
+            | "try_expression"
+    ) {
+        expr = expr.named_child(0)?;
+    }
+    if expr.kind() != "call_expression" {
+        return None;
+    }
+
+    let callee = expr.child_by_field_name("function")?;
+    let name = match callee.kind() {
+        "member_expression" => callee.child_by_field_name("property")?,
+        "field_expression" => callee.child_by_field_name("field")?,
+        "scoped_identifier" => callee.child_by_field_name("name")?,
+        "identifier" => callee,
+        _ => return None,
+    };
+    Some(source[name.start_byte()..name.end_byte()].to_string())
+}
+
+/// Definitions that get their own node; closures and arrow functions
+/// do not, so their env reads count toward the enclosing function
+const NESTED_DEFINITION_KINDS: &[&str] = &[
+    "function_declaration",
+    "generator_function_declaration",
+    "method_definition",
+    "function_item",
+];
+
+lazy_static::lazy_static! {
+    /// `TODO: text`, `FIXME(owner) text`, `HACK - text`...
+    static ref DEBT_MARKER: Regex =
+        Regex::new(r"\b(TODO|FIXME|HACK)\b(?:\([^)]*\))?[\s:-]*(.*)").unwrap();
+}
+
+fn is_comment(node: Node) -> bool {
+    matches!(node.kind(), "comment" | "line_comment" | "block_comment")
+}
+
+/// `TODO` / `FIXME` / `HACK` markers of the comments leading a definition
+/// and of the comments in its body (nested functions excluded), as
+/// `MARKER: text`. Duplicates are removed, order is kept.
+fn extract_annotations(node: Node, source: &str) -> Vec<String> {
+    let mut comments = Vec::new();
+    let mut cursor = node.prev_sibling();
+    while let Some(sibling) = cursor.filter(|sibling| is_comment(*sibling)) {
+        comments.push(sibling);
+        cursor = sibling.prev_sibling();
+    }
+    comments.reverse();
+    collect_body_comments(node, true, &mut comments);
+
+    let mut annotations = Vec::new();
+    for comment in comments {
+        for line in source[comment.start_byte()..comment.end_byte()].lines() {
+            let Some(captures) = DEBT_MARKER.captures(line) else {
+                continue;
+            };
+            let text = captures[2].trim().trim_end_matches("*/").trim_end();
+            let annotation = if text.is_empty() {
+                captures[1].to_string()
+            } else {
+                format!("{}: {}", &captures[1], text)
+            };
+            if !annotations.contains(&annotation) {
+                annotations.push(annotation);
+            }
+        }
+    }
+    annotations
+}
+
+fn collect_body_comments<'a>(node: Node<'a>, is_root: bool, comments: &mut Vec<Node<'a>>) {
+    if !is_root && NESTED_DEFINITION_KINDS.contains(&node.kind()) {
+        return;
+    }
+    if is_comment(node) {
+        comments.push(node);
+        return;
+    }
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        collect_body_comments(child, false, comments);
+    }
+}
+
+/// Keys of the environment variables and config entries read in a body:
+/// TS/JS `process.env.X` / `process.env["X"]` and `config.get("X")` on a
+/// receiver named `config` / `configService`; Rust `std::env::var("X")`,
+/// `env::var_os("X")`. Only literal keys are kept; duplicates are removed,
+/// order is kept.
+fn extract_env_access(node: Node, source: &str) -> Vec<String> {
+    let mut keys = Vec::new();
+    collect_env_access(node, source, true, &mut keys);
+    keys
+}
+
+fn collect_env_access(node: Node, source: &str, is_root: bool, keys: &mut Vec<String>) {
+    if !is_root && NESTED_DEFINITION_KINDS.contains(&node.kind()) {
+        return;
+    }
+
+    let text = |n: Node| &source[n.start_byte()..n.end_byte()];
+    let key = match node.kind() {
+        "member_expression" => node
+            .child_by_field_name("object")
+            .filter(|object| text(*object) == "process.env")
+            .and_then(|_| node.child_by_field_name("property"))
+            .map(|property| text(property).to_string()),
+        "subscript_expression" => node
+            .child_by_field_name("object")
+            .filter(|object| text(*object) == "process.env")
+            .and_then(|_| node.child_by_field_name("index"))
+            .and_then(|index| string_literal(text(index))),
+        "call_expression" => node
+            .child_by_field_name("function")
+            .filter(|callee| is_env_getter(*callee, source))
+            .and_then(|_| node.child_by_field_name("arguments"))
+            .and_then(|args| args.named_child(0))
+            .and_then(|arg| string_literal(text(arg))),
+        _ => None,
+    };
+    if let Some(key) = key.filter(|k| !keys.contains(k)) {
+        keys.push(key);
+    }
+
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        collect_env_access(child, source, false, keys);
+    }
+}
+
+/// `configService.get`, `this.config.get`, `std::env::var`, `env::var_os`
+fn is_env_getter(callee: Node, source: &str) -> bool {
+    let text = |n: Node| &source[n.start_byte()..n.end_byte()];
+    match callee.kind() {
+        "member_expression" => {
+            let property = callee.child_by_field_name("property").map(text);
+            let receiver = callee
+                .child_by_field_name("object")
+                .map(|object| text(object).rsplit('.').next().unwrap_or("").to_lowercase());
+            property == Some("get")
+                && receiver.is_some_and(|r| r.ends_with("config") || r.ends_with("configservice"))
+        }
+        "scoped_identifier" => {
+            let path = text(callee).replace(char::is_whitespace, "");
+            ["env::var", "env::var_os"]
+                .iter()
+                .any(|getter| path == *getter || path.ends_with(&format!("::{}", getter)))
+        }
+        _ => false,
+    }
+}
+
+/// Contents of a quoted literal without interpolation (`"X"`, `'X'`, `` `X` ``)
+fn string_literal(literal: &str) -> Option<String> {
+    let inner = ['"', '\'', '`'].iter().find_map(|quote| {
+        literal
+            .strip_prefix(*quote)
+            .and_then(|rest| rest.strip_suffix(*quote))
+    })?;
+    (!inner.is_empty() && !inner.contains("${") && !inner.contains('\\')).then(|| inner.to_string())
+}
+
+/// `function*`, `async function*` and `*method()` / `async *method()` definitions
+fn is_generator(node: Node) -> bool {
+    match node.kind() {
+        "generator_function_declaration" | "generator_function" => true,
+        "method_definition" => (0..node.child_count())
+            .filter_map(|i| node.child(i))
+            .take_while(|child| child.kind() != "statement_block")
+            .any(|child| child.kind() == "*"),
+        _ => false,
+    }
+}
+
+/// `yield x` -> `yield(x)`, `yield* other()` -> `yield*(other())`, in body order.
+/// Nested functions are skipped; whitespace in the operand is collapsed.
+fn extract_yield_steps(node: Node, source: &str) -> Vec<String> {
+    let mut steps = Vec::new();
+    collect_yield_steps(node, source, true, &mut steps);
+    steps
+}
+
+fn collect_yield_steps(node: Node, source: &str, is_root: bool, steps: &mut Vec<String>) {
+    if !is_root && NESTED_FUNCTION_KINDS.contains(&node.kind()) {
+        return;
+    }
+
+    if node.kind() == "yield_expression" {
+        let delegate = (0..node.child_count())
+            .filter_map(|i| node.child(i))
+            .any(|child| child.kind() == "*");
+        let operand = node
+            .named_child(0)
+            .map(|expr| {
+                source[expr.start_byte()..expr.end_byte()]
+                    .split_whitespace()
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            })
+            .unwrap_or_default();
+        let keyword = if delegate { "yield*" } else { "yield" };
+        steps.push(format!("{}({})", keyword, operand));
+    }
+
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        collect_yield_steps(child, source, false, steps);
+    }
+}
+
+/// Initialization order of a TS/JS `constructor`: its top-level statements
+/// that assign a field (`this.x = ...` -> `set(x)`) or call something
+/// (`super(...)` -> `call(super)`, `this.init()` -> `call(this.init)`).
+///
+/// Constructors that only receive injected dependencies have an empty body
+/// and yield no steps; other definitions never do.
+fn extract_init_steps(node: Node, source: &str) -> Vec<String> {
+    let text = |n: Node| {
+        source[n.start_byte()..n.end_byte()]
+            .split_whitespace()
+            .collect::<String>()
+    };
+    let is_constructor = node.kind() == "method_definition"
+        && node
+            .child_by_field_name("name")
+            .is_some_and(|name| text(name) == "constructor");
+    let Some(body) = node.child_by_field_name("body").filter(|_| is_constructor) else {
+        return Vec::new();
+    };
+
+    let mut steps = Vec::new();
+    let mut cursor = body.walk();
+    for statement in body.named_children(&mut cursor) {
+        let Some(mut expr) = statement
+            .named_child(0)
+            .filter(|_| statement.kind() == "expression_statement")
+        else {
+            continue;
+        };
+        if expr.kind() == "await_expression"
+            && let Some(awaited) = expr.named_child(0)
+        {
+            expr = awaited;
+        }
+        match expr.kind() {
+            "assignment_expression" | "augmented_assignment_expression" => {
+                if let Some(left) = expr.child_by_field_name("left")
+                    && left.kind() == "member_expression"
+                    && left
+                        .child_by_field_name("object")
+                        .is_some_and(|object| object.kind() == "this")
+                    && let Some(property) = left.child_by_field_name("property")
+                {
+                    steps.push(format!("set({})", text(property)));
+                }
+            }
+            "call_expression" => {
+                if let Some(callee) = expr.child_by_field_name("function") {
+                    steps.push(format!("call({})", text(callee)));
+                }
+            }
+            _ => {}
+        }
+    }
+    steps
+}
+
+/// `new NotFoundException(..)` -> `NotFoundException`, anything else -> its text
+fn thrown_type(expr: Node, source: &str) -> String {
+    let target = if expr.kind() == "new_expression" {
+        expr.child_by_field_name("constructor").unwrap_or(expr)
+    } else {
+        expr
+    };
+    source[target.start_byte()..target.end_byte()]
+        .trim()
+        .to_string()
+}
+
 /// Finds the start of the function body by matching brackets.
 /// Returns the index of the first unmatched opening brace '{'.
 /// Handles nested parentheses and angle brackets to avoid false positives.
+/// The `>` of an arrow (`-> T`, `=> T`) closes no angle bracket, so return
+/// types and bounds (`<T: Clone>(x: T) -> T`) do not hide the body.
 fn find_body_start(text: &str) -> Option<usize> {
     let mut paren_depth = 0;
     let mut angle_depth = 0;
+    let mut prev = '\0';
 
     for (i, ch) in text.char_indices() {
         match ch {
             '(' => paren_depth += 1,
             ')' => paren_depth -= 1,
             '<' => angle_depth += 1,
-            '>' => angle_depth -= 1,
+            '>' if prev != '-' && prev != '=' => angle_depth -= 1,
             '{' if paren_depth == 0 && angle_depth == 0 => return Some(i),
             _ => {}
         }
+        prev = ch;
     }
 
     None
@@ -265,10 +1728,13 @@ fn find_body_start(text: &str) -> Option<usize> {
 /// Detects if a signature is truncated or incomplete.
 /// Checks for unmatched brackets and incomplete patterns.
 fn is_truncated(signature: &str) -> bool {
+    let closing_angles = signature.matches('>').count()
+        - signature.matches("->").count()
+        - signature.matches("=>").count();
     signature.ends_with('(')
         || signature.ends_with('<')
         || signature.matches('(').count() != signature.matches(')').count()
-        || signature.matches('<').count() != signature.matches('>').count()
+        || signature.matches('<').count() != closing_angles
 }
 
 /// Finds the smallest node at a specific position in the AST.
@@ -312,3 +1778,533 @@ fn find_node_at_position(node: Node, line: usize, col: usize) -> Option<Node> {
     // No more specific child found, return this node
     Some(node)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Writes `source` to `sample.<ext>` in a fresh temp dir, kept alive by the guard
+    fn write_sample(ext: &str, source: &str) -> (tempfile::TempDir, std::path::PathBuf) {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(format!("sample.{}", ext));
+        std::fs::write(&path, source).unwrap();
+        (dir, path)
+    }
+
+    fn enrich_at(ext: &str, source: &str, line: usize) -> EnrichmentResult {
+        let (_dir, path) = write_sample(ext, source);
+        TreeSitterEnricher::new()
+            .enrich(&path, line)
+            .expect("definition should be enriched")
+    }
+
+    fn call_text_at(
+        ext: &str,
+        source: &str,
+        line: usize,
+        col: usize,
+        max: usize,
+    ) -> Option<String> {
+        let (_dir, path) = write_sample(ext, source);
+        TreeSitterEnricher::new()
+            .parse_source(&path)
+            .expect("source should parse")
+            .call_text(line, col, ColumnEncoding::Utf8, max)
+    }
+
+    #[test]
+    fn test_ts_call_text_at_method_reference() {
+        let source = r#"export class UsersController {
+  findOne(id: number): User {
+    return this.userService.findOne(
+      id,
+    );
+  }
+}
+"#;
+        // `findOne` in `this.userService.findOne(` starts at column 28
+        assert_eq!(
+            call_text_at("ts", source, 2, 28, 80).as_deref(),
+            Some("this.userService.findOne( id, )")
+        );
+        assert_eq!(
+            call_text_at("ts", source, 2, 28, 12).as_deref(),
+            Some("this.userSe…")
+        );
+        // `User` in the return type annotation is not a call
+        assert_eq!(call_text_at("ts", source, 1, 23, 80), None);
+    }
+
+    #[test]
+    fn test_column_encoding_to_byte_column() {
+        // "🚀 Woo": 'W' is UTF-8 byte 5, UTF-16 unit 3, UTF-32 unit 2
+        let line = "🚀 Woo";
+        assert_eq!(ColumnEncoding::Utf8.to_byte_column(line, 5), 5);
+        assert_eq!(ColumnEncoding::Utf16.to_byte_column(line, 3), 5);
+        assert_eq!(ColumnEncoding::Utf32.to_byte_column(line, 2), 5);
+        assert_eq!(ColumnEncoding::Utf16.to_byte_column(line, 99), line.len());
+    }
+
+    #[test]
+    fn test_call_text_ignores_arguments() {
+        let source = "fn run() {\n    save(load(1));\n}\n";
+        assert_eq!(
+            call_text_at("rs", source, 1, 4, 80).as_deref(),
+            Some("save(load(1))")
+        );
+        assert_eq!(
+            call_text_at("rs", source, 1, 9, 80).as_deref(),
+            Some("load(1)")
+        );
+    }
+
+    #[test]
+    fn test_delegating_bodies() {
+        let source = "class UserController {\n  find(id: number) {\n    return this.service.find(id);\n  }\n  async save(dto: Dto) {\n    // forward\n    await this.service.save(dto);\n  }\n  count() {\n    const n = this.service.count();\n    return n;\n  }\n}\n";
+        assert_eq!(
+            enrich_at("ts", source, 1).delegate_call.as_deref(),
+            Some("find")
+        );
+        assert_eq!(
+            enrich_at("ts", source, 4).delegate_call.as_deref(),
+            Some("save")
+        );
+        assert_eq!(enrich_at("ts", source, 8).delegate_call, None);
+
+        let source = "impl Store {\n    fn get(&self, id: u32) -> Option<Item> {\n        self.inner.get(id)\n    }\n}\n";
+        assert_eq!(
+            enrich_at("rs", source, 1).delegate_call.as_deref(),
+            Some("get")
+        );
+    }
+
+    #[test]
+    fn test_error_guard_vs_success_early_return() {
+        let source = "class Users {\n  find(id: number) {\n    if (id <= 0) {\n      throw new BadRequestException();\n    }\n    if (this.cache.has(id)) {\n      return this.cache.get(id);\n    }\n    if (!this.ready) {\n      return null;\n    }\n    return this.repo.find(id);\n  }\n}\n";
+        let result = enrich_at("ts", source, 1);
+        assert_eq!(
+            result.preconditions,
+            vec!["must avoid: id <= 0", "must avoid: !this.ready"]
+        );
+        assert_eq!(result.early_returns, vec!["return(this.cache.has(id))"]);
+    }
+
+    #[test]
+    fn test_max_preconditions_keeps_first_guards_and_counts_the_rest() {
+        let source = "function create(a: number, b: number, c: number, d: number, e: number) {\n  if (a < 0) {\n    throw new Error();\n  }\n  if (b < 0) {\n    throw new Error();\n  }\n  if (c < 0) {\n    throw new Error();\n  }\n  if (d < 0) {\n    throw new Error();\n  }\n  if (e < 0) {\n    throw new Error();\n  }\n  return a + b + c + d + e;\n}\n";
+        let (_dir, path) = write_sample("ts", source);
+
+        let all = TreeSitterEnricher::new().enrich(&path, 0).unwrap();
+        assert_eq!(all.preconditions.len(), 5);
+
+        let capped = TreeSitterEnricher::new()
+            .with_max_preconditions(Some(2))
+            .enrich(&path, 0)
+            .unwrap();
+        assert_eq!(
+            capped.preconditions,
+            vec!["must avoid: a < 0", "must avoid: b < 0", "(+3 more)"]
+        );
+    }
+
+    #[test]
+    fn test_long_documentation_is_cut_at_a_word_boundary() {
+        let doc =
+            "Finds the users matching every filter of the query and loads their roles".to_string();
+        assert_eq!(
+            truncate_documentation(doc.clone(), Some(30)),
+            "Finds the users matching every…"
+        );
+        // A full sentence is kept whole when it fills enough of the budget
+        assert_eq!(
+            truncate_documentation(
+                "Loads a user. Falls back to the cache when the database is down.".to_string(),
+                Some(20)
+            ),
+            "Loads a user.…"
+        );
+        // Short (or unlimited) documentation is left intact
+        assert_eq!(
+            truncate_documentation("Loads a user.".to_string(), Some(30)),
+            "Loads a user."
+        );
+        assert_eq!(truncate_documentation(doc.clone(), None), doc);
+    }
+
+    #[test]
+    fn test_doc_limits_apply_during_enrichment() {
+        let source = "// eslint-disable-next-line complexity\n/**\n * Finds a user by id and loads every role it holds\n * @param id\n * @returns\n */\nfunction find(id: number) {\n  return id;\n}\n";
+        let (_dir, path) = write_sample("ts", source);
+
+        let full = TreeSitterEnricher::new().enrich(&path, 6).unwrap();
+        assert!(full.documentation.unwrap().contains("@param id"));
+
+        let trimmed = TreeSitterEnricher::new()
+            .with_doc_boilerplate_stripped(true)
+            .with_max_doc_length(Some(32))
+            .enrich(&path, 6)
+            .unwrap();
+        assert_eq!(
+            trimmed.documentation.as_deref(),
+            Some("Finds a user by id and loads…")
+        );
+    }
+
+    #[test]
+    fn test_todo_comments_become_annotations() {
+        let ts = "class Users {\n  // FIXME(ana): cache misses\n  find(id: number) {\n    // TODO: refactor\n    const helper = () => {\n      // TODO: inline the helper\n    };\n    return this.repo.get(id); // HACK avoids the N+1 query\n  }\n\n  // Plain comment\n  save(user: User) {\n    return this.repo.put(user);\n  }\n}\n";
+        assert_eq!(
+            enrich_at("ts", ts, 2).annotations,
+            vec![
+                "FIXME: cache misses",
+                "TODO: refactor",
+                "TODO: inline the helper",
+                "HACK: avoids the N+1 query",
+            ]
+        );
+        assert!(enrich_at("ts", ts, 11).annotations.is_empty());
+
+        let rs = "/* TODO */\nfn run() {\n    // todo is not a marker\n}\n";
+        assert_eq!(enrich_at("rs", rs, 1).annotations, vec!["TODO"]);
+    }
+
+    #[test]
+    fn test_purity_heuristic() {
+        let ts = "class Prices {\n  total(price: number, qty: number) {\n    const subtotal = price * qty;\n    return subtotal + subtotal * 0.2;\n  }\n  async find(id: number) {\n    return await this.repo.findOne(id);\n  }\n  reset(cart: Cart) {\n    cart.items = [];\n  }\n  log(x: number) {\n    console.log(x);\n  }\n}\n";
+        assert!(enrich_at("ts", ts, 1).pure);
+        assert!(!enrich_at("ts", ts, 5).pure);
+        // Mutates a parameter / writes to the console
+        assert!(!enrich_at("ts", ts, 8).pure);
+        assert!(!enrich_at("ts", ts, 11).pure);
+
+        let rs = "fn area(w: f64, h: f64) -> f64 {\n    let mut a = w;\n    a *= h;\n    a\n}\n\nimpl Counter {\n    fn bump(&mut self) {\n        self.count += 1;\n    }\n}\n\nfn report(total: u32) {\n    println!(\"{}\", total);\n}\n";
+        assert!(enrich_at("rs", rs, 0).pure);
+        assert!(!enrich_at("rs", rs, 7).pure);
+        assert!(!enrich_at("rs", rs, 12).pure);
+    }
+
+    #[test]
+    fn test_embedded_sql_is_captured() {
+        let ts = "class UsersRepository {\n  find(id: number) {\n    return this.db.query(`SELECT id, name\n      FROM users WHERE id = $1`, [id]);\n  }\n  async archive(id: number) {\n    await prisma.$executeRaw`UPDATE users SET archived = true WHERE id = ${id}`;\n    await this.cache.query(\"users\");\n  }\n  count() {\n    return this.repo.count();\n  }\n}\n";
+        assert_eq!(
+            enrich_at("ts", ts, 1).queries,
+            vec!["SELECT id, name FROM users WHERE id = $1"]
+        );
+        assert_eq!(
+            enrich_at("ts", ts, 5).queries,
+            vec!["UPDATE users SET archived = true WHERE id = ${id}"]
+        );
+        assert!(enrich_at("ts", ts, 9).queries.is_empty());
+
+        let long = format!("SELECT {} FROM wide", vec!["col"; 100].join(", "));
+        let rs = format!(
+            "async fn load(pool: &Pool) {{\n    sqlx::query(r#\"{}\"#).fetch_all(pool).await;\n}}\n",
+            long
+        );
+        let queries = enrich_at("rs", &rs, 0).queries;
+        assert_eq!(queries.len(), 1);
+        assert!(queries[0].starts_with("SELECT col, col"));
+        assert!(queries[0].ends_with('…'));
+        assert!(queries[0].chars().count() <= MAX_QUERY_LENGTH + 1);
+    }
+
+    #[test]
+    fn test_registered_language_is_enriched() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("service.mjs");
+        std::fs::write(
+            &path,
+            "export function load(id) {\n  if (!id) throw new Error('id');\n  return fetch(id);\n}\n",
+        )
+        .unwrap();
+        let scala = dir.path().join("Service.scala");
+        std::fs::write(&scala, "object Service\n").unwrap();
+
+        let mut enricher = TreeSitterEnricher::new();
+        assert!(enricher.enrich(&path, 0).is_none());
+        assert!(enricher.enrich(&scala, 0).is_none());
+        assert_eq!(
+            enricher.unsupported_extensions().iter().collect::<Vec<_>>(),
+            vec!["mjs", "scala"]
+        );
+
+        enricher.register_language("mjs", tree_sitter_javascript::language());
+        let result = enricher.enrich(&path, 0).expect("mjs should be enriched");
+        assert!(result.signature.is_some_and(|sig| sig.contains("load")));
+        assert_eq!(result.complexity, 2);
+    }
+
+    #[test]
+    fn test_constructor_initialization_order() {
+        let ts = "class Cache {\n  constructor(private readonly store: Store, size: number) {\n    super();\n    this.size = size;\n    if (size > 0) {\n      this.ready = false;\n    }\n    this.entries = new Map();\n    this.warmUp();\n  }\n  warmUp() {\n    this.ready = true;\n  }\n}\n";
+        assert_eq!(
+            enrich_at("ts", ts, 1).init_steps,
+            vec![
+                "call(super)",
+                "set(size)",
+                "set(entries)",
+                "call(this.warmUp)"
+            ]
+        );
+        assert!(enrich_at("ts", ts, 10).init_steps.is_empty());
+
+        let di = "class UserService {\n  constructor(private readonly repo: Repo) {}\n}\n";
+        assert!(enrich_at("ts", di, 1).init_steps.is_empty());
+    }
+
+    #[test]
+    fn test_bounded_generics_survive_extraction() {
+        let ts = r#"class Repo {
+  findAll<T extends Entity>(items: Map<string, T>): T[] {
+    return [...items.values()];
+  }
+}
+"#;
+        assert_eq!(
+            enrich_at("ts", ts, 1).signature.as_deref(),
+            Some("findAll<T extends Entity>(items: Map<string, T>): T[]")
+        );
+
+        let rs = r#"pub fn longest<T: Clone + Ord>(items: &[T]) -> Option<T>
+where
+    T: Debug,
+{
+    items.iter().max().cloned()
+}
+"#;
+        assert_eq!(
+            enrich_at("rs", rs, 0).signature.as_deref(),
+            Some("pub fn longest<T: Clone + Ord>(items: &[T]) -> Option<T>\nwhere\n    T: Debug,")
+        );
+    }
+
+    #[test]
+    fn test_cyclomatic_complexity_counts_decision_points() {
+        let ts = "class Orders {\n  total(order: Order) {\n    return order.sum;\n  }\n  ship(order: Order) {\n    if (!order || order.cancelled) {\n      throw new Error();\n    }\n    for (const item of order.items) {\n      switch (item.kind) {\n        case 'box': pack(item); break;\n        case 'bag': wrap(item); break;\n        default: skip(item);\n      }\n    }\n    order.items.forEach((item) => { if (item.fragile) { mark(item); } });\n    return order.express ? 'air' : 'ground';\n  }\n}\n";
+        assert_eq!(enrich_at("ts", ts, 1).complexity, 1);
+        // if, ||, for, 2 cases, ?: (the closure's `if` is its own)
+        assert_eq!(enrich_at("ts", ts, 4).complexity, 7);
+
+        let rs = "fn id(x: u32) -> u32 {\n    x\n}\n\nfn classify(x: i32, strict: bool) -> &'static str {\n    if x < 0 && strict {\n        return \"negative\";\n    }\n    while x > 100 {\n        break;\n    }\n    match x {\n        0 => \"zero\",\n        1 | 2 => \"small\",\n        _ => \"large\",\n    }\n}\n";
+        assert_eq!(enrich_at("rs", rs, 0).complexity, 1);
+        // if, &&, while, 2 non-wildcard arms
+        assert_eq!(enrich_at("rs", rs, 4).complexity, 6);
+    }
+
+    #[test]
+    fn test_guards_of_nested_functions_are_not_preconditions() {
+        let source = "class Jobs {\n  run(items: Item[]) {\n    if (!items) {\n      throw new Error();\n    }\n    items.forEach((item) => {\n      if (!item.id) {\n        throw new Error();\n      }\n    });\n    for (const item of items) {\n      if (item.done) {\n        throw new Error();\n      }\n    }\n  }\n}\nconst check = (id: number) => {\n  if (id < 0) {\n    throw new Error();\n  }\n};\n";
+        assert_eq!(
+            enrich_at("ts", source, 1).preconditions,
+            vec!["must avoid: !items"]
+        );
+        // The arrow function is the value of `check`: its guards are kept
+        assert_eq!(
+            enrich_at("ts", source, 17).preconditions,
+            vec!["must avoid: id < 0"]
+        );
+
+        // Deeper guards (inside the loop) with a larger depth, never the closure's
+        let (_dir, path) = write_sample("ts", source);
+        let deeper = TreeSitterEnricher::new()
+            .with_max_guard_depth(2)
+            .enrich(&path, 1)
+            .unwrap();
+        assert_eq!(
+            deeper.preconditions,
+            vec!["must avoid: !items", "must avoid: item.done"]
+        );
+    }
+
+    #[test]
+    fn test_ts_env_and_config_reads() {
+        let source = "class Db {\n  connect() {\n    const url = process.env.DATABASE_URL;\n    const pool = process.env['POOL_SIZE'] ?? process.env.DATABASE_URL;\n    const retry = () => this.configService.get<number>('db.retries');\n    return open(url, pool, retry, process.env[name]);\n  }\n  close() {\n    return this.pool.end();\n  }\n}\n";
+        assert_eq!(
+            enrich_at("ts", source, 1).env_access,
+            vec!["DATABASE_URL", "POOL_SIZE", "db.retries"]
+        );
+        assert!(enrich_at("ts", source, 7).env_access.is_empty());
+    }
+
+    #[test]
+    fn test_rust_env_var_reads() {
+        let source = "fn load() -> Config {\n    let url = std::env::var(\"DATABASE_URL\").unwrap();\n    let home = env::var_os(\"HOME\");\n    let map = std::collections::HashMap::new();\n    Config { url, home, port: map.get(\"PORT\") }\n}\n";
+        assert_eq!(
+            enrich_at("rs", source, 0).env_access,
+            vec!["DATABASE_URL", "HOME"]
+        );
+    }
+
+    #[test]
+    fn test_ts_sync_generator_function() {
+        let source = r#"function* range(start: number, end: number) {
+  for (let i = start; i < end; i++) {
+    yield i;
+  }
+  yield* [end];
+  const inner = function* () { yield -1; };
+}
+
+function plain() {
+  return 1;
+}
+"#;
+        let result = enrich_at("ts", source, 0);
+        assert!(result.generator);
+        assert_eq!(result.yields, vec!["yield(i)", "yield*([end])"]);
+
+        let result = enrich_at("ts", source, 8);
+        assert!(!result.generator);
+        assert!(result.yields.is_empty());
+    }
+
+    #[test]
+    fn test_ts_async_generator_method() {
+        let source = r#"export class Pager {
+  async *pages(client: Client) {
+    let cursor = await client.first();
+    while (cursor) {
+      yield cursor.items;
+      cursor = await client.next(
+        cursor,
+      );
+    }
+  }
+
+  async load() {
+    return [];
+  }
+}
+"#;
+        let result = enrich_at("ts", source, 1);
+        assert!(result.generator);
+        assert_eq!(result.yields, vec!["yield(cursor.items)"]);
+
+        assert!(!enrich_at("ts", source, 11).generator);
+    }
+
+    #[test]
+    fn test_ts_deprecated_method() {
+        let source = r#"export class UsersService {
+  /**
+   * @deprecated use findById instead
+   */
+  findOne(id: number) {
+    return id;
+  }
+
+  /** Finds a user by id */
+  findById(id: number) {
+    return id;
+  }
+}
+"#;
+        assert!(enrich_at("ts", source, 4).deprecated);
+        assert!(!enrich_at("ts", source, 10).deprecated);
+    }
+
+    #[test]
+    fn test_nestjs_routes_include_controller_prefix() {
+        let source = r#"@Controller('users')
+export class UsersController {
+  constructor(private readonly service: UsersService) {}
+
+  @Get(':id')
+  findOne(@Param('id') id: string) {
+    return this.service.findOne(id);
+  }
+
+  // Creates a user
+  @Post()
+  @HttpCode(201)
+  create(@Body() dto: CreateUserDto) {
+    return this.service.create(dto);
+  }
+
+  private toDto(user: User) {
+    return user;
+  }
+}
+"#;
+        let route_at = |line| enrich_at("ts", source, line).route;
+        assert_eq!(route_at(5).as_deref(), Some("GET /users/:id"));
+        assert_eq!(route_at(12).as_deref(), Some("POST /users"));
+        assert_eq!(route_at(2), None);
+        assert_eq!(route_at(16), None);
+    }
+
+    #[test]
+    fn test_rust_route_attribute() {
+        let source = r#"#[get("/users/{id}")]
+async fn show(id: web::Path<u32>) -> impl Responder {
+    HttpResponse::Ok()
+}
+
+#[inline]
+fn helper() {}
+"#;
+        assert_eq!(
+            enrich_at("rs", source, 1).route.as_deref(),
+            Some("GET /users/{id}")
+        );
+        assert_eq!(enrich_at("rs", source, 6).route, None);
+    }
+
+    #[test]
+    fn test_rust_deprecated_function() {
+        let source = r#"#[deprecated(since = "0.2.0", note = "use parse_v2")]
+/// Parses the legacy format
+pub fn parse(input: &str) -> usize {
+    input.len()
+}
+
+#[inline]
+pub fn parse_v2(input: &str) -> usize {
+    input.len()
+}
+"#;
+        assert!(enrich_at("rs", source, 2).deprecated);
+        assert!(!enrich_at("rs", source, 7).deprecated);
+    }
+
+    #[test]
+    fn test_ts_throw_inside_catch_block() {
+        let source = r#"function load(id: number) {
+  try {
+    return fetch(id);
+  } catch (err) {
+    throw new NotFoundException(`missing ${id}`);
+  }
+}
+"#;
+        assert_eq!(enrich_at("ts", source, 0).throws, vec!["NotFoundException"]);
+    }
+
+    #[test]
+    fn test_ts_function_that_never_throws() {
+        let source = r#"function add(a: number, b: number): number {
+  const inner = () => { throw new Error("not mine"); };
+  return a + b;
+}
+"#;
+        assert!(enrich_at("ts", source, 0).throws.is_empty());
+    }
+
+    #[test]
+    fn test_rust_panic_is_a_throw_site() {
+        let source = r#"fn parse(input: &str) -> u32 {
+    match input.parse() {
+        Ok(v) => v,
+        Err(_) => panic!("bad input"),
+    }
+}
+"#;
+        assert_eq!(enrich_at("rs", source, 0).throws, vec!["panic!"]);
+    }
+
+    #[test]
+    fn test_rust_function_that_never_throws() {
+        let source = "fn double(x: u32) -> u32 {\n    println!(\"{}\", x);\n    x * 2\n}\n";
+        assert!(enrich_at("rs", source, 0).throws.is_empty());
+    }
+}
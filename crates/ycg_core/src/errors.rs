@@ -132,6 +132,16 @@ pub enum ParseError {
     /// Failed to read source file for enrichment
     #[error("Failed to read source file '{path}': {reason}")]
     SourceFileReadError { path: PathBuf, reason: String },
+
+    /// SCIP protocol version outside the range YCG is tested against (strict mode)
+    #[error(
+        "Unsupported SCIP protocol version {version} (produced by {tool}). Supported versions: {supported}. Re-run without --strict to continue anyway."
+    )]
+    UnsupportedProtocolVersion {
+        version: i32,
+        tool: String,
+        supported: String,
+    },
 }
 
 /// Output validation errors
@@ -307,6 +317,15 @@ impl ParseError {
     pub fn scip_decode_error(path: PathBuf, reason: String) -> Self {
         ParseError::ScipDecodeError { path, reason }
     }
+
+    /// Create an UnsupportedProtocolVersion error
+    pub fn unsupported_protocol_version(version: i32, tool: String, supported: String) -> Self {
+        ParseError::UnsupportedProtocolVersion {
+            version,
+            tool,
+            supported,
+        }
+    }
 }
 
 impl ValidationError {
@@ -441,6 +460,19 @@ mod tests {
         assert!(display.contains("not found"));
     }
 
+    #[test]
+    fn test_unsupported_protocol_version_display() {
+        let err = ParseError::unsupported_protocol_version(
+            3,
+            "scip-python 0.3.0".to_string(),
+            "0".to_string(),
+        );
+        let display = format!("{}", err);
+        assert!(display.contains("version 3"));
+        assert!(display.contains("scip-python 0.3.0"));
+        assert!(display.contains("--strict"));
+    }
+
     #[test]
     fn test_validation_error_referential_integrity() {
         let invalid_edges = vec![
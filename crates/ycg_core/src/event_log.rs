@@ -0,0 +1,153 @@
+// crates/ycg_core/src/event_log.rs
+//! Structured diagnostics (`--log-json`).
+//!
+//! Conversion diagnostics (rejected signatures, unresolved variable names,
+//! skipped files...) are [`Event`]s sent to an [`EventLog`]. By default an
+//! event is printed to stderr as the same human-readable line as before;
+//! with `--log-json <path>` every event is written to that file as one JSON
+//! object per line instead, tagged by `event`. Files read and symbols
+//! dropped by the level of detail are only recorded in the JSON log, since
+//! they would flood the terminal.
+
+use crate::LevelOfDetail;
+use crate::model::ScipSymbolKind;
+use anyhow::{Context, Result};
+use serde::Serialize;
+use std::fmt;
+use std::fs::File;
+use std::io::{LineWriter, Write};
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+
+/// A diagnostic raised while building the graph
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum Event {
+    /// A source document was read for token counting and extraction
+    FileRead { path: String, bytes: usize },
+    /// A definition was dropped by the level of detail (`--lod`)
+    SymbolSkipped {
+        symbol: String,
+        kind: ScipSymbolKind,
+        lod: LevelOfDetail,
+    },
+    /// A method-like signature extracted for a variable was discarded
+    SignatureRejected {
+        symbol: String,
+        signature: String,
+        reason: &'static str,
+    },
+    /// A signature cut off mid-declaration was discarded
+    SignatureTruncated { file: String, line: usize },
+    /// A generic variable name (`default`, `constructor`...) was resolved from source
+    VariableResolved {
+        name: String,
+        resolved: String,
+        file: String,
+        line: i32,
+    },
+    /// A generic variable name could not be resolved; the SCIP name is kept
+    VariableUnresolved {
+        name: String,
+        file: String,
+        line: i32,
+    },
+    /// A source file above `--max-file-bytes` was not parsed
+    FileSkipped {
+        path: String,
+        bytes: u64,
+        limit: u64,
+    },
+    /// Any other warning
+    Warning { message: String },
+}
+
+impl Event {
+    pub fn warning(message: impl Into<String>) -> Self {
+        Event::Warning {
+            message: message.into(),
+        }
+    }
+
+    /// Whether the event is worth a stderr line when no JSON log is set
+    fn is_diagnostic(&self) -> bool {
+        !matches!(self, Event::FileRead { .. } | Event::SymbolSkipped { .. })
+    }
+}
+
+impl fmt::Display for Event {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Event::FileRead { path, bytes } => write!(f, "Read {} ({} bytes)", path, bytes),
+            Event::SymbolSkipped { symbol, lod, .. } => {
+                write!(f, "Skipped {} (level of detail {:?})", symbol, lod)
+            }
+            Event::SignatureRejected { symbol, reason, .. } => write!(
+                f,
+                "⚠️  Rejecting method signature for variable {}: {}",
+                symbol, reason
+            ),
+            Event::SignatureTruncated { file, line } => write!(
+                f,
+                "Warning: Truncated signature at {}:{} - falling back to symbol name",
+                file, line
+            ),
+            Event::VariableResolved {
+                name,
+                resolved,
+                file,
+                line,
+            } => write!(
+                f,
+                "✓ Resolved generic name '{}' to '{}' at {}:{}",
+                name, resolved, file, line
+            ),
+            Event::VariableUnresolved { name, file, line } => write!(
+                f,
+                "⚠️  Failed to resolve generic name '{}' at {}:{}, using SCIP name",
+                name, file, line
+            ),
+            Event::FileSkipped { path, bytes, limit } => write!(
+                f,
+                "⚠️  Skipping enrichment of {} ({} bytes > --max-file-bytes {})",
+                path, bytes, limit
+            ),
+            Event::Warning { message } => write!(f, "⚠️  {}", message),
+        }
+    }
+}
+
+/// Where events go: stderr by default, an NDJSON file with `--log-json`.
+///
+/// Cloning shares the same file, so the enricher and the converter write
+/// to one log.
+#[derive(Clone, Default)]
+pub struct EventLog {
+    sink: Option<Arc<Mutex<LineWriter<File>>>>,
+}
+
+impl EventLog {
+    /// Logs to a new NDJSON file (truncated if it exists)
+    pub fn create(path: &Path) -> Result<Self> {
+        let file = File::create(path)
+            .with_context(|| format!("Failed to create event log: {:?}", path))?;
+        Ok(Self {
+            sink: Some(Arc::new(Mutex::new(LineWriter::new(file)))),
+        })
+    }
+
+    pub fn emit(&self, event: Event) {
+        match &self.sink {
+            // A failing log must not fail the conversion
+            Some(sink) => {
+                if let Ok(line) = serde_json::to_string(&event)
+                    && let Ok(mut writer) = sink.lock()
+                {
+                    let _ = writeln!(writer, "{}", line);
+                }
+            }
+            None if event.is_diagnostic() => eprintln!("{}", event),
+            None => {}
+        }
+    }
+}
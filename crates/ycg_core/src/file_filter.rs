@@ -10,6 +10,7 @@ pub struct FileFilter {
     include_patterns: Vec<glob::Pattern>,
     exclude_patterns: Vec<glob::Pattern>,
     gitignore_matcher: Option<GitignoreMatcher>,
+    exclude_tests: bool,
 }
 
 impl FileFilter {
@@ -46,16 +47,24 @@ impl FileFilter {
             include_patterns,
             exclude_patterns,
             gitignore_matcher,
+            exclude_tests: false,
         })
     }
 
+    /// Also exclude test files (see [`is_test_file`])
+    pub fn excluding_tests(mut self, exclude_tests: bool) -> Self {
+        self.exclude_tests = exclude_tests;
+        self
+    }
+
     /// Check if a file should be processed based on filtering rules
     ///
     /// Rules (in order):
     /// 1. If include patterns exist, file must match at least one
     /// 2. If file matches any exclude pattern, it's excluded
-    /// 3. If gitignore is enabled and file is ignored, it's excluded
-    /// 4. Otherwise, file is included
+    /// 3. If test files are excluded and this is one, it's excluded
+    /// 4. If gitignore is enabled and file is ignored, it's excluded
+    /// 5. Otherwise, file is included
     pub fn should_process(&self, file_path: &Path) -> bool {
         let path_str = file_path.to_string_lossy();
 
@@ -80,7 +89,12 @@ impl FileFilter {
             return false;
         }
 
-        // Rule 3: Check gitignore
+        // Rule 3: Test files (--tests exclude / separate)
+        if self.exclude_tests && is_test_file(file_path) {
+            return false;
+        }
+
+        // Rule 4: Check gitignore
         if let Some(ref matcher) = self.gitignore_matcher {
             if matcher.is_ignored(file_path) {
                 return false;
@@ -91,6 +105,15 @@ impl FileFilter {
         true
     }
 
+    /// Paths of the documents `filter_documents` would drop
+    pub fn excluded_paths(&self, documents: &[scip_proto::Document]) -> Vec<String> {
+        documents
+            .iter()
+            .filter(|doc| !self.should_process(Path::new(&doc.relative_path)))
+            .map(|doc| doc.relative_path.clone())
+            .collect()
+    }
+
     /// Filter a list of SCIP documents based on filtering rules
     pub fn filter_documents(
         &self,
@@ -106,6 +129,61 @@ impl FileFilter {
     }
 }
 
+/// Path heuristics for test files:
+///
+/// - `*.spec.*` / `*.test.*` JS/TS files (`users.spec.ts`, `app.test.tsx`)
+/// - `*_test.rs` files and `.rs` files under a `tests/` directory
+/// - anything under a `__tests__/` directory
+pub fn is_test_file(path: &Path) -> bool {
+    let Some(file_name) = path.file_name().and_then(|name| name.to_str()) else {
+        return false;
+    };
+    let in_dir = |dir: &str| {
+        path.parent()
+            .is_some_and(|parent| parent.components().any(|c| c.as_os_str() == dir))
+    };
+
+    let (stem, ext) = file_name.rsplit_once('.').unwrap_or((file_name, ""));
+    match ext {
+        "ts" | "tsx" | "js" | "jsx" | "mts" | "cts" | "mjs" | "cjs" => {
+            stem.ends_with(".spec") || stem.ends_with(".test") || in_dir("__tests__")
+        }
+        "rs" => stem.ends_with("_test") || in_dir("tests") || in_dir("__tests__"),
+        _ => in_dir("__tests__"),
+    }
+}
+
+/// Content heuristic for generated files (`--skip-generated`): the leading
+/// comment block of the file carries a generator marker
+///
+/// - `@generated` (Facebook / Relay / GraphQL codegen convention)
+/// - `Code generated ... DO NOT EDIT.` (Go convention, used by protoc plugins)
+/// - `<auto-generated>` (.NET tooling)
+///
+/// Only comment lines before the first line of code are inspected, so a
+/// string literal mentioning `@generated` does not mark the file.
+pub fn is_generated_source(content: &str) -> bool {
+    for line in content.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with("#!") {
+            continue;
+        }
+        let is_comment = ["//", "/*", "*", "#", "<!--"]
+            .iter()
+            .any(|marker| line.starts_with(marker));
+        if !is_comment {
+            return false;
+        }
+        if line.contains("@generated")
+            || line.contains("<auto-generated")
+            || (line.contains("Code generated") && line.contains("DO NOT EDIT"))
+        {
+            return true;
+        }
+    }
+    false
+}
+
 /// Wrapper around gitignore crate for matching ignored files
 struct GitignoreMatcher {
     gitignore: ignore::gitignore::Gitignore,
@@ -147,6 +225,61 @@ mod tests {
     use std::fs;
     use tempfile::TempDir;
 
+    #[test]
+    fn test_is_generated_source() {
+        for content in [
+            "// @generated\nexport const a = 1;\n",
+            "/**\n * This file is @generated by relay-compiler\n */\nexport {};\n",
+            "// Code generated by protoc-gen-ts. DO NOT EDIT.\nexport class User {}\n",
+            "#!/usr/bin/env node\n// @generated\nrun();\n",
+            "// <auto-generated>\n// </auto-generated>\n",
+        ] {
+            assert!(is_generated_source(content), "{content:?}");
+        }
+        for content in [
+            "export const marker = \"@generated\";\n",
+            "// Users service\nexport class Users {}\n// @generated below\n",
+            "// Code generated by hand\nfn main() {}\n",
+            "",
+        ] {
+            assert!(!is_generated_source(content), "{content:?}");
+        }
+    }
+
+    #[test]
+    fn test_is_test_file() {
+        for path in [
+            "src/users.spec.ts",
+            "src/app.test.tsx",
+            "src/__tests__/users.ts",
+            "src/parser_test.rs",
+            "tests/integration.rs",
+        ] {
+            assert!(is_test_file(Path::new(path)), "{}", path);
+        }
+        for path in [
+            "src/users.ts",
+            "src/spec.ts",
+            "src/contest.ts",
+            "src/testing.rs",
+            "tests/fixtures/data.json",
+        ] {
+            assert!(!is_test_file(Path::new(path)), "{}", path);
+        }
+    }
+
+    #[test]
+    fn test_excluding_tests() {
+        let filter = FileFilter::new(&FileFilterConfig::default(), Path::new("."))
+            .unwrap()
+            .excluding_tests(true);
+        assert!(filter.should_process(Path::new("src/users.ts")));
+        assert!(!filter.should_process(Path::new("src/users.spec.ts")));
+
+        let keep = FileFilter::new(&FileFilterConfig::default(), Path::new(".")).unwrap();
+        assert!(keep.should_process(Path::new("src/users.spec.ts")));
+    }
+
     #[test]
     fn test_no_patterns_includes_all() {
         let config = FileFilterConfig {
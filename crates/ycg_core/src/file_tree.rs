@@ -0,0 +1,68 @@
+// crates/ycg_core/src/file_tree.rs
+//! File hierarchy by path (`--file-paths`).
+//!
+//! File nodes normally hang off nothing (or another module anchor), so the
+//! directory layout of the project is lost in `_defs`. In this mode File
+//! nodes carry their `path`, and their `parent_id` is the directory that
+//! holds them: one Module node per directory, whose id and `path` are the
+//! directory path and whose parent is the enclosing directory. Files at the
+//! project root keep no parent.
+
+use crate::model::{ScipSymbolKind, SymbolNode, YcgGraph};
+use std::collections::{BTreeSet, HashSet};
+
+/// Directory of a relative path (`src/users/users.ts` -> `src/users`)
+fn parent_dir(path: &str) -> Option<&str> {
+    path.rsplit_once('/')
+        .map(|(dir, _)| dir)
+        .filter(|dir| !dir.is_empty())
+}
+
+/// Nests File nodes carrying a `path` under directory nodes
+///
+/// # Returns
+/// Number of directory nodes added
+pub fn nest_files_by_directory(graph: &mut YcgGraph) -> usize {
+    let mut directories = BTreeSet::new();
+    for node in &mut graph.definitions {
+        if node.kind != ScipSymbolKind::File {
+            continue;
+        }
+        let Some(path) = node.path.as_deref() else {
+            continue;
+        };
+        node.parent_id = parent_dir(path).map(str::to_string);
+        let mut dir = parent_dir(path);
+        while let Some(current) = dir {
+            if !directories.insert(current.to_string()) {
+                break;
+            }
+            dir = parent_dir(current);
+        }
+    }
+
+    // A directory path never collides with an anchor (`name_hash`), but an
+    // existing definition with that id must not be duplicated
+    let existing: HashSet<&str> = graph.definitions.iter().map(|n| n.id.as_str()).collect();
+    let directory_nodes: Vec<SymbolNode> = directories
+        .iter()
+        .filter(|dir| !existing.contains(dir.as_str()))
+        .map(|dir| directory_node(dir))
+        .collect();
+    let added = directory_nodes.len();
+
+    // BTreeSet order puts every directory before its subdirectories
+    graph.definitions.splice(0..0, directory_nodes);
+    added
+}
+
+fn directory_node(dir: &str) -> SymbolNode {
+    SymbolNode {
+        id: dir.to_string(),
+        name: dir.to_string(),
+        kind: ScipSymbolKind::Module,
+        parent_id: parent_dir(dir).map(str::to_string),
+        path: Some(dir.to_string()),
+        ..Default::default()
+    }
+}
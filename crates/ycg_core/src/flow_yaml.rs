@@ -0,0 +1,145 @@
+// crates/ycg_core/src/flow_yaml.rs
+//! Flow-style YAML emitter (`--style flow`).
+//!
+//! serde_yaml only writes block style, where every map key and list item
+//! takes its own indented line. Here the top-level sections stay in block
+//! style (one line per list item or map entry), while everything below
+//! them is written inline: `- {id: a, n: findOne}`, `a: {calls: [b, c]}`.
+//! Scalars stay plain whenever they read back unchanged inside a flow
+//! collection and are double-quoted otherwise.
+
+use anyhow::Result;
+use serde_yaml::Value;
+
+/// Re-emits a block-style YAML document in flow style
+pub fn to_flow(yaml: &str) -> Result<String> {
+    let value: Value = serde_yaml::from_str(yaml)?;
+    let mut out = String::new();
+    match &value {
+        Value::Mapping(sections) => {
+            for (key, section) in sections {
+                out.push_str(&key_scalar(key));
+                out.push(':');
+                match section {
+                    Value::Sequence(items) if !items.is_empty() => {
+                        out.push('\n');
+                        for item in items {
+                            out.push_str("- ");
+                            out.push_str(&flow(item));
+                            out.push('\n');
+                        }
+                    }
+                    Value::Mapping(entries) if !entries.is_empty() => {
+                        out.push('\n');
+                        for (entry_key, entry) in entries {
+                            out.push_str("  ");
+                            out.push_str(&key_scalar(entry_key));
+                            out.push_str(": ");
+                            out.push_str(&flow(entry));
+                            out.push('\n');
+                        }
+                    }
+                    _ => {
+                        out.push(' ');
+                        out.push_str(&flow(section));
+                        out.push('\n');
+                    }
+                }
+            }
+        }
+        _ => {
+            out.push_str(&flow(&value));
+            out.push('\n');
+        }
+    }
+    Ok(out)
+}
+
+fn flow(value: &Value) -> String {
+    match value {
+        Value::Null => "null".to_string(),
+        Value::Bool(b) => b.to_string(),
+        Value::Number(n) => n.to_string(),
+        Value::String(s) => scalar(s, "[$]"),
+        Value::Sequence(items) => {
+            let items: Vec<String> = items.iter().map(flow).collect();
+            format!("[{}]", items.join(", "))
+        }
+        Value::Mapping(entries) => {
+            let entries: Vec<String> = entries
+                .iter()
+                .map(|(key, entry)| format!("{}: {}", key_scalar(key), flow(entry)))
+                .collect();
+            format!("{{{}}}", entries.join(", "))
+        }
+        Value::Tagged(tagged) => format!("{} {}", tagged.tag, flow(&tagged.value)),
+    }
+}
+
+fn key_scalar(key: &Value) -> String {
+    match key {
+        Value::String(s) => scalar(s, "{$: 0}"),
+        other => flow(other),
+    }
+}
+
+/// Plain when `s` reads back as the same string in `probe`
+/// (a flow collection with `$` standing for the scalar), quoted otherwise
+fn scalar(s: &str, probe: &str) -> String {
+    let plain = !s.is_empty()
+        && !s.contains(['\n', '\r', '\t', '"', '\''])
+        && s.trim() == s
+        && serde_yaml::from_str::<Value>(&probe.replacen('$', s, 1))
+            .ok()
+            .is_some_and(|parsed| reads_back(&parsed, s));
+    if plain {
+        s.to_string()
+    } else {
+        // JSON string escapes are valid in YAML double-quoted scalars
+        serde_json::to_string(s).unwrap_or_else(|_| format!("{:?}", s))
+    }
+}
+
+fn reads_back(parsed: &Value, s: &str) -> bool {
+    match parsed {
+        Value::Sequence(items) => items.len() == 1 && items[0] == Value::String(s.to_string()),
+        Value::Mapping(entries) => {
+            entries.len() == 1 && entries.contains_key(Value::String(s.to_string()))
+        }
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sections_stay_block_and_nested_values_go_inline() {
+        let block = "_meta:\n  name: ycg\n_defs:\n- id: a_1\n  n: findOne\n  s: 'findOne(id: number): User'\ngraph:\n  a_1:\n    calls:\n    - b_2\n    - c_3\n";
+        let flow = to_flow(block).unwrap();
+        assert_eq!(
+            flow,
+            "_meta:\n  name: ycg\n_defs:\n- {id: a_1, n: findOne, s: \"findOne(id: number): User\"}\ngraph:\n  a_1: {calls: [b_2, c_3]}\n"
+        );
+    }
+
+    #[test]
+    fn test_ambiguous_scalars_are_quoted() {
+        for s in [
+            "true", "null", "42", "a, b", "[x]", "- x", "# c", "", " pad", "a\nb",
+        ] {
+            assert_ne!(scalar(s, "[$]"), s, "{:?} must be quoted", s);
+        }
+        assert_eq!(scalar("Users.findOne", "[$]"), "Users.findOne");
+    }
+
+    #[test]
+    fn test_round_trip_preserves_value() {
+        let block = "_defs:\n- id: a\n  doc: 'Line one\n\n    line two'\n  tags: []\n  n: 'on'\nempty: {}\ncount: 3\n";
+        let flow = to_flow(block).unwrap();
+        let expected: Value = serde_yaml::from_str(block).unwrap();
+        let actual: Value = serde_yaml::from_str(&flow).unwrap();
+        assert_eq!(actual, expected, "{}", flow);
+    }
+}
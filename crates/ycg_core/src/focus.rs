@@ -0,0 +1,189 @@
+// crates/ycg_core/src/focus.rs
+//! Subgraph of one file (`--focus-file`).
+//!
+//! "Explain this file in context": after the full conversion, only the
+//! definitions of the focused file are kept, together with their direct
+//! neighbors, the definitions one edge away in either direction (callers,
+//! callees, implemented interfaces...). Only the edges touching the focused
+//! file are kept; edges between two neighbors are not part of the context.
+//! The file is matched against the SCIP document paths of the
+//! [`SymbolIndex`], exactly or as a trailing path (`users/users.service.ts`).
+
+use crate::model::{SymbolIndex, YcgGraph};
+use std::collections::{HashMap, HashSet};
+
+/// True when the document `path` is `focus`, or ends with `/focus`
+fn matches(path: &str, focus: &str) -> bool {
+    let focus = focus.trim_start_matches("./");
+    path == focus
+        || path
+            .strip_suffix(focus)
+            .is_some_and(|prefix| prefix.ends_with('/'))
+}
+
+/// Restricts `graph` to the definitions of `focus` and their one-hop neighbors
+///
+/// # Returns
+/// Number of definitions of the focused file (0 when no document matches,
+/// in which case the graph is left empty)
+pub fn focus_file(graph: &mut YcgGraph, symbol_index: &SymbolIndex, focus: &str) -> usize {
+    let anchors: HashSet<&str> = symbol_index
+        .files
+        .iter()
+        .filter(|(path, _)| matches(path, focus))
+        .flat_map(|(_, anchors)| anchors.iter().map(String::as_str))
+        .collect();
+    let parents: HashMap<&str, &str> = graph
+        .definitions
+        .iter()
+        .filter_map(|node| Some((node.id.as_str(), node.parent_id.as_deref()?)))
+        .collect();
+    // A definition the index does not list lives in the file of its nearest listed ancestor
+    let indexed: HashSet<&str> = symbol_index
+        .files
+        .values()
+        .flat_map(|anchors| anchors.iter().map(String::as_str))
+        .collect();
+    let in_file: HashSet<String> = graph
+        .definitions
+        .iter()
+        .filter(|node| {
+            let mut current = node.id.as_str();
+            // Parent chains are short; the cap only guards against cycles
+            for _ in 0..32 {
+                if indexed.contains(current) {
+                    return anchors.contains(current);
+                }
+                match parents.get(current) {
+                    Some(parent) => current = parent,
+                    None => return false,
+                }
+            }
+            false
+        })
+        .map(|node| node.id.clone())
+        .collect();
+    let definitions: HashSet<String> = graph.definitions.iter().map(|n| n.id.clone()).collect();
+
+    let mut kept: HashSet<String> = definitions
+        .iter()
+        .filter(|id| in_file.contains(*id))
+        .cloned()
+        .collect();
+    let focused = kept.len();
+    for edge in &graph.references {
+        if in_file.contains(&edge.from) && definitions.contains(&edge.to) {
+            kept.insert(edge.to.clone());
+        }
+        if in_file.contains(&edge.to) && definitions.contains(&edge.from) {
+            kept.insert(edge.from.clone());
+        }
+    }
+
+    graph.definitions.retain(|node| kept.contains(&node.id));
+    graph
+        .references
+        .retain(|edge| in_file.contains(&edge.from) || in_file.contains(&edge.to));
+    focused
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{EdgeType, ProjectMetadata, ReferenceEdge, ScipSymbolKind, SymbolNode};
+
+    fn node(id: &str) -> SymbolNode {
+        SymbolNode {
+            id: id.to_string(),
+            name: id.to_string(),
+            kind: ScipSymbolKind::Method,
+            ..Default::default()
+        }
+    }
+
+    fn edge(from: &str, to: &str) -> ReferenceEdge {
+        ReferenceEdge {
+            from: from.to_string(),
+            to: to.to_string(),
+            edge_type: EdgeType::Calls,
+            call_text: None,
+        }
+    }
+
+    #[test]
+    fn test_focus_keeps_file_and_one_hop_neighbors() {
+        let mut graph = YcgGraph {
+            metadata: ProjectMetadata::default(),
+            definitions: [
+                "UsersService",
+                "findOne",
+                "save",
+                "controllerGet",
+                "repoFind",
+                "dbQuery",
+                "orders",
+            ]
+            .into_iter()
+            .map(node)
+            .map(|mut node| {
+                // Not listed by the index: found through its class
+                if node.id == "save" {
+                    node.parent_id = Some("UsersService".to_string());
+                }
+                node
+            })
+            .collect(),
+            references: vec![
+                edge("controllerGet", "findOne"),
+                edge("findOne", "repoFind"),
+                edge("findOne", "save"),
+                edge("save", "ext_lodash"),
+                // Two hops away, and between neighbors: out of the subgraph
+                edge("repoFind", "dbQuery"),
+                edge("controllerGet", "repoFind"),
+                edge("orders", "dbQuery"),
+            ],
+        };
+        let mut symbol_index = SymbolIndex::default();
+        symbol_index.insert("src/users/users.service.ts", "findOne", 1);
+        symbol_index.insert("src/users/users.service.ts", "UsersService", 3);
+        symbol_index.insert("src/users/users.controller.ts", "controllerGet", 1);
+        symbol_index.insert("src/users/users.repository.ts", "repoFind", 1);
+        symbol_index.insert("src/db.ts", "dbQuery", 1);
+        symbol_index.insert("src/orders/orders.service.ts", "orders", 1);
+
+        assert_eq!(
+            focus_file(&mut graph, &symbol_index, "users/users.service.ts"),
+            3
+        );
+        let ids: Vec<&str> = graph.definitions.iter().map(|n| n.id.as_str()).collect();
+        assert_eq!(
+            ids,
+            vec![
+                "UsersService",
+                "findOne",
+                "save",
+                "controllerGet",
+                "repoFind"
+            ]
+        );
+        assert_eq!(
+            graph.references,
+            vec![
+                edge("controllerGet", "findOne"),
+                edge("findOne", "repoFind"),
+                edge("findOne", "save"),
+                edge("save", "ext_lodash"),
+            ]
+        );
+
+        // `service.ts` is not a trailing path of `users.service.ts`
+        let mut graph = YcgGraph {
+            metadata: ProjectMetadata::default(),
+            definitions: vec![node("findOne")],
+            references: Vec::new(),
+        };
+        assert_eq!(focus_file(&mut graph, &symbol_index, "service.ts"), 0);
+        assert!(graph.definitions.is_empty());
+    }
+}
@@ -4,6 +4,7 @@ use crate::model::{ScipSymbolKind, SymbolNode, YcgGraph};
 use regex::Regex;
 use std::collections::HashSet;
 use std::path::Path;
+use xxhash_rust::xxh64::xxh64;
 
 /// Framework noise filter for removing boilerplate patterns
 /// Targets framework-specific patterns like NestJS and TypeORM
@@ -13,24 +14,31 @@ impl FrameworkNoiseFilter {
     /// Apply framework-specific noise reduction to the graph
     ///
     /// This removes:
-    /// - Dependency injection only constructors
+    /// - Dependency injection only constructors (their parameter properties
+    ///   become fields of the class first)
     /// - Decorator metadata from DTO properties
     ///
     /// This preserves:
     /// - Property names and types in DTOs
     /// - All non-boilerplate code elements
     pub fn filter_graph(graph: &mut YcgGraph) {
-        let mut nodes_to_remove = HashSet::new();
+        Self::filter_graph_keeping(graph, &[]);
+    }
+
+    /// [`Self::filter_graph`], first turning the decorators named in `keep`
+    /// into node metadata (see [`Self::keep_decorator_metadata`]); every
+    /// decorator is still stripped from signatures afterwards
+    pub fn filter_graph_keeping(graph: &mut YcgGraph, keep: &[String]) {
+        let nodes_to_remove = Self::nodes_to_remove(graph);
+        Self::hoist_parameter_properties(graph, &nodes_to_remove);
 
         for node in &mut graph.definitions {
-            // Check if this is a DI-only constructor
-            if node.kind == ScipSymbolKind::Method
-                && node.name == "constructor"
-                && Self::is_di_only_constructor(node)
-            {
-                nodes_to_remove.insert(node.id.clone());
+            if nodes_to_remove.contains(&node.id) {
                 continue;
             }
+            if !keep.is_empty() {
+                Self::keep_decorator_metadata(node, keep);
+            }
 
             // Simplify DTO properties
             if node.parent_id.is_some() {
@@ -53,6 +61,125 @@ impl FrameworkNoiseFilter {
         });
     }
 
+    /// IDs of the DI-only constructors `filter_graph` would remove, without touching the graph
+    pub fn nodes_to_remove(graph: &YcgGraph) -> HashSet<String> {
+        graph
+            .definitions
+            .iter()
+            .filter(|node| {
+                node.kind == ScipSymbolKind::Method
+                    && node.name == "constructor"
+                    && Self::is_di_only_constructor(node)
+            })
+            .map(|node| node.id.clone())
+            .collect()
+    }
+
+    /// Turns the parameter properties of the constructors about to be removed
+    /// into fields of their class.
+    ///
+    /// `constructor(private readonly repo: Repo)` declares `repo` on the class;
+    /// without this the field would vanish with the constructor while the
+    /// code still reads `this.repo`. A parameter node already in the graph
+    /// (high level of detail) is moved under the class, keeping its ID and
+    /// the edges to it; a class that already has the member is left as is.
+    fn hoist_parameter_properties(graph: &mut YcgGraph, constructors: &HashSet<String>) {
+        let mut members: HashSet<(String, String)> = graph
+            .definitions
+            .iter()
+            .filter_map(|node| Some((node.parent_id.clone()?, node.name.clone())))
+            .collect();
+
+        let mut hoisted = Vec::new();
+        for constructor in graph
+            .definitions
+            .iter()
+            .filter(|node| constructors.contains(&node.id))
+        {
+            let (Some(class), Some(signature)) = (&constructor.parent_id, &constructor.signature)
+            else {
+                continue;
+            };
+            for (name, declaration) in Self::parameter_properties(signature) {
+                if members.insert((class.clone(), name.clone())) {
+                    hoisted.push((constructor.id.clone(), class.clone(), name, declaration));
+                }
+            }
+        }
+
+        for (constructor, class, name, declaration) in hoisted {
+            let parameter = graph.definitions.iter_mut().find(|node| {
+                node.parent_id.as_deref() == Some(constructor.as_str()) && node.name == name
+            });
+            match parameter {
+                Some(node) => {
+                    node.parent_id = Some(class);
+                    node.kind = ScipSymbolKind::Variable;
+                    node.signature = Some(declaration);
+                }
+                None => graph.definitions.push(SymbolNode {
+                    id: crate::generate_anchor(
+                        &name,
+                        xxh64(format!("{}#{}", class, name).as_bytes(), 0),
+                    ),
+                    name,
+                    kind: ScipSymbolKind::Variable,
+                    parent_id: Some(class),
+                    signature: Some(declaration),
+                    ..Default::default()
+                }),
+            }
+        }
+    }
+
+    /// `(name, declaration)` of each parameter property in a constructor signature
+    ///
+    /// `constructor(@Inject(REPO) private readonly repo: Repo, id: string)`
+    /// yields `("repo", "private readonly repo: Repo")`; plain parameters
+    /// declare nothing on the class.
+    fn parameter_properties(signature: &str) -> Vec<(String, String)> {
+        lazy_static::lazy_static! {
+            static ref PARAMETER_PROPERTY: Regex = Regex::new(
+                r"^(?:(?:private|public|protected|readonly)\s+)+(\w+)"
+            ).unwrap();
+        }
+
+        let signature = Self::strip_inline_decorators(signature);
+        let Some(start) = signature.find('(') else {
+            return Vec::new();
+        };
+
+        // Split the parameter list on top-level commas (`Map<K, V>` stays whole)
+        let mut parameters = Vec::new();
+        let mut depth = 0;
+        let mut current = String::new();
+        for ch in signature[start + 1..].chars() {
+            match ch {
+                '(' | '<' | '[' | '{' => depth += 1,
+                ')' if depth == 0 => break,
+                // `=>` of a function type closes nothing
+                '>' if current.ends_with('=') => {}
+                ')' | '>' | ']' | '}' => depth -= 1,
+                ',' if depth == 0 => {
+                    parameters.push(std::mem::take(&mut current));
+                    continue;
+                }
+                _ => {}
+            }
+            current.push(ch);
+        }
+        parameters.push(current);
+
+        parameters
+            .iter()
+            .map(|parameter| parameter.split_whitespace().collect::<Vec<_>>().join(" "))
+            .filter_map(|parameter| {
+                let name = PARAMETER_PROPERTY.captures(&parameter)?[1].to_string();
+                Some((name, parameter))
+            })
+            .collect()
+    }
+
     /// Detect if a constructor only performs dependency injection assignments
     ///
     /// A DI-only constructor has a signature that:
@@ -338,6 +465,44 @@ impl FrameworkNoiseFilter {
         depth
     }
 
+    /// Records the decorators named in `keep` (case-insensitive) found in the
+    /// signature or documentation of `node` as metadata:
+    ///
+    /// - `@Get('/users')`, `@Post()`... -> `route` (`GET /users`), unless the
+    ///   enricher already resolved one (with its controller prefix)
+    /// - `@deprecated` -> `deprecated`
+    /// - `@Injectable()` -> `injectable`
+    ///
+    /// Other kept names have no metadata field and are stripped like the rest.
+    pub fn keep_decorator_metadata(node: &mut SymbolNode, keep: &[String]) {
+        lazy_static::lazy_static! {
+            static ref DECORATOR: Regex = Regex::new(r"@[\w.]+(?:\([^()]*\))?").unwrap();
+        }
+
+        let texts = [node.signature.as_deref(), node.documentation.as_deref()];
+        let markers: Vec<(String, String)> = texts
+            .into_iter()
+            .flatten()
+            .flat_map(|text| DECORATOR.find_iter(text))
+            .filter_map(|m| crate::enricher::parse_route_marker(m.as_str()))
+            .filter(|(name, _)| keep.iter().any(|kept| kept.eq_ignore_ascii_case(name)))
+            .collect();
+
+        for (name, path) in markers {
+            match name.as_str() {
+                "deprecated" => node.deprecated = true,
+                "injectable" => node.injectable = true,
+                _ => {
+                    if node.route.is_none()
+                        && let Some(method) = crate::enricher::route_method(&name)
+                    {
+                        node.route = Some(format!("{} /{}", method, path.trim_matches('/')));
+                    }
+                }
+            }
+        }
+    }
+
     /// Simplify DTO property definitions by removing decorator metadata
     ///
     /// This preserves:
@@ -387,10 +552,8 @@ mod tests {
             id: id.to_string(),
             name: name.to_string(),
             kind,
-            parent_id: None,
-            documentation: None,
             signature,
-            logic: None,
+            ..Default::default()
         }
     }
 
@@ -579,6 +742,7 @@ mod tests {
             metadata: ProjectMetadata {
                 name: "test".to_string(),
                 version: "1.0".to_string(),
+                ..Default::default()
             },
             definitions: vec![
                 create_test_node("User_01a2", "User", ScipSymbolKind::Class, None),
@@ -600,11 +764,13 @@ mod tests {
                     from: "User_01a2".to_string(),
                     to: "ctor_1".to_string(),
                     edge_type: EdgeType::Calls,
+                    call_text: None,
                 },
                 ReferenceEdge {
                     from: "User_01a2".to_string(),
                     to: "save_03c4".to_string(),
                     edge_type: EdgeType::Calls,
+                    call_text: None,
                 },
             ],
         };
@@ -623,12 +789,92 @@ mod tests {
         assert_eq!(graph.references[0].to, "save_03c4");
     }
 
+    #[test]
+    fn test_parameter_properties_become_class_fields() {
+        let child = |id: &str, name: &str, kind, sig: &str, parent: &str| SymbolNode {
+            parent_id: Some(parent.to_string()),
+            ..create_test_node(id, name, kind, Some(sig.to_string()))
+        };
+        let mut graph = YcgGraph {
+            metadata: ProjectMetadata::default(),
+            definitions: vec![
+                create_test_node("Users_01a2", "UsersService", ScipSymbolKind::Class, None),
+                child(
+                    "ctor_1",
+                    "constructor",
+                    ScipSymbolKind::Method,
+                    "constructor(\n    private readonly repo: Repository<User>,\n    private cache: Map<string, User>,\n    retries: number,\n  )",
+                    "Users_01a2",
+                ),
+                // Parameter symbol referenced by `this.cache` (high level of detail)
+                child(
+                    "cache_5e6f",
+                    "cache",
+                    ScipSymbolKind::Variable,
+                    "cache",
+                    "ctor_1",
+                ),
+                child(
+                    "find_03c4",
+                    "find",
+                    ScipSymbolKind::Method,
+                    "find(id: string)",
+                    "Users_01a2",
+                ),
+            ],
+            references: vec![ReferenceEdge {
+                from: "find_03c4".to_string(),
+                to: "cache_5e6f".to_string(),
+                edge_type: EdgeType::References,
+                call_text: None,
+            }],
+        };
+
+        FrameworkNoiseFilter::filter_graph(&mut graph);
+
+        assert!(!graph.definitions.iter().any(|n| n.id == "ctor_1"));
+        let field = |name: &str| {
+            graph
+                .definitions
+                .iter()
+                .find(|n| n.name == name)
+                .unwrap_or_else(|| panic!("{} must be a field", name))
+        };
+        let repo = field("repo");
+        assert_eq!(repo.kind, ScipSymbolKind::Variable);
+        assert_eq!(repo.parent_id.as_deref(), Some("Users_01a2"));
+        assert_eq!(
+            repo.signature.as_deref(),
+            Some("private readonly repo: Repository<User>")
+        );
+        let cache = field("cache");
+        assert_eq!(cache.id, "cache_5e6f");
+        assert_eq!(cache.parent_id.as_deref(), Some("Users_01a2"));
+        assert_eq!(
+            cache.signature.as_deref(),
+            Some("private cache: Map<string, User>")
+        );
+        assert!(!graph.definitions.iter().any(|n| n.name == "retries"));
+        assert_eq!(graph.references.len(), 1, "this.cache still resolves");
+
+        assert_eq!(
+            FrameworkNoiseFilter::parameter_properties(
+                "constructor(@Inject(REPO) private readonly repo: Repo, onDone: (e: Error) => void)"
+            ),
+            vec![(
+                "repo".to_string(),
+                "private readonly repo: Repo".to_string()
+            )]
+        );
+    }
+
     #[test]
     fn test_filter_graph_simplifies_dto_properties() {
         let mut graph = YcgGraph {
             metadata: ProjectMetadata {
                 name: "test".to_string(),
                 version: "1.0".to_string(),
+                ..Default::default()
             },
             definitions: vec![
                 create_test_node("UserDto_01a2", "UserDto", ScipSymbolKind::Class, None),
@@ -662,6 +908,7 @@ mod tests {
             metadata: ProjectMetadata {
                 name: "test".to_string(),
                 version: "1.0".to_string(),
+                ..Default::default()
             },
             definitions: vec![
                 create_test_node("User_01a2", "User", ScipSymbolKind::Class, None),
@@ -755,4 +1002,46 @@ mod tests {
         let expected = "findOne(id: string): Promise<User>";
         assert_eq!(FrameworkNoiseFilter::strip_decorators(input), expected);
     }
+
+    #[test]
+    fn test_kept_decorators_become_metadata() {
+        let mut graph = YcgGraph {
+            metadata: ProjectMetadata::default(),
+            definitions: vec![
+                create_test_node(
+                    "UsersController_a1b2",
+                    "UsersController",
+                    ScipSymbolKind::Class,
+                    Some("@Injectable()\nclass UsersController".to_string()),
+                ),
+                create_test_node(
+                    "findAll_c3d4",
+                    "findAll",
+                    ScipSymbolKind::Method,
+                    Some("@Get('/x')\nfindAll(): User[]".to_string()),
+                ),
+                create_test_node(
+                    "name_e5f6",
+                    "name",
+                    ScipSymbolKind::Variable,
+                    Some("@ApiProperty()\nname: string".to_string()),
+                ),
+            ],
+            references: Vec::new(),
+        };
+        for node in &mut graph.definitions[1..] {
+            node.parent_id = Some("UsersController_a1b2".to_string());
+        }
+        let keep = vec!["Get".to_string(), "Injectable".to_string()];
+        FrameworkNoiseFilter::filter_graph_keeping(&mut graph, &keep);
+
+        assert!(graph.definitions[0].injectable);
+        let find_all = &graph.definitions[1];
+        assert_eq!(find_all.route.as_deref(), Some("GET /x"));
+        assert_eq!(find_all.signature.as_deref(), Some("findAll(): User[]"));
+        let name = &graph.definitions[2];
+        assert_eq!(name.route, None);
+        assert_eq!(name.signature.as_deref(), Some("name: string"));
+        assert!(!name.injectable);
+    }
 }
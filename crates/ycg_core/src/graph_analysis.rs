@@ -0,0 +1,178 @@
+// crates/ycg_core/src/graph_analysis.rs
+
+use crate::model::{EdgeType, ScipSymbolKind, SymbolNode, YcgGraph};
+use serde::Serialize;
+use std::collections::{BTreeSet, HashMap};
+
+/// A class that claims to implement an interface but lacks some of its methods
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ImplementationGap {
+    /// Anchor of the implementing class
+    pub class_id: String,
+    /// Anchor of the implemented interface / trait
+    pub interface_id: String,
+    /// Interface method names the class does not define, sorted
+    pub missing: Vec<String>,
+}
+
+/// For every `Implements` edge, lists the interface's methods that the class
+/// does not define itself.
+///
+/// Members are matched by their short name (`UserRepo#save` and `Repo#save`
+/// both count as `save`). Only direct children are compared, so methods
+/// inherited from a base class are reported as missing. Interfaces that are
+/// not in the graph (external) or have no method children are skipped.
+pub fn unimplemented_members(graph: &YcgGraph) -> Vec<ImplementationGap> {
+    let mut methods_by_parent: HashMap<&str, BTreeSet<&str>> = HashMap::new();
+    for node in &graph.definitions {
+        if let (Some(parent), true) = (node.parent_id.as_deref(), is_method(node)) {
+            methods_by_parent
+                .entry(parent)
+                .or_default()
+                .insert(member_name(&node.name));
+        }
+    }
+
+    let mut gaps = Vec::new();
+    for edge in graph
+        .references
+        .iter()
+        .filter(|e| e.edge_type == EdgeType::Implements)
+    {
+        let Some(required) = methods_by_parent.get(edge.to.as_str()) else {
+            continue;
+        };
+        let defined = methods_by_parent.get(edge.from.as_str());
+
+        let missing: Vec<String> = required
+            .iter()
+            .filter(|name| !defined.is_some_and(|d| d.contains(*name)))
+            .map(|name| name.to_string())
+            .collect();
+
+        if !missing.is_empty() {
+            gaps.push(ImplementationGap {
+                class_id: edge.from.clone(),
+                interface_id: edge.to.clone(),
+                missing,
+            });
+        }
+    }
+
+    gaps.sort_by(|a, b| (&a.class_id, &a.interface_id).cmp(&(&b.class_id, &b.interface_id)));
+    gaps
+}
+
+fn is_method(node: &SymbolNode) -> bool {
+    matches!(node.kind, ScipSymbolKind::Method | ScipSymbolKind::Function)
+}
+
+/// `UsersService#findOne` / `Dog::speak` / `findOne` -> the member name
+pub(crate) fn member_name(name: &str) -> &str {
+    let after_hash = name.rsplit('#').next().unwrap_or(name);
+    after_hash.rsplit("::").next().unwrap_or(after_hash)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{ProjectMetadata, ReferenceEdge};
+
+    fn create_test_node(
+        id: &str,
+        name: &str,
+        kind: ScipSymbolKind,
+        parent_id: Option<&str>,
+    ) -> SymbolNode {
+        SymbolNode {
+            id: id.to_string(),
+            name: name.to_string(),
+            kind,
+            parent_id: parent_id.map(|p| p.to_string()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_reports_missing_interface_method() {
+        let graph = YcgGraph {
+            metadata: ProjectMetadata::default(),
+            definitions: vec![
+                create_test_node("Repo_a1b2", "Repo", ScipSymbolKind::Interface, None),
+                create_test_node(
+                    "Repo_save_c3d4",
+                    "Repo#save",
+                    ScipSymbolKind::Method,
+                    Some("Repo_a1b2"),
+                ),
+                create_test_node(
+                    "Repo_delete_e5f6",
+                    "Repo#delete",
+                    ScipSymbolKind::Method,
+                    Some("Repo_a1b2"),
+                ),
+                create_test_node("UserRepo_0a0b", "UserRepo", ScipSymbolKind::Class, None),
+                create_test_node(
+                    "UserRepo_save_1c1d",
+                    "UserRepo#save",
+                    ScipSymbolKind::Method,
+                    Some("UserRepo_0a0b"),
+                ),
+            ],
+            references: vec![ReferenceEdge {
+                from: "UserRepo_0a0b".to_string(),
+                to: "Repo_a1b2".to_string(),
+                edge_type: EdgeType::Implements,
+                call_text: None,
+            }],
+        };
+
+        assert_eq!(
+            unimplemented_members(&graph),
+            vec![ImplementationGap {
+                class_id: "UserRepo_0a0b".to_string(),
+                interface_id: "Repo_a1b2".to_string(),
+                missing: vec!["delete".to_string()],
+            }]
+        );
+    }
+
+    #[test]
+    fn test_complete_and_external_implementations_not_reported() {
+        let graph = YcgGraph {
+            metadata: ProjectMetadata::default(),
+            definitions: vec![
+                create_test_node("Speak_a1b2", "Speak", ScipSymbolKind::Interface, None),
+                create_test_node(
+                    "speak_c3d4",
+                    "Speak#speak",
+                    ScipSymbolKind::Method,
+                    Some("Speak_a1b2"),
+                ),
+                create_test_node("Dog_e5f6", "Dog", ScipSymbolKind::Class, None),
+                create_test_node(
+                    "speak_0a0b",
+                    "Dog::speak",
+                    ScipSymbolKind::Method,
+                    Some("Dog_e5f6"),
+                ),
+            ],
+            references: vec![
+                ReferenceEdge {
+                    from: "Dog_e5f6".to_string(),
+                    to: "Speak_a1b2".to_string(),
+                    edge_type: EdgeType::Implements,
+                    call_text: None,
+                },
+                ReferenceEdge {
+                    from: "Dog_e5f6".to_string(),
+                    to: "ext_9f9f".to_string(),
+                    edge_type: EdgeType::Implements,
+                    call_text: None,
+                },
+            ],
+        };
+
+        assert!(unimplemented_members(&graph).is_empty());
+    }
+}
@@ -0,0 +1,358 @@
+// crates/ycg_core/src/import_cycles.rs
+//! Circular imports between files (`analyze --import-cycles`).
+//!
+//! Occurrences carrying the SCIP `Import` role link the importing file to
+//! the file defining the imported symbol. Files that reach each other
+//! through those links form a strongly connected component; for each one,
+//! the shortest cycle through its first file is reported together with the
+//! import statements behind every hop, the last hop being the back-edge
+//! that closes the cycle.
+
+use crate::scip_proto;
+use std::collections::{BTreeMap, BTreeSet, HashMap, VecDeque};
+use std::fmt;
+
+/// An import statement: `file:line` imports `symbol`
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct ImportSite {
+    /// Importing file (SCIP relative path)
+    pub file: String,
+    /// 1-based line of the import
+    pub line: u32,
+    /// Short name of the imported symbol
+    pub symbol: String,
+}
+
+/// One hop of a cycle: every import of `to` made by `from`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImportHop {
+    pub from: String,
+    pub to: String,
+    pub imports: Vec<ImportSite>,
+}
+
+/// A cycle of files, one hop per import link; the last hop is the back-edge
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImportCycle {
+    pub hops: Vec<ImportHop>,
+}
+
+/// Import cycles found in an index
+#[derive(Debug, Clone, Default)]
+pub struct ImportCycleReport {
+    pub cycles: Vec<ImportCycle>,
+}
+
+impl ImportCycleReport {
+    pub fn from_index(index: &scip_proto::Index) -> Self {
+        let links = import_links(index);
+
+        let mut cycles: Vec<ImportCycle> = strongly_connected(&links, [])
+            .into_iter()
+            .filter(|component| component.len() > 1)
+            .filter_map(|component| shortest_cycle(&links, &component))
+            .map(|files| ImportCycle {
+                hops: files
+                    .windows(2)
+                    .map(|pair| ImportHop {
+                        from: pair[0].clone(),
+                        to: pair[1].clone(),
+                        imports: links[&pair[0]][&pair[1]].iter().cloned().collect(),
+                    })
+                    .collect(),
+            })
+            .collect();
+        cycles.sort_by(|a, b| a.hops[0].from.cmp(&b.hops[0].from));
+        Self { cycles }
+    }
+}
+
+impl fmt::Display for ImportCycleReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "--- Import cycles: {} ---", self.cycles.len())?;
+        for (i, cycle) in self.cycles.iter().enumerate() {
+            let files: Vec<&str> = cycle
+                .hops
+                .iter()
+                .map(|hop| hop.from.as_str())
+                .chain(cycle.hops.last().map(|hop| hop.to.as_str()))
+                .collect();
+            writeln!(f, "\nCycle {}: {}", i + 1, files.join(" -> "))?;
+            for (h, hop) in cycle.hops.iter().enumerate() {
+                let back_edge = if h + 1 == cycle.hops.len() {
+                    "  <- back-edge"
+                } else {
+                    ""
+                };
+                for site in &hop.imports {
+                    writeln!(
+                        f,
+                        "  {}:{} imports {} (from {}){}",
+                        site.file, site.line, site.symbol, hop.to, back_edge
+                    )?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+type ImportLinks = BTreeMap<String, BTreeMap<String, BTreeSet<ImportSite>>>;
+
+/// importing file -> defining file -> import statements
+fn import_links(index: &scip_proto::Index) -> ImportLinks {
+    let definition_role = scip_proto::SymbolRole::Definition as i32;
+    let import_role = scip_proto::SymbolRole::Import as i32;
+
+    let mut defined_in: HashMap<&str, &str> = HashMap::new();
+    for doc in &index.documents {
+        for occurrence in &doc.occurrences {
+            if occurrence.symbol_roles & definition_role != 0 {
+                defined_in
+                    .entry(occurrence.symbol.as_str())
+                    .or_insert(doc.relative_path.as_str());
+            }
+        }
+    }
+
+    let mut links = ImportLinks::new();
+    for doc in &index.documents {
+        for occurrence in &doc.occurrences {
+            if occurrence.symbol_roles & import_role == 0
+                || crate::is_malformed_range(&occurrence.range)
+            {
+                continue;
+            }
+            let Some(&target) = defined_in.get(occurrence.symbol.as_str()) else {
+                continue;
+            };
+            if target == doc.relative_path {
+                continue;
+            }
+            links
+                .entry(doc.relative_path.clone())
+                .or_default()
+                .entry(target.to_string())
+                .or_default()
+                .insert(ImportSite {
+                    file: doc.relative_path.clone(),
+                    line: occurrence.range.first().copied().unwrap_or(0) as u32 + 1,
+                    symbol: crate::extract_name_from_uri(&occurrence.symbol),
+                });
+        }
+    }
+    links
+}
+
+/// Tarjan's algorithm over a graph of named nodes (`node -> targets`),
+/// started from `roots` in order, then from every other node with edges.
+///
+/// Members of a component come out sorted; components come out in reverse
+/// topological order, each after every component it reaches.
+pub(crate) fn strongly_connected<'a, V>(
+    links: &'a BTreeMap<String, BTreeMap<String, V>>,
+    roots: impl IntoIterator<Item = &'a str>,
+) -> Vec<Vec<String>> {
+    let mut tarjan = Tarjan {
+        links,
+        index: HashMap::new(),
+        low: HashMap::new(),
+        stack: Vec::new(),
+        on_stack: BTreeSet::new(),
+        components: Vec::new(),
+    };
+    for root in roots.into_iter().chain(links.keys().map(String::as_str)) {
+        if !tarjan.index.contains_key(root) {
+            tarjan.visit(root);
+        }
+    }
+    tarjan.components
+}
+
+struct Tarjan<'a, V> {
+    links: &'a BTreeMap<String, BTreeMap<String, V>>,
+    index: HashMap<&'a str, usize>,
+    low: HashMap<&'a str, usize>,
+    stack: Vec<&'a str>,
+    on_stack: BTreeSet<&'a str>,
+    components: Vec<Vec<String>>,
+}
+
+impl<'a, V> Tarjan<'a, V> {
+    fn visit(&mut self, file: &'a str) {
+        let next = self.index.len();
+        self.index.insert(file, next);
+        self.low.insert(file, next);
+        self.stack.push(file);
+        self.on_stack.insert(file);
+
+        for target in self.links.get(file).into_iter().flat_map(|t| t.keys()) {
+            let target = target.as_str();
+            if !self.index.contains_key(target) {
+                self.visit(target);
+                let low = self.low[file].min(self.low[target]);
+                self.low.insert(file, low);
+            } else if self.on_stack.contains(target) {
+                let low = self.low[file].min(self.index[target]);
+                self.low.insert(file, low);
+            }
+        }
+
+        if self.low[file] == self.index[file] {
+            let mut component = Vec::new();
+            while let Some(member) = self.stack.pop() {
+                self.on_stack.remove(member);
+                component.push(member.to_string());
+                if member == file {
+                    break;
+                }
+            }
+            component.sort();
+            self.components.push(component);
+        }
+    }
+}
+
+/// Shortest path from the component's first file back to itself,
+/// as the list of files visited (first and last are the same)
+fn shortest_cycle(links: &ImportLinks, component: &[String]) -> Option<Vec<String>> {
+    let start = component.first()?;
+    let members: BTreeSet<&str> = component.iter().map(String::as_str).collect();
+
+    let mut previous: HashMap<&str, &str> = HashMap::new();
+    let mut queue = VecDeque::from([start.as_str()]);
+    while let Some(file) = queue.pop_front() {
+        for target in links.get(file).into_iter().flat_map(|t| t.keys()) {
+            let target = target.as_str();
+            if target == start {
+                let mut path = vec![file.to_string()];
+                let mut current = file;
+                while let Some(&prev) = previous.get(current) {
+                    path.push(prev.to_string());
+                    current = prev;
+                }
+                path.reverse();
+                path.push(start.clone());
+                return Some(path);
+            }
+            if members.contains(target) && target != start && !previous.contains_key(target) {
+                previous.insert(target, file);
+                queue.push_back(target);
+            }
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn occurrence(
+        symbol: &str,
+        line: i32,
+        roles: scip_proto::SymbolRole,
+    ) -> scip_proto::Occurrence {
+        scip_proto::Occurrence {
+            range: vec![line, 0, 10],
+            symbol: symbol.to_string(),
+            symbol_roles: roles as i32,
+            ..Default::default()
+        }
+    }
+
+    fn document(path: &str, occurrences: Vec<scip_proto::Occurrence>) -> scip_proto::Document {
+        scip_proto::Document {
+            relative_path: path.to_string(),
+            occurrences,
+            ..Default::default()
+        }
+    }
+
+    const USERS: &str = "scip-typescript npm pkg 1.0.0 src/`users.ts`/UserService#";
+    const ORDERS: &str = "scip-typescript npm pkg 1.0.0 src/`orders.ts`/OrderService#";
+    const MONEY: &str = "scip-typescript npm pkg 1.0.0 src/`money.ts`/Money#";
+
+    #[test]
+    fn test_two_file_cycle_reports_importing_symbols_on_each_side() {
+        use scip_proto::SymbolRole::{Definition, Import, ReadAccess};
+
+        let index = scip_proto::Index {
+            documents: vec![
+                document(
+                    "src/users.ts",
+                    vec![
+                        occurrence(ORDERS, 0, Import),
+                        occurrence(MONEY, 1, Import),
+                        occurrence(USERS, 3, Definition),
+                        // Plain use of the imported class, not an import
+                        occurrence(ORDERS, 5, ReadAccess),
+                    ],
+                ),
+                document(
+                    "src/orders.ts",
+                    vec![
+                        occurrence(USERS, 1, Import),
+                        occurrence(ORDERS, 4, Definition),
+                    ],
+                ),
+                document("src/money.ts", vec![occurrence(MONEY, 0, Definition)]),
+            ],
+            ..Default::default()
+        };
+
+        let report = ImportCycleReport::from_index(&index);
+        assert_eq!(
+            report.cycles,
+            vec![ImportCycle {
+                hops: vec![
+                    ImportHop {
+                        from: "src/orders.ts".to_string(),
+                        to: "src/users.ts".to_string(),
+                        imports: vec![ImportSite {
+                            file: "src/orders.ts".to_string(),
+                            line: 2,
+                            symbol: "UserService".to_string(),
+                        }],
+                    },
+                    ImportHop {
+                        from: "src/users.ts".to_string(),
+                        to: "src/orders.ts".to_string(),
+                        imports: vec![ImportSite {
+                            file: "src/users.ts".to_string(),
+                            line: 1,
+                            symbol: "OrderService".to_string(),
+                        }],
+                    },
+                ],
+            }]
+        );
+
+        let text = report.to_string();
+        assert!(
+            text.contains("Cycle 1: src/orders.ts -> src/users.ts -> src/orders.ts"),
+            "{}",
+            text
+        );
+        assert!(
+            text.contains("src/users.ts:1 imports OrderService (from src/orders.ts)  <- back-edge"),
+            "{}",
+            text
+        );
+        assert!(!text.contains("Money"), "{}", text);
+    }
+
+    #[test]
+    fn test_acyclic_imports_report_nothing() {
+        use scip_proto::SymbolRole::{Definition, Import};
+
+        let index = scip_proto::Index {
+            documents: vec![
+                document("src/users.ts", vec![occurrence(MONEY, 0, Import)]),
+                document("src/money.ts", vec![occurrence(MONEY, 0, Definition)]),
+            ],
+            ..Default::default()
+        };
+        assert!(ImportCycleReport::from_index(&index).cycles.is_empty());
+    }
+}
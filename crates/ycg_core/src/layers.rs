@@ -0,0 +1,269 @@
+// crates/ycg_core/src/layers.rs
+//! Architectural layers (`layerRules`) and `analyze --layer-violations`.
+//!
+//! Every definition is tagged with the layer of the file it is defined in:
+//! the first rule whose glob matches the file path (relative to the project
+//! root) names it. `layerDependencies` then lists, per layer, the layers it
+//! may depend on; an edge leaving a listed layer for a layer not in its list
+//! (a controller calling a repository) is a violation. Layers without an
+//! entry, and untagged definitions, are not checked.
+
+use crate::compiled_filters::compile_glob;
+use crate::event_log::{Event, EventLog};
+use crate::model::{EdgeType, LayerRule, YcgGraph};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+
+/// Compiled `layerRules`, first match wins
+pub struct LayerRules {
+    rules: Vec<(glob::Pattern, String)>,
+}
+
+impl LayerRules {
+    /// Compiles the rules; invalid globs are skipped with a warning
+    pub fn new(rules: &[LayerRule], log: &EventLog) -> Self {
+        let rules = rules
+            .iter()
+            .filter_map(|rule| match compile_glob(&rule.pattern) {
+                Ok(pattern) => Some((pattern, rule.layer.clone())),
+                Err(err) => {
+                    log.emit(Event::warning(format!(
+                        "Ignoring layer rule '{}': {}",
+                        rule.pattern, err
+                    )));
+                    None
+                }
+            })
+            .collect();
+        Self { rules }
+    }
+
+    /// Number of compiled globs
+    pub fn pattern_count(&self) -> usize {
+        self.rules.len()
+    }
+
+    /// Layer of a file, given its path relative to the project root
+    pub fn layer_for(&self, path: &str) -> Option<&str> {
+        self.rules
+            .iter()
+            .find(|(pattern, _)| pattern.matches(path))
+            .map(|(_, layer)| layer.as_str())
+    }
+}
+
+/// An edge crossing into a layer its source layer may not depend on
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct LayerViolation {
+    pub from_layer: String,
+    pub to_layer: String,
+    /// Anchor and name of the depending definition
+    pub from: String,
+    pub from_name: String,
+    /// Anchor and name of the definition depended on
+    pub to: String,
+    pub to_name: String,
+    pub edge_type: EdgeType,
+}
+
+/// Layer violations found in a tagged graph
+#[derive(Debug, Clone, Default)]
+pub struct LayerViolationReport {
+    pub violations: Vec<LayerViolation>,
+}
+
+impl LayerViolationReport {
+    /// Checks every edge between tagged definitions against `dependencies`
+    /// (layer -> layers it may depend on); edges within a layer are allowed
+    pub fn from_graph(graph: &YcgGraph, dependencies: &BTreeMap<String, Vec<String>>) -> Self {
+        let nodes: HashMap<&str, (&str, &str)> = graph
+            .definitions
+            .iter()
+            .filter_map(|node| {
+                let layer = node.layer.as_deref()?;
+                Some((node.id.as_str(), (layer, node.name.as_str())))
+            })
+            .collect();
+
+        let mut violations: Vec<LayerViolation> = graph
+            .references
+            .iter()
+            .filter_map(|edge| {
+                let (from_layer, from_name) = nodes.get(edge.from.as_str())?;
+                let (to_layer, to_name) = nodes.get(edge.to.as_str())?;
+                let allowed = dependencies.get(*from_layer)?;
+                if from_layer == to_layer || allowed.iter().any(|layer| layer == to_layer) {
+                    return None;
+                }
+                Some(LayerViolation {
+                    from_layer: from_layer.to_string(),
+                    to_layer: to_layer.to_string(),
+                    from: edge.from.clone(),
+                    from_name: from_name.to_string(),
+                    to: edge.to.clone(),
+                    to_name: to_name.to_string(),
+                    edge_type: edge.edge_type.clone(),
+                })
+            })
+            .collect();
+        violations.sort();
+        violations.dedup();
+        Self { violations }
+    }
+}
+
+impl fmt::Display for LayerViolationReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "--- Layer violations: {} ---", self.violations.len())?;
+        for violation in &self.violations {
+            writeln!(
+                f,
+                "  {} -> {}: {} ({}) {} {} ({})",
+                violation.from_layer,
+                violation.to_layer,
+                violation.from_name,
+                violation.from,
+                violation.edge_type.as_str(),
+                violation.to_name,
+                violation.to
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{ProjectMetadata, ReferenceEdge, ScipSymbolKind, SymbolNode};
+
+    fn node(id: &str, name: &str, layer: &str) -> SymbolNode {
+        SymbolNode {
+            id: id.to_string(),
+            name: name.to_string(),
+            kind: ScipSymbolKind::Method,
+            layer: Some(layer.to_string()),
+            ..Default::default()
+        }
+    }
+
+    fn calls(from: &str, to: &str) -> ReferenceEdge {
+        ReferenceEdge {
+            from: from.to_string(),
+            to: to.to_string(),
+            edge_type: EdgeType::Calls,
+            call_text: None,
+        }
+    }
+
+    #[test]
+    fn test_layer_rules_first_match_wins() {
+        let rules = LayerRules::new(
+            &[
+                LayerRule {
+                    pattern: "src/**/*.controller.ts".to_string(),
+                    layer: "controller".to_string(),
+                },
+                LayerRule {
+                    pattern: "src/**/*.repository.ts".to_string(),
+                    layer: "repository".to_string(),
+                },
+                LayerRule {
+                    pattern: "src/**".to_string(),
+                    layer: "service".to_string(),
+                },
+            ],
+            &EventLog::default(),
+        );
+        assert_eq!(
+            rules.layer_for("src/users/users.controller.ts"),
+            Some("controller")
+        );
+        assert_eq!(
+            rules.layer_for("src/users/users.repository.ts"),
+            Some("repository")
+        );
+        assert_eq!(
+            rules.layer_for("src/users/users.service.ts"),
+            Some("service")
+        );
+        assert_eq!(rules.layer_for("test/users.spec.ts"), None);
+    }
+
+    #[test]
+    fn test_invalid_layer_rule_is_logged_and_skipped() {
+        let dir = tempfile::tempdir().unwrap();
+        let log_path = dir.path().join("events.ndjson");
+        let log = EventLog::create(&log_path).unwrap();
+
+        let rules = LayerRules::new(
+            &[
+                LayerRule {
+                    pattern: "src/[".to_string(),
+                    layer: "broken".to_string(),
+                },
+                LayerRule {
+                    pattern: "src/**".to_string(),
+                    layer: "service".to_string(),
+                },
+            ],
+            &log,
+        );
+        assert_eq!(rules.pattern_count(), 1);
+        assert_eq!(rules.layer_for("src/users.ts"), Some("service"));
+
+        let event: serde_json::Value =
+            serde_json::from_str(std::fs::read_to_string(&log_path).unwrap().trim()).unwrap();
+        assert_eq!(event["event"], "warning");
+        assert!(
+            event["message"]
+                .as_str()
+                .unwrap()
+                .starts_with("Ignoring layer rule 'src/['")
+        );
+    }
+
+    #[test]
+    fn test_controller_calling_repository_is_a_violation() {
+        let graph = YcgGraph {
+            metadata: ProjectMetadata::default(),
+            definitions: vec![
+                node("ctl_find", "UsersController#find", "controller"),
+                node("svc_find", "UsersService#find", "service"),
+                node("repo_find", "UsersRepository#find", "repository"),
+            ],
+            references: vec![
+                calls("ctl_find", "svc_find"),
+                calls("svc_find", "repo_find"),
+                calls("ctl_find", "repo_find"),
+                calls("repo_find", "ext_a1b2"),
+            ],
+        };
+        let dependencies = BTreeMap::from([
+            ("controller".to_string(), vec!["service".to_string()]),
+            ("service".to_string(), vec!["repository".to_string()]),
+        ]);
+
+        let report = LayerViolationReport::from_graph(&graph, &dependencies);
+        assert_eq!(
+            report.violations,
+            vec![LayerViolation {
+                from_layer: "controller".to_string(),
+                to_layer: "repository".to_string(),
+                from: "ctl_find".to_string(),
+                from_name: "UsersController#find".to_string(),
+                to: "repo_find".to_string(),
+                to_name: "UsersRepository#find".to_string(),
+                edge_type: EdgeType::Calls,
+            }]
+        );
+        let text = report.to_string();
+        assert!(
+            text.contains(
+                "controller -> repository: UsersController#find (ctl_find) calls UsersRepository#find (repo_find)"
+            ),
+            "{}",
+            text
+        );
+    }
+}
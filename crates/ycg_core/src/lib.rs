@@ -1,27 +1,67 @@
 // crates/ycg_core/src/lib.rs
 pub mod adhoc_format;
 pub mod adhoc_serializer_v2;
+pub mod anchor_prefix;
+pub mod api_surface;
 pub mod ast_cache;
+#[cfg(feature = "bincode")]
+pub mod binary_graph;
+pub mod chunker;
+pub mod compiled_filters;
 pub mod config;
+pub mod cross_file;
+pub mod delegates;
+pub mod delta;
+pub mod density;
+pub mod descriptor_signature;
+pub mod document_symbols;
+pub mod dry_run;
+pub mod edge_dedup;
 pub mod enricher;
 pub mod errors;
+pub mod event_log;
 pub mod file_filter;
+pub mod file_tree;
+pub mod flow_yaml;
+pub mod focus;
 pub mod framework_filter;
+pub mod graph_analysis;
+pub mod import_cycles;
+pub mod layers;
 pub mod logic_extractor;
 pub mod model;
+pub mod module_splitter;
+pub mod name_compactor;
+pub mod name_filter;
+pub mod ndjson;
+pub mod numeric_ids;
+pub mod ownership;
+pub mod plantuml;
+pub mod redactor;
+pub mod scip_decoder;
 pub mod semantic_filter;
+pub mod signature_coverage;
 pub mod signature_extractor;
+pub mod single_impl;
+pub mod source_paths;
+pub mod stats;
+pub mod string_interner;
+pub mod todos;
+pub mod topo_sort;
 pub mod type_abbreviator;
+pub mod unused_imports;
 pub mod validators;
+pub mod visibility;
 
 pub mod scip_proto {
     include!(concat!(env!("OUT_DIR"), "/scip.rs"));
 }
 
 use crate::enricher::TreeSitterEnricher;
+use crate::event_log::{Event, EventLog};
 use crate::model::{
-    EdgeType, LogicMetadata, ProjectMetadata, ReferenceEdge, ScipSymbolKind, SymbolNode, YcgGraph,
-    YcgGraphOptimized,
+    EdgeType, LogicMetadata, ProjectMetadata, ReferenceEdge, ScipSymbolKind, SymbolIndex,
+    SymbolNode, YcgGraph, YcgGraphOptimized,
 };
 use anyhow::{Context, Result};
 use prost::Message;
@@ -33,7 +73,8 @@ use xxhash_rust::xxh64::xxh64;
 
 // --- CONFIGURAÇÃO ---
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize)]
+#[serde(rename_all = "lowercase")]
 pub enum LevelOfDetail {
     Low,
     Medium,
@@ -48,10 +89,273 @@ pub struct YcgConfig {
     // New fields for token optimization
     pub output_format: model::OutputFormat,
     pub ignore_framework_noise: bool,
+    // Decorators kept as node metadata (route, deprecated, injectable) by the noise filter
+    pub keep_decorators: Vec<String>,
     pub file_filter: model::FileFilterConfig,
 
     // Ad-hoc granularity level (Requirements 1.1-1.6)
     pub adhoc_granularity: model::AdHocGranularity,
+    // Kinds serialized at their own level (`adhocGranularityByKind`)
+    pub adhoc_granularity_by_kind: HashMap<model::ScipSymbolKind, model::AdHocGranularity>,
+
+    // Parameter rendering for inline signatures (named vs types-only)
+    pub signature_style: model::SignatureStyle,
+
+    // Companion path -> symbol index (JSON) written alongside the graph
+    pub emit_index: Option<PathBuf>,
+
+    // Symbol name include/exclude regexes, applied after conversion
+    pub name_filter: model::NameFilterConfig,
+
+    // Fail (instead of warn) on SCIP protocol versions outside the tested range
+    pub strict: bool,
+
+    // Abbreviate common type suffixes (Controller -> Ctrl) across the whole graph
+    pub compact_names: bool,
+    pub name_abbreviations: BTreeMap<String, String>, // empty = defaults
+
+    // Also write one graph file per top-level module (plus `_cross.yaml`) into this directory
+    pub split_by_module: Option<PathBuf>,
+
+    // Mask secret-looking literals in signatures/documentation
+    pub redact: bool,
+
+    // YAML format: hoist repeated signatures/docs into a `_strings` table
+    pub intern_strings: bool,
+
+    // YAML format: replace hash anchors with sequential integers (mapping in `_meta.anchors`)
+    pub numeric_ids: bool,
+
+    // Common namespace prefix removed from symbol names before anchors are generated
+    pub strip_prefix: Option<String>,
+
+    // Capture the call expression source on each call edge (reads source per file)
+    pub with_call_text: bool,
+    // Maximum characters kept from a captured call expression
+    pub call_text_max_len: usize,
+
+    // Attach top git authors to File nodes (skipped outside a git repository)
+    pub with_ownership: bool,
+
+    // Keep only classes/interfaces and their public methods
+    pub api_surface: bool,
+
+    // Free text prepended to the output as `#` comment lines
+    pub header_comment: Option<String>,
+    // Prepend a built-in legend of the output format (before header_comment)
+    pub explain_format: bool,
+
+    // Drop parameter default values (`limit = 100`) from signatures
+    pub no_defaults: bool,
+
+    // Leading path stripped from SCIP document paths before joining with
+    // project_root (monorepos indexed at the repo root); detected when None
+    pub path_prefix: Option<String>,
+
+    // Further source roots tried in order when a document is not found
+    // under project_root (packages living under different bases)
+    pub source_roots: Vec<PathBuf>,
+
+    // Flat format: group edges into one section per type (`calls: [...]`)
+    pub edges_by_type: bool,
+
+    // Each definition carries its outgoing edges (`calls: [ids]`); no `graph` section
+    pub inline_edges: bool,
+
+    // Source files above this size are not parsed for signatures/logic
+    pub max_file_bytes: u64,
+
+    // Preconditions kept per node, the rest summarized as `(+N more)`
+    pub max_preconditions: Option<usize>,
+
+    // Statement blocks a guard clause may be nested in (1 = function body)
+    pub max_guard_depth: usize,
+
+    // Documentation cut at a sentence/word boundary beyond this many characters
+    pub max_doc_length: Option<usize>,
+
+    // Drop tag-only and tooling lines (`@param id`, `eslint-disable`) from documentation
+    pub strip_doc_boilerplate: bool,
+
+    // Baseline SCIP index: emit only the definitions changed since it
+    pub baseline: Option<PathBuf>,
+
+    // Call expressions matching a rule produce edges of its custom type
+    pub edge_rules: Vec<model::EdgeRule>,
+
+    // Definitions of files matching a rule are tagged with its layer
+    pub layer_rules: Vec<model::LayerRule>,
+    // Layer -> layers it may depend on (`analyze --layer-violations`)
+    pub layer_dependencies: BTreeMap<String, Vec<String>>,
+
+    // YAML layout: block (one key per line) or flow (inline nested values)
+    pub yaml_style: model::YamlStyle,
+
+    // Ad-hoc signatures: Promise<T> -> {marker}T, Result<T, E> -> T? (None keeps them)
+    pub abbreviate_wrappers: Option<String>,
+
+    // LSP documentSymbol outline per file, written as JSON
+    pub emit_document_symbols: Option<PathBuf>,
+
+    // Single-call pass-through methods: logic becomes delegates_to, callers rewired
+    pub collapse_delegates: bool,
+
+    // Test files: kept, dropped, or written to tests_output as their own graph
+    pub tests: model::TestFilesMode,
+    pub tests_output: Option<PathBuf>,
+
+    // Read sources through symbolic links; when false, symlinked documents are dropped
+    pub follow_symlinks: bool,
+
+    // Drop documents whose source starts with a generator marker (`@generated`, `DO NOT EDIT`)
+    pub skip_generated: bool,
+
+    // File nodes carry their relative path and nest under directory nodes
+    pub file_paths: bool,
+
+    // Deduplicate edges per document and merge sorted runs instead of one global set
+    pub streaming_edges: bool,
+
+    // Definition anchors prefixed with a short kind tag (`cls:User_4702`)
+    pub kind_prefixed_anchors: bool,
+
+    // One edge per reference occurrence instead of one per (from, to, type)
+    pub dedupe_edges: bool,
+
+    // Repeated edges serialized once with their count (`count: 5`, `B (x5)`)
+    pub weighted_edges: bool,
+
+    // Method/function signatures in a language-neutral form (`find_one(id: int) -> User`)
+    pub neutral_signatures: bool,
+
+    // Extra tree-sitter grammars by file extension (library API, no CLI flag)
+    pub languages: Vec<(String, tree_sitter::Language)>,
+
+    // Ad-hoc signatures: optional parameters (`?`, `| undefined`, `Option<T>`) as `name?:type`
+    pub optional_param_markers: bool,
+    // Ad-hoc signatures: QueryBuilder / fetch / iterator chains longer than this
+    // are summarized to their result type (None = 100 characters)
+    pub chain_summary_threshold: Option<usize>,
+
+    // Directory receiving defs.yaml (definitions) and edges.yaml (references)
+    pub split_defs_edges: Option<PathBuf>,
+
+    // Diagnostics sink: stderr lines, or an NDJSON file (--log-json)
+    pub event_log: EventLog,
+
+    // Order definitions so callees/imports come before their dependents
+    pub topo_sort: bool,
+
+    // Keep only edges between definitions of different files
+    pub cross_file_edges_only: bool,
+
+    // Original SCIP symbol stored on each definition, to join the output back to the index
+    pub keep_scip_symbols: bool,
+
+    // Only this file's definitions and their one-hop neighbors (SCIP document path)
+    pub focus_file: Option<String>,
+
+    // Print how many methods/functions of each file got a signature
+    pub signature_coverage: bool,
+
+    // Name globs of definitions whose full source text is attached (`source`)
+    pub inline_source: Vec<String>,
+
+    // Merge interfaces implemented by exactly one class into that class
+    pub merge_single_impl: bool,
+
+    // Annotate methods/functions with their cyclomatic complexity
+    pub complexity: bool,
+}
+
+impl Default for YcgConfig {
+    fn default() -> Self {
+        Self {
+            lod: LevelOfDetail::Medium,
+            project_root: PathBuf::from("."),
+            compact: false,
+            output_format: model::OutputFormat::default(),
+            ignore_framework_noise: false,
+            keep_decorators: Vec::new(),
+            file_filter: model::FileFilterConfig::default(),
+            adhoc_granularity: model::AdHocGranularity::default(),
+            adhoc_granularity_by_kind: HashMap::new(),
+            signature_style: model::SignatureStyle::default(),
+            emit_index: None,
+            name_filter: model::NameFilterConfig::default(),
+            strict: false,
+            compact_names: false,
+            name_abbreviations: BTreeMap::new(),
+            split_by_module: None,
+            redact: false,
+            intern_strings: false,
+            numeric_ids: false,
+            strip_prefix: None,
+            with_call_text: false,
+            call_text_max_len: 80,
+            with_ownership: false,
+            api_surface: false,
+            header_comment: None,
+            explain_format: false,
+            no_defaults: false,
+            path_prefix: None,
+            source_roots: Vec::new(),
+            edges_by_type: false,
+            inline_edges: false,
+            max_file_bytes: enricher::DEFAULT_MAX_FILE_BYTES,
+            max_preconditions: None,
+            max_guard_depth: enricher::DEFAULT_MAX_GUARD_DEPTH,
+            max_doc_length: None,
+            strip_doc_boilerplate: false,
+            baseline: None,
+            edge_rules: Vec::new(),
+            layer_rules: Vec::new(),
+            layer_dependencies: BTreeMap::new(),
+            yaml_style: model::YamlStyle::default(),
+            abbreviate_wrappers: None,
+            emit_document_symbols: None,
+            collapse_delegates: false,
+            tests: model::TestFilesMode::default(),
+            tests_output: None,
+            follow_symlinks: true,
+            skip_generated: false,
+            file_paths: false,
+            streaming_edges: false,
+            kind_prefixed_anchors: false,
+            dedupe_edges: true,
+            weighted_edges: false,
+            neutral_signatures: false,
+            languages: Vec::new(),
+            optional_param_markers: false,
+            chain_summary_threshold: None,
+            split_defs_edges: None,
+            event_log: EventLog::default(),
+            topo_sort: false,
+            cross_file_edges_only: false,
+            keep_scip_symbols: false,
+            focus_file: None,
+            signature_coverage: false,
+            inline_source: Vec::new(),
+            merge_single_impl: false,
+            complexity: false,
+        }
+    }
+}
+
+/// Inlined source text longer than this is cut (`--inline-source`)
+const INLINE_SOURCE_MAX_BYTES: usize = 16 * 1024;
+
+/// Bytes of a source read to look for a generator marker (`--skip-generated`)
+const SOURCE_HEAD_BYTES: u64 = 2048;
+
+/// SCIP protocol versions this converter has been tested against
+const SUPPORTED_PROTOCOL_VERSIONS: std::ops::RangeInclusive<i32> = 0..=0;
+
+/// Summary of the indexer that produced a SCIP index
+struct ScipMetadataReport {
+    tool: String,
+    protocol_version: i32,
+    warnings: Vec<String>,
 }
 
 struct Scope {
@@ -61,65 +365,501 @@ struct Scope {
     kind: ScipSymbolKind, // Track the kind to prioritize methods over classes
 }
 
+impl Scope {
+    /// Stable position in the scope stack: the file scope first, then by
+    /// range and symbol hash, so ties resolve the same way whatever order
+    /// the indexer emitted the definitions in
+    fn sort_key(&self) -> (bool, i32, i32, u64) {
+        (
+            self.kind != ScipSymbolKind::File,
+            self.start_line,
+            self.end_line,
+            self.id,
+        )
+    }
+}
+
+/// Inserts a scope keeping the stack sorted by [`Scope::sort_key`]
+fn push_scope(scopes: &mut Vec<Scope>, scope: Scope) {
+    let key = scope.sort_key();
+    let position = scopes.partition_point(|existing| existing.sort_key() <= key);
+    scopes.insert(position, scope);
+}
+
+lazy_static::lazy_static! {
+    // Loading the BPE ranks is far more expensive than encoding, so load once
+    static ref CL100K: tiktoken_rs::CoreBPE = cl100k_base().unwrap();
+}
+
 pub fn count_tokens(text: &str) -> usize {
-    let bpe = cl100k_base().unwrap();
-    let tokens = bpe.encode_with_special_tokens(text);
+    let tokens = CL100K.encode_with_special_tokens(text);
     tokens.len()
 }
 
-pub fn run_scip_conversion(scip_path: &Path, config: YcgConfig) -> Result<String> {
+/// Reads and decodes a SCIP index, then checks its metadata block
+fn load_scip_index(scip_path: &Path, strict: bool, log: &EventLog) -> Result<scip_proto::Index> {
     if !scip_path.exists() {
         anyhow::bail!("Arquivo SCIP não encontrado: {:?}", scip_path);
     }
     println!("Carregando índice SCIP de: {:?}", scip_path);
 
     let data = fs::read(scip_path).with_context(|| format!("Falha ao ler: {:?}", scip_path))?;
-    let mut index = scip_proto::Index::decode(&data[..]).context("Falha ao decodificar SCIP")?;
+    // Outside --strict, the valid documents of a partly corrupt index are kept
+    let mut index = if strict {
+        scip_proto::Index::decode(&data[..]).context("Falha ao decodificar SCIP")?
+    } else {
+        let partial = scip_decoder::decode_resilient(&data).context("Falha ao decodificar SCIP")?;
+        for warning in partial.warnings {
+            log.emit(Event::warning(warning));
+        }
+        partial.index
+    };
+    normalize_document_paths(&mut index);
+
+    // Sanity check: indexer and protocol version
+    let report = inspect_scip_metadata(index.metadata.as_ref(), strict)?;
+    println!(
+        "Indexador: {} (protocolo SCIP v{})",
+        report.tool, report.protocol_version
+    );
+    for warning in &report.warnings {
+        log.emit(Event::warning(warning.as_str()));
+    }
+
+    Ok(index)
+}
+
+/// Rewrites `\` separators in document paths as `/`.
+///
+/// Indexers running on Windows may emit `src\users\dto\user.dto.ts`; every
+/// path check after ingestion (file globs, `/dto/`, `*.test.ts`, joining onto
+/// the project root) assumes forward slashes.
+fn normalize_document_paths(index: &mut scip_proto::Index) {
+    for doc in &mut index.documents {
+        if doc.relative_path.contains('\\') {
+            doc.relative_path = doc.relative_path.replace('\\', "/");
+        }
+    }
+}
+
+/// Runs the file, semantic and framework filters in reporting mode (`--dry-run`).
+///
+/// The graph is built from every document and left untouched; the report
+/// lists what each filter would drop and the tokens that would save.
+/// Filters are previewed whether or not their flags are enabled.
+pub fn run_dry_run(scip_path: &Path, config: &YcgConfig) -> Result<dry_run::DryRunReport> {
+    let index = load_scip_index(scip_path, config.strict, &config.event_log)?;
+
+    let file_filter = file_filter::FileFilter::new(&config.file_filter, &config.project_root)?;
+    let excluded_files = file_filter.excluded_paths(&index.documents);
+
+    let (graph, symbol_index) = convert_scip_to_ycg(index, config);
+    Ok(dry_run::DryRunReport::build(
+        &graph,
+        &symbol_index,
+        excluded_files,
+    ))
+}
+
+/// Finds circular imports between the files of an index (`analyze --import-cycles`)
+pub fn run_import_cycles(scip_path: &Path) -> Result<import_cycles::ImportCycleReport> {
+    let index = load_scip_index(scip_path, false, &EventLog::default())?;
+    Ok(import_cycles::ImportCycleReport::from_index(&index))
+}
+
+/// Finds imports never used in the importing file (`analyze --unused-imports`)
+pub fn run_unused_imports(scip_path: &Path) -> Result<unused_imports::UnusedImportReport> {
+    let index = load_scip_index(scip_path, false, &EventLog::default())?;
+    Ok(unused_imports::UnusedImportReport::from_index(&index))
+}
+
+/// Tags definitions with their layer and reports the edges that cross into
+/// a layer their source layer may not depend on (`analyze --layer-violations`)
+pub fn run_layer_violations(
+    scip_path: &Path,
+    config: &YcgConfig,
+) -> Result<layers::LayerViolationReport> {
+    if config.layer_rules.is_empty() || config.layer_dependencies.is_empty() {
+        anyhow::bail!(
+            "Layer analysis needs `layerRules` and `layerDependencies` in ycg.config.json"
+        );
+    }
+    let mut index = load_scip_index(scip_path, config.strict, &config.event_log)?;
+    apply_file_filter(&mut index, config, false)?;
+    let (graph, _) = convert_scip_to_ycg(index, config);
+    Ok(layers::LayerViolationReport::from_graph(
+        &graph,
+        &config.layer_dependencies,
+    ))
+}
+
+/// Lists the `TODO` / `FIXME` / `HACK` annotations of every definition
+/// (`analyze --todos`)
+pub fn run_todos(scip_path: &Path, config: &YcgConfig) -> Result<todos::TodoReport> {
+    let mut index = load_scip_index(scip_path, config.strict, &config.event_log)?;
+    apply_file_filter(&mut index, config, false)?;
+    let (graph, _) = convert_scip_to_ycg(index, config);
+    Ok(todos::TodoReport::from_graph(&graph))
+}
+
+/// Converts an index to a bincode graph (`--output-format bincode`).
+///
+/// The graph goes through the same filters as [`run_scip_conversion`];
+/// YAML layout options (compact, numeric IDs, string interning, style,
+/// header comments) do not apply.
+#[cfg(feature = "bincode")]
+pub fn run_binary_conversion(scip_path: &Path, config: YcgConfig) -> Result<Vec<u8>> {
+    let mut index = load_scip_index(scip_path, config.strict, &config.event_log)?;
+    let exclude_tests = config.tests != model::TestFilesMode::Include;
+    apply_file_filter(&mut index, &config, exclude_tests)?;
+
+    let filters = compiled_filters::CompiledFilters::new(&config)?;
+    let (graph, _) = build_graph(index, &config, &filters)?;
+    println!(">>> Serializando em bincode...");
+    binary_graph::encode(&graph)
+}
+
+pub fn run_scip_conversion(scip_path: &Path, mut config: YcgConfig) -> Result<String> {
+    let mut index = load_scip_index(scip_path, config.strict, &config.event_log)?;
+
+    // Test files leave the main graph unless included (--tests);
+    // `separate` keeps the full index around for the test-only graph
+    let exclude_tests = config.tests != model::TestFilesMode::Include;
+    let tests_index = (config.tests == model::TestFilesMode::Separate).then(|| index.clone());
 
     // STEP 1: File Filtering (Requirements 4.1-4.7)
-    // Apply file filtering before processing if any filters are configured
-    if !config.file_filter.include_patterns.is_empty()
-        || !config.file_filter.exclude_patterns.is_empty()
-        || config.file_filter.use_gitignore
+    apply_file_filter(&mut index, &config, exclude_tests)?;
+
+    // A valid but empty index (no documents) yields an empty graph; token
+    // density is meaningless there, so its report is skipped
+    let empty_index = index.documents.is_empty();
+    if empty_index {
+        config.event_log.emit(Event::warning(
+            "SCIP index contains no documents, generating an empty graph",
+        ));
+    }
+
+    // Pre-flight: SCIP document paths must resolve to files under project_root
+    let source_paths = document_source_paths(&config, &index.documents);
+    if config.path_prefix.is_none()
+        && let Some(prefix) = source_paths.prefix()
     {
-        println!(">>> Aplicando filtros de arquivo...");
-        let file_filter = file_filter::FileFilter::new(&config.file_filter, &config.project_root)?;
-        let original_count = index.documents.len();
-        index.documents = file_filter.filter_documents(index.documents);
-        let filtered_count = index.documents.len();
-        println!(
-            "    Arquivos filtrados: {} -> {} ({} removidos)",
-            original_count,
-            filtered_count,
-            original_count - filtered_count
-        );
+        println!(">>> Prefixo de caminho detectado: {}/", prefix);
+        config.path_prefix = Some(prefix.to_string());
+    }
+    let resolved =
+        source_paths.count_resolved(index.documents.iter().map(|doc| doc.relative_path.as_str()));
+    if !empty_index && resolved == 0 {
+        config.event_log.emit(Event::warning(format!(
+            "None of the {} SCIP document paths exist under {}; signatures and logic will be missing (use --root or --path-prefix)",
+            index.documents.len(),
+            config.project_root.display()
+        )));
     }
 
     // Contagem de Tokens de Entrada
     let mut total_input_tokens = 0;
-    let project_root = &config.project_root;
 
     for doc in &index.documents {
-        let real_path = project_root.join(&doc.relative_path);
+        let real_path = source_paths.resolve(&doc.relative_path);
         if let Ok(content) = fs::read_to_string(&real_path) {
             total_input_tokens += count_tokens(&content);
         }
     }
-    println!("--- Métrica de Densidade ---");
-    println!("Input Total Tokens (Código Bruto): {}", total_input_tokens);
 
     // Build source code map for signature/logic extraction (before consuming index)
     let mut sources = HashMap::new();
     for doc in &index.documents {
-        let real_path = project_root.join(&doc.relative_path);
+        let real_path = source_paths.resolve(&doc.relative_path);
         if let Ok(content) = fs::read_to_string(&real_path) {
+            config.event_log.emit(Event::FileRead {
+                path: doc.relative_path.clone(),
+                bytes: content.len(),
+            });
             // Map file path to source content
             sources.insert(doc.relative_path.clone(), content);
         }
     }
 
-    // Gera o grafo padrão (Flat)
-    let mut graph = convert_scip_to_ycg(index, &config);
+    // Gera o grafo padrão (Flat) e aplica os filtros (STEPS 2-3e); os padrões
+    // são compilados uma vez e compartilhados com os grafos base e de testes
+    let filters = compiled_filters::CompiledFilters::new(&config)?;
+    let (mut graph, mut symbol_index) = build_graph(index, &config, &filters)?;
+
+    // Every converted definition is in the index until it is restricted below;
+    // the survival share keeps the compression ratio honest
+    let converted_symbols = symbol_index.lines.len();
+    let output_symbols = graph.definitions.len();
+
+    // Methods/functions left without a signature, per file (--signature-coverage)
+    if config.signature_coverage {
+        println!(
+            "{}",
+            signature_coverage::SignatureCoverageReport::build(&graph, &symbol_index)
+        );
+    }
+
+    // STEP 3f: Delta against a baseline index (--baseline)
+    let mut graph_delta = None;
+    if let Some(baseline_path) = &config.baseline {
+        println!(">>> Calculando delta contra o índice base...");
+        let mut baseline_index = load_scip_index(baseline_path, config.strict, &config.event_log)?;
+        apply_file_filter(&mut baseline_index, &config, exclude_tests)?;
+        let (baseline, baseline_symbols) = build_graph(baseline_index, &config, &filters)?;
+        let (delta, changed) =
+            delta::GraphDelta::between(baseline, &baseline_symbols, graph, &symbol_index);
+        println!(
+            "    Adicionados: {}, removidos: {}, modificados: {}",
+            delta.added.len(),
+            delta.removed.len(),
+            delta.modified.len()
+        );
+        graph = changed;
+        graph_delta = Some(delta);
+    }
+
+    // STEP 3g: Test files in their own graph (--tests separate)
+    if let Some(mut tests_index) = tests_index {
+        let tests_path = config.tests_output.as_ref().ok_or_else(|| {
+            anyhow::anyhow!("--tests separate needs a path for the test graph (--output)")
+        })?;
+        println!(">>> Gerando grafo dos arquivos de teste...");
+        apply_file_filter(&mut tests_index, &config, false)?;
+        let (full, full_index) = build_graph(tests_index, &config, &filters)?;
+        let tests_graph = test_file_graph(full, &full_index);
+        println!(
+            "    Definições de teste: {}, arestas: {}",
+            tests_graph.definitions.len(),
+            tests_graph.references.len()
+        );
+        let yaml = if config.compact {
+            serde_yaml::to_string(&optimize_graph(tests_graph))?
+        } else {
+            serde_yaml::to_string(&tests_graph)?
+        };
+        fs::write(tests_path, yaml)
+            .with_context(|| format!("Falha ao escrever grafo de testes: {:?}", tests_path))?;
+        println!(">>> Grafo de testes salvo em: {:?}", tests_path);
+    }
+
+    // Companion symbol index, restricted to the definitions that survived filtering
+    symbol_index.retain_definitions(&graph);
+    if let Some(index_path) = &config.emit_index {
+        let json = serde_json::to_string_pretty(&symbol_index)?;
+        fs::write(index_path, json)
+            .with_context(|| format!("Falha ao escrever índice: {:?}", index_path))?;
+        println!(">>> Índice de símbolos salvo em: {:?}", index_path);
+    }
+    if let Some(outline_path) = &config.emit_document_symbols {
+        let outline = document_symbols::document_symbols(&graph, &symbol_index);
+        let json = serde_json::to_string_pretty(&outline)?;
+        fs::write(outline_path, json)
+            .with_context(|| format!("Falha ao escrever outline LSP: {:?}", outline_path))?;
+        println!(
+            ">>> Outline LSP (documentSymbol) salvo em: {:?}",
+            outline_path
+        );
+    }
+
+    // Per-module graph files (--split-by-module)
+    if let Some(split_dir) = &config.split_by_module {
+        write_module_split(&graph, &symbol_index, split_dir, config.compact)?;
+    }
+
+    // Definitions and edges in separate files (--split-defs-edges)
+    if let Some(split_dir) = &config.split_defs_edges {
+        write_defs_edges_split(&graph, split_dir, config.compact)?;
+    }
+
+    // STEP 4: Format Selection (Requirements 3.1-3.5)
+    if config.output_format == model::OutputFormat::Ndjson && graph_delta.is_some() {
+        config.event_log.emit(Event::warning(
+            "NDJSON has no _delta marker, emitting the changed subgraph only",
+        ));
+    }
+    if config.weighted_edges
+        && (!matches!(
+            config.output_format,
+            model::OutputFormat::Yaml | model::OutputFormat::Json
+        ) || config.numeric_ids
+            || (config.edges_by_type && !config.compact && !config.inline_edges))
+    {
+        config.event_log.emit(Event::warning(
+            "--weighted-edges applies to the flat edge list, --compact and --inline-edges only",
+        ));
+    }
+    let opts = SerializeOpts {
+        delta: graph_delta,
+        sources: Some(&sources),
+        ..SerializeOpts::from_config(&config)
+    };
+    let output = serialize_graph(&graph, config.output_format, &opts)?;
+
+    // Flow style (--style flow): nested maps and lists written inline
+    let output = if config.yaml_style == model::YamlStyle::Flow {
+        if config.output_format == model::OutputFormat::Ndjson {
+            config.event_log.emit(Event::warning(
+                "NDJSON is already one object per line, --style flow ignored",
+            ));
+            output
+        } else if config.output_format == model::OutputFormat::Json {
+            config.event_log.emit(Event::warning(
+                "JSON output is not YAML, --style flow ignored",
+            ));
+            output
+        } else if config.output_format == model::OutputFormat::PlantUml {
+            config.event_log.emit(Event::warning(
+                "PlantUML output is not YAML, --style flow ignored",
+            ));
+            output
+        } else {
+            println!(">>> Reescrevendo YAML em estilo flow...");
+            flow_yaml::to_flow(&output)?
+        }
+    } else {
+        output
+    };
+
+    // Header comments (--explain-format / --header-comment) are part of the output
+    let mut header = Vec::new();
+    if config.explain_format {
+        header.push(format_legend(&config));
+    }
+    header.extend(config.header_comment.clone());
+    let output = if header.is_empty() {
+        output
+    } else if matches!(
+        config.output_format,
+        model::OutputFormat::Ndjson | model::OutputFormat::Json
+    ) {
+        config.event_log.emit(Event::warning(
+            "JSON has no comment syntax, header comment skipped",
+        ));
+        output
+    } else if config.output_format == model::OutputFormat::PlantUml {
+        config.event_log.emit(Event::warning(
+            "PlantUML output is a diagram, header comment skipped",
+        ));
+        output
+    } else {
+        format!("{}{}", comment_lines(&header.join("\n")), output)
+    };
+
+    // Contagem de Tokens de Saída
+    if !empty_index {
+        let report = density::DensityReport {
+            input_tokens: total_input_tokens,
+            output_tokens: count_tokens(&output),
+            converted_symbols,
+            output_symbols,
+        };
+        println!("{}", report);
+    }
+
+    Ok(output)
+}
+
+/// Where the documents' sources live (`--root`, `--source-root`, `--path-prefix`)
+fn document_source_paths(
+    config: &YcgConfig,
+    documents: &[scip_proto::Document],
+) -> source_paths::SourcePaths {
+    // The prefix is only detected against a single root
+    if config.source_roots.is_empty() {
+        source_paths::SourcePaths::for_documents(
+            &config.project_root,
+            config.path_prefix.as_deref(),
+            documents.iter().map(|doc| doc.relative_path.as_str()),
+        )
+    } else {
+        source_paths::SourcePaths::new(&config.project_root, config.path_prefix.as_deref())
+            .with_extra_roots(&config.source_roots)
+    }
+}
+
+/// Drops documents excluded by the include/exclude/gitignore file filters
+fn apply_file_filter(
+    index: &mut scip_proto::Index,
+    config: &YcgConfig,
+    exclude_tests: bool,
+) -> Result<()> {
+    // Apply file filtering before processing if any filters are configured
+    if !config.file_filter.include_patterns.is_empty()
+        || !config.file_filter.exclude_patterns.is_empty()
+        || config.file_filter.use_gitignore
+        || exclude_tests
+    {
+        println!(">>> Aplicando filtros de arquivo...");
+        let file_filter = file_filter::FileFilter::new(&config.file_filter, &config.project_root)?
+            .excluding_tests(exclude_tests);
+        let original_count = index.documents.len();
+        index.documents = file_filter.filter_documents(std::mem::take(&mut index.documents));
+        let filtered_count = index.documents.len();
+        println!(
+            "    Arquivos filtrados: {} -> {} ({} removidos)",
+            original_count,
+            filtered_count,
+            original_count - filtered_count
+        );
+    }
+
+    // Symlinked sources (--no-follow-symlinks) are dropped instead of read through the link
+    if !config.follow_symlinks {
+        let source_paths = document_source_paths(config, &index.documents);
+        index.documents.retain(|doc| {
+            let symlinked = source_paths.is_symlinked(&doc.relative_path);
+            if symlinked {
+                config.event_log.emit(Event::warning(format!(
+                    "Skipping symlinked source file: {}",
+                    doc.relative_path
+                )));
+            }
+            !symlinked
+        });
+    }
+
+    // Generated sources (--skip-generated) are recognized by their leading comment
+    if config.skip_generated {
+        let source_paths = document_source_paths(config, &index.documents);
+        let original_count = index.documents.len();
+        index.documents.retain(|doc| {
+            let generated = read_source_head(&source_paths.resolve(&doc.relative_path))
+                .is_some_and(|head| file_filter::is_generated_source(&head));
+            if generated {
+                config.event_log.emit(Event::warning(format!(
+                    "Skipping generated source file: {}",
+                    doc.relative_path
+                )));
+            }
+            !generated
+        });
+        let removed = original_count - index.documents.len();
+        if removed > 0 {
+            println!(">>> Arquivos gerados ignorados: {}", removed);
+        }
+    }
+    Ok(())
+}
+
+/// First bytes of a source file, enough for its header comment
+fn read_source_head(path: &Path) -> Option<String> {
+    use std::io::Read;
+    let mut head = Vec::new();
+    fs::File::open(path)
+        .ok()?
+        .take(SOURCE_HEAD_BYTES)
+        .read_to_end(&mut head)
+        .ok()?;
+    Some(String::from_utf8_lossy(&head).into_owned())
+}
+
+/// Converts an index to a flat graph and applies the graph filters (STEPS 2-3e)
+fn build_graph(
+    index: scip_proto::Index,
+    config: &YcgConfig,
+    filters: &compiled_filters::CompiledFilters,
+) -> Result<(YcgGraph, SymbolIndex)> {
+    let (mut graph, mut symbol_index) = convert_with_filters(index, config, filters);
 
     // STEP 2: Semantic Filtering / Graph Compaction (Requirements 1.1-1.8)
     // Apply semantic filtering if compact mode is enabled
@@ -149,7 +889,10 @@ pub fn run_scip_conversion(scip_path: &Path, config: YcgConfig) -> Result<String
     if config.ignore_framework_noise {
         println!(">>> Removendo ruído de framework...");
         let original_nodes = graph.definitions.len();
-        framework_filter::FrameworkNoiseFilter::filter_graph(&mut graph);
+        framework_filter::FrameworkNoiseFilter::filter_graph_keeping(
+            &mut graph,
+            &config.keep_decorators,
+        );
         let filtered_nodes = graph.definitions.len();
         println!(
             "    Nós após remoção de boilerplate: {} -> {} ({} removidos)",
@@ -159,69 +902,564 @@ pub fn run_scip_conversion(scip_path: &Path, config: YcgConfig) -> Result<String
         );
     }
 
-    // STEP 4: Format Selection (Requirements 3.1-3.5)
-    // Serialize based on output format
-    let output = match config.output_format {
-        model::OutputFormat::AdHoc => {
-            println!(">>> Serializando em formato Ad-Hoc...");
-
-            // Log granularity level (Requirements 1.1-1.6)
-            let granularity_str = match config.adhoc_granularity {
-                model::AdHocGranularity::Default => "Level 0 (Default)",
-                model::AdHocGranularity::InlineSignatures => "Level 1 (Inline Signatures)",
-                model::AdHocGranularity::InlineLogic => "Level 2 (Inline Logic)",
-            };
-            println!("    Granularity: {}", granularity_str);
-
-            // Use AdHocSerializerV2 with granularity support
-            let serializer = adhoc_serializer_v2::AdHocSerializerV2::new(config.adhoc_granularity);
-            let adhoc_graph = serializer.serialize_graph(&graph, &sources);
-            serde_yaml::to_string(&adhoc_graph)?
-        }
-        model::OutputFormat::Yaml => {
-            // Standard YAML format
-            if config.compact {
-                println!(">>> Otimizando Grafo: Aplicando Lista de Adjacência...");
-                let optimized_graph = optimize_graph(graph);
-                serde_yaml::to_string(&optimized_graph)?
-            } else {
-                serde_yaml::to_string(&graph)?
-            }
-        }
-    };
-
-    // Contagem de Tokens de Saída
-    let output_tokens = count_tokens(&output);
-    println!("Output Total Tokens (Grafo YAML): {}", output_tokens);
+    // STEP 3a: Public API surface (--api-surface)
+    if config.api_surface {
+        println!(">>> Reduzindo o grafo à superfície de API pública...");
+        let original_nodes = graph.definitions.len();
+        api_surface::ApiSurfaceFilter::filter_graph(&mut graph);
+        println!(
+            "    Nós: {} -> {} ({} removidos)",
+            original_nodes,
+            graph.definitions.len(),
+            original_nodes - graph.definitions.len()
+        );
+    }
 
-    if total_input_tokens > 0 {
-        let ratio = total_input_tokens as f64 / output_tokens as f64;
-        println!("Taxa de Compressão: {:.2}x", ratio);
+    // Interfaces with a single implementation (--merge-single-impl)
+    if config.merge_single_impl {
+        println!(">>> Fundindo interfaces com implementação única...");
+        let merged = single_impl::SingleImplMerger::merge_graph(&mut graph);
+        println!("    Interfaces fundidas: {}", merged);
     }
-    println!("--------------------------");
 
-    Ok(output)
-}
+    // STEP 3b: Symbol name filtering (--include-name / --exclude-name)
+    if let Some(name_filter) = &filters.name_filter {
+        println!(">>> Aplicando filtros de nome de símbolo...");
+        let original_nodes = graph.definitions.len();
+        name_filter.filter_graph(&mut graph);
+        let filtered_nodes = graph.definitions.len();
+        println!(
+            "    Nós: {} -> {} ({} removidos)",
+            original_nodes,
+            filtered_nodes,
+            original_nodes - filtered_nodes
+        );
+    }
 
-// Transformador: Flat List -> Adjacency List
-fn optimize_graph(graph: YcgGraph) -> YcgGraphOptimized {
-    let mut adjacency: BTreeMap<String, BTreeMap<EdgeType, Vec<String>>> = BTreeMap::new();
+    // Intra-file edges (--cross-file-edges-only), while ids still match the index
+    if config.cross_file_edges_only {
+        println!(">>> Removendo arestas internas aos arquivos...");
+        let removed = cross_file::retain_cross_file_edges(&mut graph, &symbol_index);
+        println!("    Arestas removidas: {}", removed);
+    }
 
-    for edge in graph.references {
-        // Pega ou cria o mapa para este nó de origem
-        let node_edges = adjacency.entry(edge.from).or_insert_with(BTreeMap::new);
+    // One file in context (--focus-file), while ids still match the index
+    if let Some(focus) = &config.focus_file {
+        println!(
+            ">>> Restringindo o grafo ao arquivo {} e vizinhos...",
+            focus
+        );
+        let focused = focus::focus_file(&mut graph, &symbol_index, focus);
+        if focused == 0 {
+            config.event_log.emit(Event::warning(format!(
+                "--focus-file {}: no definitions found in that file",
+                focus
+            )));
+        }
+        println!(
+            "    Definições do arquivo: {}, com vizinhos: {}",
+            focused,
+            graph.definitions.len()
+        );
+    }
 
-        // Pega ou cria a lista para este tipo de aresta
-        let targets = node_edges.entry(edge.edge_type).or_insert_with(Vec::new);
+    // STEP 3c: Name compaction (--compact-names), applied to ids and edges alike
+    if config.compact_names {
+        println!(">>> Abreviando sufixos de nomes...");
+        name_compactor::NameCompactor::new(&config.name_abbreviations)
+            .apply_to_graph(&mut graph, &mut symbol_index);
+    }
 
-        // Adiciona o destino
-        targets.push(edge.to);
+    // STEP 3d: Secret redaction (--redact)
+    if config.redact {
+        println!(">>> Mascarando literais sensíveis...");
+        let redacted = redactor::Redactor::redact_graph(&mut graph);
+        println!("    Nós e arestas com dados mascarados: {}", redacted);
     }
 
-    // Ordena os vetores de destino para garantir determinismo
-    for inner_map in adjacency.values_mut() {
-        for targets in inner_map.values_mut() {
-            targets.sort();
+    // STEP 3e: Parameter default values (--no-defaults)
+    if config.no_defaults {
+        println!(">>> Removendo valores padrão de parâmetros das assinaturas...");
+        for node in &mut graph.definitions {
+            if let Some(sig) = &node.signature {
+                node.signature =
+                    Some(signature_extractor::SignatureExtractor::strip_default_values(sig));
+            }
+        }
+    }
+
+    // Language-neutral signatures (--neutral-signatures), after --no-defaults
+    if config.neutral_signatures {
+        println!(">>> Normalizando assinaturas para a forma neutra...");
+        for node in &mut graph.definitions {
+            if matches!(node.kind, ScipSymbolKind::Method | ScipSymbolKind::Function)
+                && let Some(neutral) = node
+                    .signature
+                    .as_deref()
+                    .and_then(type_abbreviator::TypeAbbreviator::neutral_signature)
+            {
+                node.signature = Some(neutral);
+            }
+        }
+    }
+
+    // STEP 3f: File hierarchy by path (--file-paths)
+    if config.file_paths {
+        println!(">>> Aninhando arquivos por diretório...");
+        let directories = file_tree::nest_files_by_directory(&mut graph);
+        println!("    Diretórios: {}", directories);
+    }
+
+    // Dependency order (--topo-sort), once the filters have run
+    if config.topo_sort {
+        println!(">>> Ordenando definições por dependência...");
+        topo_sort::sort_definitions(&mut graph);
+    }
+
+    // Importance signal, counted once every pass has dropped or redirected edges
+    count_inbound_edges(&mut graph);
+
+    Ok((graph, symbol_index))
+}
+
+/// Sets `ref_count` of every definition to its number of inbound edges
+fn count_inbound_edges(graph: &mut YcgGraph) {
+    let mut ref_counts: HashMap<&str, u32> = HashMap::new();
+    for edge in &graph.references {
+        *ref_counts.entry(edge.to.as_str()).or_insert(0) += 1;
+    }
+    for node in &mut graph.definitions {
+        node.ref_count = ref_counts.get(node.id.as_str()).copied().unwrap_or(0);
+    }
+}
+
+/// Serialization settings of [`serialize_graph`], usually taken from a [`YcgConfig`]
+#[derive(Debug, Clone, Default)]
+pub struct SerializeOpts<'a> {
+    /// Adjacency-list layout (`--compact`)
+    pub compact: bool,
+    /// Integer IDs with an `_meta.anchors` table (`--numeric-ids`)
+    pub numeric_ids: bool,
+    /// Edges grouped by type (`--edges-by-type`)
+    pub edges_by_type: bool,
+    /// Outgoing edges under each definition (`--inline-edges`)
+    pub inline_edges: bool,
+    /// Repeated edges written once with their count (`--weighted-edges`)
+    pub weighted_edges: bool,
+    /// Repeated signatures/documentation moved to a `_strings` table (`--intern-strings`)
+    pub intern_strings: bool,
+    pub adhoc_granularity: model::AdHocGranularity,
+    pub adhoc_granularity_by_kind: HashMap<model::ScipSymbolKind, model::AdHocGranularity>,
+    pub signature_style: model::SignatureStyle,
+    pub abbreviate_wrappers: Option<String>,
+    pub optional_param_markers: bool,
+    /// Builder / fluent chains longer than this are summarized (`--chain-summary-threshold`)
+    pub chain_summary_threshold: Option<usize>,
+    /// `_delta` marker written before the graph (`--baseline`); NDJSON drops it
+    pub delta: Option<delta::GraphDelta>,
+    /// Source text per file, for the ad-hoc serializer
+    pub sources: Option<&'a HashMap<String, String>>,
+}
+
+impl SerializeOpts<'_> {
+    /// The layout options of `config`, without delta or sources
+    pub fn from_config(config: &YcgConfig) -> Self {
+        Self {
+            compact: config.compact,
+            numeric_ids: config.numeric_ids,
+            edges_by_type: config.edges_by_type,
+            inline_edges: config.inline_edges,
+            weighted_edges: config.weighted_edges,
+            intern_strings: config.intern_strings,
+            adhoc_granularity: config.adhoc_granularity,
+            adhoc_granularity_by_kind: config.adhoc_granularity_by_kind.clone(),
+            signature_style: config.signature_style,
+            abbreviate_wrappers: config.abbreviate_wrappers.clone(),
+            optional_param_markers: config.optional_param_markers,
+            chain_summary_threshold: config.chain_summary_threshold,
+            delta: None,
+            sources: None,
+        }
+    }
+}
+
+/// Serializes an in-memory graph to a text format.
+///
+/// This is the serialization step of [`run_scip_conversion`] on its own: a
+/// graph built once can be transformed and written in several formats
+/// without converting the index again. Header comments and `--style flow`
+/// are applied by the caller. Bincode is binary, see [`binary_graph::encode`].
+pub fn serialize_graph(
+    graph: &YcgGraph,
+    format: model::OutputFormat,
+    opts: &SerializeOpts,
+) -> Result<String> {
+    let delta = opts.delta.clone();
+    Ok(match format {
+        model::OutputFormat::AdHoc => {
+            println!(">>> Serializando em formato Ad-Hoc...");
+
+            // Log granularity level (Requirements 1.1-1.6)
+            let granularity_str = match opts.adhoc_granularity {
+                model::AdHocGranularity::Default => "Level 0 (Default)",
+                model::AdHocGranularity::InlineSignatures => "Level 1 (Inline Signatures)",
+                model::AdHocGranularity::InlineLogic => "Level 2 (Inline Logic)",
+            };
+            println!("    Granularity: {}", granularity_str);
+
+            // Use AdHocSerializerV2 with granularity support
+            let serializer = adhoc_serializer_v2::AdHocSerializerV2::new(opts.adhoc_granularity)
+                .with_signature_style(opts.signature_style)
+                .with_wrapper_abbreviation(opts.abbreviate_wrappers.clone())
+                .with_optional_param_markers(opts.optional_param_markers)
+                .with_chain_summary_threshold(
+                    opts.chain_summary_threshold
+                        .unwrap_or(signature_extractor::DEFAULT_CHAIN_SUMMARY_THRESHOLD),
+                )
+                .with_kind_granularity(opts.adhoc_granularity_by_kind.clone());
+            let no_sources = HashMap::new();
+            let adhoc_graph =
+                serializer.serialize_graph(graph, opts.sources.unwrap_or(&no_sources));
+            serde_yaml::to_string(&delta::DeltaGraph {
+                delta,
+                graph: adhoc_graph,
+            })?
+        }
+        model::OutputFormat::Ndjson => {
+            println!(">>> Serializando em NDJSON (um objeto JSON por linha)...");
+            ndjson::to_ndjson(graph.clone())?
+        }
+        model::OutputFormat::PlantUml => {
+            println!(">>> Gerando diagrama de classes PlantUML...");
+            plantuml::to_plantuml(graph)
+        }
+        #[cfg(feature = "bincode")]
+        model::OutputFormat::Bincode => {
+            anyhow::bail!("Bincode output is binary, use run_binary_conversion")
+        }
+        model::OutputFormat::Yaml | model::OutputFormat::Json => {
+            let mut graph = graph.clone();
+            let strings = if opts.intern_strings {
+                println!(">>> Internando assinaturas e documentação repetidas...");
+                let strings = string_interner::StringInterner::intern(&mut graph.definitions);
+                println!("    Strings compartilhadas: {}", strings.len());
+                strings
+            } else {
+                BTreeMap::new()
+            };
+
+            // Standard YAML format, or the same document as JSON
+            let text = TextGraph {
+                format,
+                strings,
+                intern_strings: opts.intern_strings,
+                delta,
+            };
+            // Repeated edges (--no-dedupe-edges) counted before the layouts dedup them
+            let counts = opts
+                .weighted_edges
+                .then(|| model::edge_counts(&graph.references));
+            if opts.numeric_ids {
+                println!(">>> Substituindo anchors por IDs numéricos...");
+                text.write(numeric_ids::NumericGraph::from_graph(graph, opts.compact))?
+            } else if opts.inline_edges {
+                println!(">>> Embutindo arestas de saída em cada definição...");
+                let mut inline = model::YcgGraphInlineEdges::from_graph(graph);
+                if let Some(counts) = &counts {
+                    for definition in &mut inline.definitions {
+                        model::weigh_targets(&definition.node.id, &mut definition.edges, counts);
+                    }
+                    for (from, edges) in &mut inline.unattached {
+                        model::weigh_targets(from, edges, counts);
+                    }
+                }
+                text.write(inline)?
+            } else if opts.compact {
+                println!(">>> Otimizando Grafo: Aplicando Lista de Adjacência...");
+                let mut optimized = optimize_graph(graph);
+                if let Some(counts) = &counts {
+                    for (from, edges) in &mut optimized.adjacency {
+                        model::weigh_targets(from, edges, counts);
+                    }
+                }
+                text.write(optimized)?
+            } else if opts.edges_by_type {
+                println!(">>> Agrupando arestas por tipo...");
+                text.write(model::YcgGraphByType::from_graph(graph))?
+            } else if opts.weighted_edges {
+                println!(">>> Agrupando arestas repetidas com contagem...");
+                text.write(model::YcgGraphWeighted::from_graph(graph))?
+            } else {
+                text.write(graph)?
+            }
+        }
+    })
+}
+
+/// Short description of the output layout, for `--explain-format`
+fn format_legend(config: &YcgConfig) -> String {
+    let defs = match config.output_format {
+        model::OutputFormat::AdHoc => match config.adhoc_granularity {
+            model::AdHocGranularity::Default => "_defs entries are \"id|name|type\"",
+            model::AdHocGranularity::InlineSignatures => "_defs entries are \"id|signature|type\"",
+            model::AdHocGranularity::InlineLogic => {
+                "_defs entries are \"id|signature|type\" with an optional \"|logic:steps\""
+            }
+        },
+        _ if config.numeric_ids => {
+            "_defs: id (integer, see _meta.anchors), n = name, t = type, sig = signature"
+        }
+        _ => "_defs: id, n = name, t = type, sig = signature, parent_id = enclosing symbol",
+    };
+    let graph = if config.output_format == model::OutputFormat::AdHoc {
+        "graph maps source id -> edge type (c = calls, r = references, i = imports, e = implements, w = writes) -> target ids"
+    } else if config.inline_edges && !config.numeric_ids {
+        "each _defs entry maps edge type -> target ids of its outgoing edges"
+    } else if config.compact {
+        "graph maps source id -> edge type -> target ids"
+    } else if config.edges_by_type {
+        "graph maps edge type -> edges {from, to}"
+    } else if config.weighted_edges {
+        "graph lists edges {from, to, type}, count = number of identical edges"
+    } else {
+        "graph lists edges {from, to, type}"
+    };
+    format!(
+        "This is a YCG code graph (symbols and their relationships).\n{}.\n{}; edges are directed (from uses to).",
+        defs, graph
+    )
+}
+
+/// Turns free text into YAML comment lines, so parsers skip it
+fn comment_lines(text: &str) -> String {
+    text.lines()
+        .map(|line| {
+            if line.trim().is_empty() {
+                "#\n".to_string()
+            } else {
+                format!("# {}\n", line)
+            }
+        })
+        .collect()
+}
+
+/// What wraps a YAML/JSON graph: the `_delta` marker (`--baseline`) and
+/// the `_strings` table when interning is enabled
+struct TextGraph {
+    format: model::OutputFormat,
+    strings: BTreeMap<String, String>,
+    intern_strings: bool,
+    delta: Option<delta::GraphDelta>,
+}
+
+impl TextGraph {
+    fn write<G: serde::Serialize>(self, graph: G) -> Result<String> {
+        let graph = delta::DeltaGraph {
+            delta: self.delta,
+            graph,
+        };
+        if self.intern_strings {
+            let strings = self.strings;
+            to_text(
+                self.format,
+                &string_interner::InternedGraph { strings, graph },
+            )
+        } else {
+            to_text(self.format, &graph)
+        }
+    }
+}
+
+fn to_text<T: serde::Serialize>(format: model::OutputFormat, value: &T) -> Result<String> {
+    Ok(if format == model::OutputFormat::Json {
+        serde_json::to_string_pretty(value)? + "\n"
+    } else {
+        serde_yaml::to_string(value)?
+    })
+}
+
+/// Restricts a graph to the definitions in test files and the edges leaving
+/// them (calls from tests into production code included)
+fn test_file_graph(graph: YcgGraph, symbol_index: &SymbolIndex) -> YcgGraph {
+    let mut sources: HashSet<String> = HashSet::new();
+    for (path, anchors) in &symbol_index.files {
+        if file_filter::is_test_file(Path::new(path)) {
+            sources.insert(generate_anchor("file", xxh64(path.as_bytes(), 0)));
+            sources.extend(anchors.iter().cloned());
+        }
+    }
+    YcgGraph {
+        metadata: graph.metadata,
+        definitions: graph
+            .definitions
+            .into_iter()
+            .filter(|node| sources.contains(&node.id))
+            .collect(),
+        references: graph
+            .references
+            .into_iter()
+            .filter(|edge| sources.contains(&edge.from))
+            .collect(),
+    }
+}
+
+/// Writes `<module>.yaml` per top-level module and `_cross.yaml` with the
+/// edges between modules into `dir`, honoring compact (adjacency list) mode.
+fn write_module_split(
+    graph: &YcgGraph,
+    symbol_index: &model::SymbolIndex,
+    dir: &Path,
+    compact: bool,
+) -> Result<()> {
+    let split = module_splitter::ModuleSplitter::split(graph, symbol_index);
+
+    fs::create_dir_all(dir).with_context(|| format!("Falha ao criar diretório: {:?}", dir))?;
+
+    let to_yaml = |g: YcgGraph| -> Result<String> {
+        Ok(if compact {
+            serde_yaml::to_string(&optimize_graph(g))?
+        } else {
+            serde_yaml::to_string(&g)?
+        })
+    };
+
+    let module_count = split.modules.len();
+    for (module, module_graph) in split.modules {
+        let path = dir.join(format!("{}.yaml", module));
+        fs::write(&path, to_yaml(module_graph)?)
+            .with_context(|| format!("Falha ao escrever módulo: {:?}", path))?;
+    }
+
+    let cross_path = dir.join(format!("{}.yaml", module_splitter::CROSS_MODULE_FILE));
+    let cross_edges = split.cross.references.len();
+    fs::write(&cross_path, to_yaml(split.cross)?)
+        .with_context(|| format!("Falha ao escrever arestas entre módulos: {:?}", cross_path))?;
+
+    println!(
+        ">>> Grafo dividido em {} módulos ({} arestas entre módulos) em: {:?}",
+        module_count, cross_edges, dir
+    );
+    Ok(())
+}
+
+/// Writes `defs.yaml` (metadata + definitions) and `edges.yaml` (metadata +
+/// references, as an adjacency list when `compact`). Each is a complete
+/// graph document on its own: the other half is simply empty.
+fn write_defs_edges_split(graph: &YcgGraph, dir: &Path, compact: bool) -> Result<()> {
+    fs::create_dir_all(dir).with_context(|| format!("Falha ao criar diretório: {:?}", dir))?;
+
+    let defs = YcgGraph {
+        metadata: graph.metadata.clone(),
+        definitions: graph.definitions.clone(),
+        references: Vec::new(),
+    };
+    let edges = YcgGraph {
+        metadata: graph.metadata.clone(),
+        definitions: Vec::new(),
+        references: graph.references.clone(),
+    };
+    let edges_yaml = if compact {
+        serde_yaml::to_string(&optimize_graph(edges))?
+    } else {
+        serde_yaml::to_string(&edges)?
+    };
+
+    let defs_path = dir.join("defs.yaml");
+    fs::write(&defs_path, serde_yaml::to_string(&defs)?)
+        .with_context(|| format!("Falha ao escrever definições: {:?}", defs_path))?;
+    let edges_path = dir.join("edges.yaml");
+    fs::write(&edges_path, edges_yaml)
+        .with_context(|| format!("Falha ao escrever arestas: {:?}", edges_path))?;
+
+    println!(
+        ">>> Definições ({}) e arestas ({}) salvas separadamente em: {:?}",
+        graph.definitions.len(),
+        graph.references.len(),
+        dir
+    );
+    Ok(())
+}
+
+/// Reads the SCIP `Metadata` block and checks the protocol version.
+///
+/// Indexer mismatches tend to show up as silently wrong signatures or
+/// parents, so the tool name/version is reported and any protocol version
+/// outside `SUPPORTED_PROTOCOL_VERSIONS` produces a warning, or an error
+/// when `strict` is set. A missing metadata block only warns.
+fn inspect_scip_metadata(
+    metadata: Option<&scip_proto::Metadata>,
+    strict: bool,
+) -> Result<ScipMetadataReport> {
+    let Some(metadata) = metadata else {
+        return Ok(ScipMetadataReport {
+            tool: "unknown".to_string(),
+            protocol_version: 0,
+            warnings: vec![
+                "SCIP index has no metadata block; cannot verify indexer compatibility".to_string(),
+            ],
+        });
+    };
+
+    let tool = match &metadata.tool_info {
+        Some(info) if !info.name.is_empty() => {
+            if info.version.is_empty() {
+                info.name.clone()
+            } else {
+                format!("{} {}", info.name, info.version)
+            }
+        }
+        _ => "unknown".to_string(),
+    };
+
+    let mut warnings = Vec::new();
+    if !SUPPORTED_PROTOCOL_VERSIONS.contains(&metadata.version) {
+        let supported = format!(
+            "{}..={}",
+            SUPPORTED_PROTOCOL_VERSIONS.start(),
+            SUPPORTED_PROTOCOL_VERSIONS.end()
+        );
+        if strict {
+            return Err(errors::ParseError::unsupported_protocol_version(
+                metadata.version,
+                tool,
+                supported,
+            )
+            .into());
+        }
+        warnings.push(format!(
+            "SCIP protocol version {} from {} is outside the tested range ({}); output may be inaccurate",
+            metadata.version, tool, supported
+        ));
+    }
+
+    Ok(ScipMetadataReport {
+        tool,
+        protocol_version: metadata.version,
+        warnings,
+    })
+}
+
+// Transformador: Flat List -> Adjacency List
+fn optimize_graph(graph: YcgGraph) -> YcgGraphOptimized {
+    let mut adjacency: BTreeMap<String, BTreeMap<EdgeType, Vec<String>>> = BTreeMap::new();
+
+    for edge in graph.references {
+        // Pega ou cria o mapa para este nó de origem
+        let node_edges = adjacency.entry(edge.from).or_insert_with(BTreeMap::new);
+
+        // Pega ou cria a lista para este tipo de aresta
+        let targets = node_edges.entry(edge.edge_type).or_insert_with(Vec::new);
+
+        // Adiciona o destino
+        targets.push(edge.to);
+    }
+
+    // Ordena os vetores de destino para garantir determinismo
+    // (chaves: anchors em ordem alfabética; tipos de aresta por EdgeType::rank)
+    for inner_map in adjacency.values_mut() {
+        for targets in inner_map.values_mut() {
+            targets.sort();
+            targets.dedup();
         }
     }
 
@@ -232,9 +1470,28 @@ fn optimize_graph(graph: YcgGraph) -> YcgGraphOptimized {
     }
 }
 
-fn convert_scip_to_ycg(index: scip_proto::Index, config: &YcgConfig) -> YcgGraph {
+fn convert_scip_to_ycg(index: scip_proto::Index, config: &YcgConfig) -> (YcgGraph, SymbolIndex) {
+    let filters = compiled_filters::CompiledFilters::for_conversion(config);
+    convert_with_filters(index, config, &filters)
+}
+
+fn convert_with_filters(
+    index: scip_proto::Index,
+    config: &YcgConfig,
+    filters: &compiled_filters::CompiledFilters,
+) -> (YcgGraph, SymbolIndex) {
     let mut symbol_kind_map: HashMap<String, i32> = HashMap::new();
-    let enricher = TreeSitterEnricher::new();
+    let mut relationship_map: HashMap<String, Vec<scip_proto::Relationship>> = HashMap::new();
+    let mut enricher = TreeSitterEnricher::new()
+        .with_max_file_bytes(config.max_file_bytes)
+        .with_max_preconditions(config.max_preconditions)
+        .with_max_guard_depth(config.max_guard_depth)
+        .with_max_doc_length(config.max_doc_length)
+        .with_doc_boilerplate_stripped(config.strip_doc_boilerplate)
+        .with_event_log(config.event_log.clone());
+    for (ext, language) in &config.languages {
+        enricher.register_language(ext.clone(), *language);
+    }
 
     for info in &index.external_symbols {
         symbol_kind_map.insert(info.symbol.clone(), info.kind);
@@ -242,22 +1499,85 @@ fn convert_scip_to_ycg(index: scip_proto::Index, config: &YcgConfig) -> YcgGraph
     for doc in &index.documents {
         for info in &doc.symbols {
             symbol_kind_map.insert(info.symbol.clone(), info.kind);
+            if !info.relationships.is_empty() {
+                relationship_map
+                    .entry(info.symbol.clone())
+                    .or_default()
+                    .extend(info.relationships.iter().cloned());
+            }
         }
     }
 
-    convert_with_two_passes(index, symbol_kind_map, enricher, config)
+    let hints = descriptor_signature::DescriptorHints::from_index(&index);
+
+    convert_with_two_passes(
+        index,
+        symbol_kind_map,
+        relationship_map,
+        hints,
+        enricher,
+        config,
+        filters,
+    )
+}
+
+/// Maps a SCIP relationship to the edge type it should produce.
+///
+/// `is_implementation` takes precedence over `is_reference` and
+/// `is_type_definition`, since a relationship flagged as both
+/// (e.g. `Dog#sound()` overriding `Animal#sound()`) is primarily an
+/// implementation. Relationships with no recognised flag yield `None`.
+fn relationship_edge_type(rel: &scip_proto::Relationship) -> Option<EdgeType> {
+    if rel.is_implementation {
+        Some(EdgeType::Implements)
+    } else if rel.is_reference || rel.is_type_definition {
+        Some(EdgeType::References)
+    } else {
+        None
+    }
 }
 
 fn convert_with_two_passes(
     index: scip_proto::Index,
     kind_map: HashMap<String, i32>,
+    relationship_map: HashMap<String, Vec<scip_proto::Relationship>>,
+    hints: descriptor_signature::DescriptorHints,
     mut enricher: TreeSitterEnricher,
     config: &YcgConfig,
-) -> YcgGraph {
+    filters: &compiled_filters::CompiledFilters,
+) -> (YcgGraph, SymbolIndex) {
     let mut nodes: Vec<SymbolNode> = Vec::new();
-    let mut edges_set: HashSet<ReferenceEdge> = HashSet::new();
+    let mut symbol_index = SymbolIndex::default();
+    // Method anchor -> callee name of its pass-through body (--collapse-delegates)
+    let mut delegate_calls: HashMap<String, String> = HashMap::new();
+    let mut ownership = config
+        .with_ownership
+        .then(|| ownership::OwnershipResolver::new(&config.project_root, &config.event_log));
+    let mut edges_set = if config.dedupe_edges {
+        edge_dedup::EdgeDedup::new(config.streaming_edges)
+    } else {
+        edge_dedup::EdgeDedup::KeepAll(Vec::new())
+    };
+    // First call text seen per (from, to) call edge, attached after dedup
+    let mut call_texts: HashMap<(String, String), String> = HashMap::new();
     let mut registry: HashMap<u64, String> = HashMap::new();
-    let project_root = &config.project_root;
+    // Short type/trait name -> symbol id (`None` when several types share it),
+    // used to resolve `impl Trait for Type`
+    let mut type_names: HashMap<String, Option<u64>> = HashMap::new();
+    // Canonical URI -> definition id (`None` when several definitions share it),
+    // so references spelled differently still reach their definition
+    let mut canonical_ids: HashMap<String, Option<u64>> = HashMap::new();
+    let mut skipped_malformed = 0usize;
+    let mut skipped_ranges = 0usize;
+    let source_paths = document_source_paths(config, &index.documents);
+
+    // Patterns compiled once per run (edge rules, layer rules, --inline-source)
+    let edge_rules = &filters.edge_rules;
+    let layer_rules = &filters.layer_rules;
+    let inline_source = &filters.inline_source;
+
+    // Default exports renamed from their declaration or file name, keyed by symbol id
+    let mut default_export_names: HashMap<u64, String> = HashMap::new();
 
     // --- PASSADA A ---
     for doc in &index.documents {
@@ -266,27 +1586,66 @@ fn convert_with_two_passes(
         registry.insert(file_id, file_anchor);
 
         for occurrence in &doc.occurrences {
+            if is_malformed_symbol(&occurrence.symbol) || is_malformed_range(&occurrence.range) {
+                continue;
+            }
             if (occurrence.symbol_roles & scip_proto::SymbolRole::Definition as i32) != 0 {
-                let clean_name = extract_name_from_uri(&occurrence.symbol);
+                let mut clean_name = strip_name_prefix(
+                    extract_name_from_uri(&occurrence.symbol),
+                    config.strip_prefix.as_deref(),
+                );
                 let id = xxh64(occurrence.symbol.as_bytes(), 0);
-                let base = if clean_name.is_empty()
-                    || clean_name.ends_with(".ts")
-                    || clean_name.ends_with(".rs")
-                {
-                    "def".to_string()
-                } else {
-                    clean_name.replace(|c: char| !c.is_alphanumeric(), "_")
-                };
-                let anchor = generate_anchor(&base, id);
+                if is_default_export(&occurrence.symbol) {
+                    let line = occurrence.range.first().copied().unwrap_or(0) as usize;
+                    clean_name = enricher
+                        .resolve_default_export_name(
+                            &source_paths.resolve(&doc.relative_path),
+                            line,
+                        )
+                        .unwrap_or_else(|| synthesize_default_export_name(&doc.relative_path));
+                    default_export_names.insert(id, clean_name.clone());
+                }
+                let anchor = generate_anchor(&anchor_base(&clean_name), id);
                 registry.insert(id, anchor);
+                canonical_ids
+                    .entry(canonical_symbol(&occurrence.symbol))
+                    .and_modify(|existing| {
+                        if *existing != Some(id) {
+                            *existing = None;
+                        }
+                    })
+                    .or_insert(Some(id));
+                if occurrence.symbol.ends_with('#') {
+                    type_names
+                        .entry(short_symbol_name(&clean_name).to_string())
+                        .and_modify(|existing| {
+                            if *existing != Some(id) {
+                                *existing = None;
+                            }
+                        })
+                        .or_insert(Some(id));
+                }
             }
         }
     }
 
     // --- PASSADA B ---
     for doc in index.documents {
-        let real_path = project_root.join(&doc.relative_path);
+        let real_path = source_paths.resolve(&doc.relative_path);
         let file_id = xxh64(doc.relative_path.as_bytes(), 0);
+        let relative_path = doc.relative_path.clone();
+        // Without the source file, signatures can only come from SCIP itself
+        let source_available = real_path.is_file();
+        let column_encoding = column_encoding(doc.position_encoding, &doc.relative_path);
+        let layer = layer_rules
+            .layer_for(source_paths.strip(&relative_path))
+            .map(str::to_string);
+        // Compared against the baseline graph (--baseline)
+        let fingerprints = if config.baseline.is_some() {
+            delta::definition_fingerprints(&doc)
+        } else {
+            HashMap::new()
+        };
 
         // Initialize scope stack with file scope as the root
         // The file scope covers all lines and serves as the fallback parent
@@ -299,39 +1658,95 @@ fn convert_with_two_passes(
             kind: ScipSymbolKind::File,
         });
 
+        // Types this document defines or mentions, by short name: `impl Trait for
+        // Type` names them as written in the file, whatever other modules define
+        let mut doc_types: HashMap<String, Option<u64>> = HashMap::new();
+        for occurrence in &doc.occurrences {
+            if occurrence.symbol.ends_with('#') && !is_malformed_symbol(&occurrence.symbol) {
+                let id = xxh64(occurrence.symbol.as_bytes(), 0);
+                doc_types
+                    .entry(
+                        short_symbol_name(&extract_name_from_uri(&occurrence.symbol)).to_string(),
+                    )
+                    .and_modify(|existing| {
+                        if *existing != Some(id) {
+                            *existing = None;
+                        }
+                    })
+                    .or_insert(Some(id));
+            }
+        }
+        let resolve_type = |name: &str| match doc_types.get(name) {
+            Some(id) => *id,
+            None => type_names.get(name).copied().flatten(),
+        };
+
         // B.1 DEFINIÇÕES
         for occurrence in &doc.occurrences {
+            // Empty or whitespace-only URIs would hash into phantom nodes
+            if is_malformed_symbol(&occurrence.symbol) {
+                skipped_malformed += 1;
+                continue;
+            }
+            // Without a start line the symbol would land at the top of the file
+            if is_malformed_range(&occurrence.range) {
+                skipped_ranges += 1;
+                continue;
+            }
             let is_def = (occurrence.symbol_roles & scip_proto::SymbolRole::Definition as i32) != 0;
             if is_def {
                 let id = xxh64(occurrence.symbol.as_bytes(), 0);
                 let raw_kind = kind_map.get(&occurrence.symbol).copied().unwrap_or(0);
                 let kind = if raw_kind == 0 {
-                    infer_kind_from_uri(&occurrence.symbol)
+                    // `Color#` is a type either way; only the source tells enums from classes
+                    match infer_kind_from_uri(&occurrence.symbol) {
+                        ScipSymbolKind::Class
+                            if enricher.is_enum_declaration(
+                                &real_path,
+                                occurrence.range.first().copied().unwrap_or(0) as usize,
+                            ) =>
+                        {
+                            ScipSymbolKind::Enum
+                        }
+                        inferred => inferred,
+                    }
                 } else {
                     map_kind(raw_kind)
                 };
 
                 let should_skip = match config.lod {
-                    LevelOfDetail::Low => matches!(
-                        kind,
-                        ScipSymbolKind::Variable
-                            | ScipSymbolKind::Interface
-                            | ScipSymbolKind::Module
-                    ),
+                    LevelOfDetail::Low => {
+                        matches!(
+                            kind,
+                            ScipSymbolKind::Variable
+                                | ScipSymbolKind::Interface
+                                | ScipSymbolKind::Module
+                        ) || is_type_parameter_uri(&occurrence.symbol)
+                    }
                     LevelOfDetail::Medium => {
                         let is_local_var = kind == ScipSymbolKind::Variable
                             && !occurrence.symbol.contains('#')
                             && !occurrence.symbol.contains('.');
                         let is_param = occurrence.symbol.contains("().(");
-                        is_local_var || is_param
+                        is_local_var || is_param || is_type_parameter_uri(&occurrence.symbol)
                     }
                     LevelOfDetail::High => false,
                 };
                 if should_skip {
+                    config.event_log.emit(Event::SymbolSkipped {
+                        symbol: occurrence.symbol.clone(),
+                        kind,
+                        lod: config.lod,
+                    });
                     continue;
                 }
 
-                let clean_name = extract_name_from_uri(&occurrence.symbol);
+                let clean_name = default_export_names.get(&id).cloned().unwrap_or_else(|| {
+                    strip_name_prefix(
+                        extract_name_from_uri(&occurrence.symbol),
+                        config.strip_prefix.as_deref(),
+                    )
+                });
 
                 // Resolve generic variable names from source code
                 // **Validates: Requirements 6.1, 6.3, 6.4, 6.5**
@@ -340,34 +1755,34 @@ fn convert_with_two_passes(
                     let start_line = occurrence.range.get(0).copied().unwrap_or(0);
                     let start_col = occurrence.range.get(1).copied().unwrap_or(0);
 
-                    match enricher.resolve_variable_name(
+                    match enricher.resolve_variable_name_with_encoding(
                         &real_path,
                         start_line as usize,
                         start_col as usize,
+                        column_encoding,
                     ) {
                         Some(resolved) => {
-                            eprintln!(
-                                "✓ Resolved generic name '{}' to '{}' at {}:{}",
-                                clean_name,
-                                resolved,
-                                real_path.display(),
-                                start_line
-                            );
+                            config.event_log.emit(Event::VariableResolved {
+                                name: clean_name,
+                                resolved: resolved.clone(),
+                                file: real_path.display().to_string(),
+                                line: start_line,
+                            });
                             resolved
                         }
                         None => {
-                            eprintln!(
-                                "⚠️  Failed to resolve generic name '{}' at {}:{}, using SCIP name",
-                                clean_name,
-                                real_path.display(),
-                                start_line
-                            );
+                            config.event_log.emit(Event::VariableUnresolved {
+                                name: clean_name.clone(),
+                                file: real_path.display().to_string(),
+                                line: start_line,
+                            });
                             clean_name
                         }
                     }
                 } else {
                     clean_name
                 };
+                let mut final_name = final_name;
 
                 // Get the line number for this symbol (needed for scope resolution)
                 let start_line = occurrence.range.get(0).copied().unwrap_or(0);
@@ -382,8 +1797,10 @@ fn convert_with_two_passes(
                 // For class members and other symbols, we use the extracted parent from SCIP URI
                 //
                 // **Validates: Requirements 7.1, 7.2, 7.4, 7.5**
-                let is_local_variable =
-                    kind == ScipSymbolKind::Variable && !occurrence.symbol.contains('#');
+                // Namespace members (`Ns/config.`) nest under the namespace instead
+                let is_local_variable = kind == ScipSymbolKind::Variable
+                    && !occurrence.symbol.contains('#')
+                    && namespace_parent(&occurrence.symbol).is_none();
 
                 let parent_anchor = if is_local_variable {
                     // Local variable (not a class member)
@@ -413,45 +1830,172 @@ fn convert_with_two_passes(
                     }
                 };
 
-                let (sig, doc, logic) =
-                    if kind != ScipSymbolKind::File && kind != ScipSymbolKind::Module {
-                        match enricher.enrich(&real_path, start_line as usize) {
-                            Some(res) => {
-                                // Only attach logic metadata to methods and functions
-                                // Variables and other symbol kinds should not have logic metadata
-                                // **Validates: Requirements 4.1, 4.2, 4.4**
-                                let l = if matches!(
-                                    kind,
-                                    ScipSymbolKind::Method | ScipSymbolKind::Function
-                                ) && !res.preconditions.is_empty()
-                                {
-                                    Some(LogicMetadata {
-                                        preconditions: res.preconditions,
-                                    })
-                                } else {
-                                    None
-                                };
-
-                                // Validate variable signatures to prevent inheriting method signatures
-                                let validated_sig = if kind == ScipSymbolKind::Variable {
-                                    validate_variable_signature(res.signature, &occurrence.symbol)
-                                } else {
-                                    res.signature
-                                };
-
-                                (validated_sig, res.documentation, l)
+                let mut impl_context = None;
+                let mut throws = Vec::new();
+                let mut deprecated = false;
+                let mut generator = false;
+                let mut route = None;
+                let mut delegate_call = None;
+                let mut env_access = Vec::new();
+                let mut source_text = None;
+                let mut complexity = None;
+                let mut annotations = Vec::new();
+                let mut pure = false;
+                let mut queries = Vec::new();
+                let (sig, doc, logic) = if kind != ScipSymbolKind::File
+                    && kind != ScipSymbolKind::Module
+                {
+                    match enricher.enrich(&real_path, start_line as usize) {
+                        Some(res) => {
+                            // Only attach logic metadata to methods and functions
+                            // Variables and other symbol kinds should not have logic metadata
+                            // **Validates: Requirements 4.1, 4.2, 4.4**
+                            let l = if matches!(
+                                kind,
+                                ScipSymbolKind::Method | ScipSymbolKind::Function
+                            ) && (!res.preconditions.is_empty()
+                                || !res.early_returns.is_empty()
+                                || !res.yields.is_empty()
+                                || !res.init_steps.is_empty())
+                            {
+                                Some(LogicMetadata {
+                                    preconditions: res.preconditions,
+                                    steps: [res.early_returns, res.yields, res.init_steps].concat(),
+                                    delegates_to: None,
+                                })
+                            } else {
+                                None
+                            };
+
+                            // Validate variable signatures to prevent inheriting method signatures
+                            let validated_sig = if kind == ScipSymbolKind::Variable {
+                                validate_variable_signature(
+                                    res.signature,
+                                    &occurrence.symbol,
+                                    &config.event_log,
+                                )
+                            } else {
+                                res.signature
+                            };
+
+                            if matches!(kind, ScipSymbolKind::Method | ScipSymbolKind::Function) {
+                                throws = res.throws;
+                                route = res.route;
+                                delegate_call = res.delegate_call;
+                                env_access = res.env_access;
+                                complexity = config.complexity.then_some(res.complexity);
+                                pure = res.pure;
+                                queries = res.queries;
                             }
-                            None => (None, None, None),
+                            deprecated = res.deprecated;
+                            generator = res.generator;
+                            annotations = res.annotations;
+                            impl_context = res.impl_context;
+                            source_text = Some(res.source);
+                            (validated_sig, res.documentation, l)
                         }
-                    } else {
-                        (None, None, None)
-                    };
+                        None if !source_available => {
+                            (hints.signature_for(&occurrence.symbol), None, None)
+                        }
+                        None => (None, None, None),
+                    }
+                } else {
+                    (None, None, None)
+                };
 
                 let my_anchor = registry
                     .get(&id)
                     .cloned()
                     .unwrap_or_else(|| generate_anchor("gen", id));
 
+                symbol_index.insert(&relative_path, &my_anchor, start_line + 1);
+                if let Some(fingerprint) = fingerprints.get(&occurrence.symbol) {
+                    symbol_index
+                        .fingerprints
+                        .entry(my_anchor.clone())
+                        .or_insert(*fingerprint);
+                }
+                if let Some(callee) = delegate_call {
+                    delegate_calls.insert(my_anchor.clone(), callee);
+                }
+
+                // Rust `impl [Trait for] Type` methods: qualify with the self type so
+                // `speak` on Dog and Cat stay distinct, and link the type to its trait
+                if let Some(ctx) = impl_context
+                    .filter(|_| matches!(kind, ScipSymbolKind::Method | ScipSymbolKind::Function))
+                {
+                    final_name = format!("{}::{}", ctx.self_type, short_symbol_name(&final_name));
+
+                    if let Some(trait_name) = &ctx.trait_name {
+                        // The trait of an implemented trait method, else the type of
+                        // that name the document mentions
+                        let trait_id = relationship_map
+                            .get(&occurrence.symbol)
+                            .into_iter()
+                            .flatten()
+                            .filter(|rel| rel.is_implementation)
+                            .find_map(|rel| extract_parent_id(&rel.symbol))
+                            .or_else(|| resolve_type(trait_name));
+                        let trait_anchor = trait_id.and_then(|trait_id| {
+                            registry.get(&trait_id).cloned().or_else(|| {
+                                matches!(config.lod, LevelOfDetail::High)
+                                    .then(|| generate_anchor("ext", trait_id))
+                            })
+                        });
+                        // Without a known self type there is nothing to link
+                        let self_anchor = resolve_type(&ctx.self_type)
+                            .and_then(|type_id| registry.get(&type_id))
+                            .cloned();
+
+                        if let (Some(from), Some(to)) = (self_anchor, trait_anchor) {
+                            edges_set.insert(ReferenceEdge {
+                                from,
+                                to,
+                                edge_type: EdgeType::Implements,
+                                call_text: None,
+                            });
+                        }
+                    }
+                }
+
+                // Relationship edges (implements / references) declared in SymbolInformation
+                if let Some(relationships) = relationship_map.get(&occurrence.symbol) {
+                    for rel in relationships {
+                        let Some(edge_type) = relationship_edge_type(rel) else {
+                            continue;
+                        };
+                        let target_u64 = resolve_symbol_id(&rel.symbol, &registry, &canonical_ids);
+                        if target_u64 == id {
+                            continue;
+                        }
+                        let target_exists = registry.contains_key(&target_u64);
+                        if !target_exists && !matches!(config.lod, LevelOfDetail::High) {
+                            continue;
+                        }
+                        let to_anchor = registry
+                            .get(&target_u64)
+                            .cloned()
+                            .unwrap_or_else(|| generate_anchor("ext", target_u64));
+                        edges_set.insert(ReferenceEdge {
+                            from: my_anchor.clone(),
+                            to: to_anchor,
+                            edge_type,
+                            call_text: None,
+                        });
+                    }
+                }
+
+                let owners = match ownership.as_mut() {
+                    Some(resolver) if kind == ScipSymbolKind::File => {
+                        resolver.owners(source_paths.strip(&relative_path))
+                    }
+                    _ => Vec::new(),
+                };
+
+                let source = source_text
+                    .filter(|_| inline_source.iter().any(|glob| glob.matches(&final_name)))
+                    .map(truncate_inline_source);
+
                 nodes.push(SymbolNode {
                     id: my_anchor,
                     name: final_name,
@@ -460,6 +2004,22 @@ fn convert_with_two_passes(
                     documentation: doc,
                     signature: sig,
                     logic,
+                    throws,
+                    deprecated,
+                    owners,
+                    generator,
+                    route,
+                    env_access,
+                    layer: layer.clone(),
+                    source,
+                    complexity,
+                    path: (config.file_paths && kind == ScipSymbolKind::File)
+                        .then(|| relative_path.clone()),
+                    annotations,
+                    pure,
+                    queries,
+                    scip_symbol: config.keep_scip_symbols.then(|| occurrence.symbol.clone()),
+                    ..Default::default()
                 });
 
                 // Push new scope for functions, methods, and classes
@@ -474,7 +2034,10 @@ fn convert_with_two_passes(
                 // **Validates: Requirements 7.3**
                 if matches!(
                     kind,
-                    ScipSymbolKind::Function | ScipSymbolKind::Method | ScipSymbolKind::Class
+                    ScipSymbolKind::Function
+                        | ScipSymbolKind::Method
+                        | ScipSymbolKind::Class
+                        | ScipSymbolKind::Enum
                 ) {
                     // Extract end line from SCIP range (index 2)
                     // SCIP sometimes provides incorrect end_line for definitions
@@ -493,19 +2056,34 @@ fn convert_with_two_passes(
                         raw_end_line
                     };
 
-                    local_scopes.push(Scope {
-                        id,
-                        start_line,
-                        end_line,
-                        kind,
-                    });
+                    push_scope(
+                        &mut local_scopes,
+                        Scope {
+                            id,
+                            start_line,
+                            end_line,
+                            kind,
+                        },
+                    );
                 }
             }
         }
 
         // B.2 REFERÊNCIAS
         // Process symbol references to build the call graph
+        let parsed_source = if config.with_call_text || !edge_rules.is_empty() {
+            enricher.parse_source(&real_path)
+        } else {
+            None
+        };
         for occurrence in &doc.occurrences {
+            if is_malformed_symbol(&occurrence.symbol) {
+                continue;
+            }
+            if is_malformed_range(&occurrence.range) {
+                skipped_ranges += 1;
+                continue;
+            }
             let is_def = (occurrence.symbol_roles & scip_proto::SymbolRole::Definition as i32) != 0;
             if !is_def {
                 // Get the line number where this reference occurs
@@ -517,7 +2095,7 @@ fn convert_with_two_passes(
                 //
                 // **Validates: Requirements 7.1, 7.2, 7.4**
                 let source_u64 = find_enclosing_scope(&local_scopes, ref_line).unwrap_or(file_id);
-                let target_u64 = xxh64(occurrence.symbol.as_bytes(), 0);
+                let target_u64 = resolve_symbol_id(&occurrence.symbol, &registry, &canonical_ids);
 
                 if source_u64 != target_u64 {
                     let from_anchor = registry
@@ -528,36 +2106,212 @@ fn convert_with_two_passes(
                     if !target_exists && !matches!(config.lod, LevelOfDetail::High) {
                         continue;
                     }
-                    let to_anchor = registry
-                        .get(&target_u64)
-                        .cloned()
-                        .unwrap_or_else(|| generate_anchor("ext", target_u64));
+                    let is_import =
+                        occurrence.symbol_roles & scip_proto::SymbolRole::Import as i32 != 0;
+                    // External imports name their package (`ext:@nestjs/common`):
+                    // which dependencies a file uses says more than a hash
+                    let to_anchor = registry.get(&target_u64).cloned().unwrap_or_else(|| {
+                        is_import
+                            .then(|| external_package(&occurrence.symbol))
+                            .flatten()
+                            .map(|package| format!("ext:{}", package))
+                            .unwrap_or_else(|| generate_anchor("ext", target_u64))
+                    });
+                    // Assignments to a variable or field (`this.count = 0`) and
+                    // reads of one (`return this.count`)
+                    let has_role =
+                        |role: scip_proto::SymbolRole| occurrence.symbol_roles & role as i32 != 0;
+                    let variable_access = !is_import
+                        && (has_role(scip_proto::SymbolRole::WriteAccess)
+                            || has_role(scip_proto::SymbolRole::ReadAccess))
+                        && match kind_map.get(&occurrence.symbol).copied().unwrap_or(0) {
+                            0 => infer_kind_from_uri(&occurrence.symbol),
+                            raw_kind => map_kind(raw_kind),
+                        } == ScipSymbolKind::Variable;
+                    let mut edge_type = if is_import {
+                        EdgeType::Imports
+                    } else if variable_access && has_role(scip_proto::SymbolRole::WriteAccess) {
+                        EdgeType::Writes
+                    } else if variable_access {
+                        EdgeType::Reads
+                    } else {
+                        EdgeType::Calls
+                    };
+                    if !is_import
+                        && !variable_access
+                        && let Some(parsed) = &parsed_source
+                    {
+                        let ref_col = occurrence.range.get(1).copied().unwrap_or(0) as usize;
+                        let call_text = |max_len| {
+                            parsed.call_text(ref_line as usize, ref_col, column_encoding, max_len)
+                        };
+                        // Edge rules see the whole call expression, not the truncated one
+                        if !edge_rules.is_empty()
+                            && let Some(text) = call_text(usize::MAX)
+                            && let Some((_, custom)) = edge_rules
+                                .iter()
+                                .find(|(pattern, _)| pattern.is_match(&text))
+                        {
+                            edge_type = custom.clone();
+                        }
+                        if config.with_call_text
+                            && let Some(text) = call_text(config.call_text_max_len)
+                        {
+                            call_texts
+                                .entry((from_anchor.clone(), to_anchor.clone()))
+                                .or_insert(text);
+                        }
+                    }
 
                     edges_set.insert(ReferenceEdge {
                         from: from_anchor,
                         to: to_anchor,
-                        edge_type: EdgeType::Calls,
+                        edge_type,
+                        call_text: None,
                     });
                 }
             }
         }
+        edges_set.flush();
     }
 
-    let mut edges: Vec<ReferenceEdge> = edges_set.into_iter().collect();
-    edges.sort();
+    if skipped_malformed > 0 {
+        config.event_log.emit(Event::warning(format!(
+            "Skipped {} occurrence(s) with empty or malformed symbol URIs",
+            skipped_malformed
+        )));
+    }
+    if skipped_ranges > 0 {
+        config.event_log.emit(Event::warning(format!(
+            "Skipped {} occurrence(s) with empty or malformed ranges",
+            skipped_ranges
+        )));
+    }
+    let unsupported = enricher.unsupported_extensions();
+    if !unsupported.is_empty() {
+        let extensions: Vec<String> = unsupported.iter().map(|ext| format!(".{}", ext)).collect();
+        config.event_log.emit(Event::warning(format!(
+            "No tree-sitter grammar for {} files: they were not enriched (see TreeSitterEnricher::register_language)",
+            extensions.join(", ")
+        )));
+    }
 
-    YcgGraph {
+    let edges = edges_set.into_sorted(|mut edge| {
+        if matches!(edge.edge_type, EdgeType::Calls | EdgeType::Custom(_)) {
+            edge.call_text = call_texts
+                .get(&(edge.from.clone(), edge.to.clone()))
+                .cloned();
+        }
+        edge
+    });
+
+    let mut graph = YcgGraph {
         metadata: ProjectMetadata {
             name: "ycg-v1.3".to_string(),
             version: "1.3.0".to_string(),
+            stripped_prefix: config.strip_prefix.clone(),
         },
         definitions: nodes,
         references: edges,
+    };
+
+    // Pass-through methods: logic -> delegates_to, callers rewired to the callee
+    if config.collapse_delegates {
+        let collapsed = delegates::collapse(&mut graph, &delegate_calls);
+        println!(">>> Métodos delegadores colapsados: {}", collapsed);
     }
+
+    // Self-describing anchors (`cls:User_4702`), renamed everywhere at once
+    if config.kind_prefixed_anchors {
+        anchor_prefix::prefix_anchors(&mut graph, &mut symbol_index);
+    }
+
+    (graph, symbol_index)
 }
 
 // --- HELPERS (Inalterados) ---
-fn generate_anchor(name: &str, id: u64) -> String {
+
+/// Derives the human-readable part of an anchor from a symbol's clean name.
+///
+/// Non-alphanumeric characters become `_`. Computed property keys
+/// (`Foo#[Symbol.iterator]`, `Foo#['dynamic-key']`) additionally have their
+/// brackets/quotes dropped and underscore runs collapsed, so they read as
+/// `Foo_Symbol_iterator` instead of `Foo__Symbol_iterator_`.
+fn anchor_base(clean_name: &str) -> String {
+    if clean_name.is_empty() || clean_name.ends_with(".ts") || clean_name.ends_with(".rs") {
+        return "def".to_string();
+    }
+
+    let sanitized = clean_name.replace(|c: char| !c.is_alphanumeric(), "_");
+    if !is_computed_key(clean_name) {
+        return sanitized;
+    }
+
+    let collapsed = sanitized
+        .split('_')
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("_");
+    if collapsed.is_empty() {
+        "computed".to_string()
+    } else {
+        collapsed
+    }
+}
+
+/// Checks whether the last descriptor of a clean name is a computed property
+/// key such as `[Symbol.iterator]` or `['dynamic-key']`.
+fn is_computed_key(clean_name: &str) -> bool {
+    let last = clean_name.rsplit('#').next().unwrap_or(clean_name);
+    last.len() >= 2 && last.starts_with('[') && last.ends_with(']')
+}
+
+/// Cuts inlined source above `INLINE_SOURCE_MAX_BYTES`, on a char boundary
+fn truncate_inline_source(mut text: String) -> String {
+    if text.len() <= INLINE_SOURCE_MAX_BYTES {
+        return text;
+    }
+    let mut end = INLINE_SOURCE_MAX_BYTES;
+    while !text.is_char_boundary(end) {
+        end -= 1;
+    }
+    let total = text.len();
+    text.truncate(end);
+    text.push_str(&format!("\n... (truncated, {} bytes)", total));
+    text
+}
+
+/// Generic type parameters: the SCIP `[name]` descriptor closing a method
+/// or type symbol (`identity().[T]`, `Box#[T]`). Rust impl blocks
+/// (`impl#[Dog][Speak]`) and computed keys (`[Symbol.iterator]`) are not.
+fn is_type_parameter_uri(uri: &str) -> bool {
+    let Some((owner, name)) = uri.strip_suffix(']').and_then(|rest| rest.rsplit_once('[')) else {
+        return false;
+    };
+    !name.is_empty()
+        && name.chars().all(|c| c.is_alphanumeric() || c == '_')
+        && (owner.ends_with("().") || owner.ends_with('#'))
+        && !owner.ends_with("impl#")
+}
+
+/// Trailing identifier of a clean name: `impl#[Dog][Speak]speak` -> `speak`,
+/// `UsersService#findOne` -> `findOne`.
+fn short_symbol_name(clean_name: &str) -> &str {
+    let start = clean_name
+        .char_indices()
+        .rev()
+        .take_while(|(_, c)| c.is_alphanumeric() || *c == '_')
+        .last()
+        .map(|(i, _)| i)
+        .unwrap_or(clean_name.len());
+    if start == clean_name.len() {
+        clean_name
+    } else {
+        &clean_name[start..]
+    }
+}
+
+pub(crate) fn generate_anchor(name: &str, id: u64) -> String {
     let suffix = format!("{:x}", id);
     let short_suffix = &suffix[0..4.min(suffix.len())];
     format!("{}_{}", name, short_suffix)
@@ -576,7 +2330,9 @@ fn generate_anchor(name: &str, id: u64) -> String {
 ///
 /// # Edge Cases
 /// - Line exactly on scope boundary (start_line or end_line): Included in scope
-/// - Multiple scopes with same size: First one encountered is selected
+/// - Multiple scopes with same size: First one in the stack is selected; the
+///   stack is kept sorted (see [`push_scope`]) so this does not depend on the
+///   order definitions appear in the index
 /// - No matching scope: Returns None (should fall back to file scope)
 ///
 /// # Example
@@ -632,13 +2388,102 @@ fn find_enclosing_scope(scopes: &[Scope], line: i32) -> Option<u64> {
     best_scope
 }
 
-/// Checks if a variable name is a generic SCIP-generated name.
+/// Checks if a SCIP symbol URI is unusable (empty or whitespace-only).
 ///
-/// SCIP generates generic names for local variables in the format: `[a-z]+[0-9]+`
-/// Examples: status0, timestamp1, user2, result3
+/// Such URIs still hash to a valid-looking id, so letting them through
+/// would produce phantom nodes and edges with garbage names.
+fn is_malformed_symbol(symbol: &str) -> bool {
+    symbol.trim().is_empty()
+}
+
+/// Id of the definition a referenced symbol points to
 ///
-/// **Validates: Requirement 6.2**
-fn is_generic_name(name: &str) -> bool {
+/// The same symbol can be spelled differently by the definition and by its
+/// references (an overload disambiguator or a trailing space), which
+/// would split it into a node and a phantom `ext_` target. When the exact URI
+/// is not defined, the reference falls back to the only definition sharing
+/// its [`canonical_symbol`]; otherwise it keeps its own hash.
+fn resolve_symbol_id(
+    symbol: &str,
+    registry: &HashMap<u64, String>,
+    canonical_ids: &HashMap<String, Option<u64>>,
+) -> u64 {
+    let id = xxh64(symbol.as_bytes(), 0);
+    if registry.contains_key(&id) {
+        return id;
+    }
+    canonical_ids
+        .get(&canonical_symbol(symbol))
+        .copied()
+        .flatten()
+        .unwrap_or(id)
+}
+
+/// A SCIP symbol without the parts that vary between occurrences:
+/// `` src/`users.ts`/UserService#find(+1). `` -> `` src/`users.ts`/UserService#find(). ``
+///
+/// Surrounding whitespace and method disambiguators are dropped. Descriptor
+/// suffixes (`#`, `.`, `:`, `!`) and parameter names are kept, so a type
+/// `Foo#` and a term `Foo.` stay distinct; backtick-escaped names are kept.
+fn canonical_symbol(symbol: &str) -> String {
+    let mut canonical = String::with_capacity(symbol.len());
+    let mut in_backticks = false;
+    let mut parens: Option<String> = None;
+    let mut chars = symbol.trim().chars().peekable();
+    while let Some(c) = chars.next() {
+        match (c, parens.as_mut()) {
+            ('(', None) if !in_backticks => parens = Some(String::new()),
+            (')', Some(inner)) => {
+                // `name(disambiguator).` is a method; `(name)` a parameter
+                canonical.push('(');
+                if chars.peek() != Some(&'.') {
+                    canonical.push_str(inner);
+                }
+                canonical.push(')');
+                parens = None;
+            }
+            (_, Some(inner)) => inner.push(c),
+            ('`', None) => {
+                in_backticks = !in_backticks;
+                canonical.push(c);
+            }
+            (_, None) => canonical.push(c),
+        }
+    }
+    canonical
+}
+
+/// Package name of a SCIP symbol: `@nestjs/common` for
+/// `` scip-typescript npm @nestjs/common 10.3.0 decorators/`index.d.ts`/Injectable(). ``
+///
+/// `None` for local symbols and for the `.` placeholder of unknown packages.
+fn external_package(symbol: &str) -> Option<&str> {
+    let mut parts = symbol.split(' ');
+    let scheme = parts.next()?;
+    let _manager = parts.next()?;
+    let name = parts.next()?;
+    (scheme != "local" && !name.is_empty() && name != ".").then_some(name)
+}
+
+/// Checks if a SCIP occurrence range is unusable.
+///
+/// Valid ranges are `[startLine, startChar, endChar]` or
+/// `[startLine, startChar, endLine, endChar]`. Partial indexes sometimes
+/// leave them empty; defaulting the start line to 0 would attribute the
+/// symbol to the top of the file and corrupt scope attribution.
+fn is_malformed_range(range: &[i32]) -> bool {
+    !matches!(range.len(), 3 | 4)
+        || range.iter().any(|&v| v < 0)
+        || range.len() == 4 && range[2] < range[0]
+}
+
+/// Checks if a variable name is a generic SCIP-generated name.
+///
+/// SCIP generates generic names for local variables in the format: `[a-z]+[0-9]+`
+/// Examples: status0, timestamp1, user2, result3
+///
+/// **Validates: Requirement 6.2**
+fn is_generic_name(name: &str) -> bool {
     // Pattern: lowercase letters followed by digits
     // Examples: status0, timestamp1, user2
     if name.is_empty() {
@@ -704,69 +2549,54 @@ fn is_generic_name(name: &str) -> bool {
 /// // "async function getData(): Promise<Data>"
 /// // "catch(exception: unknown, host: ArgumentsHost)"
 /// ```
-fn validate_variable_signature(sig: Option<String>, symbol_uri: &str) -> Option<String> {
-    if let Some(ref s) = sig {
+fn validate_variable_signature(
+    sig: Option<String>,
+    symbol_uri: &str,
+    log: &EventLog,
+) -> Option<String> {
+    let s = sig?;
+    let reason = if s.contains("function") {
         // Pattern 1: Contains function keyword
-        if s.contains("function") {
-            eprintln!(
-                "⚠️  Rejecting method signature for variable {}: contains 'function'",
-                symbol_uri
-            );
-            return None;
-        }
-
+        Some("contains 'function'")
+    } else if s.contains("=>") {
         // Pattern 2: Contains arrow function syntax
-        if s.contains("=>") {
-            eprintln!(
-                "⚠️  Rejecting method signature for variable {}: contains '=>'",
-                symbol_uri
-            );
-            return None;
-        }
-
+        Some("contains '=>'")
+    } else if s.trim().starts_with("async ") {
         // Pattern 3: Starts with async (method pattern)
-        if s.trim().starts_with("async ") {
-            eprintln!(
-                "⚠️  Rejecting method signature for variable {}: starts with 'async'",
-                symbol_uri
-            );
-            return None;
-        }
-
+        Some("starts with 'async'")
+    } else if s.contains('@') && s.contains('(') {
         // Pattern 4: Contains parameter list with decorators (e.g., @Param('id', ParseIntPipe))
-        if s.contains('@') && s.contains('(') {
-            eprintln!(
-                "⚠️  Rejecting method signature for variable {}: contains decorator pattern",
-                symbol_uri
-            );
-            return None;
-        }
-
+        Some("contains decorator pattern")
+    } else if s
+        .rfind(')')
+        .is_some_and(|paren_pos| s[paren_pos + 1..].trim().starts_with(':'))
+    {
         // Pattern 5: Contains return type annotation with colon after closing paren
         // Example: "findOne(id: number): Promise<UserDto>"
-        if let Some(paren_pos) = s.rfind(')') {
-            let after_paren = &s[paren_pos + 1..].trim();
-            if after_paren.starts_with(':') {
-                eprintln!(
-                    "⚠️  Rejecting method signature for variable {}: contains return type annotation",
-                    symbol_uri
-                );
-                return None;
-            }
-        }
-
+        Some("contains return type annotation")
+    } else if s.contains("()") {
         // Pattern 6: Contains empty parentheses (method call or declaration)
-        if s.contains("()") {
-            eprintln!(
-                "⚠️  Rejecting method signature for variable {}: contains '()'",
-                symbol_uri
-            );
-            return None;
+        Some("contains '()'")
+    } else {
+        None
+    };
+
+    match reason {
+        Some(reason) => {
+            log.emit(Event::SignatureRejected {
+                symbol: symbol_uri.to_string(),
+                signature: s,
+                reason,
+            });
+            None
         }
+        None => Some(s),
     }
-    sig
 }
 fn extract_parent_id(symbol: &str) -> Option<u64> {
+    if let Some(namespace) = namespace_parent(symbol) {
+        return Some(xxhash_rust::xxh64::xxh64(namespace.as_bytes(), 0));
+    }
     let mut chars: Vec<char> = symbol.chars().collect();
     if let Some(&last) = chars.last() {
         if last == '.' {
@@ -777,6 +2607,15 @@ fn extract_parent_id(symbol: &str) -> Option<u64> {
     while idx > 0 {
         idx -= 1;
         let c = chars[idx];
+        // Escaped computed key (`[Symbol.iterator]`): its dots are not separators
+        if c == '`'
+            && idx > 0
+            && chars[idx - 1] == ']'
+            && let Some(open) = (0..idx).rev().find(|&i| chars[i] == '`')
+        {
+            idx = open;
+            continue;
+        }
         if c == '#' || c == '.' || c == '/' || c == '`' {
             let end_idx = if c == '#' { idx + 1 } else { idx };
             let parent_str: String = chars[0..end_idx].iter().collect();
@@ -796,11 +2635,47 @@ fn map_kind(k: i32) -> ScipSymbolKind {
         Kind::Function => ScipSymbolKind::Function,
         Kind::Variable => ScipSymbolKind::Variable,
         Kind::Interface => ScipSymbolKind::Interface,
-        Kind::Module => ScipSymbolKind::Module,
+        Kind::Enum => ScipSymbolKind::Enum,
+        Kind::EnumMember => ScipSymbolKind::Variable,
+        Kind::Module | Kind::Namespace => ScipSymbolKind::Module,
         _ => ScipSymbolKind::Variable,
     }
 }
+/// Unit of a document's SCIP columns. Indexes that leave it unspecified use
+/// their language's native unit: UTF-16 for TS/JS (scip-typescript), bytes otherwise.
+fn column_encoding(position_encoding: i32, relative_path: &str) -> enricher::ColumnEncoding {
+    use enricher::ColumnEncoding;
+    use scip_proto::PositionEncoding;
+
+    match PositionEncoding::try_from(position_encoding)
+        .unwrap_or(PositionEncoding::UnspecifiedPositionEncoding)
+    {
+        PositionEncoding::Utf8CodeUnitOffsetFromLineStart => ColumnEncoding::Utf8,
+        PositionEncoding::Utf16CodeUnitOffsetFromLineStart => ColumnEncoding::Utf16,
+        PositionEncoding::Utf32CodeUnitOffsetFromLineStart => ColumnEncoding::Utf32,
+        PositionEncoding::UnspecifiedPositionEncoding => {
+            let is_js_family = Path::new(relative_path)
+                .extension()
+                .and_then(|ext| ext.to_str())
+                .is_some_and(|ext| {
+                    matches!(
+                        ext,
+                        "ts" | "tsx" | "js" | "jsx" | "mts" | "cts" | "mjs" | "cjs"
+                    )
+                });
+            if is_js_family {
+                ColumnEncoding::Utf16
+            } else {
+                ColumnEncoding::Utf8
+            }
+        }
+    }
+}
+
 fn infer_kind_from_uri(uri: &str) -> ScipSymbolKind {
+    if is_namespace_uri(uri) {
+        return ScipSymbolKind::Module;
+    }
     if uri.ends_with("().") || uri.contains("#<constructor>") {
         return ScipSymbolKind::Method;
     }
@@ -815,14 +2690,47 @@ fn infer_kind_from_uri(uri: &str) -> ScipSymbolKind {
     }
     ScipSymbolKind::Variable
 }
+/// TypeScript `namespace Foo` / `declare module 'x'` symbols: a `/`
+/// descriptor following the file's, as in `` src/`ns.ts`/Outer/Inner/ ``.
+/// The file symbol itself (`` src/`ns.ts`/ ``) is not one.
+fn is_namespace_uri(uri: &str) -> bool {
+    let Some(body) = uri.strip_suffix('/') else {
+        return false;
+    };
+    let last = last_path_segment(body);
+    let head = &body[..body.len() - last.len()];
+    // An escaped file name (`users.ts`) may follow escaped directories
+    let name = last.trim_matches('`');
+    let is_file_name = name.contains('.') && !name.starts_with(['\'', '"']);
+    !last.is_empty() && head.contains("`/") && !is_file_name
+}
+
+/// The namespace a symbol is declared directly in:
+/// `` src/`ns.ts`/Outer/Inner/run(). `` -> `` src/`ns.ts`/Outer/Inner/ ``.
+/// `None` for class members (their parent is the class) and top-level symbols.
+fn namespace_parent(symbol: &str) -> Option<&str> {
+    let body = symbol
+        .strip_suffix('.')
+        .or_else(|| symbol.strip_suffix('#'))
+        .or_else(|| symbol.strip_suffix('/'))
+        .unwrap_or(symbol);
+    let last = last_path_segment(body);
+    let unescaped: String = last.split('`').step_by(2).collect();
+    if last.is_empty() || unescaped.contains(['#', '.']) {
+        return None;
+    }
+    let parent = &body[..body.len() - last.len()];
+    is_namespace_uri(parent).then_some(parent)
+}
+
 fn extract_name_from_uri(uri: &str) -> String {
     let trimmed = uri
         .trim_end_matches('.')
         .trim_end_matches("()")
         .trim_end_matches('#')
         .trim_end_matches('/');
-    let last_part = trimmed.split('/').last().unwrap_or(trimmed);
-    let clean = last_part.replace('`', "");
+    let last_part = last_path_segment(trimmed);
+    let clean = unescape_backticks(last_part);
     if clean == "<constructor>" {
         return "constructor".to_string();
     }
@@ -832,6 +2740,92 @@ fn extract_name_from_uri(uri: &str) -> String {
     clean
 }
 
+/// Drops the backticks escaping a SCIP descriptor; a doubled one (` `` `)
+/// stands for a literal backtick
+fn unescape_backticks(descriptor: &str) -> String {
+    let mut clean = String::with_capacity(descriptor.len());
+    let mut chars = descriptor.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c != '`' {
+            clean.push(c);
+        } else if chars.next_if_eq(&'`').is_some() {
+            clean.push('`');
+        }
+    }
+    clean
+}
+
+/// Removes a namespace prefix and the separator after it:
+/// `com.acme.platform.UserService#find` -> `UserService#find` for `com.acme.platform`.
+///
+/// The prefix must end at a separator (`.`, `/`, `#`, `:`), so
+/// `com.acme.platformTools` is left alone. A name equal to the prefix is kept.
+fn strip_name_prefix(name: String, prefix: Option<&str>) -> String {
+    const SEPARATORS: [char; 4] = ['.', '/', '#', ':'];
+
+    let Some(prefix) = prefix.filter(|p| !p.is_empty()) else {
+        return name;
+    };
+    let Some(rest) = name.strip_prefix(prefix) else {
+        return name;
+    };
+    let at_boundary = prefix.ends_with(SEPARATORS) || rest.starts_with(SEPARATORS);
+    let rest = rest.trim_start_matches(SEPARATORS);
+    if !at_boundary || rest.is_empty() {
+        return name;
+    }
+    rest.to_string()
+}
+
+/// Whether a SCIP symbol is the `export default` of its file: a `default#`,
+/// `default().` or `default.` descriptor right under the file descriptor
+fn is_default_export(symbol: &str) -> bool {
+    let symbol = symbol.trim_end();
+    ["default#", "default().", "default."]
+        .iter()
+        .any(|descriptor| {
+            symbol
+                .strip_suffix(descriptor)
+                .is_some_and(|file| file.ends_with("`/"))
+        })
+}
+
+/// `src/users/users.controller.ts` -> `defaultUsersController`
+fn synthesize_default_export_name(relative_path: &str) -> String {
+    let file_name = relative_path.rsplit('/').next().unwrap_or(relative_path);
+    let stem = file_name
+        .rsplit_once('.')
+        .map(|(stem, _ext)| stem)
+        .unwrap_or(file_name);
+    let pascal: String = stem
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .map(|part| {
+            let mut chars = part.chars();
+            match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars).collect::<String>(),
+                None => String::new(),
+            }
+        })
+        .collect();
+    format!("default{}", pascal)
+}
+
+/// Returns the text after the last `/` that is not inside a backtick-escaped
+/// descriptor, so computed keys like `` `['a/b']` `` are not split apart.
+fn last_path_segment(uri: &str) -> &str {
+    let mut in_backticks = false;
+    let mut start = 0;
+    for (i, c) in uri.char_indices() {
+        match c {
+            '`' => in_backticks = !in_backticks,
+            '/' if !in_backticks => start = i + 1,
+            _ => {}
+        }
+    }
+    &uri[start..]
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -908,4 +2902,3396 @@ mod tests {
         // Edge case: only digits (should fail)
         assert!(!is_generic_name("123456"));
     }
+
+    fn test_config(lod: LevelOfDetail) -> YcgConfig {
+        YcgConfig {
+            lod,
+            project_root: PathBuf::from("/nonexistent"),
+            compact: false,
+            output_format: model::OutputFormat::Yaml,
+            ignore_framework_noise: false,
+            file_filter: model::FileFilterConfig::default(),
+            adhoc_granularity: model::AdHocGranularity::Default,
+            ..Default::default()
+        }
+    }
+
+    fn definition(symbol: &str, line: i32) -> scip_proto::Occurrence {
+        scip_proto::Occurrence {
+            range: vec![line, 0, line, 10],
+            symbol: symbol.to_string(),
+            symbol_roles: scip_proto::SymbolRole::Definition as i32,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_relationship_is_implementation_produces_implements_edge() {
+        let animal = "scip-typescript npm pkg 1.0.0 src/`zoo.ts`/Animal#";
+        let dog = "scip-typescript npm pkg 1.0.0 src/`zoo.ts`/Dog#";
+
+        let index = scip_proto::Index {
+            documents: vec![scip_proto::Document {
+                relative_path: "src/zoo.ts".to_string(),
+                occurrences: vec![definition(animal, 0), definition(dog, 10)],
+                symbols: vec![
+                    scip_proto::SymbolInformation {
+                        symbol: animal.to_string(),
+                        kind: scip_proto::symbol_information::Kind::Interface as i32,
+                        ..Default::default()
+                    },
+                    scip_proto::SymbolInformation {
+                        symbol: dog.to_string(),
+                        kind: scip_proto::symbol_information::Kind::Class as i32,
+                        relationships: vec![scip_proto::Relationship {
+                            symbol: animal.to_string(),
+                            is_implementation: true,
+                            ..Default::default()
+                        }],
+                        ..Default::default()
+                    },
+                ],
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+
+        let (graph, _) = convert_scip_to_ycg(index, &test_config(LevelOfDetail::High));
+
+        let dog_id = &graph
+            .definitions
+            .iter()
+            .find(|n| n.name == "Dog")
+            .unwrap()
+            .id;
+        let animal_id = &graph
+            .definitions
+            .iter()
+            .find(|n| n.name == "Animal")
+            .unwrap()
+            .id;
+
+        assert!(graph.references.contains(&ReferenceEdge {
+            from: dog_id.clone(),
+            to: animal_id.clone(),
+            edge_type: EdgeType::Implements,
+            call_text: None,
+        }));
+    }
+
+    #[test]
+    fn test_merged_class_counts_edges_redirected_from_its_interface() {
+        let animal = "scip-typescript npm pkg 1.0.0 src/`zoo.ts`/Animal#";
+        let dog = "scip-typescript npm pkg 1.0.0 src/`zoo.ts`/Dog#";
+        let feed = "scip-typescript npm pkg 1.0.0 src/`zoo.ts`/feed().";
+        let info = |symbol: &str, kind: scip_proto::symbol_information::Kind| {
+            scip_proto::SymbolInformation {
+                symbol: symbol.to_string(),
+                kind: kind as i32,
+                ..Default::default()
+            }
+        };
+        let index = scip_proto::Index {
+            documents: vec![scip_proto::Document {
+                relative_path: "src/zoo.ts".to_string(),
+                occurrences: vec![
+                    definition(animal, 0),
+                    definition(dog, 10),
+                    scip_proto::Occurrence {
+                        range: vec![20, 0, 22, 1],
+                        ..definition(feed, 20)
+                    },
+                    scip_proto::Occurrence {
+                        range: vec![21, 10, 16],
+                        symbol: animal.to_string(),
+                        ..Default::default()
+                    },
+                ],
+                symbols: vec![
+                    info(animal, scip_proto::symbol_information::Kind::Interface),
+                    scip_proto::SymbolInformation {
+                        relationships: vec![scip_proto::Relationship {
+                            symbol: animal.to_string(),
+                            is_implementation: true,
+                            ..Default::default()
+                        }],
+                        ..info(dog, scip_proto::symbol_information::Kind::Class)
+                    },
+                    info(feed, scip_proto::symbol_information::Kind::Function),
+                ],
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+
+        let config = YcgConfig {
+            merge_single_impl: true,
+            ..test_config(LevelOfDetail::High)
+        };
+        let filters = compiled_filters::CompiledFilters::new(&config).unwrap();
+        let (graph, _) = build_graph(index, &config, &filters).unwrap();
+
+        assert!(graph.definitions.iter().all(|n| n.name != "Animal"));
+        let dog = graph.definitions.iter().find(|n| n.name == "Dog").unwrap();
+        let inbound = graph.references.iter().filter(|e| e.to == dog.id).count();
+        assert_eq!(inbound, 1, "{:?}", graph.references);
+        assert_eq!(dog.ref_count, 1);
+    }
+
+    #[test]
+    fn test_relationship_edge_type_mapping() {
+        let implementation = scip_proto::Relationship {
+            is_implementation: true,
+            is_reference: true,
+            ..Default::default()
+        };
+        let reference = scip_proto::Relationship {
+            is_reference: true,
+            ..Default::default()
+        };
+        let type_definition = scip_proto::Relationship {
+            is_type_definition: true,
+            ..Default::default()
+        };
+
+        assert_eq!(
+            relationship_edge_type(&implementation),
+            Some(EdgeType::Implements)
+        );
+        assert_eq!(
+            relationship_edge_type(&reference),
+            Some(EdgeType::References)
+        );
+        assert_eq!(
+            relationship_edge_type(&type_definition),
+            Some(EdgeType::References)
+        );
+        assert_eq!(
+            relationship_edge_type(&scip_proto::Relationship::default()),
+            None
+        );
+    }
+
+    #[test]
+    fn test_empty_symbol_uri_produces_no_node() {
+        let user = "scip-typescript npm pkg 1.0.0 src/`user.ts`/User#";
+
+        let index = scip_proto::Index {
+            documents: vec![scip_proto::Document {
+                relative_path: "src/user.ts".to_string(),
+                occurrences: vec![
+                    definition(user, 0),
+                    definition("", 2),
+                    definition("   ", 4),
+                    scip_proto::Occurrence {
+                        range: vec![5, 0, 5, 4],
+                        symbol: " ".to_string(),
+                        ..Default::default()
+                    },
+                ],
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+
+        let (graph, _) = convert_scip_to_ycg(index, &test_config(LevelOfDetail::High));
+
+        assert_eq!(graph.definitions.len(), 1);
+        assert_eq!(graph.definitions[0].name, "User");
+        assert!(graph.references.is_empty());
+    }
+
+    #[test]
+    fn test_is_malformed_symbol() {
+        assert!(is_malformed_symbol(""));
+        assert!(is_malformed_symbol(" \t\n"));
+        assert!(!is_malformed_symbol("local 0"));
+    }
+
+    #[test]
+    fn test_is_malformed_range() {
+        assert!(is_malformed_range(&[]));
+        assert!(is_malformed_range(&[3]));
+        assert!(is_malformed_range(&[3, 0, -1]));
+        assert!(is_malformed_range(&[5, 0, 2, 1]));
+        assert!(!is_malformed_range(&[3, 0, 10]));
+        assert!(!is_malformed_range(&[3, 0, 8, 1]));
+    }
+
+    #[test]
+    fn test_occurrences_with_empty_range_are_skipped() {
+        let service = "scip-typescript npm pkg 1.0.0 src/`users.ts`/UserService#";
+        let find = "scip-typescript npm pkg 1.0.0 src/`users.ts`/UserService#find().";
+        let ghost = "scip-typescript npm pkg 1.0.0 src/`users.ts`/Ghost#";
+        let index = scip_proto::Index {
+            documents: vec![scip_proto::Document {
+                relative_path: "src/users.ts".to_string(),
+                occurrences: vec![
+                    scip_proto::Occurrence {
+                        range: vec![0, 0, 10, 1],
+                        ..definition(service, 0)
+                    },
+                    scip_proto::Occurrence {
+                        range: vec![2, 2, 4, 3],
+                        ..definition(find, 2)
+                    },
+                    // Definition without a range: no phantom node at line 0
+                    scip_proto::Occurrence {
+                        range: Vec::new(),
+                        ..definition(ghost, 0)
+                    },
+                    // Reference without a range: not attributed to the class at line 0
+                    scip_proto::Occurrence {
+                        symbol: find.to_string(),
+                        ..Default::default()
+                    },
+                ],
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+
+        let (graph, _) = convert_scip_to_ycg(index, &test_config(LevelOfDetail::High));
+        let names: Vec<&str> = graph.definitions.iter().map(|n| n.name.as_str()).collect();
+        assert!(!names.contains(&"Ghost"), "{:?}", names);
+        assert!(names.contains(&"UserService#find"), "{:?}", names);
+        assert!(graph.references.is_empty(), "{:?}", graph.references);
+    }
+
+    #[test]
+    fn test_symbol_index_lists_each_definition_under_one_file() {
+        let user = "scip-typescript npm pkg 1.0.0 src/`user.ts`/User#";
+        let find = "scip-typescript npm pkg 1.0.0 src/`user.ts`/User#find().";
+        let service = "scip-typescript npm pkg 1.0.0 src/`service.ts`/UserService#";
+
+        let index = scip_proto::Index {
+            documents: vec![
+                scip_proto::Document {
+                    relative_path: "src/user.ts".to_string(),
+                    occurrences: vec![definition(user, 0), definition(find, 3)],
+                    ..Default::default()
+                },
+                scip_proto::Document {
+                    relative_path: "src/service.ts".to_string(),
+                    occurrences: vec![definition(service, 7), definition(user, 9)],
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        };
+
+        let (graph, symbol_index) = convert_scip_to_ycg(index, &test_config(LevelOfDetail::High));
+
+        for node in &graph.definitions {
+            let owners = symbol_index
+                .files
+                .values()
+                .filter(|anchors| anchors.contains(&node.id))
+                .count();
+            assert_eq!(
+                owners, 1,
+                "{} should appear under exactly one file",
+                node.id
+            );
+            assert!(symbol_index.lines.contains_key(&node.id));
+        }
+
+        let user_anchor = &graph
+            .definitions
+            .iter()
+            .find(|n| n.name == "User")
+            .unwrap()
+            .id;
+        assert!(symbol_index.files["src/user.ts"].contains(user_anchor));
+        assert_eq!(symbol_index.lines[user_anchor], 1);
+    }
+
+    #[test]
+    fn test_computed_symbol_iterator_name_and_anchor() {
+        let uri = "scip-typescript npm pkg 1.0.0 src/`list.ts`/List#`[Symbol.iterator]`().";
+
+        let name = extract_name_from_uri(uri);
+        assert_eq!(name, "List#[Symbol.iterator]");
+        assert_eq!(anchor_base(&name), "List_Symbol_iterator");
+
+        // Parent is the class, not a fragment of the computed key
+        let class_uri = "scip-typescript npm pkg 1.0.0 src/`list.ts`/List#";
+        assert_eq!(extract_parent_id(uri), Some(xxh64(class_uri.as_bytes(), 0)));
+    }
+
+    #[test]
+    fn test_computed_string_literal_key() {
+        let uri = "scip-typescript npm pkg 1.0.0 src/`routes.ts`/Routes#`['users/list']`.";
+
+        let name = extract_name_from_uri(uri);
+        assert_eq!(name, "Routes#['users/list']");
+        assert_eq!(anchor_base(&name), "Routes_users_list");
+    }
+
+    #[test]
+    fn test_escaped_backtick_and_nul_survive_name_extraction() {
+        let uri = "scip-typescript npm pkg 1.0.0 src/`tags.ts`/Tags#`['a``b\u{0}c']`.";
+        assert_eq!(extract_name_from_uri(uri), "Tags#['a`b\u{0}c']");
+    }
+
+    #[test]
+    fn test_anchor_base_regular_names_unchanged() {
+        assert_eq!(anchor_base("UsersService#findOne"), "UsersService_findOne");
+        assert_eq!(
+            anchor_base("AuthService#<constructor>"),
+            "AuthService__constructor_"
+        );
+        assert_eq!(anchor_base("user.ts"), "def");
+    }
+
+    fn metadata(version: i32) -> scip_proto::Metadata {
+        scip_proto::Metadata {
+            version,
+            tool_info: Some(scip_proto::ToolInfo {
+                name: "scip-typescript".to_string(),
+                version: "0.3.14".to_string(),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_scip_metadata_supported_version_reports_tool() {
+        let report = inspect_scip_metadata(Some(&metadata(0)), true).unwrap();
+
+        assert_eq!(report.tool, "scip-typescript 0.3.14");
+        assert_eq!(report.protocol_version, 0);
+        assert!(report.warnings.is_empty());
+    }
+
+    #[test]
+    fn test_scip_metadata_unknown_version_warns() {
+        let report = inspect_scip_metadata(Some(&metadata(7)), false).unwrap();
+
+        assert_eq!(report.protocol_version, 7);
+        assert_eq!(report.warnings.len(), 1);
+        assert!(report.warnings[0].contains("protocol version 7"));
+        assert!(report.warnings[0].contains("scip-typescript 0.3.14"));
+    }
+
+    #[test]
+    fn test_scip_metadata_unknown_version_errors_when_strict() {
+        let result = inspect_scip_metadata(Some(&metadata(7)), true);
+
+        assert!(result.is_err());
+        assert!(
+            result
+                .err()
+                .unwrap()
+                .to_string()
+                .contains("Unsupported SCIP protocol version 7")
+        );
+    }
+
+    #[test]
+    fn test_scip_metadata_missing_block_warns() {
+        let report = inspect_scip_metadata(None, true).unwrap();
+
+        assert_eq!(report.tool, "unknown");
+        assert_eq!(report.warnings.len(), 1);
+    }
+
+    #[test]
+    fn test_rust_trait_impls_are_qualified_by_self_type() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let source = "\
+pub trait Speak {
+    fn speak(&self) -> String;
+}
+
+pub struct Dog;
+
+pub struct Cat;
+
+impl Speak for Dog {
+    fn speak(&self) -> String {
+        \"woof\".to_string()
+    }
+}
+
+impl Speak for Cat {
+    fn speak(&self) -> String {
+        \"meow\".to_string()
+    }
+}
+";
+        fs::create_dir_all(temp_dir.path().join("src")).unwrap();
+        fs::write(temp_dir.path().join("src/zoo.rs"), source).unwrap();
+
+        let speak = "rust-analyzer cargo zoo 0.1.0 zoo/Speak#";
+        let dog = "rust-analyzer cargo zoo 0.1.0 zoo/Dog#";
+        let cat = "rust-analyzer cargo zoo 0.1.0 zoo/Cat#";
+        let dog_speak = "rust-analyzer cargo zoo 0.1.0 zoo/impl#[Dog][Speak]speak().";
+        let cat_speak = "rust-analyzer cargo zoo 0.1.0 zoo/impl#[Cat][Speak]speak().";
+
+        let info = |symbol: &str, kind: scip_proto::symbol_information::Kind| {
+            scip_proto::SymbolInformation {
+                symbol: symbol.to_string(),
+                kind: kind as i32,
+                ..Default::default()
+            }
+        };
+        use scip_proto::symbol_information::Kind;
+
+        let index = scip_proto::Index {
+            documents: vec![scip_proto::Document {
+                relative_path: "src/zoo.rs".to_string(),
+                occurrences: vec![
+                    definition(speak, 0),
+                    definition(dog, 4),
+                    definition(cat, 6),
+                    definition(dog_speak, 9),
+                    definition(cat_speak, 15),
+                ],
+                symbols: vec![
+                    info(speak, Kind::Interface),
+                    info(dog, Kind::Class),
+                    info(cat, Kind::Class),
+                    info(dog_speak, Kind::Method),
+                    info(cat_speak, Kind::Method),
+                ],
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+
+        let mut config = test_config(LevelOfDetail::High);
+        config.project_root = temp_dir.path().to_path_buf();
+        let (graph, _) = convert_scip_to_ycg(index, &config);
+
+        let anchor_of = |name: &str| {
+            graph
+                .definitions
+                .iter()
+                .find(|n| n.name == name)
+                .unwrap_or_else(|| panic!("missing node {}", name))
+                .id
+                .clone()
+        };
+
+        let dog_method = anchor_of("Dog::speak");
+        let cat_method = anchor_of("Cat::speak");
+        assert_ne!(dog_method, cat_method);
+
+        let trait_anchor = anchor_of("Speak");
+        for type_name in ["Dog", "Cat"] {
+            assert!(graph.references.contains(&ReferenceEdge {
+                from: anchor_of(type_name),
+                to: trait_anchor.clone(),
+                edge_type: EdgeType::Implements,
+                call_text: None,
+            }));
+        }
+    }
+
+    #[test]
+    fn test_rust_impl_links_the_type_the_file_names() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        fs::create_dir_all(temp_dir.path().join("src")).unwrap();
+        let impl_source = |type_name: &str| {
+            format!(
+                "pub struct {0};\n\nimpl Display for {0} {{\n    fn fmt(&self) -> String {{\n        String::new()\n    }}\n}}\n",
+                type_name
+            )
+        };
+        fs::write(temp_dir.path().join("src/a.rs"), "pub struct User;\n").unwrap();
+        fs::write(temp_dir.path().join("src/b.rs"), impl_source("User")).unwrap();
+        // `Ghost` is declared in the file but unknown to the index
+        fs::write(temp_dir.path().join("src/c.rs"), impl_source("Ghost")).unwrap();
+
+        let a_user = "rust-analyzer cargo app 0.1.0 a/User#";
+        let b_user = "rust-analyzer cargo app 0.1.0 b/User#";
+        let display = "rust-analyzer cargo std 1.0.0 fmt/Display#";
+        let b_fmt = "rust-analyzer cargo app 0.1.0 b/impl#[User][Display]fmt().";
+        let c_fmt = "rust-analyzer cargo app 0.1.0 c/impl#[Ghost][Display]fmt().";
+        let reference = |symbol: &str, line: i32| scip_proto::Occurrence {
+            range: vec![line, 5, 12],
+            symbol: symbol.to_string(),
+            ..Default::default()
+        };
+        let index = scip_proto::Index {
+            documents: vec![
+                scip_proto::Document {
+                    relative_path: "src/a.rs".to_string(),
+                    occurrences: vec![definition(a_user, 0)],
+                    ..Default::default()
+                },
+                scip_proto::Document {
+                    relative_path: "src/b.rs".to_string(),
+                    occurrences: vec![
+                        definition(b_user, 0),
+                        reference(display, 2),
+                        reference(b_user, 2),
+                        definition(b_fmt, 3),
+                    ],
+                    ..Default::default()
+                },
+                scip_proto::Document {
+                    relative_path: "src/c.rs".to_string(),
+                    occurrences: vec![reference(display, 2), definition(c_fmt, 3)],
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        };
+
+        let mut config = test_config(LevelOfDetail::High);
+        config.project_root = temp_dir.path().to_path_buf();
+        let (graph, symbol_index) = convert_scip_to_ycg(index, &config);
+
+        let implements: Vec<&ReferenceEdge> = graph
+            .references
+            .iter()
+            .filter(|e| e.edge_type == EdgeType::Implements)
+            .collect();
+        let b_user_anchor = &symbol_index.files["src/b.rs"][0];
+        // The external trait gets the anchor every other reference to it gets
+        let display_anchor = generate_anchor("ext", xxh64(display.as_bytes(), 0));
+        assert_eq!(
+            implements,
+            vec![&ReferenceEdge {
+                from: b_user_anchor.clone(),
+                to: display_anchor,
+                edge_type: EdgeType::Implements,
+                call_text: None,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_split_by_module_writes_module_and_cross_files() {
+        let user = "scip-typescript npm pkg 1.0.0 src/users/`user.ts`/User#";
+        let order = "scip-typescript npm pkg 1.0.0 src/orders/`order.ts`/Order#";
+        let service = "scip-typescript npm pkg 1.0.0 src/orders/`order.ts`/OrderService#";
+
+        let references = |targets: &[&str]| {
+            targets
+                .iter()
+                .map(|t| scip_proto::Relationship {
+                    symbol: t.to_string(),
+                    is_reference: true,
+                    ..Default::default()
+                })
+                .collect()
+        };
+
+        let index = scip_proto::Index {
+            documents: vec![
+                scip_proto::Document {
+                    relative_path: "src/users/user.ts".to_string(),
+                    occurrences: vec![definition(user, 0)],
+                    ..Default::default()
+                },
+                scip_proto::Document {
+                    relative_path: "src/orders/order.ts".to_string(),
+                    occurrences: vec![definition(order, 0), definition(service, 5)],
+                    symbols: vec![scip_proto::SymbolInformation {
+                        symbol: service.to_string(),
+                        kind: scip_proto::symbol_information::Kind::Class as i32,
+                        relationships: references(&[order, user]),
+                        ..Default::default()
+                    }],
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        };
+
+        let (graph, mut symbol_index) =
+            convert_scip_to_ycg(index, &test_config(LevelOfDetail::High));
+        symbol_index.retain_definitions(&graph);
+
+        let out_dir = tempfile::TempDir::new().unwrap();
+        write_module_split(&graph, &symbol_index, out_dir.path(), false).unwrap();
+
+        let mut written: Vec<String> = fs::read_dir(out_dir.path())
+            .unwrap()
+            .map(|e| e.unwrap().file_name().to_string_lossy().into_owned())
+            .collect();
+        written.sort();
+        assert_eq!(written, vec!["_cross.yaml", "orders.yaml", "users.yaml"]);
+
+        let read = |file: &str| -> YcgGraph {
+            serde_yaml::from_str(&fs::read_to_string(out_dir.path().join(file)).unwrap()).unwrap()
+        };
+        let orders = read("orders.yaml");
+        let users = read("users.yaml");
+        let cross = read("_cross.yaml");
+
+        let anchor_of = |g: &YcgGraph, name: &str| {
+            g.definitions
+                .iter()
+                .find(|n| n.name == name)
+                .unwrap_or_else(|| panic!("missing node {}", name))
+                .id
+                .clone()
+        };
+        let service_id = anchor_of(&orders, "OrderService");
+        let order_id = anchor_of(&orders, "Order");
+        let user_id = anchor_of(&users, "User");
+
+        assert!(
+            orders
+                .references
+                .iter()
+                .any(|e| e.from == service_id && e.to == order_id)
+        );
+        assert!(cross.definitions.is_empty());
+        assert_eq!(cross.references.len(), 1);
+        assert_eq!(cross.references[0].from, service_id);
+        assert_eq!(cross.references[0].to, user_id);
+    }
+
+    fn convert_default_export(file: &str, source: &str, symbol: &str) -> YcgGraph {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        fs::create_dir_all(temp_dir.path().join("src")).unwrap();
+        fs::write(temp_dir.path().join("src").join(file), source).unwrap();
+
+        let index = scip_proto::Index {
+            documents: vec![scip_proto::Document {
+                relative_path: format!("src/{}", file),
+                occurrences: vec![definition(symbol, 0)],
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+
+        let mut config = test_config(LevelOfDetail::Medium);
+        config.project_root = temp_dir.path().to_path_buf();
+        convert_scip_to_ycg(index, &config).0
+    }
+
+    #[test]
+    fn test_named_default_export_uses_declared_name() {
+        let graph = convert_default_export(
+            "store.ts",
+            "export default class UserStore {\n  size = 0;\n}\n",
+            "scip-typescript npm pkg 1.0.0 src/`store.ts`/default#",
+        );
+
+        let node = &graph.definitions[0];
+        assert_eq!(node.name, "UserStore");
+        assert!(node.id.starts_with("UserStore_"));
+    }
+
+    #[test]
+    fn test_anonymous_default_function_named_after_file() {
+        let graph = convert_default_export(
+            "bootstrap-app.ts",
+            "export default function () {\n  return 1;\n}\n",
+            "scip-typescript npm pkg 1.0.0 src/`bootstrap-app.ts`/default().",
+        );
+
+        let node = &graph.definitions[0];
+        assert_eq!(node.name, "defaultBootstrapApp");
+        assert!(node.id.starts_with("defaultBootstrapApp_"));
+    }
+
+    #[test]
+    fn test_default_exported_arrow_named_after_file() {
+        let graph = convert_default_export(
+            "users.handler.ts",
+            "export default (req: Request) => req.url;\n",
+            "scip-typescript npm pkg 1.0.0 src/`users.handler.ts`/default.",
+        );
+
+        assert_eq!(graph.definitions[0].name, "defaultUsersHandler");
+    }
+
+    #[test]
+    fn test_default_export_source_found_under_path_prefix() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        fs::create_dir_all(temp_dir.path().join("src")).unwrap();
+        fs::write(
+            temp_dir.path().join("src/store.ts"),
+            "export default class UserStore {\n  size = 0;\n}\n",
+        )
+        .unwrap();
+        let index = scip_proto::Index {
+            documents: vec![scip_proto::Document {
+                relative_path: "packages/api/src/store.ts".to_string(),
+                occurrences: vec![definition(
+                    "scip-typescript npm pkg 1.0.0 packages/api/src/`store.ts`/default#",
+                    0,
+                )],
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+
+        let mut config = test_config(LevelOfDetail::Medium);
+        config.project_root = temp_dir.path().to_path_buf();
+        config.path_prefix = Some("packages/api".to_string());
+        let graph = convert_scip_to_ycg(index, &config).0;
+
+        assert_eq!(graph.definitions[0].name, "UserStore");
+    }
+
+    #[test]
+    fn test_only_file_level_default_descriptor_is_a_default_export() {
+        assert!(is_default_export(
+            "scip-typescript npm pkg 1.0.0 src/`store.ts`/default#"
+        ));
+        assert!(is_default_export(
+            "scip-typescript npm pkg 1.0.0 src/`app.ts`/default()."
+        ));
+        // A member called `default`, and a symbol whose name could not be extracted
+        assert!(!is_default_export(
+            "scip-typescript npm pkg 1.0.0 src/`store.ts`/UserStore#default()."
+        ));
+        assert!(!is_default_export(
+            "scip-typescript npm pkg 1.0.0 src/`store.ts`/"
+        ));
+
+        let graph = convert_default_export(
+            "store.ts",
+            "export class UserStore {\n  default() {}\n}\n",
+            "scip-typescript npm pkg 1.0.0 src/`store.ts`/UserStore#default().",
+        );
+        assert_eq!(graph.definitions[0].name, "UserStore#default");
+    }
+
+    #[test]
+    fn test_adjacency_yaml_independent_of_insertion_order() {
+        let edge = |from: &str, to: &str, edge_type: EdgeType| ReferenceEdge {
+            from: from.to_string(),
+            to: to.to_string(),
+            edge_type,
+            call_text: None,
+        };
+        let edges = vec![
+            edge("Users_a1b2", "Repo_c3d4", EdgeType::Calls),
+            edge("Users_a1b2", "Repo_c3d4", EdgeType::References),
+            edge("Users_a1b2", "Base_e5f6", EdgeType::Implements),
+            edge("Users_a1b2", "Db_0a0b", EdgeType::Imports),
+            edge("Repo_c3d4", "Db_0a0b", EdgeType::Calls),
+            edge("Repo_c3d4", "Base_e5f6", EdgeType::Calls),
+        ];
+        let graph_from = |references: Vec<ReferenceEdge>| YcgGraph {
+            metadata: model::ProjectMetadata {
+                name: "test".to_string(),
+                version: "1.0.0".to_string(),
+                ..Default::default()
+            },
+            definitions: Vec::new(),
+            references,
+        };
+
+        let forward = optimize_graph(graph_from(edges.clone()));
+        let reversed = optimize_graph(graph_from(edges.into_iter().rev().collect()));
+
+        let forward_yaml = serde_yaml::to_string(&forward).unwrap();
+        let reversed_yaml = serde_yaml::to_string(&reversed).unwrap();
+        assert_eq!(forward_yaml, reversed_yaml);
+
+        let users: Vec<&EdgeType> = forward.adjacency["Users_a1b2"].keys().collect();
+        assert_eq!(
+            users,
+            vec![
+                &EdgeType::Calls,
+                &EdgeType::References,
+                &EdgeType::Imports,
+                &EdgeType::Implements
+            ]
+        );
+    }
+
+    #[test]
+    fn test_strip_prefix_shortens_names_and_keeps_edges() {
+        let service = "scip-typescript npm pkg 1.0.0 src/`svc.ts`/`com.acme.platform.UserService`#";
+        let find =
+            "scip-typescript npm pkg 1.0.0 src/`svc.ts`/`com.acme.platform.UserService`#find().";
+        let repo = "scip-typescript npm pkg 1.0.0 src/`svc.ts`/`com.acme.platform.UserRepo`#";
+
+        let index = scip_proto::Index {
+            documents: vec![scip_proto::Document {
+                relative_path: "src/svc.ts".to_string(),
+                occurrences: vec![
+                    definition(service, 0),
+                    definition(find, 2),
+                    definition(repo, 8),
+                ],
+                symbols: vec![scip_proto::SymbolInformation {
+                    symbol: service.to_string(),
+                    kind: scip_proto::symbol_information::Kind::Class as i32,
+                    relationships: vec![scip_proto::Relationship {
+                        symbol: repo.to_string(),
+                        is_reference: true,
+                        ..Default::default()
+                    }],
+                    ..Default::default()
+                }],
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+
+        let mut config = test_config(LevelOfDetail::Medium);
+        config.strip_prefix = Some("com.acme.platform".to_string());
+        let (graph, _) = convert_scip_to_ycg(index, &config);
+
+        let names: Vec<&str> = graph.definitions.iter().map(|n| n.name.as_str()).collect();
+        assert!(names.contains(&"UserService"));
+        assert!(names.contains(&"UserService#find"));
+        assert!(names.contains(&"UserRepo"));
+        assert!(names.iter().all(|n| !n.contains("com.acme")));
+        assert!(graph.definitions.iter().all(|n| !n.id.contains("com_acme")));
+        assert_eq!(
+            graph.metadata.stripped_prefix.as_deref(),
+            Some("com.acme.platform")
+        );
+
+        let ids: HashSet<&str> = graph.definitions.iter().map(|n| n.id.as_str()).collect();
+        assert!(!graph.references.is_empty());
+        for edge in &graph.references {
+            assert!(ids.contains(edge.from.as_str()) && ids.contains(edge.to.as_str()));
+        }
+        let service_id = &graph
+            .definitions
+            .iter()
+            .find(|n| n.name == "UserService")
+            .unwrap()
+            .id;
+        let find_node = graph
+            .definitions
+            .iter()
+            .find(|n| n.name == "UserService#find")
+            .unwrap();
+        assert_eq!(find_node.parent_id.as_ref(), Some(service_id));
+    }
+
+    #[test]
+    fn test_strip_name_prefix_requires_boundary() {
+        let prefix = Some("com.acme.platform");
+        assert_eq!(
+            strip_name_prefix("com.acme.platform.Users".to_string(), prefix),
+            "Users"
+        );
+        assert_eq!(
+            strip_name_prefix("com.acme.platformTools".to_string(), prefix),
+            "com.acme.platformTools"
+        );
+        assert_eq!(
+            strip_name_prefix("com.acme.platform".to_string(), prefix),
+            "com.acme.platform"
+        );
+        assert_eq!(
+            strip_name_prefix("Users".to_string(), Some("com.acme.")),
+            "Users"
+        );
+    }
+
+    #[test]
+    fn test_with_call_text_stores_call_expression_on_edge() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("src")).unwrap();
+        std::fs::write(
+            dir.path().join("src/users.ts"),
+            "export function findOne(id: number) {\n  return id;\n}\n\nexport function show(id: number) {\n  return findOne(id);\n}\n",
+        )
+        .unwrap();
+
+        let find_one = "scip-typescript npm pkg 1.0.0 src/`users.ts`/findOne().";
+        let show = "scip-typescript npm pkg 1.0.0 src/`users.ts`/show().";
+        let index = scip_proto::Index {
+            documents: vec![scip_proto::Document {
+                relative_path: "src/users.ts".to_string(),
+                occurrences: vec![
+                    definition(find_one, 0),
+                    definition(show, 4),
+                    scip_proto::Occurrence {
+                        range: vec![5, 9, 16],
+                        symbol: find_one.to_string(),
+                        ..Default::default()
+                    },
+                ],
+                symbols: vec![
+                    scip_proto::SymbolInformation {
+                        symbol: find_one.to_string(),
+                        kind: scip_proto::symbol_information::Kind::Function as i32,
+                        ..Default::default()
+                    },
+                    scip_proto::SymbolInformation {
+                        symbol: show.to_string(),
+                        kind: scip_proto::symbol_information::Kind::Function as i32,
+                        ..Default::default()
+                    },
+                ],
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+
+        let config = YcgConfig {
+            project_root: dir.path().to_path_buf(),
+            with_call_text: true,
+            ..test_config(LevelOfDetail::Medium)
+        };
+        let (graph, _) = convert_scip_to_ycg(index.clone(), &config);
+
+        let call = graph
+            .references
+            .iter()
+            .find(|e| e.edge_type == EdgeType::Calls && e.to.starts_with("findOne"))
+            .expect("show -> findOne call edge");
+        assert_eq!(call.call_text.as_deref(), Some("findOne(id)"));
+
+        // Opt-in: nothing is captured by default
+        let config = YcgConfig {
+            project_root: dir.path().to_path_buf(),
+            ..test_config(LevelOfDetail::Medium)
+        };
+        let (graph, _) = convert_scip_to_ycg(index, &config);
+        assert!(graph.references.iter().all(|e| e.call_text.is_none()));
+    }
+
+    #[test]
+    fn test_ref_count_counts_inbound_edges() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("src")).unwrap();
+        std::fs::write(
+            dir.path().join("src/util.ts"),
+            "export function target() {}\n\
+             export function a() {\n  target();\n}\n\
+             export function b() {\n  target();\n}\n\
+             export function c() {\n  target();\n}\n\
+             export function unused() {}\n",
+        )
+        .unwrap();
+
+        let symbol =
+            |name: &str| format!("scip-typescript npm pkg 1.0.0 src/`util.ts`/{}().", name);
+        let mut occurrences = vec![definition(&symbol("target"), 0)];
+        for (caller, line) in [("a", 1), ("b", 4), ("c", 7)] {
+            // Body spans three lines, so the call site is inside the caller's scope
+            occurrences.push(scip_proto::Occurrence {
+                range: vec![line, 0, line + 2, 1],
+                ..definition(&symbol(caller), line)
+            });
+            occurrences.push(scip_proto::Occurrence {
+                range: vec![line + 1, 2, 8],
+                symbol: symbol("target"),
+                ..Default::default()
+            });
+        }
+        occurrences.push(definition(&symbol("unused"), 10));
+        let symbols = ["target", "a", "b", "c", "unused"]
+            .into_iter()
+            .map(|name| scip_proto::SymbolInformation {
+                symbol: symbol(name),
+                kind: scip_proto::symbol_information::Kind::Function as i32,
+                ..Default::default()
+            })
+            .collect();
+        let index = scip_proto::Index {
+            documents: vec![scip_proto::Document {
+                relative_path: "src/util.ts".to_string(),
+                occurrences,
+                symbols,
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        let build = |config: YcgConfig| {
+            let filters = compiled_filters::CompiledFilters::new(&config).unwrap();
+            build_graph(index.clone(), &config, &filters).unwrap().0
+        };
+        let ref_count_of = |graph: &YcgGraph, name: &str| {
+            graph
+                .definitions
+                .iter()
+                .find(|n| n.name == name)
+                .unwrap()
+                .ref_count
+        };
+
+        let graph = build(YcgConfig {
+            project_root: dir.path().to_path_buf(),
+            ..test_config(LevelOfDetail::Medium)
+        });
+        assert_eq!(ref_count_of(&graph, "target"), 3);
+        assert_eq!(ref_count_of(&graph, "unused"), 0);
+
+        let yaml = serde_yaml::to_string(&graph).unwrap();
+        assert_eq!(yaml.matches("ref_count:").count(), 1, "{}", yaml);
+
+        // Edges of a filtered-out caller no longer count
+        let graph = build(YcgConfig {
+            project_root: dir.path().to_path_buf(),
+            name_filter: model::NameFilterConfig {
+                include_patterns: Vec::new(),
+                exclude_patterns: vec!["^c$".to_string()],
+            },
+            ..test_config(LevelOfDetail::Medium)
+        });
+        assert_eq!(ref_count_of(&graph, "target"), 2);
+    }
+
+    #[test]
+    fn test_edge_rule_maps_emit_calls_to_custom_type() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("src")).unwrap();
+        std::fs::write(
+            dir.path().join("src/users.ts"),
+            "export class EventBus {\n  emit(name: string, payload: unknown) {}\n}\n\n\
+             export function save(bus: EventBus, user: User) {\n  validate(user);\n  bus.emit('user.created', user);\n}\n\n\
+             export function validate(user: User) {}\n",
+        )
+        .unwrap();
+
+        let bus = "scip-typescript npm pkg 1.0.0 src/`users.ts`/EventBus#";
+        let emit = "scip-typescript npm pkg 1.0.0 src/`users.ts`/EventBus#emit().";
+        let save = "scip-typescript npm pkg 1.0.0 src/`users.ts`/save().";
+        let validate = "scip-typescript npm pkg 1.0.0 src/`users.ts`/validate().";
+        let info = |symbol: &str, kind: scip_proto::symbol_information::Kind| {
+            scip_proto::SymbolInformation {
+                symbol: symbol.to_string(),
+                kind: kind as i32,
+                ..Default::default()
+            }
+        };
+        let reference = |symbol: &str, range: Vec<i32>| scip_proto::Occurrence {
+            range,
+            symbol: symbol.to_string(),
+            ..Default::default()
+        };
+        let index = scip_proto::Index {
+            documents: vec![scip_proto::Document {
+                relative_path: "src/users.ts".to_string(),
+                occurrences: vec![
+                    definition(bus, 0),
+                    definition(emit, 1),
+                    scip_proto::Occurrence {
+                        range: vec![4, 0, 7, 1],
+                        ..definition(save, 4)
+                    },
+                    reference(validate, vec![5, 2, 10]),
+                    reference(emit, vec![6, 6, 10]),
+                    definition(validate, 9),
+                ],
+                symbols: vec![
+                    info(emit, scip_proto::symbol_information::Kind::Method),
+                    info(save, scip_proto::symbol_information::Kind::Function),
+                    info(validate, scip_proto::symbol_information::Kind::Function),
+                ],
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+
+        let config = YcgConfig {
+            project_root: dir.path().to_path_buf(),
+            edge_rules: vec![model::EdgeRule {
+                pattern: r"\.emit\(".to_string(),
+                edge_type: "emits".to_string(),
+            }],
+            ..test_config(LevelOfDetail::Medium)
+        };
+        let (graph, _) = convert_scip_to_ycg(index, &config);
+
+        let edge_to = |prefix: &str| {
+            graph
+                .references
+                .iter()
+                .find(|e| e.from.starts_with("save") && e.to.starts_with(prefix))
+                .unwrap_or_else(|| panic!("save -> {} edge", prefix))
+        };
+        assert_eq!(
+            edge_to("EventBus_emit").edge_type,
+            EdgeType::Custom("emits".to_string())
+        );
+        assert_eq!(edge_to("validate").edge_type, EdgeType::Calls);
+
+        let yaml = serde_yaml::to_string(&graph).unwrap();
+        assert!(yaml.contains("type: emits"), "{}", yaml);
+        let parsed: YcgGraph = serde_yaml::from_str(&yaml).unwrap();
+        assert_eq!(parsed.references, graph.references);
+    }
+
+    #[test]
+    fn test_enum_declaration_with_member_children() {
+        use scip_proto::symbol_information::Kind;
+
+        let color = "scip-typescript npm pkg 1.0.0 src/`color.ts`/Color#";
+        let red = "scip-typescript npm pkg 1.0.0 src/`color.ts`/Color#Red.";
+        let green = "scip-typescript npm pkg 1.0.0 src/`color.ts`/Color#Green.";
+        let info = |symbol: &str, kind: Kind| scip_proto::SymbolInformation {
+            symbol: symbol.to_string(),
+            kind: kind as i32,
+            ..Default::default()
+        };
+
+        let index = scip_proto::Index {
+            documents: vec![scip_proto::Document {
+                relative_path: "src/color.ts".to_string(),
+                occurrences: vec![
+                    definition(color, 0),
+                    definition(red, 1),
+                    definition(green, 2),
+                ],
+                symbols: vec![
+                    info(color, Kind::Enum),
+                    info(red, Kind::EnumMember),
+                    info(green, Kind::EnumMember),
+                ],
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+
+        let (graph, _) = convert_scip_to_ycg(index, &test_config(LevelOfDetail::High));
+
+        let color_node = graph
+            .definitions
+            .iter()
+            .find(|n| n.name == "Color")
+            .unwrap();
+        assert_eq!(color_node.kind, ScipSymbolKind::Enum);
+        for member in ["Color#Red", "Color#Green"] {
+            let node = graph.definitions.iter().find(|n| n.name == member).unwrap();
+            assert_eq!(node.kind, ScipSymbolKind::Variable);
+            assert_eq!(node.parent_id.as_ref(), Some(&color_node.id));
+        }
+    }
+
+    #[test]
+    fn test_enum_inferred_from_source_without_symbol_information() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("src")).unwrap();
+        std::fs::write(
+            dir.path().join("src/status.ts"),
+            "export enum Status {\n  Active = 'active',\n}\n\nexport class User {}\n",
+        )
+        .unwrap();
+
+        let status = "scip-typescript npm pkg 1.0.0 src/`status.ts`/Status#";
+        let user = "scip-typescript npm pkg 1.0.0 src/`status.ts`/User#";
+        let index = scip_proto::Index {
+            documents: vec![scip_proto::Document {
+                relative_path: "src/status.ts".to_string(),
+                occurrences: vec![definition(status, 0), definition(user, 4)],
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+
+        let config = YcgConfig {
+            project_root: dir.path().to_path_buf(),
+            ..test_config(LevelOfDetail::High)
+        };
+        let (graph, _) = convert_scip_to_ycg(index, &config);
+
+        let kind_of = |name: &str| {
+            graph
+                .definitions
+                .iter()
+                .find(|n| n.name == name)
+                .unwrap()
+                .kind
+        };
+        assert_eq!(kind_of("Status"), ScipSymbolKind::Enum);
+        assert_eq!(kind_of("User"), ScipSymbolKind::Class);
+    }
+
+    #[test]
+    fn test_header_comment_is_valid_yaml_comment() {
+        let dir = tempfile::tempdir().unwrap();
+        let scip_path = dir.path().join("index.scip");
+        std::fs::write(&scip_path, scip_proto::Index::default().encode_to_vec()).unwrap();
+
+        let config = YcgConfig {
+            explain_format: true,
+            header_comment: Some("Answer using only this graph.\n\nIDs are stable.".to_string()),
+            ..test_config(LevelOfDetail::Medium)
+        };
+        let output = run_scip_conversion(&scip_path, config).unwrap();
+
+        assert!(
+            output.starts_with("# This is a YCG code graph"),
+            "{}",
+            output
+        );
+        assert!(
+            output.contains("# graph lists edges {from, to, type}"),
+            "{}",
+            output
+        );
+        assert!(output.contains("# Answer using only this graph.\n#\n# IDs are stable.\n"));
+
+        let graph: YcgGraph = serde_yaml::from_str(&output).expect("header must be skippable");
+        assert!(graph.definitions.is_empty());
+    }
+
+    #[test]
+    fn test_baseline_emits_only_added_method_and_its_edges() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("src")).unwrap();
+        std::fs::write(
+            dir.path().join("src/users.ts"),
+            "export class UserService {\n  find(id: number) {\n    return id;\n  }\n  create(id: number) {\n    return this.find(id);\n  }\n}\n",
+        )
+        .unwrap();
+
+        let service = "scip-typescript npm pkg 1.0.0 src/`users.ts`/UserService#";
+        let find = "scip-typescript npm pkg 1.0.0 src/`users.ts`/UserService#find().";
+        let create = "scip-typescript npm pkg 1.0.0 src/`users.ts`/UserService#create().";
+        let method = |symbol: &str| scip_proto::SymbolInformation {
+            symbol: symbol.to_string(),
+            kind: scip_proto::symbol_information::Kind::Method as i32,
+            ..Default::default()
+        };
+        let index = |with_create: bool| {
+            let mut occurrences = vec![
+                definition(service, 0),
+                scip_proto::Occurrence {
+                    range: vec![1, 2, 3, 3],
+                    ..definition(find, 1)
+                },
+            ];
+            let mut symbols = vec![method(find)];
+            if with_create {
+                occurrences.push(scip_proto::Occurrence {
+                    range: vec![4, 2, 6, 3],
+                    ..definition(create, 4)
+                });
+                occurrences.push(scip_proto::Occurrence {
+                    range: vec![5, 16, 20],
+                    symbol: find.to_string(),
+                    ..Default::default()
+                });
+                symbols.push(method(create));
+            }
+            scip_proto::Index {
+                documents: vec![scip_proto::Document {
+                    relative_path: "src/users.ts".to_string(),
+                    occurrences,
+                    symbols,
+                    ..Default::default()
+                }],
+                ..Default::default()
+            }
+        };
+        let old_path = dir.path().join("old.scip");
+        let new_path = dir.path().join("new.scip");
+        std::fs::write(&old_path, index(false).encode_to_vec()).unwrap();
+        std::fs::write(&new_path, index(true).encode_to_vec()).unwrap();
+
+        let config = YcgConfig {
+            project_root: dir.path().to_path_buf(),
+            baseline: Some(old_path),
+            ..test_config(LevelOfDetail::Medium)
+        };
+        let output = run_scip_conversion(&new_path, config).unwrap();
+
+        let marked: delta::DeltaGraph<YcgGraph> = serde_yaml::from_str(&output).unwrap();
+        let delta = marked.delta.expect("_delta marker");
+        let graph = marked.graph;
+        assert_eq!(graph.definitions.len(), 1, "{}", output);
+        let create_node = &graph.definitions[0];
+        assert_eq!(create_node.name, "UserService#create");
+        assert_eq!(delta.added, vec![create_node.id.clone()]);
+        assert!(delta.removed.is_empty() && delta.modified.is_empty());
+        assert_eq!(graph.references.len(), 1);
+        assert_eq!(graph.references[0].from, create_node.id);
+        assert!(graph.references[0].to.starts_with("UserService_find"));
+    }
+
+    #[test]
+    fn test_baseline_reports_method_whose_signature_changed() {
+        baseline_reports_method_whose_signature_changed(false);
+    }
+
+    #[test]
+    fn test_baseline_reports_signature_change_with_kind_prefixed_anchors() {
+        baseline_reports_method_whose_signature_changed(true);
+    }
+
+    fn baseline_reports_method_whose_signature_changed(kind_prefixed_anchors: bool) {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("src")).unwrap();
+        std::fs::write(
+            dir.path().join("src/users.ts"),
+            "export class UserService {\n  find(id: string) {\n    return id;\n  }\n}\n",
+        )
+        .unwrap();
+
+        let service = "scip-typescript npm pkg 1.0.0 src/`users.ts`/UserService#";
+        let find = "scip-typescript npm pkg 1.0.0 src/`users.ts`/UserService#find().";
+        let index = |signature: &str| scip_proto::Index {
+            documents: vec![scip_proto::Document {
+                relative_path: "src/users.ts".to_string(),
+                occurrences: vec![definition(service, 0), definition(find, 1)],
+                symbols: vec![scip_proto::SymbolInformation {
+                    symbol: find.to_string(),
+                    kind: scip_proto::symbol_information::Kind::Method as i32,
+                    signature_documentation: Some(scip_proto::Document {
+                        text: signature.to_string(),
+                        ..Default::default()
+                    }),
+                    ..Default::default()
+                }],
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        let old_path = dir.path().join("old.scip");
+        let new_path = dir.path().join("new.scip");
+        std::fs::write(&old_path, index("find(id: number): number").encode_to_vec()).unwrap();
+        std::fs::write(&new_path, index("find(id: string): string").encode_to_vec()).unwrap();
+
+        let config = YcgConfig {
+            project_root: dir.path().to_path_buf(),
+            baseline: Some(old_path),
+            kind_prefixed_anchors,
+            ..test_config(LevelOfDetail::Medium)
+        };
+        let output = run_scip_conversion(&new_path, config).unwrap();
+
+        let marked: delta::DeltaGraph<YcgGraph> = serde_yaml::from_str(&output).unwrap();
+        let delta = marked.delta.expect("_delta marker");
+        assert!(delta.added.is_empty() && delta.removed.is_empty());
+        assert_eq!(marked.graph.definitions.len(), 1, "{}", output);
+        assert_eq!(marked.graph.definitions[0].name, "UserService#find");
+        assert_eq!(delta.modified, vec![marked.graph.definitions[0].id.clone()]);
+        assert_eq!(
+            marked.graph.definitions[0].id.starts_with("meth:"),
+            kind_prefixed_anchors
+        );
+    }
+
+    #[test]
+    fn test_default_parameter_values_kept_unless_no_defaults() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("src")).unwrap();
+        std::fs::write(
+            dir.path().join("src/users.ts"),
+            "export class UserService {\n  findAll(page: number, limit: number = 100) {\n    return [];\n  }\n}\n",
+        )
+        .unwrap();
+
+        let service = "scip-typescript npm pkg 1.0.0 src/`users.ts`/UserService#";
+        let find_all = "scip-typescript npm pkg 1.0.0 src/`users.ts`/UserService#findAll().";
+        let index = scip_proto::Index {
+            documents: vec![scip_proto::Document {
+                relative_path: "src/users.ts".to_string(),
+                occurrences: vec![definition(service, 0), definition(find_all, 1)],
+                symbols: vec![scip_proto::SymbolInformation {
+                    symbol: find_all.to_string(),
+                    kind: scip_proto::symbol_information::Kind::Method as i32,
+                    ..Default::default()
+                }],
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        let scip_path = dir.path().join("index.scip");
+        std::fs::write(&scip_path, index.encode_to_vec()).unwrap();
+
+        let signature = |no_defaults: bool| {
+            let config = YcgConfig {
+                project_root: dir.path().to_path_buf(),
+                no_defaults,
+                ..test_config(LevelOfDetail::Medium)
+            };
+            let output = run_scip_conversion(&scip_path, config).unwrap();
+            let graph: YcgGraph = serde_yaml::from_str(&output).unwrap();
+            graph
+                .definitions
+                .into_iter()
+                .find(|n| n.name == "UserService#findAll")
+                .and_then(|n| n.signature)
+                .unwrap()
+        };
+
+        assert!(signature(false).contains("limit: number = 100"));
+        let stripped = signature(true);
+        assert!(stripped.contains("limit: number)"), "{}", stripped);
+        assert!(!stripped.contains("100"), "{}", stripped);
+    }
+
+    #[test]
+    fn test_path_prefix_resolves_repo_root_paths_from_package() {
+        let repo = tempfile::tempdir().unwrap();
+        let package = repo.path().join("packages/api");
+        std::fs::create_dir_all(package.join("src")).unwrap();
+        std::fs::write(
+            package.join("src/users.ts"),
+            "export function findUser(id: string): User {\n  return db.get(id);\n}\n",
+        )
+        .unwrap();
+
+        let find_user = "scip-typescript npm api 1.0.0 packages/api/src/`users.ts`/findUser().";
+        let index = scip_proto::Index {
+            documents: vec![scip_proto::Document {
+                relative_path: "packages/api/src/users.ts".to_string(),
+                occurrences: vec![definition(find_user, 0)],
+                symbols: vec![scip_proto::SymbolInformation {
+                    symbol: find_user.to_string(),
+                    kind: scip_proto::symbol_information::Kind::Function as i32,
+                    ..Default::default()
+                }],
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+
+        let signature = |path_prefix: Option<&str>| {
+            let config = YcgConfig {
+                project_root: package.clone(),
+                path_prefix: path_prefix.map(str::to_string),
+                ..test_config(LevelOfDetail::Medium)
+            };
+            let (graph, _) = convert_scip_to_ycg(index.clone(), &config);
+            graph
+                .definitions
+                .into_iter()
+                .find(|n| n.name == "findUser")
+                .and_then(|n| n.signature)
+        };
+
+        assert_eq!(
+            signature(Some("packages/api")).as_deref(),
+            Some("function findUser(id: string): User")
+        );
+        // Without a prefix it is detected by probing
+        assert_eq!(signature(None), signature(Some("packages/api")));
+    }
+
+    #[test]
+    fn test_edges_by_type_partitions_flat_edges() {
+        let edge = |from: &str, to: &str, edge_type| ReferenceEdge {
+            from: from.to_string(),
+            to: to.to_string(),
+            edge_type,
+            call_text: None,
+        };
+        let graph = YcgGraph {
+            metadata: ProjectMetadata::default(),
+            definitions: Vec::new(),
+            references: vec![
+                edge("b_1", "c_2", EdgeType::Calls),
+                edge("a_0", "i_3", EdgeType::Implements),
+                edge("a_0", "c_2", EdgeType::Calls),
+                edge("a_0", "m_4", EdgeType::Imports),
+            ],
+        };
+        let original = graph.references.clone();
+
+        let grouped = model::YcgGraphByType::from_graph(graph);
+        assert_eq!(
+            grouped.edges.keys().cloned().collect::<Vec<_>>(),
+            vec![EdgeType::Calls, EdgeType::Imports, EdgeType::Implements]
+        );
+        let calls: Vec<(&str, &str)> = grouped.edges[&EdgeType::Calls]
+            .iter()
+            .map(|e| (e.from.as_str(), e.to.as_str()))
+            .collect();
+        assert_eq!(calls, vec![("a_0", "c_2"), ("b_1", "c_2")]);
+
+        let yaml = serde_yaml::to_string(&grouped).unwrap();
+        assert!(
+            yaml.contains("graph:\n  calls:\n  - from: a_0\n    to: c_2\n"),
+            "{}",
+            yaml
+        );
+        assert!(!yaml.contains("type:"), "{}", yaml);
+
+        let mut expected = original;
+        expected.sort();
+        assert_eq!(grouped.into_graph().references, expected);
+    }
+
+    #[test]
+    fn test_files_above_max_file_bytes_are_not_enriched() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("src")).unwrap();
+        std::fs::write(
+            dir.path().join("src/small.ts"),
+            "export function small(a: number): number {\n  return a;\n}\n",
+        )
+        .unwrap();
+        // Generated-looking 100k-line file, well above the 1 MiB cap below
+        let mut generated =
+            String::from("export function big(a: number): number {\n  return a;\n}\n");
+        for i in 0..100_000 {
+            generated.push_str(&format!("export const v{} = {};\n", i, i));
+        }
+        std::fs::write(dir.path().join("src/generated.ts"), generated).unwrap();
+
+        let small = "scip-typescript npm pkg 1.0.0 src/`small.ts`/small().";
+        let big = "scip-typescript npm pkg 1.0.0 src/`generated.ts`/big().";
+        let document = |path: &str, symbol: &str| scip_proto::Document {
+            relative_path: path.to_string(),
+            occurrences: vec![definition(symbol, 0)],
+            symbols: vec![scip_proto::SymbolInformation {
+                symbol: symbol.to_string(),
+                kind: scip_proto::symbol_information::Kind::Function as i32,
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        let index = scip_proto::Index {
+            documents: vec![
+                document("src/generated.ts", big),
+                document("src/small.ts", small),
+            ],
+            ..Default::default()
+        };
+
+        let config = YcgConfig {
+            project_root: dir.path().to_path_buf(),
+            max_file_bytes: 1024 * 1024,
+            ..test_config(LevelOfDetail::Medium)
+        };
+        let (graph, _) = convert_scip_to_ycg(index, &config);
+
+        let signature_of = |name: &str| {
+            graph
+                .definitions
+                .iter()
+                .find(|n| n.name == name)
+                .expect("definition is still emitted")
+                .signature
+                .clone()
+        };
+        assert_eq!(signature_of("big"), None);
+        assert_eq!(
+            signature_of("small").as_deref(),
+            Some("function small(a: number): number")
+        );
+    }
+
+    #[test]
+    fn test_missing_source_uses_descriptor_signature() {
+        let find = "scip-typescript npm pkg 1.0.0 src/`gone.ts`/UserService#find().";
+        let find_id = "scip-typescript npm pkg 1.0.0 src/`gone.ts`/UserService#find().(id)";
+        let find_name = "scip-typescript npm pkg 1.0.0 src/`gone.ts`/UserService#find().(name)";
+        let save = "scip-typescript npm pkg 1.0.0 src/`gone.ts`/UserService#save().";
+
+        let index = scip_proto::Index {
+            documents: vec![scip_proto::Document {
+                relative_path: "src/gone.ts".to_string(),
+                occurrences: vec![
+                    definition(find, 2),
+                    definition(find_id, 2),
+                    definition(find_name, 2),
+                    definition(save, 6),
+                ],
+                symbols: vec![
+                    scip_proto::SymbolInformation {
+                        symbol: find.to_string(),
+                        kind: scip_proto::symbol_information::Kind::Method as i32,
+                        ..Default::default()
+                    },
+                    scip_proto::SymbolInformation {
+                        symbol: save.to_string(),
+                        kind: scip_proto::symbol_information::Kind::Method as i32,
+                        documentation: vec![
+                            "```ts\n(method) save(user: User): Promise<void>\n```".to_string(),
+                        ],
+                        ..Default::default()
+                    },
+                ],
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+
+        // project_root does not exist, so the source file is absent
+        let (graph, _) = convert_scip_to_ycg(index, &test_config(LevelOfDetail::Medium));
+
+        let signature_of = |name: &str| {
+            graph
+                .definitions
+                .iter()
+                .find(|n| n.name == name)
+                .and_then(|n| n.signature.clone())
+        };
+        assert_eq!(
+            signature_of("UserService#find").as_deref(),
+            Some("find(id, name)")
+        );
+        assert_eq!(
+            signature_of("UserService#save").as_deref(),
+            Some("save(user: User): Promise<void>")
+        );
+    }
+
+    #[test]
+    fn test_short_symbol_name() {
+        assert_eq!(short_symbol_name("impl#[Dog][Speak]speak"), "speak");
+        assert_eq!(short_symbol_name("UsersService#findOne"), "findOne");
+        assert_eq!(short_symbol_name("helper"), "helper");
+        assert_eq!(short_symbol_name("<constructor>"), "<constructor>");
+    }
+
+    #[test]
+    fn test_flow_style_uses_fewer_tokens_and_reads_back_the_same() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("src")).unwrap();
+        let mut source = String::from("export class Orders {\n");
+        let mut occurrences = vec![definition(
+            "scip-typescript npm pkg 1.0.0 src/`orders.ts`/Orders#",
+            0,
+        )];
+        let mut symbols = Vec::new();
+        let method = |i: usize| {
+            format!(
+                "scip-typescript npm pkg 1.0.0 src/`orders.ts`/Orders#step{}().",
+                i
+            )
+        };
+        for i in 0..6 {
+            let line = 1 + i as i32 * 3;
+            source.push_str(&format!(
+                "  step{}(id: number) {{\n    return this.step{}(id) + this.step{}(id);\n  }}\n",
+                i,
+                (i + 1) % 6,
+                (i + 2) % 6
+            ));
+            occurrences.push(scip_proto::Occurrence {
+                range: vec![line, 2, line + 2, 3],
+                ..definition(&method(i), line)
+            });
+            for (col, target) in [(16, (i + 1) % 6), (34, (i + 2) % 6)] {
+                occurrences.push(scip_proto::Occurrence {
+                    range: vec![line + 1, col, col + 5],
+                    symbol: method(target),
+                    ..Default::default()
+                });
+            }
+            symbols.push(scip_proto::SymbolInformation {
+                symbol: method(i),
+                kind: scip_proto::symbol_information::Kind::Method as i32,
+                ..Default::default()
+            });
+        }
+        source.push_str("}\n");
+        std::fs::write(dir.path().join("src/orders.ts"), source).unwrap();
+        let index = scip_proto::Index {
+            documents: vec![scip_proto::Document {
+                relative_path: "src/orders.ts".to_string(),
+                occurrences,
+                symbols,
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        let index_path = dir.path().join("index.scip");
+        std::fs::write(&index_path, index.encode_to_vec()).unwrap();
+
+        let render = |yaml_style| {
+            let config = YcgConfig {
+                project_root: dir.path().to_path_buf(),
+                compact: true,
+                yaml_style,
+                ..test_config(LevelOfDetail::Medium)
+            };
+            run_scip_conversion(&index_path, config).unwrap()
+        };
+        let block = render(model::YamlStyle::Block);
+        let flow = render(model::YamlStyle::Flow);
+
+        assert!(flow.contains(": {calls: ["), "{}", flow);
+        assert!(
+            count_tokens(&flow) < count_tokens(&block),
+            "flow {} vs block {} tokens",
+            count_tokens(&flow),
+            count_tokens(&block)
+        );
+        let block_value: serde_yaml::Value = serde_yaml::from_str(&block).unwrap();
+        let flow_value: serde_yaml::Value = serde_yaml::from_str(&flow).unwrap();
+        assert_eq!(flow_value, block_value, "{}", flow);
+    }
+
+    #[test]
+    fn test_import_role_produces_imports_edge() {
+        let users = "scip-typescript npm pkg 1.0.0 src/`users.ts`/UserService#";
+        let find = "scip-typescript npm pkg 1.0.0 src/`users.ts`/UserService#find().";
+        let reference = |symbol: &str, line: i32, roles: i32| scip_proto::Occurrence {
+            range: vec![line, 9, 20],
+            symbol: symbol.to_string(),
+            symbol_roles: roles,
+            ..Default::default()
+        };
+        let index = scip_proto::Index {
+            documents: vec![
+                scip_proto::Document {
+                    relative_path: "src/users.ts".to_string(),
+                    occurrences: vec![definition(users, 0), definition(find, 1)],
+                    ..Default::default()
+                },
+                scip_proto::Document {
+                    relative_path: "src/app.ts".to_string(),
+                    occurrences: vec![
+                        reference(users, 0, scip_proto::SymbolRole::Import as i32),
+                        reference(find, 3, 0),
+                    ],
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        };
+
+        let (graph, _) = convert_scip_to_ycg(index, &test_config(LevelOfDetail::Medium));
+        let edge_type = |name: &str| {
+            let node = graph.definitions.iter().find(|n| n.name == name).unwrap();
+            graph
+                .references
+                .iter()
+                .find(|e| e.to == node.id)
+                .map(|e| e.edge_type.clone())
+        };
+        assert_eq!(edge_type("UserService"), Some(EdgeType::Imports));
+        assert_eq!(edge_type("UserService#find"), Some(EdgeType::Calls));
+    }
+
+    #[test]
+    fn test_reference_with_variant_uri_resolves_to_definition() {
+        let find = "scip-typescript npm pkg 1.0.0 src/`users.ts`/UserService#find().";
+        let handler = "scip-typescript npm pkg 1.0.0 src/`app.ts`/handler().";
+        let reference = |symbol: &str, line: i32| scip_proto::Occurrence {
+            range: vec![line, 9, 20],
+            symbol: symbol.to_string(),
+            ..Default::default()
+        };
+        let index = scip_proto::Index {
+            documents: vec![
+                scip_proto::Document {
+                    relative_path: "src/users.ts".to_string(),
+                    occurrences: vec![definition(find, 1)],
+                    ..Default::default()
+                },
+                scip_proto::Document {
+                    relative_path: "src/app.ts".to_string(),
+                    occurrences: vec![
+                        definition(handler, 0),
+                        // Same method, with a disambiguator and a trailing space
+                        reference(
+                            "scip-typescript npm pkg 1.0.0 src/`users.ts`/UserService#find(+1).",
+                            2,
+                        ),
+                        reference(
+                            "scip-typescript npm pkg 1.0.0 src/`users.ts`/UserService#find(). ",
+                            3,
+                        ),
+                    ],
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        };
+
+        let (graph, _) = convert_scip_to_ycg(index, &test_config(LevelOfDetail::High));
+        let find_id = &graph
+            .definitions
+            .iter()
+            .find(|n| n.name == "UserService#find")
+            .unwrap()
+            .id;
+        assert!(
+            graph.references.iter().any(|e| &e.to == find_id),
+            "{:?}",
+            graph.references
+        );
+        assert!(
+            graph.references.iter().all(|e| !e.to.starts_with("ext_")),
+            "{:?}",
+            graph.references
+        );
+    }
+
+    #[test]
+    fn test_type_and_term_of_same_name_stay_distinct() {
+        let foo_type = "scip-typescript npm pkg 1.0.0 src/`foo.ts`/Foo#";
+        let foo_term = "scip-typescript npm pkg 1.0.0 src/`foo.ts`/Foo.";
+        assert_ne!(canonical_symbol(foo_type), canonical_symbol(foo_term));
+        assert_eq!(
+            canonical_symbol("scip-typescript npm pkg 1.0.0 src/`foo.ts`/Foo#find(+2)."),
+            canonical_symbol("scip-typescript npm pkg 1.0.0 src/`foo.ts`/Foo#find()."),
+        );
+        // Parameter names are not disambiguators
+        assert_ne!(
+            canonical_symbol("scip-typescript npm pkg 1.0.0 src/`foo.ts`/Foo#find().(id)"),
+            canonical_symbol("scip-typescript npm pkg 1.0.0 src/`foo.ts`/Foo#find().(name)"),
+        );
+
+        // Only the type is defined: the term is another symbol, not a spelling of it
+        let index = scip_proto::Index {
+            documents: vec![scip_proto::Document {
+                relative_path: "src/foo.ts".to_string(),
+                occurrences: vec![
+                    definition(foo_type, 0),
+                    definition("scip-typescript npm pkg 1.0.0 src/`foo.ts`/make().", 2),
+                    scip_proto::Occurrence {
+                        range: vec![3, 9, 12],
+                        symbol: foo_term.to_string(),
+                        ..Default::default()
+                    },
+                ],
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        let (graph, _) = convert_scip_to_ycg(index, &test_config(LevelOfDetail::High));
+        let foo_id = &graph
+            .definitions
+            .iter()
+            .find(|n| n.name == "Foo")
+            .unwrap()
+            .id;
+        assert!(
+            graph.references.iter().all(|e| &e.to != foo_id),
+            "{:?}",
+            graph.references
+        );
+    }
+
+    #[test]
+    fn test_external_import_keeps_package_name() {
+        let users = "scip-typescript npm pkg 1.0.0 src/`users.ts`/UserService#";
+        let injectable = "scip-typescript npm @nestjs/common 10.3.0 decorators/core/`injectable.decorator.d.ts`/Injectable().";
+        let lodash = "scip-typescript npm lodash 4.17.21 `lodash.d.ts`/map().";
+        let occurrence = |symbol: &str, line: i32, roles: i32| scip_proto::Occurrence {
+            range: vec![line, 9, 20],
+            symbol: symbol.to_string(),
+            symbol_roles: roles,
+            ..Default::default()
+        };
+        let index = scip_proto::Index {
+            documents: vec![scip_proto::Document {
+                relative_path: "src/users.ts".to_string(),
+                occurrences: vec![
+                    occurrence(injectable, 0, scip_proto::SymbolRole::Import as i32),
+                    definition(users, 2),
+                    occurrence(lodash, 3, 0),
+                ],
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+
+        let (graph, _) = convert_scip_to_ycg(index, &test_config(LevelOfDetail::High));
+        let import = graph
+            .references
+            .iter()
+            .find(|e| e.edge_type == EdgeType::Imports)
+            .unwrap();
+        assert_eq!(import.to, "ext:@nestjs/common");
+        // Only imports are named; other external targets stay hashed
+        let call = graph
+            .references
+            .iter()
+            .find(|e| e.edge_type == EdgeType::Calls)
+            .unwrap();
+        assert!(call.to.starts_with("ext_"), "{}", call.to);
+
+        let yaml = serde_yaml::to_string(&graph).unwrap();
+        assert!(yaml.contains("to: ext:@nestjs/common"), "{}", yaml);
+    }
+
+    #[test]
+    fn test_collapse_delegates_marks_and_rewires_pass_through_method() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("src")).unwrap();
+        std::fs::write(
+            dir.path().join("src/users.ts"),
+            "export class UserService {\n  find(id: number) {\n    return { id };\n  }\n}\nexport class UserController {\n  find(id: number) {\n    return this.service.find(id);\n  }\n}\nexport function handler() {\n  const controller = new UserController();\n  controller.find(1);\n  console.log('done');\n}\n",
+        )
+        .unwrap();
+
+        let symbol = |name: &str| format!("scip-typescript npm pkg 1.0.0 src/`users.ts`/{}", name);
+        let scoped = |name: &str, start: i32, end: i32| scip_proto::Occurrence {
+            range: vec![start, 2, end, 3],
+            ..definition(&symbol(name), start)
+        };
+        let call = |name: &str, line: i32, col: i32| scip_proto::Occurrence {
+            range: vec![line, col, col + 4],
+            symbol: symbol(name),
+            ..Default::default()
+        };
+        let method = |name: &str| scip_proto::SymbolInformation {
+            symbol: symbol(name),
+            kind: scip_proto::symbol_information::Kind::Method as i32,
+            ..Default::default()
+        };
+        let index = scip_proto::Index {
+            documents: vec![scip_proto::Document {
+                relative_path: "src/users.ts".to_string(),
+                occurrences: vec![
+                    scoped("UserService#", 0, 4),
+                    scoped("UserService#find().", 1, 3),
+                    scoped("UserController#", 5, 9),
+                    scoped("UserController#find().", 6, 8),
+                    scoped("handler().", 10, 14),
+                    call("UserService#find().", 7, 24),
+                    call("UserController#find().", 12, 13),
+                ],
+                symbols: vec![
+                    method("UserService#find()."),
+                    method("UserController#find()."),
+                    scip_proto::SymbolInformation {
+                        symbol: symbol("handler()."),
+                        kind: scip_proto::symbol_information::Kind::Function as i32,
+                        ..Default::default()
+                    },
+                ],
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+
+        let config = |collapse_delegates| YcgConfig {
+            project_root: dir.path().to_path_buf(),
+            collapse_delegates,
+            ..test_config(LevelOfDetail::Medium)
+        };
+        let id_of = |graph: &YcgGraph, name: &str| {
+            graph
+                .definitions
+                .iter()
+                .find(|n| n.name == name)
+                .map(|n| n.id.clone())
+                .unwrap()
+        };
+        let calls = |graph: &YcgGraph| {
+            graph
+                .references
+                .iter()
+                .filter(|e| e.edge_type == EdgeType::Calls)
+                .map(|e| (e.from.clone(), e.to.clone()))
+                .collect::<Vec<_>>()
+        };
+
+        let (graph, _) = convert_scip_to_ycg(index.clone(), &config(true));
+        let handler = id_of(&graph, "handler");
+        let controller_find = id_of(&graph, "UserController#find");
+        let service_find = id_of(&graph, "UserService#find");
+        let marker = graph
+            .definitions
+            .iter()
+            .find(|n| n.id == controller_find)
+            .and_then(|n| n.logic.as_ref())
+            .and_then(|l| l.delegates_to.clone());
+        assert_eq!(marker, Some(service_find.clone()));
+        assert_eq!(calls(&graph), vec![(handler.clone(), service_find.clone())]);
+
+        // Off by default: the wrapper stays in the call chain
+        let (graph, _) = convert_scip_to_ycg(index, &config(false));
+        assert_eq!(
+            calls(&graph),
+            vec![
+                (controller_find.clone(), service_find),
+                (handler, controller_find)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_tests_mode_include_exclude_and_separate() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("src")).unwrap();
+        std::fs::write(
+            dir.path().join("src/users.ts"),
+            "export function find(id: number) {\n  return id;\n}\n",
+        )
+        .unwrap();
+        std::fs::write(
+            dir.path().join("src/users.spec.ts"),
+            "export function findsUser() {\n  expect(find(1)).toBe(1);\n}\n",
+        )
+        .unwrap();
+
+        let find = "scip-typescript npm pkg 1.0.0 src/`users.ts`/find().";
+        let spec = "scip-typescript npm pkg 1.0.0 src/`users.spec.ts`/findsUser().";
+        let function = |symbol: &str| scip_proto::SymbolInformation {
+            symbol: symbol.to_string(),
+            kind: scip_proto::symbol_information::Kind::Function as i32,
+            ..Default::default()
+        };
+        let index = scip_proto::Index {
+            documents: vec![
+                scip_proto::Document {
+                    relative_path: "src/users.ts".to_string(),
+                    occurrences: vec![scip_proto::Occurrence {
+                        range: vec![0, 0, 2, 1],
+                        ..definition(find, 0)
+                    }],
+                    symbols: vec![function(find)],
+                    ..Default::default()
+                },
+                scip_proto::Document {
+                    relative_path: "src/users.spec.ts".to_string(),
+                    occurrences: vec![
+                        scip_proto::Occurrence {
+                            range: vec![0, 0, 2, 1],
+                            ..definition(spec, 0)
+                        },
+                        scip_proto::Occurrence {
+                            range: vec![1, 9, 13],
+                            symbol: find.to_string(),
+                            ..Default::default()
+                        },
+                    ],
+                    symbols: vec![function(spec)],
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        };
+        let index_path = dir.path().join("index.scip");
+        std::fs::write(&index_path, index.encode_to_vec()).unwrap();
+        let tests_path = dir.path().join("graph.tests.yaml");
+
+        let run = |tests| {
+            let config = YcgConfig {
+                project_root: dir.path().to_path_buf(),
+                tests,
+                tests_output: Some(tests_path.clone()),
+                ..test_config(LevelOfDetail::Medium)
+            };
+            let output = run_scip_conversion(&index_path, config).unwrap();
+            let graph: YcgGraph = serde_yaml::from_str(&output).unwrap();
+            let mut names: Vec<String> = graph.definitions.into_iter().map(|n| n.name).collect();
+            names.sort();
+            names
+        };
+
+        assert_eq!(
+            run(model::TestFilesMode::Include),
+            vec!["find", "findsUser"]
+        );
+        assert!(!tests_path.exists());
+        assert_eq!(run(model::TestFilesMode::Exclude), vec!["find"]);
+        assert!(!tests_path.exists());
+
+        assert_eq!(run(model::TestFilesMode::Separate), vec!["find"]);
+        let tests_graph: YcgGraph =
+            serde_yaml::from_str(&std::fs::read_to_string(&tests_path).unwrap()).unwrap();
+        let names: Vec<&str> = tests_graph
+            .definitions
+            .iter()
+            .map(|n| n.name.as_str())
+            .collect();
+        assert_eq!(names, vec!["findsUser"]);
+        // The test keeps its edge into production code
+        assert_eq!(tests_graph.references.len(), 1);
+        assert_eq!(
+            tests_graph.references[0].from,
+            tests_graph.definitions[0].id
+        );
+        assert!(tests_graph.references[0].to.starts_with("find_"));
+    }
+
+    #[cfg(feature = "bincode")]
+    #[test]
+    fn test_binary_conversion_decodes_to_the_yaml_output() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("users.ts"),
+            "export class Users {\n  find(id: number) {\n    return id;\n  }\n}\n",
+        )
+        .unwrap();
+        let class = "scip-typescript npm pkg 1.0.0 `users.ts`/Users#";
+        let find = "scip-typescript npm pkg 1.0.0 `users.ts`/Users#find().";
+        let index = scip_proto::Index {
+            documents: vec![scip_proto::Document {
+                relative_path: "users.ts".to_string(),
+                occurrences: vec![
+                    scip_proto::Occurrence {
+                        range: vec![0, 13, 4, 1],
+                        ..definition(class, 0)
+                    },
+                    scip_proto::Occurrence {
+                        range: vec![1, 2, 3, 3],
+                        ..definition(find, 1)
+                    },
+                ],
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        let index_path = dir.path().join("index.scip");
+        std::fs::write(&index_path, index.encode_to_vec()).unwrap();
+        let config = || YcgConfig {
+            project_root: dir.path().to_path_buf(),
+            ..test_config(LevelOfDetail::Medium)
+        };
+
+        let yaml = run_scip_conversion(&index_path, config()).unwrap();
+        let bytes = run_binary_conversion(&index_path, config()).unwrap();
+        assert!(bytes.len() < yaml.len());
+        assert_eq!(binary_graph::decode_to_yaml(&bytes).unwrap(), yaml);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_no_follow_symlinks_skips_symlinked_sources() {
+        let dir = tempfile::tempdir().unwrap();
+        let vendor = dir.path().join("vendor");
+        std::fs::create_dir_all(&vendor).unwrap();
+        std::fs::write(vendor.join("lib.ts"), "export function helper() {}\n").unwrap();
+        std::fs::write(dir.path().join("users.ts"), "export function find() {}\n").unwrap();
+        std::os::unix::fs::symlink(vendor.join("lib.ts"), dir.path().join("lib.ts")).unwrap();
+
+        let find = "scip-typescript npm pkg 1.0.0 `users.ts`/find().";
+        let helper = "scip-typescript npm pkg 1.0.0 `lib.ts`/helper().";
+        let document = |path: &str, symbol: &str| scip_proto::Document {
+            relative_path: path.to_string(),
+            occurrences: vec![definition(symbol, 0)],
+            ..Default::default()
+        };
+        let index = scip_proto::Index {
+            documents: vec![document("users.ts", find), document("lib.ts", helper)],
+            ..Default::default()
+        };
+        let index_path = dir.path().join("index.scip");
+        std::fs::write(&index_path, index.encode_to_vec()).unwrap();
+
+        let names = |follow_symlinks| {
+            let config = YcgConfig {
+                project_root: dir.path().to_path_buf(),
+                follow_symlinks,
+                ..test_config(LevelOfDetail::Medium)
+            };
+            let output = run_scip_conversion(&index_path, config).unwrap();
+            let graph: YcgGraph = serde_yaml::from_str(&output).unwrap();
+            let mut names: Vec<String> = graph.definitions.into_iter().map(|n| n.name).collect();
+            names.sort();
+            names
+        };
+
+        assert_eq!(names(true), vec!["find", "helper"]);
+        assert_eq!(names(false), vec!["find"]);
+    }
+
+    #[test]
+    fn test_conversion_is_reproducible_regardless_of_occurrence_order() {
+        let file = "scip-typescript npm pkg 1.0.0 `users.ts`/";
+        let find = "scip-typescript npm pkg 1.0.0 `users.ts`/Users#find().";
+        let save = "scip-typescript npm pkg 1.0.0 `users.ts`/Users#save().";
+        let helper = "scip-typescript npm pkg 1.0.0 `users.ts`/helper().";
+        let total = "scip-typescript npm pkg 1.0.0 `users.ts`/total.";
+        let external = "scip-typescript npm lodash 4.17.21 `index.d.ts`/chunk().";
+        let reference = |symbol: &str, line: i32| scip_proto::Occurrence {
+            range: vec![line, 4, line, 9],
+            symbol: symbol.to_string(),
+            ..Default::default()
+        };
+        // `find` and `helper` span the same lines: the variable and the
+        // external call on line 2 could belong to either
+        let mut occurrences = vec![
+            definition(file, 0),
+            scip_proto::Occurrence {
+                range: vec![1, 0, 4, 1],
+                ..definition(find, 1)
+            },
+            scip_proto::Occurrence {
+                range: vec![1, 0, 4, 1],
+                ..definition(helper, 1)
+            },
+            scip_proto::Occurrence {
+                range: vec![6, 0, 8, 1],
+                ..definition(save, 6)
+            },
+            definition(total, 2),
+            reference(external, 2),
+            reference(helper, 7),
+        ];
+        let convert = |occurrences: Vec<scip_proto::Occurrence>| {
+            let index = scip_proto::Index {
+                documents: vec![scip_proto::Document {
+                    relative_path: "users.ts".to_string(),
+                    occurrences,
+                    ..Default::default()
+                }],
+                ..Default::default()
+            };
+            let (graph, _) = convert_scip_to_ycg(index, &test_config(LevelOfDetail::High));
+            graph
+        };
+
+        let first = serde_yaml::to_string(&convert(occurrences.clone())).unwrap();
+        assert_eq!(
+            serde_yaml::to_string(&convert(occurrences.clone())).unwrap(),
+            first
+        );
+        assert!(first.contains("ext_"), "{}", first);
+
+        // Same definitions emitted in another order: nodes follow the index,
+        // but anchors, parents and edges must not change
+        let sorted = |mut graph: YcgGraph| {
+            graph.definitions.sort_by(|a, b| a.id.cmp(&b.id));
+            serde_yaml::to_string(&graph).unwrap()
+        };
+        let expected = sorted(convert(occurrences.clone()));
+        occurrences.swap(1, 2);
+        occurrences[3..].reverse();
+        assert_eq!(sorted(convert(occurrences)), expected);
+    }
+
+    #[test]
+    fn test_nested_namespaces_become_modules_with_their_members() {
+        let file = "scip-typescript npm pkg 1.0.0 src/`ns.ts`/";
+        let outer = "scip-typescript npm pkg 1.0.0 src/`ns.ts`/Outer/";
+        let inner = "scip-typescript npm pkg 1.0.0 src/`ns.ts`/Outer/Inner/";
+        let run = "scip-typescript npm pkg 1.0.0 src/`ns.ts`/Outer/Inner/run().";
+        let limit = "scip-typescript npm pkg 1.0.0 src/`ns.ts`/Outer/limit.";
+        let augmented = "scip-typescript npm pkg 1.0.0 src/`ns.ts`/`'express'`/";
+
+        assert_eq!(infer_kind_from_uri(file), ScipSymbolKind::File);
+        assert_eq!(infer_kind_from_uri(outer), ScipSymbolKind::Module);
+        assert_eq!(infer_kind_from_uri(augmented), ScipSymbolKind::Module);
+        assert_eq!(
+            infer_kind_from_uri("scip-typescript npm pkg 1.0.0 `my-dir`/`users.ts`/"),
+            ScipSymbolKind::File
+        );
+
+        let index = scip_proto::Index {
+            documents: vec![scip_proto::Document {
+                relative_path: "src/ns.ts".to_string(),
+                occurrences: vec![
+                    definition(outer, 0),
+                    definition(limit, 1),
+                    definition(inner, 2),
+                    definition(run, 3),
+                ],
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        let (graph, _) = convert_scip_to_ycg(index, &test_config(LevelOfDetail::High));
+        let node = |name: &str| graph.definitions.iter().find(|n| n.name == name).unwrap();
+
+        assert_eq!(node("Outer").kind, ScipSymbolKind::Module);
+        assert_eq!(node("Inner").kind, ScipSymbolKind::Module);
+        assert_eq!(
+            node("Outer").parent_id,
+            Some(generate_anchor("file", xxh64(b"src/ns.ts", 0)))
+        );
+        assert_eq!(node("Inner").parent_id.as_ref(), Some(&node("Outer").id));
+        assert_eq!(node("run").parent_id.as_ref(), Some(&node("Inner").id));
+        assert_eq!(node("limit").parent_id.as_ref(), Some(&node("Outer").id));
+    }
+
+    #[test]
+    fn test_layer_rules_tag_definitions_and_flag_violations() {
+        let dir = tempfile::tempdir().unwrap();
+        let controller =
+            "scip-typescript npm pkg 1.0.0 src/`users.controller.ts`/UsersController#find().";
+        let service = "scip-typescript npm pkg 1.0.0 src/`users.service.ts`/UsersService#find().";
+        let repository =
+            "scip-typescript npm pkg 1.0.0 src/`users.repository.ts`/UsersRepository#find().";
+        let call = |symbol: &str, line: i32| scip_proto::Occurrence {
+            range: vec![line, 4, line, 9],
+            symbol: symbol.to_string(),
+            ..Default::default()
+        };
+        let document = |path: &str, occurrences| scip_proto::Document {
+            relative_path: path.to_string(),
+            occurrences,
+            ..Default::default()
+        };
+        let index = scip_proto::Index {
+            documents: vec![
+                document(
+                    "src/users.controller.ts",
+                    vec![
+                        scip_proto::Occurrence {
+                            range: vec![0, 0, 4, 1],
+                            ..definition(controller, 0)
+                        },
+                        call(service, 1),
+                        call(repository, 2),
+                    ],
+                ),
+                document(
+                    "src/users.service.ts",
+                    vec![
+                        scip_proto::Occurrence {
+                            range: vec![0, 0, 3, 1],
+                            ..definition(service, 0)
+                        },
+                        call(repository, 1),
+                    ],
+                ),
+                document("src/users.repository.ts", vec![definition(repository, 0)]),
+            ],
+            ..Default::default()
+        };
+        let index_path = dir.path().join("index.scip");
+        std::fs::write(&index_path, index.encode_to_vec()).unwrap();
+
+        let rule = |pattern: &str, layer: &str| model::LayerRule {
+            pattern: pattern.to_string(),
+            layer: layer.to_string(),
+        };
+        let config = || YcgConfig {
+            project_root: dir.path().to_path_buf(),
+            layer_rules: vec![
+                rule("src/*.controller.ts", "controller"),
+                rule("src/*.service.ts", "service"),
+                rule("src/*.repository.ts", "repository"),
+            ],
+            layer_dependencies: BTreeMap::from([
+                ("controller".to_string(), vec!["service".to_string()]),
+                ("service".to_string(), vec!["repository".to_string()]),
+            ]),
+            ..test_config(LevelOfDetail::Medium)
+        };
+
+        let output = run_scip_conversion(&index_path, config()).unwrap();
+        let graph: YcgGraph = serde_yaml::from_str(&output).unwrap();
+        let layer = |name: &str| {
+            graph
+                .definitions
+                .iter()
+                .find(|n| n.name == name)
+                .and_then(|n| n.layer.clone())
+        };
+        assert_eq!(layer("UsersController#find").as_deref(), Some("controller"));
+        assert_eq!(layer("UsersRepository#find").as_deref(), Some("repository"));
+
+        let report = run_layer_violations(&index_path, &config()).unwrap();
+        let pairs: Vec<(&str, &str)> = report
+            .violations
+            .iter()
+            .map(|v| (v.from_name.as_str(), v.to_name.as_str()))
+            .collect();
+        assert_eq!(
+            pairs,
+            vec![("UsersController#find", "UsersRepository#find")]
+        );
+    }
+
+    #[test]
+    fn test_split_defs_edges_files_reconstruct_the_graph() {
+        let user = "scip-typescript npm pkg 1.0.0 src/`users.ts`/User#";
+        let service = "scip-typescript npm pkg 1.0.0 src/`users.ts`/UserService#";
+        let index = scip_proto::Index {
+            documents: vec![scip_proto::Document {
+                relative_path: "src/users.ts".to_string(),
+                occurrences: vec![definition(user, 0), definition(service, 5)],
+                symbols: vec![scip_proto::SymbolInformation {
+                    symbol: service.to_string(),
+                    relationships: vec![scip_proto::Relationship {
+                        symbol: user.to_string(),
+                        is_reference: true,
+                        ..Default::default()
+                    }],
+                    ..Default::default()
+                }],
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        let (graph, _) = convert_scip_to_ycg(index, &test_config(LevelOfDetail::High));
+        assert!(!graph.references.is_empty());
+
+        let out_dir = tempfile::TempDir::new().unwrap();
+        write_defs_edges_split(&graph, out_dir.path(), false).unwrap();
+        let read = |file: &str| -> YcgGraph {
+            serde_yaml::from_str(&fs::read_to_string(out_dir.path().join(file)).unwrap()).unwrap()
+        };
+        let defs = read("defs.yaml");
+        let edges = read("edges.yaml");
+        assert!(defs.references.is_empty());
+        assert!(edges.definitions.is_empty());
+
+        let rebuilt = YcgGraph {
+            metadata: defs.metadata,
+            definitions: defs.definitions,
+            references: edges.references,
+        };
+        assert_eq!(
+            serde_yaml::to_string(&rebuilt).unwrap(),
+            serde_yaml::to_string(&graph).unwrap()
+        );
+
+        // Compact: edges as an adjacency list
+        write_defs_edges_split(&graph, out_dir.path(), true).unwrap();
+        let adjacency: YcgGraphOptimized =
+            serde_yaml::from_str(&fs::read_to_string(out_dir.path().join("edges.yaml")).unwrap())
+                .unwrap();
+        assert_eq!(adjacency.adjacency.len(), 1);
+        assert!(adjacency.definitions.is_empty());
+    }
+
+    #[test]
+    fn test_rejected_variable_signature_is_logged_as_json_event() {
+        let dir = tempfile::tempdir().unwrap();
+        let log_path = dir.path().join("events.ndjson");
+        let log = EventLog::create(&log_path).unwrap();
+        let symbol = "scip-typescript npm pkg 1.0.0 src/`users.ts`/handler.";
+
+        let sig = validate_variable_signature(
+            Some("async function handler(): Promise<void>".to_string()),
+            symbol,
+            &log,
+        );
+        assert_eq!(sig, None);
+        let kept = validate_variable_signature(Some("limit: number".to_string()), symbol, &log);
+        assert_eq!(kept.as_deref(), Some("limit: number"));
+
+        let text = fs::read_to_string(&log_path).unwrap();
+        let events: Vec<serde_json::Value> = text
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(
+            events,
+            vec![serde_json::json!({
+                "event": "signature_rejected",
+                "symbol": symbol,
+                "signature": "async function handler(): Promise<void>",
+                "reason": "contains 'function'",
+            })]
+        );
+    }
+
+    #[test]
+    fn test_signature_coverage_counts_enriched_methods_per_file() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("src")).unwrap();
+        std::fs::write(
+            dir.path().join("src/users.ts"),
+            "export class Users {}\nexport function find(id: number): Users {\n  return new Users();\n}\nexport function save(user: Users): void {\n  return;\n}\n",
+        )
+        .unwrap();
+        std::fs::write(
+            dir.path().join("src/orders.ts"),
+            "export function place(): void {\n  return;\n}\n",
+        )
+        .unwrap();
+
+        let symbol = |path: &str, descriptor: &str| {
+            format!(
+                "scip-typescript npm pkg 1.0.0 src/`{}`/{}",
+                path, descriptor
+            )
+        };
+        let function = |symbol: &str| scip_proto::SymbolInformation {
+            symbol: symbol.to_string(),
+            kind: scip_proto::symbol_information::Kind::Function as i32,
+            ..Default::default()
+        };
+        let users = symbol("users.ts", "Users#");
+        let find = symbol("users.ts", "find().");
+        let save = symbol("users.ts", "save().");
+        // Stale index: the definition line is past the end of the file
+        let removed = symbol("users.ts", "removed().");
+        let place = symbol("orders.ts", "place().");
+        let index = scip_proto::Index {
+            documents: vec![
+                scip_proto::Document {
+                    relative_path: "src/users.ts".to_string(),
+                    occurrences: vec![
+                        definition(&users, 0),
+                        definition(&find, 1),
+                        definition(&save, 4),
+                        definition(&removed, 20),
+                    ],
+                    symbols: vec![function(&find), function(&save), function(&removed)],
+                    ..Default::default()
+                },
+                scip_proto::Document {
+                    relative_path: "src/orders.ts".to_string(),
+                    occurrences: vec![definition(&place, 0)],
+                    symbols: vec![function(&place)],
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        };
+
+        let config = YcgConfig {
+            project_root: dir.path().to_path_buf(),
+            ..test_config(LevelOfDetail::Medium)
+        };
+        let (graph, symbol_index) = convert_scip_to_ycg(index, &config);
+        let report = signature_coverage::SignatureCoverageReport::build(&graph, &symbol_index);
+
+        let coverage = |eligible, with_signature| signature_coverage::Coverage {
+            eligible,
+            with_signature,
+        };
+        assert_eq!(report.files["src/users.ts"], coverage(3, 2));
+        assert_eq!(report.files["src/orders.ts"], coverage(1, 1));
+        assert_eq!(report.total(), coverage(4, 3));
+        let text = report.to_string();
+        assert!(
+            text.contains("--- Signature coverage: 3/4 (75.0%) ---"),
+            "{}",
+            text
+        );
+        assert!(text.contains("  src/users.ts: 2/3 (66.7%)"), "{}", text);
+    }
+
+    #[test]
+    fn test_inline_source_only_for_matching_definitions() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("src")).unwrap();
+        let find_body = "find(id: number): User {\n    if (id <= 0) {\n      throw new Error();\n    }\n    return this.repo.get(id);\n  }";
+        std::fs::write(
+            dir.path().join("src/users.ts"),
+            format!(
+                "export class UserService {{\n  {}\n  save(user: User): void {{\n    this.repo.put(user);\n  }}\n}}\n",
+                find_body
+            ),
+        )
+        .unwrap();
+
+        let symbol = |descriptor: &str| {
+            format!(
+                "scip-typescript npm pkg 1.0.0 src/`users.ts`/{}",
+                descriptor
+            )
+        };
+        let method = |symbol: &str| scip_proto::SymbolInformation {
+            symbol: symbol.to_string(),
+            kind: scip_proto::symbol_information::Kind::Method as i32,
+            ..Default::default()
+        };
+        let class = symbol("UserService#");
+        let find = symbol("UserService#find().");
+        let save = symbol("UserService#save().");
+        let index = scip_proto::Index {
+            documents: vec![scip_proto::Document {
+                relative_path: "src/users.ts".to_string(),
+                occurrences: vec![
+                    definition(&class, 0),
+                    definition(&find, 1),
+                    definition(&save, 7),
+                ],
+                symbols: vec![method(&find), method(&save)],
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+
+        let config = YcgConfig {
+            project_root: dir.path().to_path_buf(),
+            inline_source: vec!["*#find".to_string()],
+            ..test_config(LevelOfDetail::Medium)
+        };
+        let (graph, _) = convert_scip_to_ycg(index, &config);
+
+        let source_of = |name: &str| {
+            graph
+                .definitions
+                .iter()
+                .find(|n| n.name == name)
+                .unwrap()
+                .source
+                .clone()
+        };
+        assert_eq!(source_of("UserService#find").as_deref(), Some(find_body));
+        assert_eq!(source_of("UserService#save"), None);
+        assert_eq!(source_of("UserService"), None);
+
+        let long = "x".repeat(INLINE_SOURCE_MAX_BYTES + 10);
+        assert!(truncate_inline_source(long).ends_with(&format!(
+            "\n... (truncated, {} bytes)",
+            INLINE_SOURCE_MAX_BYTES + 10
+        )));
+    }
+
+    #[test]
+    fn test_type_parameters_are_filtered_below_high_lod() {
+        let identity = "scip-typescript npm pkg 1.0.0 src/`util.ts`/identity().";
+        let type_param = "scip-typescript npm pkg 1.0.0 src/`util.ts`/identity().[T]";
+        let boxed = "scip-typescript npm pkg 1.0.0 src/`util.ts`/Box#";
+        let box_param = "scip-typescript npm pkg 1.0.0 src/`util.ts`/Box#[Item]";
+
+        assert!(is_type_parameter_uri(type_param));
+        assert!(is_type_parameter_uri(box_param));
+        assert!(is_type_parameter_uri(
+            "rust-analyzer cargo zoo 0.1.0 zoo/Wrapper#[T]"
+        ));
+        assert!(!is_type_parameter_uri(
+            "rust-analyzer cargo zoo 0.1.0 zoo/impl#[Dog][Speak]"
+        ));
+        assert!(!is_type_parameter_uri(
+            "rust-analyzer cargo zoo 0.1.0 zoo/impl#[Dog]"
+        ));
+        assert!(!is_type_parameter_uri(identity));
+
+        let index = || scip_proto::Index {
+            documents: vec![scip_proto::Document {
+                relative_path: "src/util.ts".to_string(),
+                occurrences: vec![
+                    definition(identity, 0),
+                    definition(type_param, 0),
+                    definition(boxed, 4),
+                    definition(box_param, 4),
+                ],
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        let names = |lod| {
+            let (graph, _) = convert_scip_to_ycg(index(), &test_config(lod));
+            graph
+                .definitions
+                .into_iter()
+                .map(|n| n.name)
+                .filter(|name| !name.contains('/'))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(names(LevelOfDetail::Medium), vec!["identity", "Box"]);
+        assert_eq!(names(LevelOfDetail::Low), vec!["identity", "Box"]);
+        let high = names(LevelOfDetail::High);
+        assert_eq!(high.len(), 4, "{:?}", high);
+    }
+
+    #[test]
+    fn test_windows_document_paths_are_normalized() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("src/users/dto")).unwrap();
+        std::fs::write(
+            dir.path().join("src/users/users.service.ts"),
+            "export class UserService {\n  find(id: number) {\n    return id;\n  }\n}\n",
+        )
+        .unwrap();
+        std::fs::write(dir.path().join("src/users/users.test.ts"), "test();\n").unwrap();
+        std::fs::write(
+            dir.path().join("src/users/dto/create-user.dto.ts"),
+            "export class CreateUserDto {}\n",
+        )
+        .unwrap();
+
+        let service = "scip-typescript npm pkg 1.0.0 src/users/`users.service.ts`/UserService#";
+        let find = "scip-typescript npm pkg 1.0.0 src/users/`users.service.ts`/UserService#find().";
+        let spec = "scip-typescript npm pkg 1.0.0 src/users/`users.test.ts`/UserSpec#";
+        let dto = "scip-typescript npm pkg 1.0.0 src/users/dto/`create-user.dto.ts`/CreateUserDto#";
+        let document = |path: &str, occurrences| scip_proto::Document {
+            relative_path: path.to_string(),
+            occurrences,
+            ..Default::default()
+        };
+        let index = scip_proto::Index {
+            documents: vec![
+                document(
+                    "src\\users\\users.service.ts",
+                    vec![
+                        definition(service, 0),
+                        scip_proto::Occurrence {
+                            range: vec![1, 2, 3, 3],
+                            ..definition(find, 1)
+                        },
+                    ],
+                ),
+                document("src\\users\\users.test.ts", vec![definition(spec, 0)]),
+                document(
+                    "src\\users\\dto\\create-user.dto.ts",
+                    vec![definition(dto, 0)],
+                ),
+            ],
+            ..Default::default()
+        };
+        let scip_path = dir.path().join("index.scip");
+        std::fs::write(&scip_path, index.encode_to_vec()).unwrap();
+
+        let loaded = load_scip_index(&scip_path, false, &EventLog::default()).unwrap();
+        let paths: Vec<&str> = loaded
+            .documents
+            .iter()
+            .map(|doc| doc.relative_path.as_str())
+            .collect();
+        assert_eq!(
+            paths,
+            vec![
+                "src/users/users.service.ts",
+                "src/users/users.test.ts",
+                "src/users/dto/create-user.dto.ts"
+            ]
+        );
+        assert!(framework_filter::FrameworkNoiseFilter::is_dto_file(
+            Path::new(paths[2])
+        ));
+
+        let config = YcgConfig {
+            project_root: dir.path().to_path_buf(),
+            tests: model::TestFilesMode::Exclude,
+            ..test_config(LevelOfDetail::Medium)
+        };
+        let output = run_scip_conversion(&scip_path, config).unwrap();
+        let graph: YcgGraph = serde_yaml::from_str(&output).unwrap();
+
+        assert!(
+            !graph.definitions.iter().any(|n| n.name == "UserSpec"),
+            "test file must be filtered: {}",
+            output
+        );
+        let find = graph
+            .definitions
+            .iter()
+            .find(|n| n.name.ends_with("find"))
+            .expect("find");
+        assert!(
+            find.signature
+                .as_deref()
+                .is_some_and(|sig| sig.contains("id: number")),
+            "source must resolve: {}",
+            output
+        );
+    }
+
+    #[test]
+    fn test_serialize_graph_to_yaml_json_and_adhoc() {
+        let service = "scip-typescript npm pkg 1.0.0 src/`users.ts`/UserService#";
+        let find = "scip-typescript npm pkg 1.0.0 src/`users.ts`/UserService#find().";
+        let index = scip_proto::Index {
+            documents: vec![scip_proto::Document {
+                relative_path: "src/users.ts".to_string(),
+                occurrences: vec![
+                    definition(service, 0),
+                    definition(find, 1),
+                    scip_proto::Occurrence {
+                        range: vec![5, 4, 8],
+                        symbol: find.to_string(),
+                        ..Default::default()
+                    },
+                ],
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        let (graph, _) = convert_scip_to_ycg(index, &test_config(LevelOfDetail::High));
+        let opts = SerializeOpts::default();
+
+        let ids = |graph: &YcgGraph| -> Vec<String> {
+            graph.definitions.iter().map(|n| n.id.clone()).collect()
+        };
+        let yaml = serialize_graph(&graph, model::OutputFormat::Yaml, &opts).unwrap();
+        let from_yaml: YcgGraph = serde_yaml::from_str(&yaml).unwrap();
+        assert_eq!(ids(&from_yaml), ids(&graph));
+        assert_eq!(from_yaml.references, graph.references);
+
+        let json = serialize_graph(&graph, model::OutputFormat::Json, &opts).unwrap();
+        let from_json: YcgGraph = serde_json::from_str(&json).unwrap();
+        assert_eq!(ids(&from_json), ids(&graph));
+        assert_eq!(from_json.references, graph.references);
+
+        let adhoc = serialize_graph(&graph, model::OutputFormat::AdHoc, &opts).unwrap();
+        let find_node = graph
+            .definitions
+            .iter()
+            .find(|n| n.name.ends_with("find"))
+            .unwrap();
+        assert!(
+            adhoc.contains(&format!("{}|{}|method", find_node.id, find_node.name)),
+            "{}",
+            adhoc
+        );
+    }
+
+    #[test]
+    fn test_inline_edges_embed_outgoing_edges_in_each_definition() {
+        let edge = |from: &str, to: &str, edge_type| ReferenceEdge {
+            from: from.to_string(),
+            to: to.to_string(),
+            edge_type,
+            call_text: None,
+        };
+        let node = |id: &str| SymbolNode {
+            id: id.to_string(),
+            name: id.to_string(),
+            kind: ScipSymbolKind::Function,
+            ..Default::default()
+        };
+        let graph = YcgGraph {
+            metadata: ProjectMetadata::default(),
+            definitions: vec![node("main_0"), node("load_1"), node("parse_2")],
+            references: vec![
+                edge("main_0", "parse_2", EdgeType::Calls),
+                edge("main_0", "load_1", EdgeType::Calls),
+                edge("main_0", "fs_9", EdgeType::Imports),
+                edge("load_1", "parse_2", EdgeType::Calls),
+            ],
+        };
+        let opts = SerializeOpts {
+            inline_edges: true,
+            ..SerializeOpts::default()
+        };
+
+        let yaml = serialize_graph(&graph, model::OutputFormat::Yaml, &opts).unwrap();
+        let value: serde_yaml::Value = serde_yaml::from_str(&yaml).unwrap();
+        assert!(value.get("graph").is_none(), "{}", yaml);
+        let defs = value["_defs"].as_sequence().unwrap();
+        let targets = |i: usize, edge_type: &str| -> Vec<String> {
+            defs[i]
+                .get(edge_type)
+                .and_then(|v| v.as_sequence())
+                .map(|ids| {
+                    ids.iter()
+                        .map(|id| id.as_str().unwrap().to_string())
+                        .collect()
+                })
+                .unwrap_or_default()
+        };
+        assert_eq!(targets(0, "calls"), vec!["load_1", "parse_2"]);
+        assert_eq!(targets(0, "imports"), vec!["fs_9"]);
+        assert_eq!(targets(1, "calls"), vec!["parse_2"]);
+        assert!(targets(2, "calls").is_empty());
+        assert_eq!(defs[0]["n"].as_str(), Some("main_0"));
+    }
+
+    #[test]
+    fn test_skip_generated_drops_marked_sources() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("api.ts"),
+            "// @generated by openapi-generator\nexport function getUser() {}\n",
+        )
+        .unwrap();
+        std::fs::write(
+            dir.path().join("users.ts"),
+            "// Users service\nexport function find() {}\n",
+        )
+        .unwrap();
+
+        let find = "scip-typescript npm pkg 1.0.0 `users.ts`/find().";
+        let get_user = "scip-typescript npm pkg 1.0.0 `api.ts`/getUser().";
+        let document = |path: &str, symbol: &str| scip_proto::Document {
+            relative_path: path.to_string(),
+            occurrences: vec![definition(symbol, 1)],
+            ..Default::default()
+        };
+        let index = scip_proto::Index {
+            documents: vec![document("users.ts", find), document("api.ts", get_user)],
+            ..Default::default()
+        };
+        let index_path = dir.path().join("index.scip");
+        std::fs::write(&index_path, index.encode_to_vec()).unwrap();
+
+        let names = |skip_generated| {
+            let config = YcgConfig {
+                project_root: dir.path().to_path_buf(),
+                skip_generated,
+                ..test_config(LevelOfDetail::Medium)
+            };
+            let output = run_scip_conversion(&index_path, config).unwrap();
+            let graph: YcgGraph = serde_yaml::from_str(&output).unwrap();
+            let mut names: Vec<String> = graph.definitions.into_iter().map(|n| n.name).collect();
+            names.sort();
+            names
+        };
+
+        assert_eq!(names(false), vec!["find", "getUser"]);
+        assert_eq!(names(true), vec!["find"]);
+    }
+
+    #[test]
+    fn test_file_paths_nest_files_by_directory() {
+        let file = |path: &str| {
+            let (dir, name) = path.rsplit_once('/').unwrap_or(("", path));
+            let dir = if dir.is_empty() {
+                String::new()
+            } else {
+                format!("{}/", dir)
+            };
+            format!("scip-typescript npm pkg 1.0.0 {}`{}`/", dir, name)
+        };
+        let document = |path: &str| scip_proto::Document {
+            relative_path: path.to_string(),
+            occurrences: vec![definition(&file(path), 0)],
+            ..Default::default()
+        };
+        let index = scip_proto::Index {
+            documents: vec![
+                document("src/users/users.service.ts"),
+                document("src/users/users.controller.ts"),
+                document("src/main.ts"),
+                document("index.ts"),
+            ],
+            ..Default::default()
+        };
+        let config = YcgConfig {
+            file_paths: true,
+            ..test_config(LevelOfDetail::Medium)
+        };
+        let filters = compiled_filters::CompiledFilters::new(&config).unwrap();
+        let (graph, _) = build_graph(index, &config, &filters).unwrap();
+        let node = |path: &str| {
+            graph
+                .definitions
+                .iter()
+                .find(|n| n.path.as_deref() == Some(path))
+                .unwrap_or_else(|| panic!("no node for {path}"))
+        };
+
+        for path in [
+            "src/users/users.service.ts",
+            "src/users/users.controller.ts",
+        ] {
+            assert_eq!(node(path).kind, ScipSymbolKind::File);
+            assert_eq!(node(path).parent_id.as_deref(), Some("src/users"));
+        }
+        assert_eq!(node("src/main.ts").parent_id.as_deref(), Some("src"));
+        assert_eq!(node("index.ts").parent_id, None);
+        assert_eq!(node("src/users").kind, ScipSymbolKind::Module);
+        assert_eq!(node("src/users").parent_id.as_deref(), Some("src"));
+        assert_eq!(node("src").parent_id, None);
+        validators::validate_definitions(&graph.definitions).unwrap();
+
+        // Without the flag File nodes carry no path
+        let (graph, _) = build_graph(
+            scip_proto::Index {
+                documents: vec![document("src/main.ts")],
+                ..Default::default()
+            },
+            &test_config(LevelOfDetail::Medium),
+            &filters,
+        )
+        .unwrap();
+        assert!(graph.definitions.iter().all(|n| n.path.is_none()));
+    }
+
+    #[test]
+    fn test_streaming_edges_match_in_memory_edges() {
+        let helper = "scip-typescript npm pkg 1.0.0 `util.ts`/helper().";
+        let format = "scip-typescript npm pkg 1.0.0 `util.ts`/format().";
+        let find = "scip-typescript npm pkg 1.0.0 `users.ts`/find().";
+        let save = "scip-typescript npm pkg 1.0.0 `orders.ts`/save().";
+        let reference = |symbol: &str, line| scip_proto::Occurrence {
+            range: vec![line, 4, 10],
+            symbol: symbol.to_string(),
+            ..Default::default()
+        };
+        let index = scip_proto::Index {
+            documents: vec![
+                scip_proto::Document {
+                    relative_path: "util.ts".to_string(),
+                    occurrences: vec![
+                        definition(helper, 0),
+                        definition(format, 4),
+                        reference(helper, 5),
+                    ],
+                    ..Default::default()
+                },
+                scip_proto::Document {
+                    relative_path: "users.ts".to_string(),
+                    occurrences: vec![
+                        definition(find, 0),
+                        reference(helper, 1),
+                        reference(helper, 2),
+                        reference(format, 3),
+                    ],
+                    ..Default::default()
+                },
+                scip_proto::Document {
+                    relative_path: "orders.ts".to_string(),
+                    occurrences: vec![definition(save, 0), reference(helper, 1)],
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        };
+        let edges = |streaming_edges| {
+            let config = YcgConfig {
+                streaming_edges,
+                ..test_config(LevelOfDetail::Medium)
+            };
+            convert_scip_to_ycg(index.clone(), &config).0.references
+        };
+
+        let in_memory = edges(false);
+        assert!(in_memory.len() >= 3, "{in_memory:?}");
+        assert_eq!(edges(true), in_memory);
+    }
+
+    #[test]
+    fn test_kind_prefixed_anchors_resolve() {
+        let user = "scip-typescript npm pkg 1.0.0 `users.ts`/User#";
+        let find = "scip-typescript npm pkg 1.0.0 `users.ts`/User#find().";
+        let get_user = "scip-typescript npm pkg 1.0.0 `users.ts`/getUser().";
+        let index = scip_proto::Index {
+            documents: vec![scip_proto::Document {
+                relative_path: "users.ts".to_string(),
+                occurrences: vec![
+                    scip_proto::Occurrence {
+                        range: vec![0, 0, 4, 1],
+                        symbol: user.to_string(),
+                        symbol_roles: scip_proto::SymbolRole::Definition as i32,
+                        ..Default::default()
+                    },
+                    definition(find, 1),
+                    definition(get_user, 6),
+                    scip_proto::Occurrence {
+                        range: vec![6, 20, 24],
+                        symbol: find.to_string(),
+                        ..Default::default()
+                    },
+                ],
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        let config = YcgConfig {
+            kind_prefixed_anchors: true,
+            ..test_config(LevelOfDetail::Medium)
+        };
+        let (graph, symbol_index) = convert_scip_to_ycg(index, &config);
+        let node = |name: &str| graph.definitions.iter().find(|n| n.name == name).unwrap();
+
+        assert!(node("User").id.starts_with("cls:User_"));
+        assert!(node("User#find").id.starts_with("meth:User_find_"));
+        for node in &graph.definitions {
+            let tag = anchor_prefix::kind_tag(&node.kind);
+            assert!(node.id.starts_with(&format!("{}:", tag)), "{}", node.id);
+            assert!(symbol_index.lines.contains_key(&node.id));
+        }
+        assert_eq!(node("User#find").parent_id.as_ref(), Some(&node("User").id));
+        assert!(graph.references.iter().any(|edge| {
+            edge.from == node("getUser").id
+                && edge.to == node("User#find").id
+                && edge.edge_type == EdgeType::Calls
+        }));
+    }
+
+    #[test]
+    fn test_repeated_edges_serialize_weighted() {
+        let helper = "scip-typescript npm pkg 1.0.0 `util.ts`/helper().";
+        let run = "scip-typescript npm pkg 1.0.0 `util.ts`/run().";
+        let mut occurrences = vec![
+            definition(helper, 0),
+            scip_proto::Occurrence {
+                range: vec![2, 0, 8, 1],
+                symbol: run.to_string(),
+                symbol_roles: scip_proto::SymbolRole::Definition as i32,
+                ..Default::default()
+            },
+        ];
+        for line in 3..8 {
+            occurrences.push(scip_proto::Occurrence {
+                range: vec![line, 4, 10],
+                symbol: helper.to_string(),
+                ..Default::default()
+            });
+        }
+        let index = scip_proto::Index {
+            documents: vec![scip_proto::Document {
+                relative_path: "util.ts".to_string(),
+                occurrences,
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+
+        let deduped = convert_scip_to_ycg(index.clone(), &test_config(LevelOfDetail::Medium)).0;
+        assert_eq!(deduped.references.len(), 1);
+
+        let config = YcgConfig {
+            dedupe_edges: false,
+            weighted_edges: true,
+            ..test_config(LevelOfDetail::Medium)
+        };
+        let (graph, _) = convert_scip_to_ycg(index, &config);
+        assert_eq!(graph.references.len(), 5);
+        let (from, to) = (&graph.references[0].from, &graph.references[0].to);
+
+        let flat = serialize_graph(
+            &graph,
+            model::OutputFormat::Yaml,
+            &SerializeOpts::from_config(&config),
+        )
+        .unwrap();
+        let value: serde_yaml::Value = serde_yaml::from_str(&flat).unwrap();
+        let edges = value["graph"].as_sequence().unwrap();
+        assert_eq!(edges.len(), 1, "{flat}");
+        assert_eq!(edges[0]["from"].as_str(), Some(from.as_str()));
+        assert_eq!(edges[0]["count"].as_u64(), Some(5));
+
+        let compact = serialize_graph(
+            &graph,
+            model::OutputFormat::Yaml,
+            &SerializeOpts {
+                compact: true,
+                ..SerializeOpts::from_config(&config)
+            },
+        )
+        .unwrap();
+        let value: serde_yaml::Value = serde_yaml::from_str(&compact).unwrap();
+        assert_eq!(
+            value["graph"][from.as_str()]["calls"][0].as_str(),
+            Some(format!("{} (x5)", to).as_str())
+        );
+    }
+
+    #[test]
+    fn test_neutral_signatures_option() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("users.ts"),
+            "export class Users {\n  async findOne(id: number): Promise<void> {\n    await db.touch(id);\n  }\n}\n",
+        )
+        .unwrap();
+        std::fs::write(
+            dir.path().join("users.rs"),
+            "pub struct Users;\nimpl Users {\n    pub async fn find_one(&self, id: i64) {\n        db::touch(id).await\n    }\n}\n",
+        )
+        .unwrap();
+        let index = scip_proto::Index {
+            documents: vec![
+                scip_proto::Document {
+                    relative_path: "users.ts".to_string(),
+                    occurrences: vec![
+                        definition("scip-typescript npm pkg 1.0.0 `users.ts`/Users#", 0),
+                        definition(
+                            "scip-typescript npm pkg 1.0.0 `users.ts`/Users#findOne().",
+                            1,
+                        ),
+                    ],
+                    ..Default::default()
+                },
+                scip_proto::Document {
+                    relative_path: "users.rs".to_string(),
+                    occurrences: vec![
+                        definition("rust-analyzer cargo pkg 1.0.0 users/Users#", 0),
+                        definition("rust-analyzer cargo pkg 1.0.0 users/Users#find_one().", 2),
+                    ],
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        };
+        let mut config = test_config(LevelOfDetail::High);
+        config.project_root = dir.path().to_path_buf();
+        config.neutral_signatures = true;
+        let filters = compiled_filters::CompiledFilters::new(&config).unwrap();
+        let (graph, _) = build_graph(index, &config, &filters).unwrap();
+
+        let signatures: Vec<&str> = graph
+            .definitions
+            .iter()
+            .filter(|n| n.kind == ScipSymbolKind::Method)
+            .filter_map(|n| n.signature.as_deref())
+            .collect();
+        assert_eq!(signatures, vec!["find_one(id: int)", "find_one(id: int)"]);
+    }
+
+    #[test]
+    fn test_variable_access_produces_writes_and_reads_edges() {
+        let count = "scip-typescript npm pkg 1.0.0 `counter.ts`/Counter#count.";
+        let reset = "scip-typescript npm pkg 1.0.0 `counter.ts`/Counter#reset().";
+        let value = "scip-typescript npm pkg 1.0.0 `counter.ts`/Counter#value().";
+        let access = |symbol: &str, line, role: scip_proto::SymbolRole| scip_proto::Occurrence {
+            range: vec![line, 4, 9],
+            symbol: symbol.to_string(),
+            symbol_roles: role as i32,
+            ..Default::default()
+        };
+        // Bodies spanning several lines, so the accesses fall inside them
+        let method = |symbol: &str, start, end| scip_proto::Occurrence {
+            range: vec![start, 2, end, 3],
+            ..definition(symbol, start)
+        };
+        let index = scip_proto::Index {
+            documents: vec![scip_proto::Document {
+                relative_path: "counter.ts".to_string(),
+                occurrences: vec![
+                    method(reset, 0, 2),
+                    access(count, 1, scip_proto::SymbolRole::WriteAccess),
+                    method(value, 3, 5),
+                    access(count, 4, scip_proto::SymbolRole::ReadAccess),
+                    definition(count, 30),
+                ],
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        let (graph, _) = convert_scip_to_ycg(index, &test_config(LevelOfDetail::High));
+        let anchor = |name: &str| {
+            graph
+                .definitions
+                .iter()
+                .find(|n| n.name.ends_with(name))
+                .map(|n| n.id.clone())
+                .unwrap()
+        };
+        let edge_to_count = |from: &str| {
+            graph
+                .references
+                .iter()
+                .find(|e| e.from == anchor(from) && e.to == anchor("count"))
+                .map(|e| e.edge_type.clone())
+        };
+        assert_eq!(edge_to_count("reset"), Some(EdgeType::Writes));
+        assert_eq!(edge_to_count("value"), Some(EdgeType::Reads));
+
+        let yaml = serde_yaml::to_string(&graph).unwrap();
+        assert!(yaml.contains("type: writes"));
+        assert!(yaml.contains("type: reads"));
+    }
+
+    #[test]
+    fn test_documents_resolve_across_source_roots() {
+        let repo = tempfile::tempdir().unwrap();
+        let api = repo.path().join("services/api");
+        let web = repo.path().join("frontends/web");
+        std::fs::create_dir_all(api.join("src")).unwrap();
+        std::fs::create_dir_all(web.join("src")).unwrap();
+        std::fs::write(
+            api.join("src/users.ts"),
+            "export function findUser(id: number): User {\n  return db.get(id);\n}\n",
+        )
+        .unwrap();
+        std::fs::write(
+            web.join("src/app.ts"),
+            "export function render(root: Element): void {\n  mount(root);\n}\n",
+        )
+        .unwrap();
+
+        let document = |path: &str, symbol: &str| scip_proto::Document {
+            relative_path: path.to_string(),
+            occurrences: vec![definition(symbol, 0)],
+            ..Default::default()
+        };
+        let index = scip_proto::Index {
+            documents: vec![
+                document(
+                    "src/users.ts",
+                    "scip-typescript npm api 1.0.0 src/`users.ts`/findUser().",
+                ),
+                document(
+                    "src/app.ts",
+                    "scip-typescript npm web 1.0.0 src/`app.ts`/render().",
+                ),
+            ],
+            ..Default::default()
+        };
+        let mut config = test_config(LevelOfDetail::High);
+        config.project_root = api;
+        config.source_roots = vec![web];
+        let (graph, _) = convert_scip_to_ycg(index, &config);
+
+        let signature = |name: &str| {
+            graph
+                .definitions
+                .iter()
+                .find(|n| n.name == name)
+                .and_then(|n| n.signature.clone())
+        };
+        assert_eq!(
+            signature("findUser").as_deref(),
+            Some("function findUser(id: number): User")
+        );
+        assert_eq!(
+            signature("render").as_deref(),
+            Some("function render(root: Element): void")
+        );
+    }
+
+    #[test]
+    fn test_keep_scip_symbols_preserves_uri() {
+        let symbol = "scip-typescript npm app 1.0.0 src/`users.ts`/UserService#";
+        let index = || scip_proto::Index {
+            documents: vec![scip_proto::Document {
+                relative_path: "src/users.ts".to_string(),
+                occurrences: vec![definition(symbol, 0)],
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        let mut config = test_config(LevelOfDetail::High);
+        let (graph, _) = convert_scip_to_ycg(index(), &config);
+        assert_eq!(graph.definitions[0].scip_symbol, None);
+
+        config.keep_scip_symbols = true;
+        let (graph, _) = convert_scip_to_ycg(index(), &config);
+        let node = &graph.definitions[0];
+        assert_eq!(node.name, "UserService");
+        assert_eq!(node.scip_symbol.as_deref(), Some(symbol));
+        assert!(
+            serde_yaml::to_string(&graph)
+                .unwrap()
+                .contains("scip_symbol: scip-typescript npm app 1.0.0 src/`users.ts`/UserService#")
+        );
+    }
+
+    #[test]
+    fn test_compact_names_keep_index_and_module_split() {
+        let controller =
+            "scip-typescript npm pkg 1.0.0 src/users/`users.controller.ts`/UsersController#";
+        let service = "scip-typescript npm pkg 1.0.0 src/users/`users.service.ts`/UsersService#";
+        let dir = tempfile::tempdir().unwrap();
+        let index = scip_proto::Index {
+            documents: vec![
+                scip_proto::Document {
+                    relative_path: "src/users/users.controller.ts".to_string(),
+                    occurrences: vec![definition(controller, 2)],
+                    ..Default::default()
+                },
+                scip_proto::Document {
+                    relative_path: "src/users/users.service.ts".to_string(),
+                    occurrences: vec![definition(service, 4)],
+                    ..Default::default()
+                },
+                scip_proto::Document {
+                    relative_path: "src/orders/orders.ts".to_string(),
+                    occurrences: vec![definition(
+                        "scip-typescript npm pkg 1.0.0 src/orders/`orders.ts`/Order#",
+                        0,
+                    )],
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        };
+        let index_path = dir.path().join("index.scip");
+        std::fs::write(&index_path, index.encode_to_vec()).unwrap();
+        let symbols_path = dir.path().join("symbols.json");
+        let split_dir = dir.path().join("modules");
+
+        let config = YcgConfig {
+            project_root: dir.path().to_path_buf(),
+            compact_names: true,
+            emit_index: Some(symbols_path.clone()),
+            split_by_module: Some(split_dir.clone()),
+            ..test_config(LevelOfDetail::High)
+        };
+        let output = run_scip_conversion(&index_path, config).unwrap();
+        let graph: YcgGraph = serde_yaml::from_str(&output).unwrap();
+        let anchor = |name: &str| {
+            graph
+                .definitions
+                .iter()
+                .find(|n| n.name == name)
+                .unwrap_or_else(|| panic!("missing node {}", name))
+                .id
+                .clone()
+        };
+        let (ctrl, svc) = (anchor("UsersCtrl"), anchor("UsersSvc"));
+        assert!(ctrl.starts_with("UsersCtrl_"), "{}", ctrl);
+
+        let symbol_index: model::SymbolIndex =
+            serde_json::from_str(&std::fs::read_to_string(&symbols_path).unwrap()).unwrap();
+        assert_eq!(
+            symbol_index.files["src/users/users.controller.ts"],
+            vec![ctrl.clone()]
+        );
+        assert_eq!(
+            symbol_index.files["src/users/users.service.ts"],
+            vec![svc.clone()]
+        );
+        assert_eq!(symbol_index.lines[&ctrl], 3);
+
+        let users: YcgGraph =
+            serde_yaml::from_str(&std::fs::read_to_string(split_dir.join("users.yaml")).unwrap())
+                .unwrap();
+        let mut ids: Vec<String> = users.definitions.into_iter().map(|n| n.id).collect();
+        ids.sort();
+        let mut expected = vec![ctrl, svc];
+        expected.sort();
+        assert_eq!(ids, expected);
+        assert!(!split_dir.join("_root.yaml").exists());
+    }
 }
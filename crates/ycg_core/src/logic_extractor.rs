@@ -10,9 +10,11 @@
 //!
 //! - `check(condition)`: Conditional checks (if statements, guards, throws)
 //! - `action(operation)`: Side-effect operations (assignments, method calls)
-//! - `return(expression)`: Return statements
+//! - `return(expression)`: Return statements; for an early return in a
+//!   success branch, the condition under which the function returns
 //! - `match(pattern)?true:false`: Pattern matching (ternary, switch)
 //! - `get(source)`: Data retrieval operations
+//! - `yield(value)`: Generator yield points (`yield*(iterable)` for delegation)
 //!
 //! ## Examples
 //!
@@ -43,10 +45,31 @@
 
 use crate::ast_cache::AstCache;
 use crate::model::{ScipSymbolKind, SymbolNode};
+use regex::Regex;
 
 /// Maximum length for logic representation (excluding "logic:" prefix)
 const MAX_LOGIC_LENGTH: usize = 200;
 
+/// How an `if` block that leaves the function early reads
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EarlyExit {
+    /// Throws, panics, or returns an error/empty value (`null`, `Err(..)`):
+    /// the condition is a precondition the caller must avoid
+    ErrorGuard,
+    /// Returns a regular value: a legitimate shortcut (`return(cond)` step)
+    SuccessReturn,
+}
+
+lazy_static::lazy_static! {
+    static ref THROW_RE: Regex = Regex::new(r"\b(throw|panic!|unreachable!)").unwrap();
+    static ref RETURN_RE: Regex = Regex::new(r"\breturn\b\s*([^;}\n]*)").unwrap();
+    /// Returned values that signal failure rather than a result
+    static ref ERROR_VALUE_RE: Regex = Regex::new(
+        r"^(null|undefined|None|false|Err\s*\(|new\s+\w*Error\b|\w*Error\s*\(|Promise\.reject\s*\()"
+    )
+    .unwrap();
+}
+
 /// Logic extractor for methods and functions
 pub struct LogicExtractor;
 
@@ -129,6 +152,49 @@ impl LogicExtractor {
         None
     }
 
+    /// Classifies the body of an `if` block: `None` when it neither throws
+    /// nor returns, [`EarlyExit::ErrorGuard`] when it throws or any of its
+    /// returns is bare or yields an error/empty value, and
+    /// [`EarlyExit::SuccessReturn`] when every return yields a regular value.
+    pub fn classify_early_exit(block: &str) -> Option<EarlyExit> {
+        if THROW_RE.is_match(block) {
+            return Some(EarlyExit::ErrorGuard);
+        }
+
+        let mut returns = RETURN_RE
+            .captures_iter(block)
+            .map(|caps| caps.get(1).map_or("", |m| m.as_str().trim()))
+            .peekable();
+        returns.peek()?;
+        if returns.any(|value| value.is_empty() || ERROR_VALUE_RE.is_match(value)) {
+            Some(EarlyExit::ErrorGuard)
+        } else {
+            Some(EarlyExit::SuccessReturn)
+        }
+    }
+
+    /// `return(<condition>)` step for a success early return,
+    /// whitespace in the condition collapsed
+    pub fn early_return_step(condition: &str) -> String {
+        format!(
+            "return({})",
+            condition.split_whitespace().collect::<Vec<_>>().join(" ")
+        )
+    }
+
+    /// Keeps the first `max` preconditions, followed by a `(+N more)` marker
+    /// for the dropped ones; `None` keeps them all
+    pub fn cap_preconditions(mut preconditions: Vec<String>, max: Option<usize>) -> Vec<String> {
+        if let Some(max) = max
+            && preconditions.len() > max
+        {
+            let dropped = preconditions.len() - max;
+            preconditions.truncate(max);
+            preconditions.push(format!("(+{} more)", dropped));
+        }
+        preconditions
+    }
+
     /// Validate logic representation format
     ///
     /// Ensures logic string follows the correct format:
@@ -158,7 +224,7 @@ impl LogicExtractor {
     /// Validate that logic string contains only valid keywords
     fn validate_keywords(content: &str) -> bool {
         // Valid keywords
-        const VALID_KEYWORDS: &[&str] = &["check", "action", "return", "match", "get"];
+        const VALID_KEYWORDS: &[&str] = &["check", "action", "return", "match", "get", "yield"];
 
         // Split by semicolons to get individual steps
         for step in content.split(';') {
@@ -204,10 +270,7 @@ mod tests {
             id: "test_id".to_string(),
             name: "testMethod".to_string(),
             kind,
-            parent_id: None,
-            documentation: None,
-            signature: None,
-            logic: None,
+            ..Default::default()
         }
     }
 
@@ -727,6 +790,51 @@ mod tests {
         assert!(result.is_none());
     }
 
+    #[test]
+    fn test_classify_error_guard_vs_success_early_return() {
+        for block in [
+            "{ throw new NotFoundException(); }",
+            "{ return null; }",
+            "{ return; }",
+            "{ return Err(AppError::NotFound); }",
+            "{ return Promise.reject(new Error('x')); }",
+            "{ log(); return undefined; }",
+        ] {
+            assert_eq!(
+                LogicExtractor::classify_early_exit(block),
+                Some(EarlyExit::ErrorGuard),
+                "{}",
+                block
+            );
+        }
+
+        for block in [
+            "{ return this.cache.get(id); }",
+            "{\n  return user;\n}",
+            "{ return Ok(cached); }",
+        ] {
+            assert_eq!(
+                LogicExtractor::classify_early_exit(block),
+                Some(EarlyExit::SuccessReturn),
+                "{}",
+                block
+            );
+        }
+
+        assert_eq!(
+            LogicExtractor::classify_early_exit("{ count++; returned = true; }"),
+            None
+        );
+        assert_eq!(
+            LogicExtractor::early_return_step("this.cache.has(id)\n  && fresh"),
+            "return(this.cache.has(id) && fresh)"
+        );
+        assert!(LogicExtractor::validate_logic(&format!(
+            "logic:{}",
+            LogicExtractor::early_return_step("cached")
+        )));
+    }
+
     /// Test that non-method/function nodes don't extract logic
     /// Requirement 3.1: Only extract logic for methods and functions
     #[test]
@@ -1,9 +1,9 @@
 // crates/ycg_core/src/model.rs
 use serde::{Deserialize, Serialize};
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, HashMap, HashSet};
 
 // --- MODELO PADRÃO (Flat List) ---
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct YcgGraph {
     #[serde(rename = "_meta")]
     pub metadata: ProjectMetadata,
@@ -27,14 +27,215 @@ pub struct YcgGraphOptimized {
     pub adjacency: BTreeMap<String, BTreeMap<EdgeType, Vec<String>>>,
 }
 
+// --- MODELO AGRUPADO (Flat edges grouped by type) ---
+#[derive(Debug, Serialize, Deserialize)]
+pub struct YcgGraphByType {
+    #[serde(rename = "_meta")]
+    pub metadata: ProjectMetadata,
+    #[serde(rename = "_defs")]
+    pub definitions: Vec<SymbolNode>,
+
+    // Tipo -> Lista de arestas; o tipo não se repete em cada aresta
+    // BTreeMap ordena as seções por EdgeType::rank
+    #[serde(rename = "graph", default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub edges: BTreeMap<EdgeType, Vec<TypedEdge>>,
+}
+
+// --- MODELO PONDERADO (Flat list, repeated edges merged with a count) ---
+#[derive(Debug, Serialize)]
+pub struct YcgGraphWeighted {
+    #[serde(rename = "_meta")]
+    pub metadata: ProjectMetadata,
+    #[serde(rename = "_defs")]
+    pub definitions: Vec<SymbolNode>,
+    #[serde(rename = "graph", skip_serializing_if = "Vec::is_empty")]
+    pub references: Vec<WeightedEdge>,
+}
+
+/// `ReferenceEdge` of [`YcgGraphWeighted`]: `count` identical edges in a row
+#[derive(Debug, Serialize, PartialEq)]
+pub struct WeightedEdge {
+    #[serde(flatten)]
+    pub edge: ReferenceEdge,
+    #[serde(skip_serializing_if = "is_one")]
+    pub count: u32,
+}
+
+fn is_one(n: &u32) -> bool {
+    *n == 1
+}
+
+impl YcgGraphWeighted {
+    /// Merges each run of identical edges (the list is sorted, so duplicates
+    /// are consecutive) into one edge with its run length
+    pub fn from_graph(graph: YcgGraph) -> Self {
+        let mut edges = graph.references;
+        edges.sort();
+        let mut references: Vec<WeightedEdge> = Vec::new();
+        for edge in edges {
+            match references.last_mut() {
+                Some(last) if last.edge == edge => last.count += 1,
+                _ => references.push(WeightedEdge { edge, count: 1 }),
+            }
+        }
+        Self {
+            metadata: graph.metadata,
+            definitions: graph.definitions,
+            references,
+        }
+    }
+}
+
+/// Number of identical (from, type, to) edges, for weighted adjacency lists
+pub type EdgeCounts = HashMap<(String, EdgeType, String), u32>;
+
+pub fn edge_counts(edges: &[ReferenceEdge]) -> EdgeCounts {
+    let mut counts = EdgeCounts::new();
+    for edge in edges {
+        *counts
+            .entry((edge.from.clone(), edge.edge_type.clone(), edge.to.clone()))
+            .or_insert(0) += 1;
+    }
+    counts
+}
+
+/// Writes each repeated target of an adjacency entry as `target (xN)`
+pub fn weigh_targets(from: &str, edges: &mut BTreeMap<EdgeType, Vec<String>>, counts: &EdgeCounts) {
+    for (edge_type, targets) in edges.iter_mut() {
+        for target in targets.iter_mut() {
+            let key = (from.to_string(), edge_type.clone(), target.clone());
+            if let Some(&count) = counts.get(&key).filter(|&&count| count > 1) {
+                *target = format!("{} (x{})", target, count);
+            }
+        }
+    }
+}
+
+// --- MODELO EMBUTIDO (Outgoing edges inline under each definition) ---
+#[derive(Debug, Serialize)]
+pub struct YcgGraphInlineEdges {
+    #[serde(rename = "_meta")]
+    pub metadata: ProjectMetadata,
+    #[serde(rename = "_defs")]
+    pub definitions: Vec<InlineEdgesNode>,
+
+    // Arestas cuja origem não é uma definição (ex.: nós de arquivo removidos)
+    #[serde(rename = "graph", skip_serializing_if = "BTreeMap::is_empty")]
+    pub unattached: BTreeMap<String, BTreeMap<EdgeType, Vec<String>>>,
+}
+
+/// A definition of [`YcgGraphInlineEdges`] followed by its outgoing edges
+/// (`calls: [ids]`, `imports: [ids]`...)
+#[derive(Debug, Serialize)]
+pub struct InlineEdgesNode {
+    #[serde(flatten)]
+    pub node: SymbolNode,
+    #[serde(flatten)]
+    pub edges: BTreeMap<EdgeType, Vec<String>>,
+}
+
+impl YcgGraphInlineEdges {
+    /// Moves each adjacency entry under the definition it starts from
+    pub fn from_graph(graph: YcgGraph) -> Self {
+        let mut adjacency: BTreeMap<String, BTreeMap<EdgeType, Vec<String>>> = BTreeMap::new();
+        for edge in graph.references {
+            adjacency
+                .entry(edge.from)
+                .or_default()
+                .entry(edge.edge_type)
+                .or_default()
+                .push(edge.to);
+        }
+        for targets in adjacency.values_mut().flat_map(|edges| edges.values_mut()) {
+            targets.sort();
+            targets.dedup();
+        }
+
+        let definitions = graph
+            .definitions
+            .into_iter()
+            .map(|node| InlineEdgesNode {
+                edges: adjacency.remove(&node.id).unwrap_or_default(),
+                node,
+            })
+            .collect();
+
+        Self {
+            metadata: graph.metadata,
+            definitions,
+            unattached: adjacency,
+        }
+    }
+}
+
+/// `ReferenceEdge` inside a per-type section of [`YcgGraphByType`]
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Clone)]
+pub struct TypedEdge {
+    pub from: String,
+    pub to: String,
+    #[serde(rename = "call", default, skip_serializing_if = "Option::is_none")]
+    pub call_text: Option<String>,
+}
+
+impl YcgGraphByType {
+    /// Partitions the flat edge list into one sorted section per edge type
+    pub fn from_graph(graph: YcgGraph) -> Self {
+        let mut edges: BTreeMap<EdgeType, Vec<TypedEdge>> = BTreeMap::new();
+        for edge in graph.references {
+            edges.entry(edge.edge_type).or_default().push(TypedEdge {
+                from: edge.from,
+                to: edge.to,
+                call_text: edge.call_text,
+            });
+        }
+        for section in edges.values_mut() {
+            section.sort();
+        }
+
+        Self {
+            metadata: graph.metadata,
+            definitions: graph.definitions,
+            edges,
+        }
+    }
+
+    /// Flattens the sections back into a `YcgGraph`
+    pub fn into_graph(self) -> YcgGraph {
+        let mut references: Vec<ReferenceEdge> = self
+            .edges
+            .into_iter()
+            .flat_map(|(edge_type, section)| {
+                section.into_iter().map(move |edge| ReferenceEdge {
+                    from: edge.from,
+                    to: edge.to,
+                    edge_type: edge_type.clone(),
+                    call_text: edge.call_text,
+                })
+            })
+            .collect();
+        references.sort();
+
+        YcgGraph {
+            metadata: self.metadata,
+            definitions: self.definitions,
+            references,
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Default, Clone)]
 pub struct ProjectMetadata {
     pub name: String,
     pub version: String,
+    /// Namespace prefix removed from every symbol name (`--strip-prefix`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stripped_prefix: Option<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
 pub struct SymbolNode {
+    /// Empty only inside a numeric-ID node, which carries its own `id`
+    #[serde(default, skip_serializing_if = "String::is_empty")]
     pub id: String,
     #[serde(rename = "n")]
     pub name: String,
@@ -50,12 +251,74 @@ pub struct SymbolNode {
     pub signature: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub logic: Option<LogicMetadata>,
+    /// Exception types / panic macros raised anywhere in the body (empty = never throws)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub throws: Vec<String>,
+    /// Marked `@deprecated` (JSDoc) or `#[deprecated]` (Rust)
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub deprecated: bool,
+    /// Top git authors of the file, File nodes only (`--with-ownership`)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub owners: Vec<String>,
+    /// Generator (`function*`, `async function*`, `*method()`): lazily yields values
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub generator: bool,
+    /// Number of inbound edges, i.e. how many places reference this symbol
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub ref_count: u32,
+    /// HTTP route served by a handler method (`GET /users/:id`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub route: Option<String>,
+    /// Environment variables / config keys read in the body (`DATABASE_URL`)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub env_access: Vec<String>,
+    /// Architectural layer of the defining file (`controller`, `service`...), from `layerRules`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub layer: Option<String>,
+    /// Full source text of the definition, for nodes matching `--inline-source`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+    /// Interfaces whose only implementation this class is, merged into it (`--merge-single-impl`)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub merged_interfaces: Vec<String>,
+    /// Cyclomatic complexity of a method/function body (`--complexity`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub complexity: Option<u32>,
+    /// Relative path of a File node, or of a directory node nesting files (`--file-paths`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+    /// `TODO` / `FIXME` / `HACK` comments in the body or leading comments
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub annotations: Vec<String>,
+    /// Heuristic: the method/function body shows no side effect (see the enricher)
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub pure: bool,
+    /// SQL passed to `query` / `execute` / `raw` / `sql` calls in the body (long ones cut)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub queries: Vec<String>,
+    /// Class marked `@Injectable()`, when kept by `keepDecorators`
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub injectable: bool,
+    /// Original SCIP symbol of the definition (`--keep-scip-symbols`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scip_symbol: Option<String>,
+}
+
+pub(crate) fn is_zero(n: &u32) -> bool {
+    *n == 0
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct LogicMetadata {
-    #[serde(skip_serializing_if = "Vec::is_empty", rename = "pre")]
+    #[serde(default, skip_serializing_if = "Vec::is_empty", rename = "pre")]
     pub preconditions: Vec<String>,
+    /// Control-flow steps: success early returns (`return(cond)`), then generator `yield(...)`
+    /// points, then constructor initialization (`set(field)`, `call(callee)`) in source order
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub steps: Vec<String>,
+    /// Pass-through body: the only thing it does is call this ID (`--collapse-delegates`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub delegates_to: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Hash, PartialOrd, Ord, Clone)]
@@ -64,17 +327,118 @@ pub struct ReferenceEdge {
     pub to: String,
     #[serde(rename = "type")]
     pub edge_type: EdgeType,
+    /// Source text of the call expression (`--with-call-text`), flat edge list only
+    #[serde(rename = "call", default, skip_serializing_if = "Option::is_none")]
+    pub call_text: Option<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Hash, PartialOrd, Ord, Clone, Copy)]
-#[serde(rename_all = "lowercase")]
+/// Edge kinds. Their order is part of the output format: see [`EdgeType::rank`].
+///
+/// Serialized as a lowercase string; any other string is a [`EdgeType::Custom`]
+/// type produced by user edge rules (`edgeRules` in `ycg.config.json`).
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
 pub enum EdgeType {
     Calls,
     References,
     Imports,
+    Implements,
+    /// Assignment to a variable or field (SCIP `WriteAccess` occurrence)
+    Writes,
+    /// Read of a variable or field (SCIP `ReadAccess` occurrence)
+    Reads,
+    /// User-defined type (`emits`, `spawns`), serialized as its name
+    Custom(String),
+}
+
+impl EdgeType {
+    /// Explicit sort rank: `calls < references < imports < implements < writes < reads`.
+    ///
+    /// This fixes the order of edge types inside each adjacency-list entry
+    /// (compact mode) and of `ReferenceEdge`s with equal endpoints, so the
+    /// output does not change if variants are ever reordered or added.
+    /// New variants must be appended with the next rank; custom types come
+    /// last, ordered by name.
+    pub fn rank(&self) -> u8 {
+        match self {
+            EdgeType::Calls => 0,
+            EdgeType::References => 1,
+            EdgeType::Imports => 2,
+            EdgeType::Implements => 3,
+            EdgeType::Writes => 4,
+            EdgeType::Reads => 5,
+            EdgeType::Custom(_) => 6,
+        }
+    }
+
+    /// Serialized name (`calls`, or the custom type's name)
+    pub fn as_str(&self) -> &str {
+        match self {
+            EdgeType::Calls => "calls",
+            EdgeType::References => "references",
+            EdgeType::Imports => "imports",
+            EdgeType::Implements => "implements",
+            EdgeType::Writes => "writes",
+            EdgeType::Reads => "reads",
+            EdgeType::Custom(name) => name,
+        }
+    }
+
+    /// Single-letter key used in the ad-hoc `graph` (`c`, `r`, `i`, `e`, `w`, `d`);
+    /// custom types keep their name
+    pub fn short_name(&self) -> &str {
+        match self {
+            EdgeType::Calls => "c",
+            EdgeType::References => "r",
+            EdgeType::Imports => "i",
+            EdgeType::Implements => "e",
+            EdgeType::Writes => "w",
+            EdgeType::Reads => "d",
+            EdgeType::Custom(name) => name,
+        }
+    }
+
+    /// Parses a serialized name; unknown names become custom types
+    pub fn from_name(name: &str) -> Self {
+        match name {
+            "calls" => EdgeType::Calls,
+            "references" => EdgeType::References,
+            "imports" => EdgeType::Imports,
+            "implements" => EdgeType::Implements,
+            "writes" => EdgeType::Writes,
+            "reads" => EdgeType::Reads,
+            other => EdgeType::Custom(other.to_string()),
+        }
+    }
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+impl Serialize for EdgeType {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for EdgeType {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let name = String::deserialize(deserializer)?;
+        Ok(EdgeType::from_name(&name))
+    }
+}
+
+impl Ord for EdgeType {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.rank()
+            .cmp(&other.rank())
+            .then_with(|| self.as_str().cmp(other.as_str()))
+    }
+}
+
+impl PartialOrd for EdgeType {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash, Default)]
 #[serde(rename_all = "lowercase")]
 pub enum ScipSymbolKind {
     File,
@@ -82,8 +446,75 @@ pub enum ScipSymbolKind {
     Class,
     Method,
     Function,
+    #[default]
     Variable,
     Interface,
+    Enum,
+}
+
+impl std::str::FromStr for ScipSymbolKind {
+    type Err = String;
+
+    /// Parses the serialized (lowercase) kind name, case-insensitively
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "file" => Ok(ScipSymbolKind::File),
+            "module" => Ok(ScipSymbolKind::Module),
+            "class" => Ok(ScipSymbolKind::Class),
+            "method" => Ok(ScipSymbolKind::Method),
+            "function" => Ok(ScipSymbolKind::Function),
+            "variable" => Ok(ScipSymbolKind::Variable),
+            "interface" => Ok(ScipSymbolKind::Interface),
+            "enum" => Ok(ScipSymbolKind::Enum),
+            _ => Err(format!("Unknown symbol kind: '{}'", s)),
+        }
+    }
+}
+
+// --- SYMBOL INDEX (companion lookup file) ---
+
+/// Flat path → symbol lookup table, emitted next to the graph with `--emit-index`
+///
+/// - `files`: each `relative_path` mapped to the anchors defined in it
+/// - `lines`: each anchor mapped to its 1-based definition line
+///
+/// With `--baseline`, it also keeps the SCIP-side fingerprint of each anchor
+/// (see [`crate::delta`]); those are not emitted.
+#[derive(Debug, Serialize, Deserialize, Default, Clone, PartialEq)]
+pub struct SymbolIndex {
+    pub files: BTreeMap<String, Vec<String>>,
+    pub lines: BTreeMap<String, i32>,
+    #[serde(skip)]
+    pub fingerprints: BTreeMap<String, u64>,
+}
+
+impl SymbolIndex {
+    /// Record a definition. An anchor is only ever listed under the first
+    /// file it was seen in, so each definition has exactly one file key.
+    pub fn insert(&mut self, path: &str, anchor: &str, line: i32) {
+        if self.lines.contains_key(anchor) {
+            return;
+        }
+        self.lines.insert(anchor.to_string(), line);
+        self.files
+            .entry(path.to_string())
+            .or_default()
+            .push(anchor.to_string());
+    }
+
+    /// Drop every anchor that is no longer a definition in `graph`
+    /// (e.g. after semantic or framework filtering)
+    pub fn retain_definitions(&mut self, graph: &YcgGraph) {
+        let kept: HashSet<&str> = graph.definitions.iter().map(|n| n.id.as_str()).collect();
+        self.lines
+            .retain(|anchor, _| kept.contains(anchor.as_str()));
+        self.fingerprints
+            .retain(|anchor, _| kept.contains(anchor.as_str()));
+        for anchors in self.files.values_mut() {
+            anchors.retain(|anchor| kept.contains(anchor.as_str()));
+        }
+        self.files.retain(|_, anchors| !anchors.is_empty());
+    }
 }
 
 // --- CONFIGURATION MODELS FOR TOKEN OPTIMIZATION ---
@@ -97,6 +528,34 @@ pub struct YcgConfigFile {
     pub ignore: IgnoreConfig,
     #[serde(default)]
     pub include: Vec<String>,
+    #[serde(default, rename = "edgeRules")]
+    pub edge_rules: Vec<EdgeRule>,
+    #[serde(default, rename = "layerRules")]
+    pub layer_rules: Vec<LayerRule>,
+    /// Layer -> layers it may depend on (`analyze --layer-violations`)
+    #[serde(default, rename = "layerDependencies")]
+    pub layer_dependencies: BTreeMap<String, Vec<String>>,
+}
+
+/// Tags the definitions of matching files with a layer
+/// (`{"pattern": "src/**/*.controller.ts", "layer": "controller"}`)
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct LayerRule {
+    /// Glob matched against the file path relative to the project root
+    pub pattern: String,
+    /// Layer name stored on the definitions
+    pub layer: String,
+}
+
+/// Turns matching call expressions into edges of a custom type
+/// (`{"pattern": "\\.emit\\(", "edgeType": "emits"}`)
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct EdgeRule {
+    /// Regex matched against the source text of the call expression
+    pub pattern: String,
+    /// Edge type emitted instead of `calls`
+    #[serde(rename = "edgeType")]
+    pub edge_type: String,
 }
 
 /// Output configuration settings
@@ -108,6 +567,16 @@ pub struct OutputConfig {
     pub ignore_framework_noise: Option<bool>,
     #[serde(rename = "adhocGranularity")]
     pub adhoc_granularity: Option<String>,
+    /// Per-kind override of `adhocGranularity` (`{"method": "signatures"}`)
+    #[serde(rename = "adhocGranularityByKind")]
+    pub adhoc_granularity_by_kind: Option<BTreeMap<String, String>>,
+    #[serde(rename = "compactNames")]
+    pub compact_names: Option<bool>,
+    #[serde(rename = "nameAbbreviations")]
+    pub name_abbreviations: Option<BTreeMap<String, String>>,
+    /// Decorators turned into node metadata instead of stripped (`["Get", "Injectable"]`)
+    #[serde(rename = "keepDecorators")]
+    pub keep_decorators: Option<Vec<String>>,
 }
 
 /// Ignore patterns configuration
@@ -124,6 +593,14 @@ pub struct IgnoreConfig {
 pub enum OutputFormat {
     Yaml,
     AdHoc,
+    Ndjson,
+    /// The YAML document as pretty-printed JSON
+    Json,
+    /// PlantUML class diagram of the classes, interfaces and enums
+    PlantUml,
+    /// Binary graph (`bincode` feature), for storage and transport
+    #[cfg(feature = "bincode")]
+    Bincode,
 }
 
 impl Default for OutputFormat {
@@ -140,6 +617,20 @@ pub struct FileFilterConfig {
     pub use_gitignore: bool,
 }
 
+/// Symbol name filtering configuration (regexes matched against `SymbolNode.name`)
+#[derive(Debug, Clone, Default)]
+pub struct NameFilterConfig {
+    pub include_patterns: Vec<String>,
+    pub exclude_patterns: Vec<String>,
+}
+
+impl NameFilterConfig {
+    /// True when no name patterns are configured
+    pub fn is_empty(&self) -> bool {
+        self.include_patterns.is_empty() && self.exclude_patterns.is_empty()
+    }
+}
+
 // --- AD-HOC GRANULARITY CONFIGURATION ---
 
 /// Granularity levels for ad-hoc format output
@@ -210,6 +701,122 @@ impl AdHocGranularity {
     }
 }
 
+// --- SIGNATURE STYLE CONFIGURATION ---
+
+/// How parameters are rendered in compact signatures
+///
+/// - `Named`: `findOne(id:num):Promise<UserDto>` (default)
+/// - `TypesOnly`: `findOne(num):Promise<UserDto>` - drops parameter names,
+///   keeping only the type contract
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SignatureStyle {
+    #[default]
+    Named,
+    TypesOnly,
+}
+
+impl std::str::FromStr for SignatureStyle {
+    type Err = String;
+
+    /// Parse from string (for CLI flag)
+    ///
+    /// # Valid Values
+    /// - "named" → SignatureStyle::Named
+    /// - "types-only" → SignatureStyle::TypesOnly
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "named" => Ok(SignatureStyle::Named),
+            "types-only" => Ok(SignatureStyle::TypesOnly),
+            _ => Err(format!(
+                "Invalid signature style: '{}'. Valid values are: 'named', 'types-only'",
+                s
+            )),
+        }
+    }
+}
+
+impl SignatureStyle {
+    /// Convert to string representation
+    pub fn to_str(&self) -> &'static str {
+        match self {
+            SignatureStyle::Named => "named",
+            SignatureStyle::TypesOnly => "types-only",
+        }
+    }
+}
+
+// --- TEST FILES CONFIGURATION ---
+
+/// Treatment of test files (`*.spec.ts`, `*.test.ts`, `*_test.rs`, ...)
+///
+/// - `Include`: test files are part of the graph (default)
+/// - `Exclude`: test files are dropped by the file filter
+/// - `Separate`: the graph holds production code only; test files go to
+///   their own graph file, with their edges into production code
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TestFilesMode {
+    #[default]
+    Include,
+    Exclude,
+    Separate,
+}
+
+impl std::str::FromStr for TestFilesMode {
+    type Err = String;
+
+    /// Parse from string (for CLI flag)
+    ///
+    /// # Valid Values
+    /// - "include" → TestFilesMode::Include
+    /// - "exclude" → TestFilesMode::Exclude
+    /// - "separate" → TestFilesMode::Separate
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "include" => Ok(TestFilesMode::Include),
+            "exclude" => Ok(TestFilesMode::Exclude),
+            "separate" => Ok(TestFilesMode::Separate),
+            _ => Err(format!(
+                "Invalid tests mode: '{}'. Valid values are: 'include', 'exclude', 'separate'",
+                s
+            )),
+        }
+    }
+}
+
+// --- YAML STYLE CONFIGURATION ---
+
+/// Layout of the YAML output
+///
+/// - `Block`: one key or list item per line, as serde_yaml writes it (default)
+/// - `Flow`: top-level sections keep one line per entry, nested maps and
+///   lists are written inline (`{id: a, n: findOne}`, `[b, c]`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum YamlStyle {
+    #[default]
+    Block,
+    Flow,
+}
+
+impl std::str::FromStr for YamlStyle {
+    type Err = String;
+
+    /// Parse from string (for CLI flag)
+    ///
+    /// # Valid Values
+    /// - "block" → YamlStyle::Block
+    /// - "flow" → YamlStyle::Flow
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "block" => Ok(YamlStyle::Block),
+            "flow" => Ok(YamlStyle::Flow),
+            _ => Err(format!(
+                "Invalid YAML style: '{}'. Valid values are: 'block', 'flow'",
+                s
+            )),
+        }
+    }
+}
+
 // --- AD-HOC FORMAT MODEL ---
 
 /// Ad-hoc format representation using pipe-separated strings
@@ -221,10 +828,35 @@ pub struct YcgGraphAdHoc {
     #[serde(rename = "_defs")]
     pub definitions: Vec<String>, // Pipe-separated strings: "id|name|type"
 
-    #[serde(rename = "graph")]
+    /// Edge types are written with their [`EdgeType::short_name`]
+    #[serde(rename = "graph", serialize_with = "serialize_short_edge_types")]
     pub adjacency: BTreeMap<String, BTreeMap<EdgeType, Vec<String>>>,
 }
 
+fn serialize_short_edge_types<S: serde::Serializer>(
+    adjacency: &BTreeMap<String, BTreeMap<EdgeType, Vec<String>>>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.collect_map(
+        adjacency
+            .iter()
+            .map(|(from, edges)| (from, ShortEdgeTypes(edges))),
+    )
+}
+
+/// Edge type -> targets, keyed by short name in `EdgeType` order
+struct ShortEdgeTypes<'a>(&'a BTreeMap<EdgeType, Vec<String>>);
+
+impl Serialize for ShortEdgeTypes<'_> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_map(
+            self.0
+                .iter()
+                .map(|(edge_type, targets)| (edge_type.short_name(), targets)),
+        )
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -281,6 +913,21 @@ mod tests {
         assert_eq!(AdHocGranularity::InlineLogic.to_str(), "logic");
     }
 
+    #[test]
+    fn test_signature_style_from_str() {
+        assert_eq!(SignatureStyle::default(), SignatureStyle::Named);
+        assert_eq!(
+            "types-only".parse::<SignatureStyle>().unwrap(),
+            SignatureStyle::TypesOnly
+        );
+        assert_eq!(
+            "NAMED".parse::<SignatureStyle>().unwrap(),
+            SignatureStyle::Named
+        );
+        assert!("typed".parse::<SignatureStyle>().is_err());
+        assert_eq!(SignatureStyle::TypesOnly.to_str(), "types-only");
+    }
+
     #[test]
     fn test_adhoc_granularity_round_trip() {
         let levels = vec![
@@ -295,4 +942,28 @@ mod tests {
             assert_eq!(level, parsed);
         }
     }
+
+    #[test]
+    fn test_edge_type_order_is_explicit() {
+        let mut types = vec![
+            EdgeType::Reads,
+            EdgeType::Writes,
+            EdgeType::Implements,
+            EdgeType::Imports,
+            EdgeType::References,
+            EdgeType::Calls,
+        ];
+        types.sort();
+        assert_eq!(
+            types,
+            vec![
+                EdgeType::Calls,
+                EdgeType::References,
+                EdgeType::Imports,
+                EdgeType::Implements,
+                EdgeType::Writes,
+                EdgeType::Reads
+            ]
+        );
+    }
 }
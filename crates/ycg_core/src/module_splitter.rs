@@ -0,0 +1,136 @@
+// crates/ycg_core/src/module_splitter.rs
+
+use crate::model::{ReferenceEdge, SymbolIndex, YcgGraph};
+use std::collections::{BTreeMap, HashMap};
+
+/// Module name for definitions whose file sits directly in the shared root
+pub const ROOT_MODULE: &str = "_root";
+
+/// File stem for edges that cross module boundaries
+pub const CROSS_MODULE_FILE: &str = "_cross";
+
+/// A graph partitioned by top-level module
+pub struct ModuleSplit {
+    /// Module name -> its definitions plus the edges originating in it
+    pub modules: BTreeMap<String, YcgGraph>,
+    /// Edges whose endpoints are defined in two different modules
+    pub cross: YcgGraph,
+}
+
+/// Splits a graph into one graph per top-level module (`--split-by-module`)
+pub struct ModuleSplitter;
+
+impl ModuleSplitter {
+    /// Partition `graph` using the file of each definition from `index`.
+    ///
+    /// A module is the first directory below the directory shared by every
+    /// file, so `src/users/*.ts` and `src/orders/*.ts` split into `users`
+    /// and `orders` rather than a single `src`. Edges go to the module of
+    /// their source; edges between two modules go to `cross` instead.
+    ///
+    /// # Arguments
+    /// * `graph` - The graph to partition
+    /// * `index` - Symbol index mapping files to the anchors defined in them
+    pub fn split(graph: &YcgGraph, index: &SymbolIndex) -> ModuleSplit {
+        let paths: Vec<&str> = index.files.keys().map(|p| p.as_str()).collect();
+        let prefix_len = common_dir_depth(&paths);
+
+        let mut module_of: HashMap<&str, String> = HashMap::new();
+        for (path, anchors) in &index.files {
+            let module = module_for_path(path, prefix_len);
+            for anchor in anchors {
+                module_of.insert(anchor.as_str(), module.clone());
+            }
+        }
+
+        let mut modules: BTreeMap<String, YcgGraph> = BTreeMap::new();
+        let mut cross_edges: Vec<ReferenceEdge> = Vec::new();
+
+        let new_graph = |metadata: &crate::model::ProjectMetadata| YcgGraph {
+            metadata: metadata.clone(),
+            definitions: Vec::new(),
+            references: Vec::new(),
+        };
+
+        for node in &graph.definitions {
+            let module = module_of
+                .get(node.id.as_str())
+                .cloned()
+                .unwrap_or_else(|| ROOT_MODULE.to_string());
+            modules
+                .entry(module)
+                .or_insert_with(|| new_graph(&graph.metadata))
+                .definitions
+                .push(node.clone());
+        }
+
+        for edge in &graph.references {
+            let from_module = module_of
+                .get(edge.from.as_str())
+                .map(|m| m.as_str())
+                .unwrap_or(ROOT_MODULE);
+
+            match module_of.get(edge.to.as_str()) {
+                Some(to_module) if to_module != from_module => cross_edges.push(edge.clone()),
+                _ => modules
+                    .entry(from_module.to_string())
+                    .or_insert_with(|| new_graph(&graph.metadata))
+                    .references
+                    .push(edge.clone()),
+            }
+        }
+
+        let mut cross = new_graph(&graph.metadata);
+        cross.references = cross_edges;
+
+        ModuleSplit { modules, cross }
+    }
+}
+
+/// Number of leading directory components shared by every path
+fn common_dir_depth(paths: &[&str]) -> usize {
+    let dirs: Vec<Vec<&str>> = paths
+        .iter()
+        .map(|p| {
+            let mut parts: Vec<&str> = p.split('/').collect();
+            parts.pop(); // file name
+            parts
+        })
+        .collect();
+
+    let Some(first) = dirs.first() else {
+        return 0;
+    };
+
+    let mut depth = 0;
+    while depth < first.len() && dirs.iter().all(|d| d.get(depth) == first.get(depth)) {
+        depth += 1;
+    }
+    depth
+}
+
+/// `src/users/users.service.ts` with a shared `src/` prefix -> `users`
+fn module_for_path(path: &str, prefix_len: usize) -> String {
+    let parts: Vec<&str> = path.split('/').collect();
+    // The last component is the file itself, never a module
+    if parts.len() > prefix_len + 1 {
+        parts[prefix_len].to_string()
+    } else {
+        ROOT_MODULE.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_module_for_path_below_common_prefix() {
+        let paths = ["src/users/users.service.ts", "src/orders/orders.service.ts"];
+        let depth = common_dir_depth(&paths);
+
+        assert_eq!(depth, 1);
+        assert_eq!(module_for_path(paths[0], depth), "users");
+        assert_eq!(module_for_path("src/main.ts", depth), ROOT_MODULE);
+    }
+}
@@ -0,0 +1,250 @@
+// crates/ycg_core/src/name_compactor.rs
+
+use crate::model::{SymbolIndex, YcgGraph};
+use std::collections::BTreeMap;
+
+/// Default suffix abbreviations used by `--compact-names`
+const DEFAULT_ABBREVIATIONS: &[(&str, &str)] = &[
+    ("Controller", "Ctrl"),
+    ("Service", "Svc"),
+    ("Repository", "Repo"),
+    ("Entity", "Ent"),
+    ("Component", "Cmp"),
+    ("Module", "Mod"),
+    ("Interceptor", "Icpt"),
+    ("Middleware", "Mw"),
+];
+
+/// Abbreviates common type-name suffixes (`UsersController` → `UsersCtrl`)
+///
+/// The same rewrite is applied to node ids, names, parents, edge endpoints
+/// and the symbol index, so everything still resolves after compaction.
+/// In signatures only type names are rewritten, parameter names are kept.
+pub struct NameCompactor {
+    rules: Vec<(String, String)>,
+}
+
+impl NameCompactor {
+    /// Create a compactor from a suffix → abbreviation map.
+    /// An empty map falls back to the default abbreviations.
+    pub fn new(abbreviations: &BTreeMap<String, String>) -> Self {
+        let mut rules: Vec<(String, String)> = if abbreviations.is_empty() {
+            DEFAULT_ABBREVIATIONS
+                .iter()
+                .map(|(suffix, abbrev)| (suffix.to_string(), abbrev.to_string()))
+                .collect()
+        } else {
+            abbreviations
+                .iter()
+                .filter(|(suffix, _)| !suffix.is_empty())
+                .map(|(suffix, abbrev)| (suffix.clone(), abbrev.clone()))
+                .collect()
+        };
+
+        // Longest suffix first so overlapping rules are deterministic
+        rules.sort_by(|a, b| b.0.len().cmp(&a.0.len()).then_with(|| a.0.cmp(&b.0)));
+
+        Self { rules }
+    }
+
+    /// Abbreviate every occurrence of a configured suffix that ends a
+    /// CamelCase word (i.e. is not followed by a lowercase letter).
+    ///
+    /// `UsersController_findAll_1a2b` → `UsersCtrl_findAll_1a2b`, while
+    /// `Services` is left intact.
+    pub fn compact(&self, text: &str) -> String {
+        let mut result = text.to_string();
+
+        for (suffix, abbrev) in &self.rules {
+            if !result.contains(suffix.as_str()) {
+                continue;
+            }
+
+            let mut output = String::with_capacity(result.len());
+            let mut rest = result.as_str();
+            while let Some(pos) = rest.find(suffix.as_str()) {
+                let after = &rest[pos + suffix.len()..];
+                let ends_word = !after.starts_with(|c: char| c.is_ascii_lowercase());
+
+                output.push_str(&rest[..pos]);
+                output.push_str(if ends_word { abbrev } else { suffix });
+                rest = after;
+            }
+            output.push_str(rest);
+            result = output;
+        }
+
+        result
+    }
+
+    /// [`Self::compact`] applied to the identifiers of `signature` that start
+    /// with an uppercase letter, i.e. type names
+    ///
+    /// `constructor(usersService: UsersService)` →
+    /// `constructor(usersService: UsersSvc)`.
+    pub fn compact_types(&self, signature: &str) -> String {
+        let mut output = String::with_capacity(signature.len());
+        let is_ident = |c: char| c.is_alphanumeric() || c == '_' || c == '$';
+        let mut rest = signature;
+        while !rest.is_empty() {
+            // An identifier, or a single other character
+            let token_len = rest
+                .find(|c: char| !is_ident(c))
+                .unwrap_or(rest.len())
+                .max(rest.chars().next().map_or(0, char::len_utf8));
+            let (token, after) = rest.split_at(token_len);
+            if token.starts_with(|c: char| c.is_uppercase()) {
+                output.push_str(&self.compact(token));
+            } else {
+                output.push_str(token);
+            }
+            rest = after;
+        }
+        output
+    }
+
+    /// Apply the compaction uniformly across the graph and its symbol index
+    pub fn apply_to_graph(&self, graph: &mut YcgGraph, symbol_index: &mut SymbolIndex) {
+        for node in &mut graph.definitions {
+            node.id = self.compact(&node.id);
+            node.name = self.compact(&node.name);
+            if let Some(parent) = &node.parent_id {
+                node.parent_id = Some(self.compact(parent));
+            }
+            if let Some(sig) = &node.signature {
+                node.signature = Some(self.compact_types(sig));
+            }
+            if let Some(target) = node
+                .logic
+                .as_mut()
+                .and_then(|logic| logic.delegates_to.as_mut())
+            {
+                *target = self.compact(target);
+            }
+        }
+
+        for edge in &mut graph.references {
+            edge.from = self.compact(&edge.from);
+            edge.to = self.compact(&edge.to);
+        }
+        graph.references.sort();
+        graph.references.dedup();
+
+        symbol_index.lines = std::mem::take(&mut symbol_index.lines)
+            .into_iter()
+            .map(|(anchor, line)| (self.compact(&anchor), line))
+            .collect();
+        symbol_index.fingerprints = std::mem::take(&mut symbol_index.fingerprints)
+            .into_iter()
+            .map(|(anchor, fingerprint)| (self.compact(&anchor), fingerprint))
+            .collect();
+        for anchors in symbol_index.files.values_mut() {
+            for anchor in anchors.iter_mut() {
+                *anchor = self.compact(anchor);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{
+        EdgeType, LogicMetadata, ProjectMetadata, ReferenceEdge, ScipSymbolKind, SymbolNode,
+    };
+
+    fn create_test_node(id: &str, name: &str, signature: Option<&str>) -> SymbolNode {
+        SymbolNode {
+            id: id.to_string(),
+            name: name.to_string(),
+            kind: ScipSymbolKind::Class,
+            signature: signature.map(|s| s.to_string()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_compact_default_suffixes() {
+        let compactor = NameCompactor::new(&BTreeMap::new());
+
+        assert_eq!(compactor.compact("UsersController"), "UsersCtrl");
+        assert_eq!(
+            compactor.compact("UsersService_findOne_7fed"),
+            "UsersSvc_findOne_7fed"
+        );
+        assert_eq!(compactor.compact("UserRepository"), "UserRepo");
+        // Not a word-ending suffix
+        assert_eq!(compactor.compact("Services"), "Services");
+        assert_eq!(compactor.compact("findUser"), "findUser");
+    }
+
+    #[test]
+    fn test_custom_abbreviations_override_defaults() {
+        let mut map = BTreeMap::new();
+        map.insert("Handler".to_string(), "H".to_string());
+        let compactor = NameCompactor::new(&map);
+
+        assert_eq!(compactor.compact("LoginHandler"), "LoginH");
+        assert_eq!(compactor.compact("UsersController"), "UsersController");
+    }
+
+    #[test]
+    fn test_graph_names_and_edges_abbreviated_identically() {
+        let mut graph = YcgGraph {
+            metadata: ProjectMetadata {
+                name: "test".to_string(),
+                version: "1.0.0".to_string(),
+                ..Default::default()
+            },
+            definitions: vec![
+                create_test_node("UsersController_a1b2", "UsersController", None),
+                create_test_node(
+                    "UsersService_c3d4",
+                    "UsersService",
+                    Some("constructor(usersService: UsersService)"),
+                ),
+            ],
+            references: vec![ReferenceEdge {
+                from: "UsersController_a1b2".to_string(),
+                to: "UsersService_c3d4".to_string(),
+                edge_type: EdgeType::Calls,
+                call_text: None,
+            }],
+        };
+        graph.definitions[1].parent_id = Some("UsersController_a1b2".to_string());
+        graph.definitions[0].logic = Some(LogicMetadata {
+            preconditions: Vec::new(),
+            steps: Vec::new(),
+            delegates_to: Some("UsersService_c3d4".to_string()),
+        });
+
+        let mut symbol_index = SymbolIndex::default();
+        symbol_index.insert("src/users.controller.ts", "UsersController_a1b2", 3);
+
+        NameCompactor::new(&BTreeMap::new()).apply_to_graph(&mut graph, &mut symbol_index);
+
+        assert_eq!(graph.definitions[0].id, "UsersCtrl_a1b2");
+        assert_eq!(graph.definitions[0].name, "UsersCtrl");
+        assert_eq!(graph.definitions[1].name, "UsersSvc");
+        assert_eq!(
+            graph.definitions[1].parent_id.as_deref(),
+            Some("UsersCtrl_a1b2")
+        );
+        assert_eq!(
+            graph.definitions[1].signature.as_deref(),
+            Some("constructor(usersService: UsersSvc)")
+        );
+
+        let ids: Vec<&str> = graph.definitions.iter().map(|n| n.id.as_str()).collect();
+        let edge = &graph.references[0];
+        assert!(ids.contains(&edge.from.as_str()));
+        assert!(ids.contains(&edge.to.as_str()));
+        let delegate = graph.definitions[0].logic.as_ref().unwrap();
+        assert_eq!(delegate.delegates_to.as_deref(), Some("UsersSvc_c3d4"));
+        assert_eq!(symbol_index.lines["UsersCtrl_a1b2"], 3);
+        assert_eq!(
+            symbol_index.files["src/users.controller.ts"],
+            vec!["UsersCtrl_a1b2"]
+        );
+    }
+}
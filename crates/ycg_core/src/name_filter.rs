@@ -0,0 +1,244 @@
+// crates/ycg_core/src/name_filter.rs
+
+use crate::compiled_filters::compile_regex;
+use crate::model::{NameFilterConfig, YcgGraph};
+use anyhow::{Context, Result};
+use regex::Regex;
+use std::collections::{HashMap, HashSet};
+
+/// Symbol name filter that applies include/exclude regexes to `SymbolNode.name`
+///
+/// Runs after conversion: matched nodes are pruned together with every edge
+/// that touches them. Patterns are compiled once when the filter is built.
+pub struct NameFilter {
+    include_patterns: Vec<Regex>,
+    exclude_patterns: Vec<Regex>,
+}
+
+impl NameFilter {
+    /// Create a new NameFilter from configuration
+    pub fn new(config: &NameFilterConfig) -> Result<Self> {
+        let include_patterns = config
+            .include_patterns
+            .iter()
+            .map(|pattern| {
+                compile_regex(pattern)
+                    .with_context(|| format!("Invalid include-name regex: {}", pattern))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let exclude_patterns = config
+            .exclude_patterns
+            .iter()
+            .map(|pattern| {
+                compile_regex(pattern)
+                    .with_context(|| format!("Invalid exclude-name regex: {}", pattern))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(NameFilter {
+            include_patterns,
+            exclude_patterns,
+        })
+    }
+
+    /// Number of compiled regexes
+    pub fn pattern_count(&self) -> usize {
+        self.include_patterns.len() + self.exclude_patterns.len()
+    }
+
+    /// Check if a symbol should be kept based on its name
+    ///
+    /// Rules (in order):
+    /// 1. If include patterns exist, the name must match at least one
+    /// 2. If the name matches any exclude pattern, it's dropped
+    /// 3. Otherwise, the symbol is kept
+    pub fn should_keep(&self, name: &str) -> bool {
+        if !self.include_patterns.is_empty()
+            && !self.include_patterns.iter().any(|re| re.is_match(name))
+        {
+            return false;
+        }
+
+        !self.exclude_patterns.iter().any(|re| re.is_match(name))
+    }
+
+    /// Remove nodes whose names are filtered out, and every edge touching them
+    ///
+    /// Children of a removed node move up to its nearest kept ancestor (no
+    /// parent when there is none).
+    pub fn filter_graph(&self, graph: &mut YcgGraph) {
+        let nodes_to_remove: HashSet<String> = graph
+            .definitions
+            .iter()
+            .filter(|node| !self.should_keep(&node.name))
+            .map(|node| node.id.clone())
+            .collect();
+        let removed_parents: HashMap<String, Option<String>> = graph
+            .definitions
+            .iter()
+            .filter(|node| nodes_to_remove.contains(&node.id))
+            .map(|node| (node.id.clone(), node.parent_id.clone()))
+            .collect();
+
+        graph
+            .definitions
+            .retain(|node| !nodes_to_remove.contains(&node.id));
+        for node in &mut graph.definitions {
+            // Parent chains are short; the cap only guards against cycles
+            for _ in 0..32 {
+                match node.parent_id.as_ref().and_then(|p| removed_parents.get(p)) {
+                    Some(grandparent) => node.parent_id = grandparent.clone(),
+                    None => break,
+                }
+            }
+            if node
+                .parent_id
+                .as_ref()
+                .is_some_and(|p| nodes_to_remove.contains(p))
+            {
+                node.parent_id = None;
+            }
+        }
+
+        graph.references.retain(|edge| {
+            !nodes_to_remove.contains(&edge.from) && !nodes_to_remove.contains(&edge.to)
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{EdgeType, ProjectMetadata, ReferenceEdge, ScipSymbolKind, SymbolNode};
+
+    fn create_test_node(id: &str, name: &str) -> SymbolNode {
+        SymbolNode {
+            id: id.to_string(),
+            name: name.to_string(),
+            kind: ScipSymbolKind::Method,
+            ..Default::default()
+        }
+    }
+
+    fn create_test_graph() -> YcgGraph {
+        YcgGraph {
+            metadata: ProjectMetadata {
+                name: "test".to_string(),
+                version: "1.0.0".to_string(),
+                ..Default::default()
+            },
+            definitions: vec![
+                create_test_node("handleLogin_1a2b", "handleLogin"),
+                create_test_node("handleLogout_3c4d", "handleLogout"),
+                create_test_node("UserRepoMock_5e6f", "UserRepoMock"),
+                create_test_node("validate_7a8b", "validate"),
+            ],
+            references: vec![
+                ReferenceEdge {
+                    from: "handleLogin_1a2b".to_string(),
+                    to: "UserRepoMock_5e6f".to_string(),
+                    edge_type: EdgeType::Calls,
+                    call_text: None,
+                },
+                ReferenceEdge {
+                    from: "handleLogin_1a2b".to_string(),
+                    to: "validate_7a8b".to_string(),
+                    edge_type: EdgeType::Calls,
+                    call_text: None,
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn test_exclude_name_removes_mocks_and_their_edges() {
+        let config = NameFilterConfig {
+            include_patterns: vec![],
+            exclude_patterns: vec![".*Mock$".to_string()],
+        };
+        let mut graph = create_test_graph();
+
+        NameFilter::new(&config).unwrap().filter_graph(&mut graph);
+
+        assert_eq!(graph.definitions.len(), 3);
+        assert!(graph.definitions.iter().all(|n| n.name != "UserRepoMock"));
+        assert_eq!(graph.references.len(), 1);
+        assert_eq!(graph.references[0].to, "validate_7a8b");
+    }
+
+    #[test]
+    fn test_children_of_removed_node_move_to_kept_ancestor() {
+        let config = NameFilterConfig {
+            include_patterns: vec![],
+            exclude_patterns: vec!["Mock".to_string()],
+        };
+        let child = |id: &str, name: &str, parent: &str| SymbolNode {
+            parent_id: Some(parent.to_string()),
+            ..create_test_node(id, name)
+        };
+        let mut graph = create_test_graph();
+        graph.definitions.extend([
+            create_test_node("users_file_0001", "users.ts"),
+            child("MockFactory_0002", "MockFactory", "users_file_0001"),
+            child("RepoMock_0003", "RepoMock", "MockFactory_0002"),
+            child("build_0004", "build", "RepoMock_0003"),
+            child("reset_0005", "reset", "UserRepoMock_5e6f"),
+        ]);
+
+        NameFilter::new(&config).unwrap().filter_graph(&mut graph);
+
+        let ids: HashSet<&str> = graph.definitions.iter().map(|n| n.id.as_str()).collect();
+        assert!(
+            graph
+                .definitions
+                .iter()
+                .filter_map(|n| n.parent_id.as_deref())
+                .all(|parent| ids.contains(parent))
+        );
+        let parent_of = |id: &str| {
+            graph
+                .definitions
+                .iter()
+                .find(|n| n.id == id)
+                .unwrap()
+                .parent_id
+                .as_deref()
+        };
+        assert_eq!(parent_of("build_0004"), Some("users_file_0001"));
+        assert_eq!(parent_of("reset_0005"), None);
+    }
+
+    #[test]
+    fn test_include_name_keeps_only_handlers() {
+        let config = NameFilterConfig {
+            include_patterns: vec!["^handle".to_string()],
+            exclude_patterns: vec![],
+        };
+        let mut graph = create_test_graph();
+
+        NameFilter::new(&config).unwrap().filter_graph(&mut graph);
+
+        let names: Vec<&str> = graph.definitions.iter().map(|n| n.name.as_str()).collect();
+        assert_eq!(names, vec!["handleLogin", "handleLogout"]);
+        assert!(graph.references.is_empty());
+    }
+
+    #[test]
+    fn test_invalid_regex_is_rejected() {
+        let config = NameFilterConfig {
+            include_patterns: vec![],
+            exclude_patterns: vec!["(unclosed".to_string()],
+        };
+
+        let result = NameFilter::new(&config);
+        assert!(result.is_err());
+        assert!(
+            result
+                .err()
+                .unwrap()
+                .to_string()
+                .contains("Invalid exclude-name regex")
+        );
+    }
+}
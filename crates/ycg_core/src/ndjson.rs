@@ -0,0 +1,117 @@
+// crates/ycg_core/src/ndjson.rs
+//! Newline-delimited JSON output (`--output-format ndjson`).
+//!
+//! One JSON object per line, so consumers can stream arbitrarily large graphs:
+//!
+//! ```text
+//! {"t":"meta","v":{"name":"ycg-v1.3","version":"1.3.0"}}
+//! {"t":"def","v":{"id":"UserService_a1b2","n":"UserService","t":"class"}}
+//! {"t":"edge","v":{"from":"findOne_c3d4","to":"UserService_a1b2","type":"calls"}}
+//! ```
+//!
+//! The record sits under `v` because definitions already use `t` for their kind.
+//! Edges are always a flat list (`--compact` does not apply).
+
+use crate::model::{ProjectMetadata, ReferenceEdge, SymbolNode, YcgGraph};
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+
+/// One NDJSON line
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "t", content = "v", rename_all = "lowercase")]
+pub enum NdjsonRecord {
+    Meta(ProjectMetadata),
+    Def(Box<SymbolNode>),
+    Edge(ReferenceEdge),
+}
+
+/// Serializes `graph` as NDJSON: the `_meta` line, then definitions, then edges
+pub fn to_ndjson(graph: YcgGraph) -> Result<String> {
+    let records = std::iter::once(NdjsonRecord::Meta(graph.metadata))
+        .chain(
+            graph
+                .definitions
+                .into_iter()
+                .map(|node| NdjsonRecord::Def(Box::new(node))),
+        )
+        .chain(graph.references.into_iter().map(NdjsonRecord::Edge));
+
+    let mut output = String::new();
+    for record in records {
+        output.push_str(&serde_json::to_string(&record)?);
+        output.push('\n');
+    }
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{EdgeType, ScipSymbolKind};
+
+    fn node(id: &str, kind: ScipSymbolKind, parent: Option<&str>) -> SymbolNode {
+        SymbolNode {
+            id: id.to_string(),
+            name: id.split('_').next().unwrap().to_string(),
+            kind,
+            parent_id: parent.map(|p| p.to_string()),
+            signature: Some("findOne(id: number)\n  : User".to_string()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_each_line_is_standalone_json_and_counts_match() {
+        let graph = YcgGraph {
+            metadata: ProjectMetadata {
+                name: "demo".to_string(),
+                version: "1.0.0".to_string(),
+                ..Default::default()
+            },
+            definitions: vec![
+                node("UserService_a1b2", ScipSymbolKind::Class, None),
+                node(
+                    "findOne_c3d4",
+                    ScipSymbolKind::Method,
+                    Some("UserService_a1b2"),
+                ),
+            ],
+            references: vec![ReferenceEdge {
+                from: "findOne_c3d4".to_string(),
+                to: "UserService_a1b2".to_string(),
+                edge_type: EdgeType::References,
+                call_text: None,
+            }],
+        };
+
+        let output = to_ndjson(graph).unwrap();
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(lines.len(), 1 + 2 + 1);
+
+        let values: Vec<serde_json::Value> = lines
+            .iter()
+            .map(|line| serde_json::from_str(line).expect("standalone JSON line"))
+            .collect();
+        let tags: Vec<&str> = values.iter().map(|v| v["t"].as_str().unwrap()).collect();
+        assert_eq!(tags, vec!["meta", "def", "def", "edge"]);
+
+        assert_eq!(values[0]["v"]["name"], "demo");
+        assert_eq!(values[2]["v"]["t"], "method");
+        assert_eq!(values[2]["v"]["parent_id"], "UserService_a1b2");
+        assert_eq!(values[3]["v"]["type"], "references");
+
+        // Records round-trip through the typed enum
+        let record: NdjsonRecord = serde_json::from_str(lines[1]).unwrap();
+        assert!(matches!(record, NdjsonRecord::Def(node) if node.id == "UserService_a1b2"));
+    }
+
+    #[test]
+    fn test_empty_graph_is_a_single_meta_line() {
+        let graph = YcgGraph {
+            metadata: ProjectMetadata::default(),
+            definitions: Vec::new(),
+            references: Vec::new(),
+        };
+        assert_eq!(to_ndjson(graph).unwrap().lines().count(), 1);
+    }
+}
@@ -0,0 +1,326 @@
+// crates/ycg_core/src/numeric_ids.rs
+
+use crate::model::{EdgeType, LogicMetadata, ProjectMetadata, ReferenceEdge, SymbolNode, YcgGraph};
+use anyhow::{Result, anyhow};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+
+/// `_meta` of a numeric-ID graph: project metadata plus the ID -> anchor table
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct NumericMetadata {
+    #[serde(flatten)]
+    pub project: ProjectMetadata,
+    /// `anchors[n]` is the hash anchor of numeric ID `n`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub anchors: Vec<String>,
+}
+
+/// `SymbolNode` with integer `id` / `parent_id` / `logic.delegates_to`
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct NumericSymbolNode {
+    pub id: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parent_id: Option<u32>,
+    /// Every other field; its own `id` is empty, its `parent_id` and `logic` unset
+    #[serde(flatten)]
+    pub node: SymbolNode,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub logic: Option<NumericLogic>,
+}
+
+/// `LogicMetadata` delegating to a numeric ID
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct NumericLogic {
+    /// Every other field; its own `delegates_to` is unset
+    #[serde(flatten)]
+    pub logic: LogicMetadata,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub delegates_to: Option<u32>,
+}
+
+/// `ReferenceEdge` between numeric IDs
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct NumericEdge {
+    pub from: u32,
+    pub to: u32,
+    #[serde(rename = "type")]
+    pub edge_type: EdgeType,
+}
+
+/// Edge section: flat list, or adjacency list in compact mode
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(untagged)]
+pub enum NumericEdges {
+    List(Vec<NumericEdge>),
+    Adjacency(BTreeMap<u32, BTreeMap<EdgeType, Vec<u32>>>),
+}
+
+/// A graph whose anchors are replaced by small sequential integers (`--numeric-ids`)
+///
+/// Definitions are numbered in output order starting at 0; anchors that are
+/// only referenced (external targets, filtered parents) get the next IDs.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct NumericGraph {
+    #[serde(rename = "_meta")]
+    pub metadata: NumericMetadata,
+    #[serde(rename = "_defs")]
+    pub definitions: Vec<NumericSymbolNode>,
+    #[serde(rename = "graph")]
+    pub references: NumericEdges,
+}
+
+/// Assigns IDs on first sight, remembering the anchor for each
+#[derive(Default)]
+struct IdAllocator {
+    ids: HashMap<String, u32>,
+    anchors: Vec<String>,
+}
+
+impl IdAllocator {
+    fn id_of(&mut self, anchor: &str) -> u32 {
+        if let Some(&id) = self.ids.get(anchor) {
+            return id;
+        }
+        let id = self.anchors.len() as u32;
+        self.ids.insert(anchor.to_string(), id);
+        self.anchors.push(anchor.to_string());
+        id
+    }
+}
+
+impl NumericGraph {
+    /// Renumber `graph`, emitting an adjacency list when `compact` is set
+    pub fn from_graph(graph: YcgGraph, compact: bool) -> Self {
+        let mut alloc = IdAllocator::default();
+
+        // Definitions first so their IDs follow output order
+        for node in &graph.definitions {
+            alloc.id_of(&node.id);
+        }
+
+        let definitions = graph
+            .definitions
+            .into_iter()
+            .map(|mut node| NumericSymbolNode {
+                id: alloc.id_of(&std::mem::take(&mut node.id)),
+                parent_id: node.parent_id.take().map(|p| alloc.id_of(&p)),
+                logic: node.logic.take().map(|mut logic| NumericLogic {
+                    delegates_to: logic.delegates_to.take().map(|t| alloc.id_of(&t)),
+                    logic,
+                }),
+                node,
+            })
+            .collect();
+
+        let mut edges: Vec<NumericEdge> = graph
+            .references
+            .iter()
+            .map(|edge| NumericEdge {
+                from: alloc.id_of(&edge.from),
+                to: alloc.id_of(&edge.to),
+                edge_type: edge.edge_type.clone(),
+            })
+            .collect();
+        edges.sort();
+        edges.dedup();
+
+        let references = if compact {
+            let mut adjacency: BTreeMap<u32, BTreeMap<EdgeType, Vec<u32>>> = BTreeMap::new();
+            for edge in edges {
+                adjacency
+                    .entry(edge.from)
+                    .or_default()
+                    .entry(edge.edge_type)
+                    .or_default()
+                    .push(edge.to);
+            }
+            NumericEdges::Adjacency(adjacency)
+        } else {
+            NumericEdges::List(edges)
+        };
+
+        NumericGraph {
+            metadata: NumericMetadata {
+                project: graph.metadata,
+                anchors: alloc.anchors,
+            },
+            definitions,
+            references,
+        }
+    }
+
+    /// Map every numeric ID back to its anchor, restoring the standard graph
+    pub fn to_graph(&self) -> Result<YcgGraph> {
+        let anchor = |id: u32| -> Result<String> {
+            self.metadata
+                .anchors
+                .get(id as usize)
+                .cloned()
+                .ok_or_else(|| anyhow!("Numeric ID {} has no anchor in _meta.anchors", id))
+        };
+
+        let definitions = self
+            .definitions
+            .iter()
+            .map(|numeric| {
+                let logic = match &numeric.logic {
+                    Some(numeric_logic) => Some(LogicMetadata {
+                        delegates_to: numeric_logic.delegates_to.map(anchor).transpose()?,
+                        ..numeric_logic.logic.clone()
+                    }),
+                    None => None,
+                };
+                Ok(SymbolNode {
+                    id: anchor(numeric.id)?,
+                    parent_id: numeric.parent_id.map(anchor).transpose()?,
+                    logic,
+                    ..numeric.node.clone()
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let edges: Vec<(u32, u32, EdgeType)> = match &self.references {
+            NumericEdges::List(edges) => edges
+                .iter()
+                .map(|e| (e.from, e.to, e.edge_type.clone()))
+                .collect(),
+            NumericEdges::Adjacency(adjacency) => adjacency
+                .iter()
+                .flat_map(|(from, by_type)| {
+                    by_type.iter().flat_map(move |(edge_type, targets)| {
+                        targets
+                            .iter()
+                            .map(move |to| (*from, *to, edge_type.clone()))
+                    })
+                })
+                .collect(),
+        };
+        let references = edges
+            .into_iter()
+            .map(|(from, to, edge_type)| {
+                Ok(ReferenceEdge {
+                    from: anchor(from)?,
+                    to: anchor(to)?,
+                    edge_type,
+                    call_text: None,
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(YcgGraph {
+            metadata: self.metadata.project.clone(),
+            definitions,
+            references,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::ScipSymbolKind;
+
+    fn create_test_node(id: &str, parent_id: Option<&str>) -> SymbolNode {
+        SymbolNode {
+            id: id.to_string(),
+            name: id.split('_').next().unwrap().to_string(),
+            kind: ScipSymbolKind::Method,
+            parent_id: parent_id.map(|p| p.to_string()),
+            ..Default::default()
+        }
+    }
+
+    fn edge(from: &str, to: &str, edge_type: EdgeType) -> ReferenceEdge {
+        ReferenceEdge {
+            from: from.to_string(),
+            to: to.to_string(),
+            edge_type,
+            call_text: None,
+        }
+    }
+
+    fn sample_graph() -> YcgGraph {
+        YcgGraph {
+            metadata: ProjectMetadata {
+                name: "test".to_string(),
+                version: "1.0.0".to_string(),
+                ..Default::default()
+            },
+            definitions: vec![
+                create_test_node("UsersController_4702", None),
+                SymbolNode {
+                    logic: Some(LogicMetadata {
+                        preconditions: Vec::new(),
+                        steps: Vec::new(),
+                        delegates_to: Some("UsersService_9c3d".to_string()),
+                    }),
+                    ..create_test_node("findAll_1a2b", Some("UsersController_4702"))
+                },
+                create_test_node("UsersService_9c3d", None),
+            ],
+            references: vec![
+                edge("findAll_1a2b", "UsersService_9c3d", EdgeType::Calls),
+                edge("UsersService_9c3d", "ext_77aa", EdgeType::Imports),
+            ],
+        }
+    }
+
+    #[test]
+    fn test_edges_reference_numeric_ids() {
+        let numeric = NumericGraph::from_graph(sample_graph(), false);
+
+        let ids: Vec<u32> = numeric.definitions.iter().map(|n| n.id).collect();
+        assert_eq!(ids, vec![0, 1, 2]);
+        assert_eq!(numeric.definitions[1].parent_id, Some(0));
+        let logic = numeric.definitions[1].logic.as_ref().unwrap();
+        assert_eq!(logic.delegates_to, Some(2));
+
+        let NumericEdges::List(edges) = &numeric.references else {
+            panic!("expected an edge list");
+        };
+        assert!(edges.contains(&NumericEdge {
+            from: 1,
+            to: 2,
+            edge_type: EdgeType::Calls
+        }));
+        // External target gets the next free ID
+        assert_eq!(numeric.metadata.anchors[3], "ext_77aa");
+
+        let yaml = serde_yaml::to_string(&numeric).unwrap();
+        assert!(yaml.contains("from: 1\n"));
+        assert!(yaml.contains("- id: 0\n"));
+        assert!(yaml.contains("  parent_id: 0\n"), "{}", yaml);
+        // The shared node's own (emptied) id is not written
+        assert!(!yaml.contains("id: ''"), "{}", yaml);
+        assert!(yaml.contains("    delegates_to: 2\n"), "{}", yaml);
+    }
+
+    #[test]
+    fn test_mapping_round_trips_to_anchors() {
+        for compact in [false, true] {
+            let numeric = NumericGraph::from_graph(sample_graph(), compact);
+            let yaml = serde_yaml::to_string(&numeric).unwrap();
+            let parsed: NumericGraph = serde_yaml::from_str(&yaml).unwrap();
+            let restored = parsed.to_graph().unwrap();
+
+            let original = sample_graph();
+            let restored_ids: Vec<&str> =
+                restored.definitions.iter().map(|n| n.id.as_str()).collect();
+            let original_ids: Vec<&str> =
+                original.definitions.iter().map(|n| n.id.as_str()).collect();
+            assert_eq!(restored_ids, original_ids);
+            assert_eq!(
+                restored.definitions[1].parent_id.as_deref(),
+                Some("UsersController_4702")
+            );
+            let logic = restored.definitions[1].logic.as_ref().unwrap();
+            assert_eq!(logic.delegates_to.as_deref(), Some("UsersService_9c3d"));
+
+            let mut restored_edges = restored.references.clone();
+            let mut original_edges = original.references.clone();
+            restored_edges.sort();
+            original_edges.sort();
+            assert_eq!(restored_edges, original_edges);
+        }
+    }
+}
@@ -0,0 +1,154 @@
+// crates/ycg_core/src/ownership.rs
+//! Code ownership from git history (`--with-ownership`).
+//!
+//! Owners of a file are the authors with the most commits touching it,
+//! taken from `git log`. Outside a git work tree (or without a `git`
+//! binary) every lookup returns no owners.
+
+use crate::event_log::{Event, EventLog};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Number of top authors kept per file
+pub const MAX_OWNERS: usize = 3;
+
+/// Resolves and caches the top git authors of files under a project root
+pub struct OwnershipResolver {
+    /// Project root, `None` when it is not inside a git work tree
+    root: Option<PathBuf>,
+    cache: HashMap<String, Vec<String>>,
+}
+
+impl OwnershipResolver {
+    pub fn new(project_root: &Path, log: &EventLog) -> Self {
+        let inside_work_tree = Command::new("git")
+            .arg("-C")
+            .arg(project_root)
+            .args(["rev-parse", "--is-inside-work-tree"])
+            .output()
+            .map(|out| out.status.success() && out.stdout.starts_with(b"true"))
+            .unwrap_or(false);
+
+        if !inside_work_tree {
+            log.emit(Event::warning(format!(
+                "{} is not a git repository, skipping ownership",
+                project_root.display()
+            )));
+        }
+
+        Self {
+            root: inside_work_tree.then(|| project_root.to_path_buf()),
+            cache: HashMap::new(),
+        }
+    }
+
+    /// Top authors of `relative_path` by commit count (ties by name), at most [`MAX_OWNERS`]
+    pub fn owners(&mut self, relative_path: &str) -> Vec<String> {
+        let Some(root) = &self.root else {
+            return Vec::new();
+        };
+        if let Some(owners) = self.cache.get(relative_path) {
+            return owners.clone();
+        }
+
+        let owners = Command::new("git")
+            .arg("-C")
+            .arg(root)
+            .args(["log", "--follow", "--format=%aN", "--"])
+            .arg(relative_path)
+            .output()
+            .ok()
+            .filter(|out| out.status.success())
+            .map(|out| top_authors(&String::from_utf8_lossy(&out.stdout)))
+            .unwrap_or_default();
+
+        self.cache.insert(relative_path.to_string(), owners.clone());
+        owners
+    }
+}
+
+/// Ranks the author names of a `git log --format=%aN` output
+fn top_authors(log: &str) -> Vec<String> {
+    let mut counts: HashMap<&str, usize> = HashMap::new();
+    for author in log.lines().map(str::trim).filter(|a| !a.is_empty()) {
+        *counts.entry(author).or_default() += 1;
+    }
+
+    let mut ranked: Vec<(&str, usize)> = counts.into_iter().collect();
+    ranked.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(b.0)));
+    ranked
+        .into_iter()
+        .take(MAX_OWNERS)
+        .map(|(author, _)| author.to_string())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn git(dir: &Path, args: &[&str]) {
+        let status = Command::new("git")
+            .arg("-C")
+            .arg(dir)
+            .args(args)
+            .status()
+            .unwrap();
+        assert!(status.success(), "git {:?} failed", args);
+    }
+
+    fn commit_as(dir: &Path, author: &str, file: &str, content: &str) {
+        std::fs::write(dir.join(file), content).unwrap();
+        git(dir, &["add", file]);
+        git(
+            dir,
+            &[
+                "-c",
+                &format!("user.name={}", author),
+                "-c",
+                "user.email=dev@example.com",
+                "commit",
+                "-q",
+                "-m",
+                "change",
+            ],
+        );
+    }
+
+    #[test]
+    fn test_owners_from_git_log() {
+        let dir = tempfile::tempdir().unwrap();
+        git(dir.path(), &["init", "-q"]);
+        commit_as(dir.path(), "Ana Souza", "users.ts", "v1");
+        commit_as(dir.path(), "Bruno Lima", "users.ts", "v2");
+        commit_as(dir.path(), "Ana Souza", "users.ts", "v3");
+        commit_as(dir.path(), "Carla Dias", "other.ts", "v1");
+
+        let mut resolver = OwnershipResolver::new(dir.path(), &EventLog::default());
+        assert_eq!(resolver.owners("users.ts"), vec!["Ana Souza", "Bruno Lima"]);
+        assert_eq!(resolver.owners("other.ts"), vec!["Carla Dias"]);
+        assert!(resolver.owners("missing.ts").is_empty());
+    }
+
+    #[test]
+    fn test_no_owners_outside_git_repo() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("users.ts"), "v1").unwrap();
+        let log_dir = tempfile::tempdir().unwrap();
+        let log_path = log_dir.path().join("events.ndjson");
+
+        let mut resolver =
+            OwnershipResolver::new(dir.path(), &EventLog::create(&log_path).unwrap());
+        assert!(resolver.owners("users.ts").is_empty());
+        let event: serde_json::Value =
+            serde_json::from_str(std::fs::read_to_string(&log_path).unwrap().trim()).unwrap();
+        assert_eq!(event["event"], "warning");
+    }
+
+    #[test]
+    fn test_top_authors_ranks_by_commit_count() {
+        let log = "b\na\nc\nb\nd\nc\nb\n";
+        assert_eq!(top_authors(log), vec!["b", "c", "a"]);
+    }
+}
@@ -0,0 +1,208 @@
+// crates/ycg_core/src/plantuml.rs
+//! PlantUML class diagram output (`--output-format plantuml`).
+//!
+//! Classes, interfaces and enums become `class` / `interface` / `enum`
+//! blocks listing their members (child definitions, written as their
+//! one-line signature when there is one). Relationships are drawn between
+//! those blocks:
+//!
+//! ```text
+//! @startuml
+//! class "UserService" as UserService_a1b2 {
+//!   findOne(id: number): User
+//! }
+//! interface "Repository" as Repository_c3d4
+//! UserService_a1b2 ..|> Repository_c3d4
+//! UserService_a1b2 ..> UserRepository_e5f6 : calls
+//! @enduml
+//! ```
+//!
+//! `implements` edges to an interface are realizations (`..|>`), to a class
+//! they are inheritance (`--|>`). Calls made by members are rolled up to
+//! their enclosing types and drawn once per pair of types. Definitions
+//! outside any type (top-level functions, variables) are left out.
+
+use crate::model::{EdgeType, ScipSymbolKind, SymbolNode, YcgGraph};
+use std::collections::{BTreeSet, HashMap};
+use std::fmt::Write;
+
+fn is_type(kind: &ScipSymbolKind) -> bool {
+    matches!(
+        kind,
+        ScipSymbolKind::Class | ScipSymbolKind::Interface | ScipSymbolKind::Enum
+    )
+}
+
+/// Anchor usable as a PlantUML alias (`cls:User_4702` -> `cls_User_4702`)
+fn alias(id: &str) -> String {
+    id.chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect()
+}
+
+/// Member line: the signature on one line, or the name (`name()` for methods)
+fn member_line(node: &SymbolNode) -> String {
+    match &node.signature {
+        Some(signature) => signature.split_whitespace().collect::<Vec<_>>().join(" "),
+        None if matches!(node.kind, ScipSymbolKind::Method | ScipSymbolKind::Function) => {
+            format!("{}()", node.name)
+        }
+        None => node.name.clone(),
+    }
+}
+
+/// Writes the class diagram of `graph`
+pub fn to_plantuml(graph: &YcgGraph) -> String {
+    let by_id: HashMap<&str, &SymbolNode> = graph
+        .definitions
+        .iter()
+        .map(|node| (node.id.as_str(), node))
+        .collect();
+
+    // Nearest enclosing type of a definition (itself for a type)
+    let owner = |id: &str| -> Option<&SymbolNode> {
+        let mut current = by_id.get(id).copied();
+        let mut depth = 0;
+        while let Some(node) = current {
+            if is_type(&node.kind) {
+                return Some(node);
+            }
+            // Parent chains are short; the cap only guards against cycles
+            depth += 1;
+            if depth > 32 {
+                return None;
+            }
+            current = node
+                .parent_id
+                .as_deref()
+                .and_then(|p| by_id.get(p).copied());
+        }
+        None
+    };
+
+    let mut members: HashMap<&str, Vec<&SymbolNode>> = HashMap::new();
+    for node in &graph.definitions {
+        if let Some(parent) = node.parent_id.as_deref()
+            && by_id.get(parent).is_some_and(|p| is_type(&p.kind))
+        {
+            members.entry(parent).or_default().push(node);
+        }
+    }
+
+    let mut out = String::from("@startuml\n");
+    for node in graph.definitions.iter().filter(|node| is_type(&node.kind)) {
+        let keyword = match node.kind {
+            ScipSymbolKind::Interface => "interface",
+            ScipSymbolKind::Enum => "enum",
+            _ => "class",
+        };
+        let _ = write!(out, "{} \"{}\" as {}", keyword, node.name, alias(&node.id));
+        match members.get(node.id.as_str()) {
+            Some(children) => {
+                out.push_str(" {\n");
+                for child in children {
+                    let _ = writeln!(out, "  {}", member_line(child));
+                }
+                out.push_str("}\n");
+            }
+            None => out.push('\n'),
+        }
+    }
+
+    let mut relations = BTreeSet::new();
+    for edge in &graph.references {
+        let (Some(from), Some(to)) = (owner(&edge.from), owner(&edge.to)) else {
+            continue;
+        };
+        if from.id == to.id {
+            continue;
+        }
+        let (arrow, label) = match &edge.edge_type {
+            EdgeType::Implements if to.kind == ScipSymbolKind::Interface => ("..|>", ""),
+            EdgeType::Implements => ("--|>", ""),
+            EdgeType::Calls => ("..>", " : calls"),
+            _ => continue,
+        };
+        relations.insert((alias(&from.id), arrow, alias(&to.id), label));
+    }
+    for (from, arrow, to, label) in relations {
+        let _ = writeln!(out, "{} {} {}{}", from, arrow, to, label);
+    }
+    out.push_str("@enduml\n");
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{ProjectMetadata, ReferenceEdge};
+
+    fn node(id: &str, kind: ScipSymbolKind, parent: Option<&str>, sig: Option<&str>) -> SymbolNode {
+        SymbolNode {
+            id: id.to_string(),
+            name: id.split('_').next().unwrap().to_string(),
+            kind,
+            parent_id: parent.map(str::to_string),
+            signature: sig.map(str::to_string),
+            ..Default::default()
+        }
+    }
+
+    fn edge(from: &str, to: &str, edge_type: EdgeType) -> ReferenceEdge {
+        ReferenceEdge {
+            from: from.to_string(),
+            to: to.to_string(),
+            edge_type,
+            call_text: None,
+        }
+    }
+
+    #[test]
+    fn test_class_diagram() {
+        let graph = YcgGraph {
+            metadata: ProjectMetadata::default(),
+            definitions: vec![
+                node("Repository_c3d4", ScipSymbolKind::Interface, None, None),
+                node("UserService_a1b2", ScipSymbolKind::Class, None, None),
+                node(
+                    "findOne_e5f6",
+                    ScipSymbolKind::Method,
+                    Some("UserService_a1b2"),
+                    Some("findOne(id: number)\n  : User"),
+                ),
+                node(
+                    "save_0a1b",
+                    ScipSymbolKind::Method,
+                    Some("UserService_a1b2"),
+                    None,
+                ),
+                node("Store_9f8e", ScipSymbolKind::Class, None, None),
+                node("get_7d6c", ScipSymbolKind::Method, Some("Store_9f8e"), None),
+                node("main_1234", ScipSymbolKind::Function, None, None),
+            ],
+            references: vec![
+                edge("UserService_a1b2", "Repository_c3d4", EdgeType::Implements),
+                edge("findOne_e5f6", "get_7d6c", EdgeType::Calls),
+                edge("save_0a1b", "get_7d6c", EdgeType::Calls),
+                edge("findOne_e5f6", "save_0a1b", EdgeType::Calls),
+                edge("main_1234", "findOne_e5f6", EdgeType::Calls),
+            ],
+        };
+        let uml = to_plantuml(&graph);
+        assert!(uml.starts_with("@startuml\n"));
+        assert!(uml.ends_with("@enduml\n"));
+        assert!(uml.contains(
+            "class \"UserService\" as UserService_a1b2 {\n  findOne(id: number) : User\n  save()\n}\n"
+        ));
+        assert!(uml.contains("interface \"Repository\" as Repository_c3d4\n"));
+        assert!(uml.contains("UserService_a1b2 ..|> Repository_c3d4\n"));
+        // Member calls rolled up to one arrow; self and top-level calls dropped
+        assert_eq!(
+            uml.matches("UserService_a1b2 ..> Store_9f8e : calls")
+                .count(),
+            1
+        );
+        assert_eq!(uml.matches(": calls").count(), 1);
+        assert!(!uml.contains("main"));
+    }
+}
@@ -0,0 +1,221 @@
+// crates/ycg_core/src/scip_decoder.rs
+//! Decoding of SCIP indexes that are partly corrupt.
+//!
+//! `Index::decode` rejects the whole index when any byte is wrong, so one
+//! truncated or damaged document (an interrupted upload, a crashed indexer)
+//! loses every other document too. [`decode_resilient`] falls back to
+//! walking the top-level fields of the `Index` message one by one: each
+//! document is decoded on its own, corrupt ones are skipped, and a field cut
+//! off by the end of the data ends the walk. The caller gets a partial index
+//! plus one warning per dropped piece.
+
+use crate::scip_proto;
+use prost::Message;
+use prost::encoding::{WireType, decode_key, decode_varint};
+
+/// `Index` field numbers (see scip.proto)
+const METADATA_FIELD: u32 = 1;
+const DOCUMENTS_FIELD: u32 = 2;
+const EXTERNAL_SYMBOLS_FIELD: u32 = 3;
+
+/// An index recovered from corrupt data, with what was dropped on the way
+#[derive(Debug, Default)]
+pub struct PartialIndex {
+    pub index: scip_proto::Index,
+    pub warnings: Vec<String>,
+}
+
+/// Decodes `data` as a SCIP index, salvaging the valid documents when the
+/// whole message does not decode.
+///
+/// Fails only when nothing could be recovered (not a single metadata block,
+/// document or external symbol), with the error of the full decode.
+pub fn decode_resilient(data: &[u8]) -> Result<PartialIndex, prost::DecodeError> {
+    let error = match scip_proto::Index::decode(data) {
+        Ok(index) => {
+            return Ok(PartialIndex {
+                index,
+                warnings: Vec::new(),
+            });
+        }
+        Err(error) => error,
+    };
+
+    let mut partial = PartialIndex::default();
+    let mut buf = data;
+    let mut documents_seen = 0;
+    let mut recovered = false;
+    while !buf.is_empty() {
+        let offset = data.len() - buf.len();
+        let Ok((field, wire_type)) = decode_key(&mut buf) else {
+            partial.warnings.push(format!(
+                "Corrupt field header at byte {}, rest of the index dropped",
+                offset
+            ));
+            break;
+        };
+        let payload = match wire_type {
+            WireType::LengthDelimited => match decode_varint(&mut buf) {
+                Ok(len) if len <= buf.len() as u64 => {
+                    let (payload, rest) = buf.split_at(len as usize);
+                    buf = rest;
+                    payload
+                }
+                _ => {
+                    partial.warnings.push(format!(
+                        "Field {} at byte {} is truncated, rest of the index dropped",
+                        field, offset
+                    ));
+                    break;
+                }
+            },
+            // Unknown scalar fields are skipped like prost would
+            WireType::Varint if decode_varint(&mut buf).is_ok() => continue,
+            WireType::SixtyFourBit if buf.len() >= 8 => {
+                buf = &buf[8..];
+                continue;
+            }
+            WireType::ThirtyTwoBit if buf.len() >= 4 => {
+                buf = &buf[4..];
+                continue;
+            }
+            _ => {
+                partial.warnings.push(format!(
+                    "Unreadable field {} at byte {}, rest of the index dropped",
+                    field, offset
+                ));
+                break;
+            }
+        };
+
+        match field {
+            METADATA_FIELD => match scip_proto::Metadata::decode(payload) {
+                Ok(metadata) => {
+                    partial.index.metadata = Some(metadata);
+                    recovered = true;
+                }
+                Err(_) => partial
+                    .warnings
+                    .push("Corrupt metadata block skipped".to_string()),
+            },
+            DOCUMENTS_FIELD => {
+                documents_seen += 1;
+                match scip_proto::Document::decode(payload) {
+                    Ok(document) => {
+                        partial.index.documents.push(document);
+                        recovered = true;
+                    }
+                    Err(error) => partial.warnings.push(format!(
+                        "Corrupt document #{} skipped ({})",
+                        documents_seen, error
+                    )),
+                }
+            }
+            EXTERNAL_SYMBOLS_FIELD => match scip_proto::SymbolInformation::decode(payload) {
+                Ok(symbol) => {
+                    partial.index.external_symbols.push(symbol);
+                    recovered = true;
+                }
+                Err(_) => partial
+                    .warnings
+                    .push("Corrupt external symbol skipped".to_string()),
+            },
+            _ => {}
+        }
+    }
+
+    if recovered { Ok(partial) } else { Err(error) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    fn document(path: &str) -> scip_proto::Document {
+        scip_proto::Document {
+            relative_path: path.to_string(),
+            occurrences: vec![scip_proto::Occurrence {
+                range: vec![0, 0, 5],
+                symbol: format!("scip-typescript npm pkg 1.0.0 `{}`/main().", path),
+                symbol_roles: scip_proto::SymbolRole::Definition as i32,
+                ..Default::default()
+            }],
+            ..Default::default()
+        }
+    }
+
+    fn index(paths: &[&str]) -> scip_proto::Index {
+        scip_proto::Index {
+            metadata: Some(scip_proto::Metadata::default()),
+            documents: paths.iter().map(|path| document(path)).collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_valid_index_decodes_without_warnings() {
+        let data = index(&["src/a.ts", "src/b.ts"]).encode_to_vec();
+        let partial = decode_resilient(&data).unwrap();
+        assert_eq!(partial.index, index(&["src/a.ts", "src/b.ts"]));
+        assert!(partial.warnings.is_empty());
+    }
+
+    #[test]
+    fn test_corrupted_trailing_document_is_skipped() {
+        let mut data = index(&["src/a.ts", "src/b.ts"]).encode_to_vec();
+        // A third document whose body is an invalid tag (field 0)
+        data.extend([0x12, 0x02, 0x00, 0x00]);
+        assert!(scip_proto::Index::decode(&data[..]).is_err());
+
+        let partial = decode_resilient(&data).unwrap();
+        let paths: Vec<&str> = partial
+            .index
+            .documents
+            .iter()
+            .map(|doc| doc.relative_path.as_str())
+            .collect();
+        assert_eq!(paths, vec!["src/a.ts", "src/b.ts"]);
+        assert!(partial.index.metadata.is_some());
+        assert_eq!(partial.warnings.len(), 1, "{:?}", partial.warnings);
+        assert!(partial.warnings[0].starts_with("Corrupt document #3 skipped"));
+
+        // A document cut off by the end of the data
+        let mut truncated = index(&["src/a.ts", "src/b.ts"]).encode_to_vec();
+        truncated.truncate(truncated.len() - 3);
+        let partial = decode_resilient(&truncated).unwrap();
+        assert_eq!(partial.index.documents.len(), 1);
+        assert!(
+            partial.warnings[0].contains("truncated"),
+            "{:?}",
+            partial.warnings
+        );
+    }
+
+    #[test]
+    fn test_garbage_is_an_error() {
+        assert!(decode_resilient(&[0xff, 0xff, 0xff]).is_err());
+    }
+
+    proptest! {
+        #[test]
+        fn test_random_bytes_never_panic(data in proptest::collection::vec(any::<u8>(), 0..512)) {
+            let _ = decode_resilient(&data);
+        }
+
+        #[test]
+        fn test_damaged_index_never_panics(
+            position in any::<prop::sample::Index>(),
+            byte in any::<u8>(),
+            cut in any::<prop::sample::Index>(),
+        ) {
+            let mut data = index(&["src/a.ts", "src/b.ts", "src/c.ts"]).encode_to_vec();
+            let i = position.index(data.len());
+            data[i] = byte;
+            data.truncate(cut.index(data.len()) + 1);
+            if let Ok(partial) = decode_resilient(&data) {
+                prop_assert!(partial.index.documents.len() <= 3);
+            }
+        }
+    }
+}
@@ -24,14 +24,7 @@ impl SemanticFilter {
     /// - Exported functions
     /// - Classes
     pub fn filter_graph(graph: &mut YcgGraph) {
-        // Collect IDs of nodes to remove
-        let mut nodes_to_remove = HashSet::new();
-
-        for node in &graph.definitions {
-            if !Self::is_significant_symbol(node) {
-                nodes_to_remove.insert(node.id.clone());
-            }
-        }
+        let nodes_to_remove = Self::nodes_to_remove(graph);
 
         // Remove insignificant nodes
         graph
@@ -44,6 +37,16 @@ impl SemanticFilter {
         });
     }
 
+    /// IDs of the nodes `filter_graph` would remove, without touching the graph
+    pub fn nodes_to_remove(graph: &YcgGraph) -> HashSet<String> {
+        graph
+            .definitions
+            .iter()
+            .filter(|node| !Self::is_significant_symbol(node))
+            .map(|node| node.id.clone())
+            .collect()
+    }
+
     /// Determine if a symbol is semantically significant
     ///
     /// A symbol is significant if it's:
@@ -66,6 +69,7 @@ impl SemanticFilter {
         match node.kind {
             ScipSymbolKind::Class => true,
             ScipSymbolKind::Interface => true,
+            ScipSymbolKind::Enum => true,
             ScipSymbolKind::Function => true,
             ScipSymbolKind::Method => true,
             ScipSymbolKind::Module => true,
@@ -142,10 +146,7 @@ mod tests {
             id: id.to_string(),
             name: name.to_string(),
             kind,
-            parent_id: None,
-            documentation: None,
-            signature: None,
-            logic: None,
+            ..Default::default()
         }
     }
 
@@ -214,6 +215,7 @@ mod tests {
             metadata: ProjectMetadata {
                 name: "test".to_string(),
                 version: "1.0".to_string(),
+                ..Default::default()
             },
             definitions: vec![
                 create_test_node("User_01a2", "User", ScipSymbolKind::Class),
@@ -226,16 +228,19 @@ mod tests {
                     from: "User_01a2".to_string(),
                     to: "getUser_03c4".to_string(),
                     edge_type: EdgeType::Calls,
+                    call_text: None,
                 },
                 ReferenceEdge {
                     from: "getUser_03c4".to_string(),
                     to: "local_11_6d84".to_string(),
                     edge_type: EdgeType::Calls,
+                    call_text: None,
                 },
                 ReferenceEdge {
                     from: "local_11_6d84".to_string(),
                     to: "unknown".to_string(),
                     edge_type: EdgeType::Calls,
+                    call_text: None,
                 },
             ],
         };
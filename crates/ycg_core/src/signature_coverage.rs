@@ -0,0 +1,96 @@
+// crates/ycg_core/src/signature_coverage.rs
+//! Signature coverage per file (`--signature-coverage`).
+//!
+//! Methods and functions are expected to carry a signature; one that falls
+//! back to its bare name means the source could not be enriched. A file
+//! with low coverage usually points at a `--root` / `--path-prefix`
+//! mismatch or a construct the enricher does not support.
+
+use crate::model::{ScipSymbolKind, SymbolIndex, SymbolNode, YcgGraph};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+
+/// Methods/functions of a file, and how many of them have a signature
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Coverage {
+    pub eligible: usize,
+    pub with_signature: usize,
+}
+
+impl Coverage {
+    pub fn percent(&self) -> f64 {
+        if self.eligible == 0 {
+            100.0
+        } else {
+            self.with_signature as f64 / self.eligible as f64 * 100.0
+        }
+    }
+}
+
+impl fmt::Display for Coverage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}/{} ({:.1}%)",
+            self.with_signature,
+            self.eligible,
+            self.percent()
+        )
+    }
+}
+
+/// Signature coverage of a graph, per file with at least one method/function
+#[derive(Debug, Clone, Default)]
+pub struct SignatureCoverageReport {
+    pub files: BTreeMap<String, Coverage>,
+}
+
+impl SignatureCoverageReport {
+    /// Attributes each definition of `graph` to its file through `symbol_index`
+    pub fn build(graph: &YcgGraph, symbol_index: &SymbolIndex) -> Self {
+        let nodes: HashMap<&str, &SymbolNode> = graph
+            .definitions
+            .iter()
+            .map(|node| (node.id.as_str(), node))
+            .collect();
+
+        let mut files = BTreeMap::new();
+        for (path, anchors) in &symbol_index.files {
+            let mut coverage = Coverage::default();
+            for node in anchors
+                .iter()
+                .filter_map(|anchor| nodes.get(anchor.as_str()))
+            {
+                if matches!(node.kind, ScipSymbolKind::Method | ScipSymbolKind::Function) {
+                    coverage.eligible += 1;
+                    if node.signature.is_some() {
+                        coverage.with_signature += 1;
+                    }
+                }
+            }
+            if coverage.eligible > 0 {
+                files.insert(path.clone(), coverage);
+            }
+        }
+        Self { files }
+    }
+
+    pub fn total(&self) -> Coverage {
+        self.files
+            .values()
+            .fold(Coverage::default(), |total, file| Coverage {
+                eligible: total.eligible + file.eligible,
+                with_signature: total.with_signature + file.with_signature,
+            })
+    }
+}
+
+impl fmt::Display for SignatureCoverageReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "--- Signature coverage: {} ---", self.total())?;
+        for (path, coverage) in &self.files {
+            writeln!(f, "  {}: {}", path, coverage)?;
+        }
+        Ok(())
+    }
+}
@@ -7,8 +7,21 @@
 //! **Requirements: 2.1, 2.2, 2.3, 2.4, 2.5, 2.6, 2.7, 2.8**
 
 use crate::ast_cache::AstCache;
-use crate::model::SymbolNode;
+use crate::model::{SignatureStyle, SymbolNode};
 use crate::type_abbreviator::TypeAbbreviator;
+use regex::Regex;
+
+lazy_static::lazy_static! {
+    // Start of a Rust `where` clause after the parameter list
+    static ref WHERE_CLAUSE: Regex = Regex::new(r"\s+where\s+").unwrap();
+    // One link of a fluent chain: `).method(` / `).method::<T>(`
+    static ref CHAIN_LINK: Regex = Regex::new(r"\)\s*\.\s*\w+\s*(?:::<[^()]*>)?\s*\(").unwrap();
+    // Type argument of an axios call (`axios.get<User[]>(`)
+    static ref AXIOS_TYPE: Regex = Regex::new(r"axios\s*\.\s*\w+\s*<([^()]+)>\s*\(").unwrap();
+}
+
+/// Length above which builder and fluent chains are summarized to their result type
+pub const DEFAULT_CHAIN_SUMMARY_THRESHOLD: usize = 100;
 
 /// Signature extractor for methods and functions
 pub struct SignatureExtractor;
@@ -34,9 +47,77 @@ impl SignatureExtractor {
     ///
     /// **Validates: Requirements 2.1, 2.2, 2.3, 2.4, 2.5, 2.6, 2.7, 2.8**
     pub fn extract_signature(node: &SymbolNode) -> Option<String> {
+        Self::extract_signature_with_style(node, SignatureStyle::Named)
+    }
+
+    /// Extract compact signature from a SymbolNode using the given style
+    ///
+    /// With `SignatureStyle::TypesOnly`, parameter names are dropped and only
+    /// their (abbreviated) types are kept.
+    ///
+    /// # Examples
+    /// ```
+    /// // Input signature: "findOne(id: number, opts: FindOptions): Promise<UserDto>"
+    /// // Named:      "findOne(id:num,opts:FindOptions):Promise<UserDto>"
+    /// // TypesOnly:  "findOne(num,FindOptions):Promise<UserDto>"
+    /// ```
+    pub fn extract_signature_with_style(
+        node: &SymbolNode,
+        style: SignatureStyle,
+    ) -> Option<String> {
+        Self::extract_signature_with_options(node, style, false)
+    }
+
+    /// Extract compact signature, optionally moving optionality onto names
+    ///
+    /// With `optional_markers`, every optional parameter is rendered the same
+    /// way whatever its source syntax: TS `id?: number`, `id: number | undefined`
+    /// and Rust `id: Option<u32>` all become `id?:num` / `id?:u32`. In the
+    /// types-only style the marker stays on the type (`num?`).
+    ///
+    /// # Examples
+    /// ```
+    /// // Input signature: "find(id?: number, limit: Option<u32>): User[]"
+    /// // Default:           "find(id:num?,limit:Option<u32>):User[]"
+    /// // optional_markers:  "find(id?:num,limit?:u32):User[]"
+    /// ```
+    pub fn extract_signature_with_options(
+        node: &SymbolNode,
+        style: SignatureStyle,
+        optional_markers: bool,
+    ) -> Option<String> {
+        Self::extract_signature_with_chain_threshold(
+            node,
+            style,
+            optional_markers,
+            DEFAULT_CHAIN_SUMMARY_THRESHOLD,
+        )
+    }
+
+    /// Extract compact signature, summarizing builder and fluent chains
+    /// (QueryBuilder, fetch/axios, Rust iterators) longer than `chain_threshold`
+    /// characters to their result type
+    ///
+    /// # Examples
+    /// ```
+    /// // Input signature: "const users = await fetch(url).then((res) => res.json()).then(...)"
+    /// // Above the threshold: "users: Promise<Json>"
+    /// ```
+    pub fn extract_signature_with_chain_threshold(
+        node: &SymbolNode,
+        style: SignatureStyle,
+        optional_markers: bool,
+        chain_threshold: usize,
+    ) -> Option<String> {
         // If node has a signature from enricher, use it
         if let Some(ref sig) = node.signature {
-            return Some(Self::compact_signature(sig, &node.name));
+            return Some(Self::compact_signature_with_threshold(
+                sig,
+                &node.name,
+                style,
+                optional_markers,
+                chain_threshold,
+            ));
         }
 
         // No signature available, return None to fall back to simple name
@@ -77,14 +158,96 @@ impl SignatureExtractor {
         None
     }
 
+    /// Remove parameter default values from a source signature
+    ///
+    /// Signatures taken from source keep defaults (`limit: number = 100`),
+    /// which often carry meaning (page sizes, feature flags). This drops them
+    /// for token savings (`--no-defaults`).
+    ///
+    /// Only an `=` directly inside a parameter list starts a default; arrow
+    /// types (`=>`), comparisons and `=` inside strings, objects or generics are kept.
+    ///
+    /// # Examples
+    /// ```
+    /// // "findAll(page = 1, limit: number = 100): User[]"
+    /// // -> "findAll(page, limit: number): User[]"
+    /// ```
+    pub fn strip_default_values(sig: &str) -> String {
+        let mut out = String::with_capacity(sig.len());
+        let mut stack: Vec<char> = Vec::new();
+        let mut quote: Option<char> = None;
+        // Bracket depth of the parameter list whose default is being skipped
+        let mut skipping: Option<usize> = None;
+        let mut prev = ' ';
+        let mut chars = sig.chars().peekable();
+
+        while let Some(ch) = chars.next() {
+            let next = chars.peek().copied();
+            if let Some(q) = quote {
+                if ch == q {
+                    quote = None;
+                }
+                if skipping.is_none() {
+                    out.push(ch);
+                }
+                if ch == '\\'
+                    && let Some(escaped) = chars.next()
+                    && skipping.is_none()
+                {
+                    out.push(escaped);
+                }
+                prev = ch;
+                continue;
+            }
+
+            match ch {
+                '\'' | '"' | '`' => quote = Some(ch),
+                '=' if next == Some('>') => {
+                    chars.next();
+                    if skipping.is_none() {
+                        out.push_str("=>");
+                    }
+                    prev = '>';
+                    continue;
+                }
+                '=' if skipping.is_none()
+                    && stack.last() == Some(&'(')
+                    && !matches!(prev, '=' | '!' | '<' | '>')
+                    && next != Some('=') =>
+                {
+                    out.truncate(out.trim_end().len());
+                    skipping = Some(stack.len());
+                    prev = ch;
+                    continue;
+                }
+                '(' | '[' | '{' | '<' => stack.push(ch),
+                ')' | ']' | '}' | '>' => {
+                    if ch == ')' && skipping == Some(stack.len()) {
+                        skipping = None;
+                    }
+                    stack.pop();
+                }
+                ',' if skipping == Some(stack.len()) => skipping = None,
+                _ => {}
+            }
+
+            if skipping.is_none() {
+                out.push(ch);
+            }
+            prev = ch;
+        }
+
+        out
+    }
+
     /// Check if a signature matches QueryBuilder pattern
     ///
     /// Detects QueryBuilder patterns by checking for:
     /// - 2+ QueryBuilder keywords (createQueryBuilder, select, where, getMany, getOne, leftJoin)
-    /// - Signature length > 100 characters
+    /// - Signature length > `threshold` characters (100 by default)
     ///
     /// **Validates: Requirements 5.1, 5.2**
-    fn is_query_builder_pattern(sig: &str) -> bool {
+    fn is_query_builder_pattern(sig: &str, threshold: usize) -> bool {
         let qb_keywords = [
             "createQueryBuilder",
             "select",
@@ -96,7 +259,122 @@ impl SignatureExtractor {
 
         let keyword_count = qb_keywords.iter().filter(|kw| sig.contains(*kw)).count();
 
-        keyword_count >= 2 && sig.len() > 100
+        keyword_count >= 2 && sig.len() > threshold
+    }
+
+    /// Summarize a fetch/axios or Rust iterator chain longer than `threshold`
+    ///
+    /// A chain is at least one link (`).then(`, `).map(`) after the first
+    /// call. It is only summarized when its final step decides the result
+    /// type:
+    /// - fetch: `.then(res => res.json())` -> `Promise<Json>`, `.text()` ->
+    ///   `Promise<string>`, `.blob()` -> `Promise<Blob>`
+    /// - axios: `axios.get<User[]>(...).then(res => res.data)` -> `Promise<User[]>`
+    /// - iterators: `.collect::<T>()` -> `T`, `.count()` -> `usize`,
+    ///   `.any()`/`.all()` -> `bool`, `.find()`/`.max()`/`.min()`/`.next()` -> `Option`
+    ///
+    /// An awaited chain yields the type inside the `Promise`. Chains ending
+    /// in an opaque callback (`.then(parse)`, `.catch(handle)`, `.map(f)`)
+    /// return `None`, like other signatures, and are compacted as usual.
+    fn summarize_fluent_chain(sig: &str, var_name: &str, threshold: usize) -> Option<String> {
+        if sig.len() <= threshold || !CHAIN_LINK.is_match(sig) {
+            return None;
+        }
+        let expr = sig.split_once(" = ").map_or(sig, |(_, init)| init).trim();
+        let (expr, awaited) = match expr.strip_prefix("await ") {
+            Some(inner) => (inner.trim_start(), true),
+            None => (expr, false),
+        };
+        let (method, turbofish, args) = Self::final_call(expr)?;
+
+        let result_type = if expr.contains("fetch(") {
+            let body = match Self::callback_projection(args) {
+                Some("json()") if method == "then" => "Json",
+                Some("text()") if method == "then" => "string",
+                Some("blob()") if method == "then" => "Blob",
+                _ => return None,
+            };
+            format!("Promise<{}>", body)
+        } else if expr.contains("axios") {
+            // `data` is only the typed body right on the axios response
+            if method != "then"
+                || Self::callback_projection(args) != Some("data")
+                || CHAIN_LINK.find_iter(expr).count() != 1
+            {
+                return None;
+            }
+            let data = AXIOS_TYPE.captures(expr)?.get(1)?.as_str().trim();
+            format!("Promise<{}>", data)
+        } else if expr.contains(".iter()") || expr.contains(".into_iter()") {
+            match method {
+                "collect" if !turbofish.is_empty() => turbofish.to_string(),
+                "count" => "usize".to_string(),
+                "any" | "all" => "bool".to_string(),
+                "find" | "max" | "min" | "next" => "Option".to_string(),
+                _ => return None,
+            }
+        } else {
+            return None;
+        };
+
+        let result_type = match result_type.strip_prefix("Promise<") {
+            Some(inner) if awaited => inner.strip_suffix('>').unwrap_or(inner).to_string(),
+            _ => result_type,
+        };
+        Some(format!("{}: {}", var_name, result_type))
+    }
+
+    /// Trailing call of a chain, as `(method, turbofish, arguments)`:
+    /// `.collect::<Vec<String>>()` -> `("collect", "Vec<String>", "")`
+    ///
+    /// `None` when the expression does not end in a method call.
+    fn final_call(expr: &str) -> Option<(&str, &str, &str)> {
+        let expr = expr.trim_end();
+        let body = expr.strip_suffix(';').unwrap_or(expr).trim_end();
+        let body = body.strip_suffix(')')?;
+
+        let mut depth = 0;
+        let (open, _) = body.char_indices().rev().find(|&(_, c)| {
+            match c {
+                ')' => depth += 1,
+                '(' if depth == 0 => return true,
+                '(' => depth -= 1,
+                _ => {}
+            }
+            false
+        })?;
+        let args = body[open + 1..].trim();
+
+        let callee = body[..open].trim_end();
+        let (callee, turbofish) = match callee.rfind("::<") {
+            Some(start) if callee.ends_with('>') => {
+                (&callee[..start], &callee[start + 3..callee.len() - 1])
+            }
+            _ => (callee, ""),
+        };
+        let name_start = callee
+            .char_indices()
+            .rev()
+            .find(|(_, c)| !(c.is_alphanumeric() || *c == '_'))
+            .map_or(0, |(i, c)| i + c.len_utf8());
+        let (receiver, method) = callee.split_at(name_start);
+        (!method.is_empty() && receiver.trim_end().ends_with('.'))
+            .then_some((method, turbofish, args))
+    }
+
+    /// What an arrow callback takes from its parameter: `(res) => res.json()` -> `json()`
+    ///
+    /// `None` for named functions and for bodies doing anything else.
+    fn callback_projection(args: &str) -> Option<&str> {
+        let (param, body) = args.split_once("=>")?;
+        let param = param.trim().trim_start_matches("async").trim();
+        let param = param.trim_start_matches('(').trim_end_matches(')');
+        // `(res: Response)`
+        let param = param.split(':').next()?.trim();
+        if param.is_empty() {
+            return None;
+        }
+        body.trim().strip_prefix(param)?.strip_prefix('.')
     }
 
     /// Extract entity type from QueryBuilder signature
@@ -185,10 +463,47 @@ impl SignatureExtractor {
     ///
     /// **Validates: Requirements 2.1, 2.2, 2.3, 2.4, 2.5, 2.6, 2.7, 2.8, 5.1, 5.2, 5.3, 5.4, 5.6**
     fn compact_signature(sig: &str, method_name: &str) -> String {
+        Self::compact_signature_with_style(sig, method_name, SignatureStyle::Named)
+    }
+
+    /// Compact a signature, rendering parameters according to `style`
+    fn compact_signature_with_style(sig: &str, method_name: &str, style: SignatureStyle) -> String {
+        Self::compact_signature_with_options(sig, method_name, style, false)
+    }
+
+    /// Compact a signature in `style`, with optional parameters marked on
+    /// their names when `optional_markers` is set
+    fn compact_signature_with_options(
+        sig: &str,
+        method_name: &str,
+        style: SignatureStyle,
+        optional_markers: bool,
+    ) -> String {
+        Self::compact_signature_with_threshold(
+            sig,
+            method_name,
+            style,
+            optional_markers,
+            DEFAULT_CHAIN_SUMMARY_THRESHOLD,
+        )
+    }
+
+    /// [`Self::compact_signature_with_options`], chains longer than
+    /// `chain_threshold` summarized
+    fn compact_signature_with_threshold(
+        sig: &str,
+        method_name: &str,
+        style: SignatureStyle,
+        optional_markers: bool,
+        chain_threshold: usize,
+    ) -> String {
         // Check if this is a QueryBuilder pattern and summarize if so
-        if Self::is_query_builder_pattern(sig) {
+        if Self::is_query_builder_pattern(sig, chain_threshold) {
             return Self::summarize_query_builder(sig, method_name);
         }
+        if let Some(summary) = Self::summarize_fluent_chain(sig, method_name, chain_threshold) {
+            return summary;
+        }
         // First, remove decorators (framework noise)
         use crate::framework_filter::FrameworkNoiseFilter;
         let sig_without_decorators = FrameworkNoiseFilter::strip_decorators(sig);
@@ -204,7 +519,7 @@ impl SignatureExtractor {
 
         // Try to parse the signature
         if let Some((name, params, return_type)) = Self::parse_signature(&cleaned, method_name) {
-            Self::format_compact_signature(&name, &params, &return_type)
+            Self::format_compact_signature(&name, &params, &return_type, style, optional_markers)
         } else {
             // Fallback: return the method name
             method_name.to_string()
@@ -213,12 +528,17 @@ impl SignatureExtractor {
 
     /// Parse a signature string into components
     ///
+    /// Bounded generics of the name are compacted (`find<T extends Entity>`
+    /// -> `find<T:Entity>`), with the predicates of a Rust `where` clause
+    /// folded into them.
+    ///
     /// Returns: (method_name, [(param_name, param_type)], return_type)
     fn parse_signature(
         sig: &str,
         fallback_name: &str,
     ) -> Option<(String, Vec<(String, String)>, String)> {
         // Pattern: name(params): return_type
+        // or: name(params) -> return_type where bounds
         // or: name(params)
 
         // Find the method name (before opening paren)
@@ -233,15 +553,84 @@ impl SignatureExtractor {
         let params_str = &sig[paren_start + 1..paren_end];
         let params = Self::parse_parameters(params_str);
 
-        // Extract return type (after colon)
-        let return_type = if let Some(colon_pos) = sig[paren_end..].find(':') {
-            let return_start = paren_end + colon_pos + 1;
-            sig[return_start..].trim().to_string()
+        // Rust `where` clause, after the return type
+        let rest = &sig[paren_end + 1..];
+        let (rest, where_clause) = match WHERE_CLAUSE.find(rest) {
+            Some(m) => (&rest[..m.start()], Some(&rest[m.end()..])),
+            None => (rest, None),
+        };
+
+        // Extract return type (after `->`, or after colon)
+        let return_type = if let Some(arrow) = rest.trim_start().strip_prefix("->") {
+            arrow.trim().to_string()
+        } else if let Some(colon_pos) = rest.find(':') {
+            rest[colon_pos + 1..].trim().to_string()
         } else {
             String::new()
         };
 
-        Some((name.to_string(), params, return_type))
+        let name = match name.find('<') {
+            Some(generic_start) if name.ends_with('>') => {
+                let bounds = where_clause.map_or_else(Vec::new, Self::split_parameters);
+                format!(
+                    "{}<{}>",
+                    &name[..generic_start],
+                    Self::compact_generics(&name[generic_start + 1..name.len() - 1], &bounds)
+                )
+            }
+            _ => name.to_string(),
+        };
+
+        Some((name, params, return_type))
+    }
+
+    /// Compacts a generic parameter list, keeping the bounds
+    ///
+    /// - `T extends Entity` -> `T:Entity` (TypeScript)
+    /// - `T: Clone + Debug` -> `T:Clone+Debug` (Rust)
+    /// - `K extends keyof T = keyof T` -> `K:keyof T` (defaults dropped)
+    ///
+    /// `where_predicates` (`T: Clone`) add to the bound of their parameter.
+    fn compact_generics(generics: &str, where_predicates: &[String]) -> String {
+        let split_bound = |param: &str| -> (String, Option<String>) {
+            let param = param.split('=').next().unwrap_or(param).trim();
+            let (name, bound) = match param.split_once(" extends ") {
+                Some((name, bound)) => (name, Some(bound)),
+                None => match param.split_once(':') {
+                    Some((name, bound)) => (name, Some(bound)),
+                    None => (param, None),
+                },
+            };
+            let bound = bound
+                .map(|bound| bound.split_whitespace().collect::<Vec<_>>().join(" "))
+                .map(|bound| bound.replace(" + ", "+").replace(" & ", "&"))
+                .filter(|bound| !bound.is_empty());
+            (name.trim().to_string(), bound)
+        };
+        let predicates: Vec<(String, Option<String>)> =
+            where_predicates.iter().map(|p| split_bound(p)).collect();
+
+        Self::split_parameters(generics)
+            .iter()
+            .map(|param| {
+                let (name, bound) = split_bound(param);
+                let bounds: Vec<String> = bound
+                    .into_iter()
+                    .chain(
+                        predicates
+                            .iter()
+                            .filter(|(predicate, _)| *predicate == name)
+                            .filter_map(|(_, bound)| bound.clone()),
+                    )
+                    .collect();
+                if bounds.is_empty() {
+                    name
+                } else {
+                    format!("{}:{}", name, bounds.join("+"))
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(",")
     }
 
     /// Find matching closing parenthesis
@@ -380,21 +769,36 @@ impl SignatureExtractor {
     /// Format a compact signature with abbreviated types
     ///
     /// Format: methodName(param1:type1,param2:type2):ReturnType
+    /// or, with `SignatureStyle::TypesOnly`: methodName(type1,type2):ReturnType
+    ///
+    /// With `optional_markers`, `Option<T>` parameters count as optional and
+    /// named optional parameters render as `param?:type`.
     ///
     /// **Validates: Requirements 2.2, 2.3, 2.4, 2.5**
     fn format_compact_signature(
         name: &str,
         params: &[(String, String)],
         return_type: &str,
+        style: SignatureStyle,
+        optional_markers: bool,
     ) -> String {
         // Abbreviate parameter types
         let compact_params = params
             .iter()
             .map(|(param_name, param_type)| {
                 // Handle union types with null/undefined (convert to optional)
-                let normalized_type = Self::normalize_optional_type(param_type);
+                let mut normalized_type = Self::normalize_optional_type(param_type);
+                if optional_markers && let Some(inner) = Self::option_inner_type(&normalized_type) {
+                    normalized_type = format!("{}?", inner);
+                }
                 let abbrev_type = TypeAbbreviator::abbreviate(&normalized_type);
-                format!("{}:{}", param_name, abbrev_type)
+                match style {
+                    SignatureStyle::Named => match abbrev_type.strip_suffix('?') {
+                        Some(inner) if optional_markers => format!("{}?:{}", param_name, inner),
+                        _ => format!("{}:{}", param_name, abbrev_type),
+                    },
+                    SignatureStyle::TypesOnly => abbrev_type,
+                }
             })
             .collect::<Vec<_>>()
             .join(",");
@@ -425,6 +829,17 @@ impl SignatureExtractor {
     fn normalize_optional_type(type_str: &str) -> String {
         let trimmed = type_str.trim();
 
+        // Already optional (`name?: User | null` gives "User | null?"): normalize
+        // the rest and keep the marker, which the union filter would drop
+        if let Some(inner) = trimmed.strip_suffix('?') {
+            let normalized = Self::normalize_optional_type(inner);
+            return if normalized.ends_with('?') {
+                normalized
+            } else {
+                format!("{}?", normalized)
+            };
+        }
+
         // Check if it's a generic type with union inside (e.g., Promise<User | null>)
         if let Some(generic_start) = trimmed.find('<') {
             // Find the matching closing bracket
@@ -475,6 +890,21 @@ impl SignatureExtractor {
         trimmed.to_string()
     }
 
+    /// `T` when the whole type is a Rust `Option<T>` (or `std::option::Option<T>`)
+    fn option_inner_type(type_str: &str) -> Option<&str> {
+        let trimmed = type_str.trim();
+        let generic_start = trimmed.find('<')?;
+        let base = &trimmed[..generic_start];
+        if base != "Option" && base != "std::option::Option" && base != "core::option::Option" {
+            return None;
+        }
+        let generic_end = Self::find_matching_angle_bracket(trimmed, generic_start)?;
+        if generic_end + 1 != trimmed.len() {
+            return None;
+        }
+        Some(trimmed[generic_start + 1..generic_end].trim())
+    }
+
     /// Split union types by | respecting nested brackets
     ///
     /// Handles:
@@ -524,10 +954,8 @@ mod tests {
             id: "test_id".to_string(),
             name: name.to_string(),
             kind: ScipSymbolKind::Method,
-            parent_id: None,
-            documentation: None,
             signature,
-            logic: None,
+            ..Default::default()
         }
     }
 
@@ -566,6 +994,23 @@ mod tests {
         assert_eq!(result, "findOne(id:str):User");
     }
 
+    #[test]
+    fn test_compact_signature_bounded_generics() {
+        let ts =
+            "async save<T extends Entity, K extends keyof T = keyof T>(e: T, key: K): Promise<T>";
+        assert_eq!(
+            SignatureExtractor::compact_signature(ts, "save"),
+            "save<T:Entity,K:keyof T>(e:T,key:K):Promise<T>"
+        );
+
+        // `where` predicates join the bounds; the return type follows `->`
+        let rust = "fn merge<T: Clone + Debug, U>(a: T, b: U) -> Vec<T> where U: Into<T>";
+        assert_eq!(
+            SignatureExtractor::compact_signature(rust, "merge"),
+            "fn merge<T:Clone+Debug,U:Into<T>>(a:T,b:U):Vec<T>"
+        );
+    }
+
     #[test]
     fn test_compact_signature_multiple_params() {
         // Requirement 2.2: Handle multiple parameters
@@ -640,6 +1085,35 @@ mod tests {
         assert_eq!(result, "create(name:str,limit:num):User");
     }
 
+    #[test]
+    fn test_strip_default_values() {
+        let cases = [
+            (
+                "async findAll(page = 1, limit: number = 100): Promise<User[]>",
+                "async findAll(page, limit: number): Promise<User[]>",
+            ),
+            (
+                "configure(opts: Options = { retries: 3, backoff: [1, 2] }, verbose = false)",
+                "configure(opts: Options, verbose)",
+            ),
+            (
+                "search(query: string, sep = \",\", max = Math.max(1, 2))",
+                "search(query: string, sep, max)",
+            ),
+            (
+                "map(fn: (x: number) => string = String, cache: Map<string, number> = new Map())",
+                "map(fn: (x: number) => string, cache: Map<string, number>)",
+            ),
+            (
+                "@Get(':id') findOne(@Param('id') id: string)",
+                "@Get(':id') findOne(@Param('id') id: string)",
+            ),
+        ];
+        for (sig, expected) in cases {
+            assert_eq!(SignatureExtractor::strip_default_values(sig), expected);
+        }
+    }
+
     #[test]
     fn test_normalize_optional_type_with_null() {
         let result = SignatureExtractor::normalize_optional_type("User | null");
@@ -740,8 +1214,13 @@ mod tests {
             ("user".to_string(), "string".to_string()),
             ("id".to_string(), "number".to_string()),
         ];
-        let result =
-            SignatureExtractor::format_compact_signature("findOne", &params, "Promise<User>");
+        let result = SignatureExtractor::format_compact_signature(
+            "findOne",
+            &params,
+            "Promise<User>",
+            SignatureStyle::Named,
+            false,
+        );
 
         assert_eq!(result, "findOne(user:str,id:num):Promise<User>");
     }
@@ -749,7 +1228,13 @@ mod tests {
     #[test]
     fn test_format_compact_signature_no_params() {
         let params = vec![];
-        let result = SignatureExtractor::format_compact_signature("getAll", &params, "User[]");
+        let result = SignatureExtractor::format_compact_signature(
+            "getAll",
+            &params,
+            "User[]",
+            SignatureStyle::Named,
+            false,
+        );
 
         assert_eq!(result, "getAll():User[]");
     }
@@ -757,11 +1242,57 @@ mod tests {
     #[test]
     fn test_format_compact_signature_void_return() {
         let params = vec![("id".to_string(), "string".to_string())];
-        let result = SignatureExtractor::format_compact_signature("delete", &params, "void");
+        let result = SignatureExtractor::format_compact_signature(
+            "delete",
+            &params,
+            "void",
+            SignatureStyle::Named,
+            false,
+        );
 
         assert_eq!(result, "delete(id:str)");
     }
 
+    #[test]
+    fn test_types_only_style_drops_parameter_names() {
+        let sig =
+            "async findOne(id: number, options?: FindOptions, tags: string[]): Promise<UserDto>";
+
+        let named =
+            SignatureExtractor::compact_signature_with_style(sig, "findOne", SignatureStyle::Named);
+        let types_only = SignatureExtractor::compact_signature_with_style(
+            sig,
+            "findOne",
+            SignatureStyle::TypesOnly,
+        );
+
+        assert_eq!(
+            named,
+            "findOne(id:num,options:FindOptions?,tags:str[]):Promise<UserDto>"
+        );
+        assert_eq!(
+            types_only,
+            "findOne(num,FindOptions?,str[]):Promise<UserDto>"
+        );
+    }
+
+    #[test]
+    fn test_extract_signature_with_style_types_only() {
+        let node = create_test_node(
+            "create",
+            Some("create(name: string, age: number): User".to_string()),
+        );
+
+        assert_eq!(
+            SignatureExtractor::extract_signature_with_style(&node, SignatureStyle::TypesOnly),
+            Some("create(str,num):User".to_string())
+        );
+        assert_eq!(
+            SignatureExtractor::extract_signature_with_style(&node, SignatureStyle::Named),
+            SignatureExtractor::extract_signature(&node)
+        );
+    }
+
     #[test]
     fn test_constructor_with_dependency_injection() {
         // Requirement 2.6: Constructors with dependency injection
@@ -870,21 +1401,30 @@ mod tests {
     fn test_is_query_builder_pattern_positive() {
         // Requirement 5.1, 5.2: Detect QueryBuilder pattern with 2+ keywords and length > 100
         let sig = "const users = await this.userRepository.createQueryBuilder('user').select('user.id').where('user.active = :active', { active: true }).getMany()";
-        assert!(SignatureExtractor::is_query_builder_pattern(sig));
+        assert!(SignatureExtractor::is_query_builder_pattern(
+            sig,
+            DEFAULT_CHAIN_SUMMARY_THRESHOLD
+        ));
     }
 
     #[test]
     fn test_is_query_builder_pattern_negative_too_short() {
         // Should not match if length <= 100
         let sig = "createQueryBuilder('user').getMany()";
-        assert!(!SignatureExtractor::is_query_builder_pattern(sig));
+        assert!(!SignatureExtractor::is_query_builder_pattern(
+            sig,
+            DEFAULT_CHAIN_SUMMARY_THRESHOLD
+        ));
     }
 
     #[test]
     fn test_is_query_builder_pattern_negative_one_keyword() {
         // Should not match with only 1 keyword
         let sig = "const result = this.repository.createQueryBuilder('entity').andThisIsAVeryLongStringToMakeItLongerThan100Characters()";
-        assert!(!SignatureExtractor::is_query_builder_pattern(sig));
+        assert!(!SignatureExtractor::is_query_builder_pattern(
+            sig,
+            DEFAULT_CHAIN_SUMMARY_THRESHOLD
+        ));
     }
 
     #[test]
@@ -976,7 +1516,10 @@ mod tests {
         // Test detection with leftJoin keyword
         let sig = "const users = await this.userRepository.createQueryBuilder('User', 'u').leftJoin('u.profile', 'profile').select('u.id, profile.bio').where('u.active = true').getMany()";
 
-        assert!(SignatureExtractor::is_query_builder_pattern(sig));
+        assert!(SignatureExtractor::is_query_builder_pattern(
+            sig,
+            DEFAULT_CHAIN_SUMMARY_THRESHOLD
+        ));
 
         let result = SignatureExtractor::compact_signature(sig, "users");
         assert_eq!(result, "users: User[]");
@@ -987,7 +1530,10 @@ mod tests {
         // Long signature that is NOT a QueryBuilder should not be summarized
         let sig = "async function processUserData(userId: string, options: ProcessOptions, callback: (result: ProcessResult) => void): Promise<UserData>";
 
-        assert!(!SignatureExtractor::is_query_builder_pattern(sig));
+        assert!(!SignatureExtractor::is_query_builder_pattern(
+            sig,
+            DEFAULT_CHAIN_SUMMARY_THRESHOLD
+        ));
 
         let result = SignatureExtractor::compact_signature(sig, "processUserData");
 
@@ -995,4 +1541,61 @@ mod tests {
         assert!(result.contains("processUserData"));
         assert!(result.contains("userId:str"));
     }
+
+    #[test]
+    fn test_optional_parameter_survives_abbreviation() {
+        // `?` on the name must not be lost when the type is a null union
+        let sig = "findOne(id?: number, owner?: User | null, cb: string | undefined): User";
+        assert_eq!(
+            SignatureExtractor::compact_signature(sig, "findOne"),
+            "findOne(id:num?,owner:User?,cb:str?):User"
+        );
+        assert_eq!(
+            SignatureExtractor::compact_signature_with_options(
+                sig,
+                "findOne",
+                SignatureStyle::Named,
+                true
+            ),
+            "findOne(id?:num,owner?:User,cb?:str):User"
+        );
+        assert_eq!(
+            SignatureExtractor::compact_signature_with_options(
+                sig,
+                "findOne",
+                SignatureStyle::TypesOnly,
+                true
+            ),
+            "findOne(num?,User?,str?):User"
+        );
+    }
+
+    #[test]
+    fn test_rust_option_params_marked_optional() {
+        let sig = "load(path: &Path, limit: Option<u32>, tags: Option<Vec<String>>): Config";
+        let marked = SignatureExtractor::compact_signature_with_options(
+            sig,
+            "load",
+            SignatureStyle::Named,
+            true,
+        );
+        assert_eq!(
+            marked,
+            "load(path:&Path,limit?:u32,tags?:Vec<String>):Config"
+        );
+        // Without the option, Option<T> is left as written
+        assert_eq!(
+            SignatureExtractor::compact_signature(sig, "load"),
+            "load(path:&Path,limit:Option<u32>,tags:Option<Vec<String>>):Config"
+        );
+        assert_eq!(
+            SignatureExtractor::option_inner_type("Option<u32>"),
+            Some("u32")
+        );
+        assert_eq!(SignatureExtractor::option_inner_type("Option<u32>[]"), None);
+        assert_eq!(
+            SignatureExtractor::option_inner_type("OptionSet<u32>"),
+            None
+        );
+    }
 }
@@ -0,0 +1,308 @@
+// crates/ycg_core/src/single_impl.rs
+
+use crate::graph_analysis::member_name;
+use crate::model::{EdgeType, ScipSymbolKind, YcgGraph};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+
+/// Merges interfaces into their only implementation (`--merge-single-impl`)
+///
+/// In DI-heavy code an interface and its single implementing class are one
+/// unit described twice. An interface implemented by exactly one class (by
+/// `implements` edges) is dropped and its name recorded in the class's
+/// `merged_interfaces`; edges to it, and to its members, are redirected to
+/// the class and the class member of the same name, as are `delegates_to`
+/// markers and the parents of nodes nested in a dropped member. Interface
+/// members the class does not define move under the class. Interfaces with
+/// several (or no) implementations are left untouched.
+pub struct SingleImplMerger;
+
+impl SingleImplMerger {
+    /// # Returns
+    /// Number of interfaces merged
+    pub fn merge_graph(graph: &mut YcgGraph) -> usize {
+        let kinds: HashMap<&str, &ScipSymbolKind> = graph
+            .definitions
+            .iter()
+            .map(|node| (node.id.as_str(), &node.kind))
+            .collect();
+
+        let mut implementors: BTreeMap<&str, BTreeSet<&str>> = BTreeMap::new();
+        for edge in graph
+            .references
+            .iter()
+            .filter(|edge| edge.edge_type == EdgeType::Implements)
+        {
+            if matches!(kinds.get(edge.to.as_str()), Some(ScipSymbolKind::Interface))
+                && matches!(kinds.get(edge.from.as_str()), Some(ScipSymbolKind::Class))
+            {
+                implementors
+                    .entry(edge.to.as_str())
+                    .or_default()
+                    .insert(edge.from.as_str());
+            }
+        }
+
+        // Merged interface -> its class
+        let merges: HashMap<String, String> = implementors
+            .into_iter()
+            .filter(|(_, classes)| classes.len() == 1)
+            .map(|(interface, classes)| {
+                let class = classes.into_iter().next().unwrap_or_default();
+                (interface.to_string(), class.to_string())
+            })
+            .collect();
+        if merges.is_empty() {
+            return 0;
+        }
+
+        let class_members: HashMap<(&str, &str), &str> = graph
+            .definitions
+            .iter()
+            .filter_map(|node| {
+                let parent = node.parent_id.as_deref()?;
+                Some(((parent, member_name(&node.name)), node.id.as_str()))
+            })
+            .collect();
+
+        // Dropped node -> node its edges now point to; members without a
+        // counterpart in the class are reparented instead
+        let mut redirect = merges.clone();
+        let mut reparent: HashMap<String, String> = HashMap::new();
+        for node in &graph.definitions {
+            let Some(class) = node.parent_id.as_ref().and_then(|p| merges.get(p)) else {
+                continue;
+            };
+            match class_members.get(&(class.as_str(), member_name(&node.name))) {
+                Some(member) => {
+                    redirect.insert(node.id.clone(), member.to_string());
+                }
+                None => {
+                    reparent.insert(node.id.clone(), class.clone());
+                }
+            }
+        }
+
+        let mut merged_names: HashMap<String, Vec<String>> = HashMap::new();
+        for node in &graph.definitions {
+            if let Some(class) = merges.get(&node.id) {
+                merged_names
+                    .entry(class.clone())
+                    .or_default()
+                    .push(node.name.clone());
+            }
+        }
+
+        graph
+            .definitions
+            .retain(|node| !redirect.contains_key(&node.id));
+        for node in &mut graph.definitions {
+            if let Some(class) = reparent.get(&node.id) {
+                node.parent_id = Some(class.clone());
+            } else if let Some(parent) = node.parent_id.as_mut()
+                && let Some(target) = redirect.get(parent.as_str())
+            {
+                *parent = target.clone();
+            }
+            if let Some(target) = node
+                .logic
+                .as_mut()
+                .and_then(|logic| logic.delegates_to.as_mut())
+                && let Some(redirected) = redirect.get(target.as_str())
+            {
+                *target = redirected.clone();
+            }
+            if let Some(names) = merged_names.remove(&node.id) {
+                node.merged_interfaces.extend(names);
+                node.merged_interfaces.sort();
+            }
+        }
+
+        // The `implements` edge becomes a self-loop and goes; redirected
+        // edges may duplicate existing ones
+        let mut seen = HashSet::new();
+        let references = std::mem::take(&mut graph.references);
+        for mut edge in references {
+            if let Some(to) = redirect.get(&edge.to) {
+                edge.to = to.clone();
+            }
+            if let Some(from) = redirect.get(&edge.from) {
+                edge.from = from.clone();
+            }
+            if edge.from != edge.to && seen.insert(edge.clone()) {
+                graph.references.push(edge);
+            }
+        }
+
+        merges.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{ProjectMetadata, ReferenceEdge, SymbolNode};
+
+    fn node(id: &str, name: &str, kind: ScipSymbolKind, parent: Option<&str>) -> SymbolNode {
+        SymbolNode {
+            id: id.to_string(),
+            name: name.to_string(),
+            kind,
+            parent_id: parent.map(|p| p.to_string()),
+            ..Default::default()
+        }
+    }
+
+    fn edge(from: &str, to: &str, edge_type: EdgeType) -> ReferenceEdge {
+        ReferenceEdge {
+            from: from.to_string(),
+            to: to.to_string(),
+            edge_type,
+            call_text: None,
+        }
+    }
+
+    #[test]
+    fn test_interface_with_single_impl_is_merged() {
+        let mut graph = YcgGraph {
+            metadata: ProjectMetadata::default(),
+            definitions: vec![
+                node("Repo_a1", "Repo", ScipSymbolKind::Interface, None),
+                node(
+                    "Repo_save_a2",
+                    "Repo#save",
+                    ScipSymbolKind::Method,
+                    Some("Repo_a1"),
+                ),
+                node(
+                    "Repo_count_a3",
+                    "Repo#count",
+                    ScipSymbolKind::Method,
+                    Some("Repo_a1"),
+                ),
+                node("UserRepo_b1", "UserRepo", ScipSymbolKind::Class, None),
+                node(
+                    "UserRepo_save_b2",
+                    "UserRepo#save",
+                    ScipSymbolKind::Method,
+                    Some("UserRepo_b1"),
+                ),
+                node("Service_c1", "Service", ScipSymbolKind::Class, None),
+            ],
+            references: vec![
+                edge("UserRepo_b1", "Repo_a1", EdgeType::Implements),
+                edge("Service_c1", "Repo_a1", EdgeType::References),
+                edge("Service_c1", "Repo_save_a2", EdgeType::Calls),
+                edge("Service_c1", "UserRepo_save_b2", EdgeType::Calls),
+            ],
+        };
+
+        assert_eq!(SingleImplMerger::merge_graph(&mut graph), 1);
+
+        let ids: Vec<&str> = graph.definitions.iter().map(|n| n.id.as_str()).collect();
+        assert_eq!(
+            ids,
+            vec![
+                "Repo_count_a3",
+                "UserRepo_b1",
+                "UserRepo_save_b2",
+                "Service_c1"
+            ]
+        );
+        assert_eq!(
+            graph.definitions[0].parent_id.as_deref(),
+            Some("UserRepo_b1")
+        );
+        assert_eq!(graph.definitions[1].merged_interfaces, vec!["Repo"]);
+        assert_eq!(
+            graph.references,
+            vec![
+                edge("Service_c1", "UserRepo_b1", EdgeType::References),
+                edge("Service_c1", "UserRepo_save_b2", EdgeType::Calls),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_interface_with_several_impls_is_untouched() {
+        let definitions = vec![
+            node("Notifier_a1", "Notifier", ScipSymbolKind::Interface, None),
+            node("Email_b1", "EmailNotifier", ScipSymbolKind::Class, None),
+            node("Sms_c1", "SmsNotifier", ScipSymbolKind::Class, None),
+        ];
+        let references = vec![
+            edge("Email_b1", "Notifier_a1", EdgeType::Implements),
+            edge("Sms_c1", "Notifier_a1", EdgeType::Implements),
+        ];
+        let mut graph = YcgGraph {
+            metadata: ProjectMetadata::default(),
+            definitions: definitions.clone(),
+            references: references.clone(),
+        };
+
+        assert_eq!(SingleImplMerger::merge_graph(&mut graph), 0);
+        assert_eq!(graph.definitions.len(), definitions.len());
+        assert!(
+            graph
+                .definitions
+                .iter()
+                .all(|n| n.merged_interfaces.is_empty())
+        );
+        assert_eq!(graph.references, references);
+    }
+
+    #[test]
+    fn test_delegate_to_interface_member_follows_the_merge() {
+        let mut graph = YcgGraph {
+            metadata: ProjectMetadata::default(),
+            definitions: vec![
+                node("Repo_a1", "Repo", ScipSymbolKind::Interface, None),
+                node(
+                    "Repo_find_a2",
+                    "Repo#find",
+                    ScipSymbolKind::Method,
+                    Some("Repo_a1"),
+                ),
+                node(
+                    "id_a3",
+                    "Repo#find().(id)",
+                    ScipSymbolKind::Variable,
+                    Some("Repo_find_a2"),
+                ),
+                node("UserRepo_b1", "UserRepo", ScipSymbolKind::Class, None),
+                node(
+                    "UserRepo_find_b2",
+                    "UserRepo#find",
+                    ScipSymbolKind::Method,
+                    Some("UserRepo_b1"),
+                ),
+                node("Ctrl_c1", "Ctrl", ScipSymbolKind::Class, None),
+                node(
+                    "Ctrl_find_c2",
+                    "Ctrl#find",
+                    ScipSymbolKind::Method,
+                    Some("Ctrl_c1"),
+                ),
+            ],
+            references: vec![
+                edge("UserRepo_b1", "Repo_a1", EdgeType::Implements),
+                edge("Ctrl_find_c2", "Repo_find_a2", EdgeType::Calls),
+            ],
+        };
+        // --collapse-delegates runs during conversion, before the merge
+        let delegate_calls = HashMap::from([("Ctrl_find_c2".to_string(), "find".to_string())]);
+        assert_eq!(crate::delegates::collapse(&mut graph, &delegate_calls), 1);
+
+        assert_eq!(SingleImplMerger::merge_graph(&mut graph), 1);
+
+        let find = |id: &str| graph.definitions.iter().find(|n| n.id == id).unwrap();
+        let logic = find("Ctrl_find_c2").logic.as_ref().unwrap();
+        assert_eq!(logic.delegates_to.as_deref(), Some("UserRepo_find_b2"));
+        assert_eq!(find("id_a3").parent_id.as_deref(), Some("UserRepo_find_b2"));
+        let ids: HashSet<&str> = graph.definitions.iter().map(|n| n.id.as_str()).collect();
+        assert!(graph.definitions.iter().all(|n| {
+            n.parent_id
+                .as_deref()
+                .is_none_or(|parent| ids.contains(parent))
+        }));
+    }
+}
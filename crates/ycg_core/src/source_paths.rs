@@ -0,0 +1,247 @@
+// crates/ycg_core/src/source_paths.rs
+//! Maps SCIP document paths to source files on disk.
+//!
+//! In a monorepo the indexer usually runs at the repository root, so
+//! document paths look like `packages/api/src/users.ts`, while the tool
+//! is pointed at the package (`--root packages/api`). Joining the two
+//! misses every file. A path prefix (`--path-prefix packages/api`) is
+//! stripped from document paths before they are joined with the root;
+//! when none is given and no document resolves, the prefix is detected
+//! by probing the leading directories of the document paths.
+//!
+//! When packages live under different bases (`--source-root`), extra roots
+//! are tried in order after the project root: a document resolves under
+//! the first root where its file exists.
+
+use std::path::{Path, PathBuf};
+
+/// Resolves SCIP `relative_path`s against the project root
+#[derive(Debug, Clone)]
+pub struct SourcePaths {
+    root: PathBuf,
+    /// Roots tried in order when a document is not found under `root`
+    extra_roots: Vec<PathBuf>,
+    /// Leading path removed from document paths, without trailing `/`
+    prefix: Option<String>,
+}
+
+impl SourcePaths {
+    pub fn new(root: &Path, prefix: Option<&str>) -> Self {
+        let prefix = prefix
+            .map(|p| p.trim_matches('/').to_string())
+            .filter(|p| !p.is_empty());
+        Self {
+            root: root.to_path_buf(),
+            extra_roots: Vec::new(),
+            prefix,
+        }
+    }
+
+    /// Also looks for documents under `roots`, in order, after the project root
+    pub fn with_extra_roots(mut self, roots: &[PathBuf]) -> Self {
+        self.extra_roots = roots.to_vec();
+        self
+    }
+
+    /// Uses `prefix` when given, otherwise detects one if no path resolves as is
+    pub fn for_documents<'a>(
+        root: &Path,
+        prefix: Option<&str>,
+        relative_paths: impl IntoIterator<Item = &'a str> + Clone,
+    ) -> Self {
+        match prefix {
+            Some(prefix) => Self::new(root, Some(prefix)),
+            None => Self::new(root, detect_prefix(root, relative_paths).as_deref()),
+        }
+    }
+
+    /// Prefix in effect (given or detected)
+    pub fn prefix(&self) -> Option<&str> {
+        self.prefix.as_deref()
+    }
+
+    /// Document path relative to the project root (prefix removed when present)
+    pub fn strip<'a>(&self, relative_path: &'a str) -> &'a str {
+        self.prefix
+            .as_deref()
+            .and_then(|prefix| relative_path.strip_prefix(prefix))
+            .and_then(|rest| rest.strip_prefix('/'))
+            .unwrap_or(relative_path)
+    }
+
+    /// Root a document resolves under: the first one holding its file, or
+    /// the project root when none does
+    fn root_for(&self, relative_path: &str) -> &Path {
+        let path = self.strip(relative_path);
+        std::iter::once(&self.root)
+            .chain(&self.extra_roots)
+            .find(|root| root.join(path).is_file())
+            .unwrap_or(&self.root)
+    }
+
+    /// Location of a document on disk
+    pub fn resolve(&self, relative_path: &str) -> PathBuf {
+        self.root_for(relative_path).join(self.strip(relative_path))
+    }
+
+    /// True when the document's source file, or a directory between the
+    /// project root and it, is a symbolic link (broken links included)
+    pub fn is_symlinked(&self, relative_path: &str) -> bool {
+        let mut path = self.root_for(relative_path).to_path_buf();
+        Path::new(self.strip(relative_path))
+            .components()
+            .any(|component| {
+                path.push(component);
+                path.symlink_metadata()
+                    .is_ok_and(|meta| meta.file_type().is_symlink())
+            })
+    }
+
+    /// Number of documents whose source file exists
+    pub fn count_resolved<'a>(&self, relative_paths: impl IntoIterator<Item = &'a str>) -> usize {
+        relative_paths
+            .into_iter()
+            .filter(|path| self.resolve(path).is_file())
+            .count()
+    }
+}
+
+/// Finds the leading directory that, once stripped, makes the most documents resolve.
+///
+/// Returns `None` when some document already resolves without a prefix or
+/// when no candidate resolves anything.
+fn detect_prefix<'a>(
+    root: &Path,
+    relative_paths: impl IntoIterator<Item = &'a str> + Clone,
+) -> Option<String> {
+    let unprefixed = SourcePaths::new(root, None);
+    if unprefixed.count_resolved(relative_paths.clone()) > 0 {
+        return None;
+    }
+
+    let mut candidates: Vec<String> = Vec::new();
+    for path in relative_paths.clone() {
+        let dirs: Vec<&str> = path.split('/').collect();
+        for depth in 1..dirs.len() {
+            let candidate = dirs[..depth].join("/");
+            if !candidates.contains(&candidate) {
+                candidates.push(candidate);
+            }
+        }
+    }
+
+    candidates
+        .into_iter()
+        .map(|candidate| {
+            let resolved = SourcePaths::new(root, Some(&candidate)).count_resolved(
+                relative_paths.clone().into_iter().filter(|path| {
+                    path.strip_prefix(candidate.as_str())
+                        .is_some_and(|rest| rest.starts_with('/'))
+                }),
+            );
+            (resolved, candidate)
+        })
+        .filter(|(resolved, _)| *resolved > 0)
+        // Most documents resolved; on ties the shorter (shallower) prefix wins
+        .max_by(|(a, pa), (b, pb)| a.cmp(b).then(pb.len().cmp(&pa.len())))
+        .map(|(_, candidate)| candidate)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn monorepo() -> tempfile::TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        let api = dir.path().join("packages/api/src");
+        std::fs::create_dir_all(&api).unwrap();
+        std::fs::write(api.join("users.ts"), "export class Users {}\n").unwrap();
+        std::fs::write(api.join("orders.ts"), "export class Orders {}\n").unwrap();
+        dir
+    }
+
+    const DOCS: [&str; 3] = [
+        "packages/api/src/users.ts",
+        "packages/api/src/orders.ts",
+        "packages/web/src/app.ts",
+    ];
+
+    #[test]
+    fn test_explicit_prefix_is_stripped() {
+        let repo = monorepo();
+        let package = repo.path().join("packages/api");
+        let paths = SourcePaths::new(&package, Some("packages/api/"));
+
+        assert_eq!(paths.strip("packages/api/src/users.ts"), "src/users.ts");
+        assert_eq!(
+            paths.resolve("packages/api/src/users.ts"),
+            package.join("src/users.ts")
+        );
+        assert!(paths.resolve("packages/api/src/users.ts").is_file());
+        // Paths outside the prefix are joined unchanged
+        assert_eq!(paths.strip("packages/apix/a.ts"), "packages/apix/a.ts");
+        assert_eq!(paths.count_resolved(DOCS), 2);
+    }
+
+    #[test]
+    fn test_prefix_detected_from_package_root() {
+        let repo = monorepo();
+        let package = repo.path().join("packages/api");
+
+        let paths = SourcePaths::for_documents(&package, None, DOCS);
+        assert_eq!(paths.prefix(), Some("packages/api"));
+        assert_eq!(paths.count_resolved(DOCS), 2);
+    }
+
+    #[test]
+    fn test_no_prefix_when_paths_already_resolve() {
+        let repo = monorepo();
+
+        let paths = SourcePaths::for_documents(repo.path(), None, DOCS);
+        assert_eq!(paths.prefix(), None);
+        assert_eq!(paths.count_resolved(DOCS), 2);
+    }
+
+    #[test]
+    fn test_extra_roots_are_tried_in_order() {
+        let repo = monorepo();
+        let web = repo.path().join("apps/web/packages/web/src");
+        std::fs::create_dir_all(&web).unwrap();
+        std::fs::write(web.join("app.ts"), "export const app = 1;\n").unwrap();
+        std::fs::write(web.join("users.ts"), "shadowed\n").unwrap();
+
+        let paths = SourcePaths::new(repo.path(), None)
+            .with_extra_roots(&[repo.path().join("missing"), repo.path().join("apps/web")]);
+        assert_eq!(paths.count_resolved(DOCS), 3);
+        assert_eq!(paths.resolve("packages/web/src/app.ts"), web.join("app.ts"));
+        // The project root comes first
+        assert_eq!(
+            paths.resolve("packages/api/src/users.ts"),
+            repo.path().join("packages/api/src/users.ts")
+        );
+        // Unresolved documents stay under the project root
+        assert_eq!(
+            paths.resolve("packages/web/src/gone.ts"),
+            repo.path().join("packages/web/src/gone.ts")
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_symlinked_files_and_directories() {
+        use std::os::unix::fs::symlink;
+
+        let repo = monorepo();
+        let src = repo.path().join("packages/api/src");
+        symlink(src.join("users.ts"), src.join("alias.ts")).unwrap();
+        symlink(src.join("missing.ts"), src.join("broken.ts")).unwrap();
+        symlink(&src, repo.path().join("linked")).unwrap();
+
+        let paths = SourcePaths::new(repo.path(), None);
+        assert!(!paths.is_symlinked("packages/api/src/users.ts"));
+        assert!(paths.is_symlinked("packages/api/src/alias.ts"));
+        assert!(paths.is_symlinked("packages/api/src/broken.ts"));
+        assert!(paths.is_symlinked("linked/orders.ts"));
+        assert!(!paths.is_symlinked("packages/web/src/app.ts"));
+    }
+}
@@ -0,0 +1,242 @@
+// crates/ycg_core/src/stats.rs
+//! Summary of a generated graph (`ycg_cli stats`).
+//!
+//! A quick health check of a graph without regenerating it: definitions
+//! per kind, edges per type, fan-in / fan-out over the definitions, the
+//! most referenced symbols, the number of files and the token estimate
+//! of the serialized graph (cl100k, as [`crate::count_tokens`]).
+
+use crate::adhoc_format::kind_to_string;
+use crate::graph_analysis::unimplemented_members;
+use crate::model::YcgGraph;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+
+/// Symbols listed under "most referenced"
+const TOP_REFERENCED: usize = 10;
+
+/// Fan-in or fan-out over the definitions of a graph
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Degree {
+    /// Edges per definition
+    pub average: f64,
+    /// Highest count, with the first definition reaching it
+    pub max: usize,
+    pub max_id: Option<String>,
+}
+
+/// Statistics of a graph
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct GraphStats {
+    pub definitions: usize,
+    pub nodes_by_kind: BTreeMap<String, usize>,
+    pub edges: usize,
+    pub edges_by_type: BTreeMap<String, usize>,
+    /// Inbound edges per definition
+    pub fan_in: Degree,
+    /// Outbound edges per definition
+    pub fan_out: Degree,
+    /// `(id, inbound edges)`, most referenced first (graph order on ties)
+    pub top_referenced: Vec<(String, usize)>,
+    /// File nodes
+    pub files: usize,
+    /// Classes missing methods of an interface they implement
+    pub implementation_gaps: usize,
+    /// Tokens of the serialized graph
+    pub tokens: usize,
+}
+
+impl GraphStats {
+    /// Computes the statistics of `graph`, serialized as `text` (for the token estimate)
+    pub fn from_graph(graph: &YcgGraph, text: &str) -> Self {
+        let mut nodes_by_kind = BTreeMap::new();
+        for node in &graph.definitions {
+            *nodes_by_kind.entry(kind_to_string(&node.kind)).or_insert(0) += 1;
+        }
+        let mut edges_by_type = BTreeMap::new();
+        let mut inbound: HashMap<&str, usize> = HashMap::new();
+        let mut outbound: HashMap<&str, usize> = HashMap::new();
+        for edge in &graph.references {
+            *edges_by_type
+                .entry(edge.edge_type.as_str().to_string())
+                .or_insert(0) += 1;
+            *inbound.entry(edge.to.as_str()).or_insert(0) += 1;
+            *outbound.entry(edge.from.as_str()).or_insert(0) += 1;
+        }
+
+        let degree = |counts: &HashMap<&str, usize>| {
+            let mut degree = Degree::default();
+            let mut total = 0;
+            for node in &graph.definitions {
+                let count = counts.get(node.id.as_str()).copied().unwrap_or(0);
+                total += count;
+                if count > degree.max {
+                    degree.max = count;
+                    degree.max_id = Some(node.id.clone());
+                }
+            }
+            if !graph.definitions.is_empty() {
+                degree.average = total as f64 / graph.definitions.len() as f64;
+            }
+            degree
+        };
+
+        let mut top_referenced: Vec<(String, usize)> = graph
+            .definitions
+            .iter()
+            .filter_map(|node| {
+                let count = inbound.get(node.id.as_str()).copied()?;
+                Some((node.id.clone(), count))
+            })
+            .collect();
+        // Stable: definitions referenced as often keep the graph order
+        top_referenced.sort_by_key(|(_, count)| std::cmp::Reverse(*count));
+        top_referenced.truncate(TOP_REFERENCED);
+
+        Self {
+            definitions: graph.definitions.len(),
+            files: nodes_by_kind.get("file").copied().unwrap_or(0),
+            nodes_by_kind,
+            edges: graph.references.len(),
+            edges_by_type,
+            fan_in: degree(&inbound),
+            fan_out: degree(&outbound),
+            top_referenced,
+            implementation_gaps: unimplemented_members(graph).len(),
+            tokens: crate::count_tokens(text),
+        }
+    }
+}
+
+impl fmt::Display for GraphStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "--- Graph statistics ---")?;
+        writeln!(f, "Definitions: {}", self.definitions)?;
+        for (kind, count) in &self.nodes_by_kind {
+            writeln!(f, "  {}: {}", kind, count)?;
+        }
+        writeln!(f, "Edges: {}", self.edges)?;
+        for (edge_type, count) in &self.edges_by_type {
+            writeln!(f, "  {}: {}", edge_type, count)?;
+        }
+        for (label, degree) in [("Fan-in", &self.fan_in), ("Fan-out", &self.fan_out)] {
+            write!(
+                f,
+                "{}: avg {:.2}, max {}",
+                label, degree.average, degree.max
+            )?;
+            match &degree.max_id {
+                Some(id) => writeln!(f, " ({})", id)?,
+                None => writeln!(f)?,
+            }
+        }
+        writeln!(f, "Most referenced:")?;
+        for (id, count) in &self.top_referenced {
+            writeln!(f, "  {} ({})", id, count)?;
+        }
+        writeln!(f, "Files: {}", self.files)?;
+        writeln!(f, "Implementation gaps: {}", self.implementation_gaps)?;
+        writeln!(f, "Estimated tokens: {}", self.tokens)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chunker::GraphChunker;
+
+    const GRAPH: &str = r#"_meta:
+  name: ycg-v1.3
+  version: 1.3.0
+_defs:
+- id: users_ts_f001
+  n: users.ts
+  t: file
+- id: Repository_c3d4
+  n: Repository
+  t: interface
+  parent_id: users_ts_f001
+- id: find_0b1c
+  n: find
+  t: method
+  parent_id: Repository_c3d4
+- id: UserService_a1b2
+  n: UserService
+  t: class
+  parent_id: users_ts_f001
+- id: findOne_e5f6
+  n: findOne
+  t: method
+  parent_id: UserService_a1b2
+- id: save_9a8b
+  n: save
+  t: method
+  parent_id: UserService_a1b2
+graph:
+- from: UserService_a1b2
+  to: Repository_c3d4
+  type: implements
+- from: findOne_e5f6
+  to: save_9a8b
+  type: calls
+- from: save_9a8b
+  to: ext_db_7f7f
+  type: calls
+- from: findOne_e5f6
+  to: Repository_c3d4
+  type: references
+- from: users_ts_f001
+  to: Repository_c3d4
+  type: imports
+"#;
+
+    #[test]
+    fn test_fixture_graph_stats() {
+        let graph = GraphChunker::load_graph_yaml(GRAPH).unwrap();
+        let stats = GraphStats::from_graph(&graph, GRAPH);
+
+        assert_eq!(stats.definitions, 6);
+        assert_eq!(
+            stats.nodes_by_kind,
+            BTreeMap::from([
+                ("class".to_string(), 1),
+                ("file".to_string(), 1),
+                ("interface".to_string(), 1),
+                ("method".to_string(), 3),
+            ])
+        );
+        assert_eq!(stats.edges, 5);
+        assert_eq!(
+            stats.edges_by_type,
+            BTreeMap::from([
+                ("calls".to_string(), 2),
+                ("implements".to_string(), 1),
+                ("imports".to_string(), 1),
+                ("references".to_string(), 1),
+            ])
+        );
+        // The external callee counts for its caller, not in the fan-in
+        assert_eq!(stats.fan_in.average, 4.0 / 6.0);
+        assert_eq!(stats.fan_in.max, 3);
+        assert_eq!(stats.fan_in.max_id.as_deref(), Some("Repository_c3d4"));
+        assert_eq!(stats.fan_out.average, 5.0 / 6.0);
+        assert_eq!(stats.fan_out.max, 2);
+        assert_eq!(stats.fan_out.max_id.as_deref(), Some("findOne_e5f6"));
+        assert_eq!(
+            stats.top_referenced,
+            vec![
+                ("Repository_c3d4".to_string(), 3),
+                ("save_9a8b".to_string(), 1),
+            ]
+        );
+        assert_eq!(stats.files, 1);
+        // UserService lacks Repository#find
+        assert_eq!(stats.implementation_gaps, 1);
+        assert_eq!(stats.tokens, crate::count_tokens(GRAPH));
+        assert!(
+            stats
+                .to_string()
+                .contains("Fan-in: avg 0.67, max 3 (Repository_c3d4)")
+        );
+    }
+}
@@ -0,0 +1,168 @@
+// crates/ycg_core/src/string_interner.rs
+
+use crate::model::{SymbolNode, YcgGraph};
+use anyhow::{Result, anyhow};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+
+/// Prefix of interned string keys (`$0`, `$1`, ...)
+pub const KEY_PREFIX: char = '$';
+
+/// A graph whose repeated `sig`/`doc` strings live once in `_strings`
+/// (`--intern-strings`). Node fields hold `$N` keys into the table.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct InternedGraph<G> {
+    #[serde(
+        rename = "_strings",
+        default,
+        skip_serializing_if = "BTreeMap::is_empty"
+    )]
+    pub strings: BTreeMap<String, String>,
+    #[serde(flatten)]
+    pub graph: G,
+}
+
+/// Hoists repeated signatures and documentation into a shared table
+pub struct StringInterner;
+
+impl StringInterner {
+    /// Replace every `signature`/`documentation` value that occurs more than
+    /// once with a `$N` key, returning the key -> string table.
+    ///
+    /// Keys are numbered in order of first appearance, so output is stable.
+    /// A unique value that itself starts with `$` is interned too, which keeps
+    /// every `$`-prefixed field unambiguous for [`StringInterner::resolve`].
+    pub fn intern(definitions: &mut [SymbolNode]) -> BTreeMap<String, String> {
+        let mut counts: HashMap<String, usize> = HashMap::new();
+        for node in definitions.iter() {
+            for text in [&node.signature, &node.documentation].into_iter().flatten() {
+                *counts.entry(text.clone()).or_insert(0) += 1;
+            }
+        }
+
+        let mut keys: HashMap<String, String> = HashMap::new();
+        let mut strings = BTreeMap::new();
+
+        for node in definitions.iter_mut() {
+            for field in [&mut node.signature, &mut node.documentation] {
+                let Some(text) = field.as_ref() else {
+                    continue;
+                };
+                if counts[text] < 2 && !text.starts_with(KEY_PREFIX) {
+                    continue;
+                }
+                let key = match keys.get(text) {
+                    Some(key) => key.clone(),
+                    None => {
+                        let key = format!("{}{}", KEY_PREFIX, keys.len());
+                        keys.insert(text.clone(), key.clone());
+                        strings.insert(key.clone(), text.clone());
+                        key
+                    }
+                };
+                *field = Some(key);
+            }
+        }
+
+        strings
+    }
+
+    /// Decode helper for consumers: expand `$N` keys back into their strings
+    pub fn resolve(
+        definitions: &mut [SymbolNode],
+        strings: &BTreeMap<String, String>,
+    ) -> Result<()> {
+        for node in definitions.iter_mut() {
+            for field in [&mut node.signature, &mut node.documentation] {
+                if let Some(key) = field.as_ref().filter(|t| t.starts_with(KEY_PREFIX)) {
+                    let value = strings.get(key).ok_or_else(|| {
+                        anyhow!(
+                            "Unknown interned string key '{}' in node '{}'",
+                            key,
+                            node.id
+                        )
+                    })?;
+                    *field = Some(value.clone());
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Parse a flat (non-compact) YAML graph written with `--intern-strings`
+    /// and return it with every string expanded. Plain graphs parse as well.
+    pub fn decode_yaml(yaml: &str) -> Result<YcgGraph> {
+        let InternedGraph { strings, mut graph }: InternedGraph<YcgGraph> =
+            serde_yaml::from_str(yaml)?;
+        Self::resolve(&mut graph.definitions, &strings)?;
+        Ok(graph)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{ProjectMetadata, ScipSymbolKind};
+
+    fn create_test_node(id: &str, signature: Option<&str>) -> SymbolNode {
+        SymbolNode {
+            id: id.to_string(),
+            name: id.to_string(),
+            kind: ScipSymbolKind::Method,
+            signature: signature.map(|s| s.to_string()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_shared_signature_stored_once_referenced_thrice() {
+        let crud = "findAll(): Promise<Entity[]>";
+        let mut definitions = vec![
+            create_test_node("Users_findAll_a1b2", Some(crud)),
+            create_test_node("Orders_findAll_c3d4", Some(crud)),
+            create_test_node("Items_findAll_e5f6", Some(crud)),
+            create_test_node("Items_count_0a0b", Some("count(): number")),
+        ];
+
+        let strings = StringInterner::intern(&mut definitions);
+
+        assert_eq!(strings.len(), 1);
+        assert_eq!(strings["$0"], crud);
+        for node in &definitions[..3] {
+            assert_eq!(node.signature.as_deref(), Some("$0"));
+        }
+        assert_eq!(definitions[3].signature.as_deref(), Some("count(): number"));
+
+        let graph = InternedGraph {
+            strings,
+            graph: YcgGraph {
+                metadata: ProjectMetadata::default(),
+                definitions,
+                references: Vec::new(),
+            },
+        };
+        let yaml = serde_yaml::to_string(&graph).unwrap();
+        assert_eq!(yaml.matches(crud).count(), 1);
+
+        let decoded = StringInterner::decode_yaml(&yaml).unwrap();
+        for node in &decoded.definitions[..3] {
+            assert_eq!(node.signature.as_deref(), Some(crud));
+        }
+    }
+
+    #[test]
+    fn test_dollar_prefixed_values_round_trip() {
+        let mut definitions = vec![create_test_node("price_a1b2", Some("$price: number"))];
+
+        let strings = StringInterner::intern(&mut definitions);
+        StringInterner::resolve(&mut definitions, &strings).unwrap();
+
+        assert_eq!(definitions[0].signature.as_deref(), Some("$price: number"));
+    }
+
+    #[test]
+    fn test_resolve_unknown_key_fails() {
+        let mut definitions = vec![create_test_node("x_a1b2", Some("$7"))];
+        assert!(StringInterner::resolve(&mut definitions, &BTreeMap::new()).is_err());
+    }
+}
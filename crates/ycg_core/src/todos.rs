@@ -0,0 +1,105 @@
+// crates/ycg_core/src/todos.rs
+//! Tech-debt map (`analyze --todos`).
+//!
+//! Enrichment attaches the `TODO` / `FIXME` / `HACK` comments of each
+//! definition as its `annotations`; the report lists them per definition,
+//! the most annotated definitions first.
+
+use crate::model::YcgGraph;
+use std::fmt;
+
+/// One annotated definition
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TodoEntry {
+    pub id: String,
+    pub name: String,
+    pub annotations: Vec<String>,
+}
+
+/// Annotated definitions of a graph
+#[derive(Debug, Clone, Default)]
+pub struct TodoReport {
+    pub entries: Vec<TodoEntry>,
+}
+
+impl TodoReport {
+    pub fn from_graph(graph: &YcgGraph) -> Self {
+        let mut entries: Vec<TodoEntry> = graph
+            .definitions
+            .iter()
+            .filter(|node| !node.annotations.is_empty())
+            .map(|node| TodoEntry {
+                id: node.id.clone(),
+                name: node.name.clone(),
+                annotations: node.annotations.clone(),
+            })
+            .collect();
+        // Stable: definitions with as many annotations keep the graph order
+        entries.sort_by_key(|entry| std::cmp::Reverse(entry.annotations.len()));
+        Self { entries }
+    }
+
+    /// Number of annotations over all definitions
+    pub fn total(&self) -> usize {
+        self.entries
+            .iter()
+            .map(|entry| entry.annotations.len())
+            .sum()
+    }
+}
+
+impl fmt::Display for TodoReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "--- TODO/FIXME/HACK: {} in {} definition(s) ---",
+            self.total(),
+            self.entries.len()
+        )?;
+        for entry in &self.entries {
+            writeln!(f, "  {} ({})", entry.name, entry.id)?;
+            for annotation in &entry.annotations {
+                writeln!(f, "    - {}", annotation)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{ProjectMetadata, ScipSymbolKind, SymbolNode};
+
+    fn node(id: &str, annotations: &[&str]) -> SymbolNode {
+        SymbolNode {
+            id: id.to_string(),
+            name: id.to_string(),
+            kind: ScipSymbolKind::Method,
+            annotations: annotations.iter().map(|a| a.to_string()).collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_most_annotated_definitions_come_first() {
+        let graph = YcgGraph {
+            metadata: ProjectMetadata::default(),
+            definitions: vec![
+                node("save", &["TODO: validate"]),
+                node("list", &[]),
+                node("find", &["FIXME: cache misses", "TODO: refactor"]),
+            ],
+            references: Vec::new(),
+        };
+        let report = TodoReport::from_graph(&graph);
+        let ids: Vec<&str> = report.entries.iter().map(|e| e.id.as_str()).collect();
+        assert_eq!(ids, vec!["find", "save"]);
+        assert_eq!(report.total(), 3);
+        assert!(
+            report
+                .to_string()
+                .starts_with("--- TODO/FIXME/HACK: 3 in 2 definition(s) ---")
+        );
+    }
+}
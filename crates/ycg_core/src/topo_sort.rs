@@ -0,0 +1,121 @@
+// crates/ycg_core/src/topo_sort.rs
+//! Dependency order of definitions (`--topo-sort`).
+//!
+//! `calls` and `imports` edges between definitions are treated as
+//! dependencies, and `_defs` is reordered so a definition comes after the
+//! definitions it depends on: a reader meets a function's callees before
+//! the function. Mutually recursive definitions form a strongly connected
+//! component and keep their original relative order; independent
+//! definitions are visited in their original order too.
+
+use crate::model::{EdgeType, YcgGraph};
+use std::collections::{BTreeMap, HashMap};
+
+/// Reorders `graph.definitions` so dependencies precede their dependents
+pub fn sort_definitions(graph: &mut YcgGraph) {
+    let mut position: HashMap<&str, usize> = HashMap::new();
+    for (i, node) in graph.definitions.iter().enumerate() {
+        position.entry(node.id.as_str()).or_insert(i);
+    }
+
+    let mut links: BTreeMap<String, BTreeMap<String, ()>> = graph
+        .definitions
+        .iter()
+        .map(|node| (node.id.clone(), BTreeMap::new()))
+        .collect();
+    for edge in &graph.references {
+        if matches!(edge.edge_type, EdgeType::Calls | EdgeType::Imports)
+            && edge.from != edge.to
+            && position.contains_key(edge.to.as_str())
+            && let Some(targets) = links.get_mut(&edge.from)
+        {
+            targets.insert(edge.to.clone(), ());
+        }
+    }
+
+    let roots = graph.definitions.iter().map(|node| node.id.as_str());
+    let order: Vec<usize> = crate::import_cycles::strongly_connected(&links, roots)
+        .into_iter()
+        .flat_map(|component| {
+            let mut members: Vec<usize> = component
+                .iter()
+                .map(|anchor| position[anchor.as_str()])
+                .collect();
+            members.sort_unstable();
+            members
+        })
+        .collect();
+
+    // A definition repeating an earlier anchor is not in `order`; it goes last
+    let mut definitions: Vec<_> = std::mem::take(&mut graph.definitions)
+        .into_iter()
+        .map(Some)
+        .collect();
+    for i in order {
+        graph.definitions.extend(definitions[i].take());
+    }
+    graph.definitions.extend(definitions.into_iter().flatten());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{ProjectMetadata, ReferenceEdge, ScipSymbolKind, SymbolNode};
+
+    fn node(id: &str) -> SymbolNode {
+        SymbolNode {
+            id: id.to_string(),
+            name: id.to_string(),
+            kind: ScipSymbolKind::Function,
+            ..Default::default()
+        }
+    }
+
+    fn edge(from: &str, to: &str, edge_type: EdgeType) -> ReferenceEdge {
+        ReferenceEdge {
+            from: from.to_string(),
+            to: to.to_string(),
+            edge_type,
+            call_text: None,
+        }
+    }
+
+    fn ids(graph: &YcgGraph) -> Vec<&str> {
+        graph.definitions.iter().map(|n| n.id.as_str()).collect()
+    }
+
+    #[test]
+    fn test_callee_comes_before_caller() {
+        let mut graph = YcgGraph {
+            metadata: ProjectMetadata::default(),
+            definitions: vec![node("main"), node("helper")],
+            references: vec![edge("main", "helper", EdgeType::Calls)],
+        };
+        sort_definitions(&mut graph);
+        assert_eq!(ids(&graph), vec!["helper", "main"]);
+    }
+
+    #[test]
+    fn test_cycles_keep_their_original_order() {
+        let mut graph = YcgGraph {
+            metadata: ProjectMetadata::default(),
+            definitions: vec![
+                node("run"),
+                node("odd"),
+                node("even"),
+                node("log"),
+                node("User"),
+            ],
+            references: vec![
+                edge("run", "even", EdgeType::Calls),
+                edge("even", "odd", EdgeType::Calls),
+                edge("odd", "even", EdgeType::Calls),
+                edge("odd", "log", EdgeType::Calls),
+                // Only calls and imports are dependencies
+                edge("log", "User", EdgeType::References),
+            ],
+        };
+        sort_definitions(&mut graph);
+        assert_eq!(ids(&graph), vec!["log", "odd", "even", "run", "User"]);
+    }
+}
@@ -122,6 +122,399 @@ impl TypeAbbreviator {
         parts
     }
 
+    /// Abbreviate `Promise`/`Result` wrappers anywhere in a compact signature
+    ///
+    /// - `Promise<T>` → `{promise_marker}T` (`~T` by default)
+    /// - `Result<T, E>` / `Result<T>` → `T?` (the error type is dropped)
+    ///
+    /// Wrappers nest: `Promise<Result<User,Error>>` → `~User?`. Other
+    /// generics and identifiers merely ending in `Promise`/`Result`
+    /// (`QueryResult<T>`) are left alone.
+    ///
+    /// # Examples
+    /// ```
+    /// use ycg_core::type_abbreviator::TypeAbbreviator;
+    ///
+    /// assert_eq!(
+    ///     TypeAbbreviator::abbreviate_wrappers("findOne(id:num):Promise<User>", "~"),
+    ///     "findOne(id:num):~User"
+    /// );
+    /// assert_eq!(
+    ///     TypeAbbreviator::abbreviate_wrappers("load(path:&Path):Result<Config,Error>", "~"),
+    ///     "load(path:&Path):Config?"
+    /// );
+    /// ```
+    pub fn abbreviate_wrappers(sig: &str, promise_marker: &str) -> String {
+        const WRAPPERS: [&str; 2] = ["Promise<", "Result<"];
+
+        let mut out = String::with_capacity(sig.len());
+        let mut rest = sig;
+        while !rest.is_empty() {
+            let wrapper = WRAPPERS.iter().find(|w| {
+                rest.starts_with(*w)
+                    && !out
+                        .chars()
+                        .next_back()
+                        .is_some_and(|c| c.is_alphanumeric() || c == '_')
+            });
+            let inner = wrapper.and_then(|w| {
+                let open = w.len() - 1;
+                Self::matching_angle(rest, open).map(|close| (*w, &rest[open + 1..close], close))
+            });
+            match inner {
+                Some((wrapper, params, close)) => {
+                    if wrapper == "Promise<" {
+                        out.push_str(promise_marker);
+                        out.push_str(&Self::abbreviate_wrappers(params, promise_marker));
+                    } else {
+                        let ok = Self::split_generic_params(params)
+                            .into_iter()
+                            .next()
+                            .unwrap_or_default();
+                        out.push_str(&Self::abbreviate_wrappers(&ok, promise_marker));
+                        out.push('?');
+                    }
+                    rest = &rest[close + 1..];
+                }
+                None => {
+                    let ch = rest.chars().next().unwrap_or_default();
+                    out.push(ch);
+                    rest = &rest[ch.len_utf8()..];
+                }
+            }
+        }
+        out
+    }
+
+    /// Index of the `>` closing the `<` at `open` (`->` arrows are skipped)
+    fn matching_angle(s: &str, open: usize) -> Option<usize> {
+        let bytes = s.as_bytes();
+        let mut depth = 0;
+        for (i, &b) in bytes.iter().enumerate().skip(open) {
+            match b {
+                b'<' => depth += 1,
+                b'>' if i > 0 && bytes[i - 1] == b'-' => {}
+                b'>' => {
+                    depth -= 1;
+                    if depth == 0 {
+                        return Some(i);
+                    }
+                }
+                _ => {}
+            }
+        }
+        None
+    }
+
+    /// Rewrites a TS or Rust function signature in a language-neutral form
+    /// (`--neutral-signatures`): `name(param: type, ...) -> return`.
+    ///
+    /// Names become snake_case, modifiers (`pub`, `async`, `public`...),
+    /// generic parameters, receivers (`self`, `this`), decorators and
+    /// default values are dropped, and types go through [`Self::neutral_type`].
+    /// A `void` return is omitted. `None` when `sig` is not a function
+    /// signature (classes, variables, arrow-function initializers).
+    ///
+    /// # Examples
+    /// ```
+    /// use ycg_core::type_abbreviator::TypeAbbreviator;
+    ///
+    /// let ts = TypeAbbreviator::neutral_signature("async findOne(id: number): Promise<User>");
+    /// let rs = TypeAbbreviator::neutral_signature("pub async fn find_one(id: i64) -> Result<User>");
+    /// assert_eq!(ts.as_deref(), Some("find_one(id: int) -> User"));
+    /// assert_eq!(ts, rs);
+    /// ```
+    pub fn neutral_signature(sig: &str) -> Option<String> {
+        let sig = sig.split_whitespace().collect::<Vec<_>>().join(" ");
+        let open = Self::top_level_position(&sig, '(')?;
+        let close = open + Self::matching_paren(&sig[open..])?;
+
+        // `pub async fn find_one<T>` / `async findOne<T>`: the last word, generics dropped
+        let head = sig[..open].trim_end();
+        let head = Self::top_level_position(head, '<').map_or(head, |lt| &head[..lt]);
+        let name = head.split_whitespace().last()?;
+        if !name
+            .chars()
+            .all(|c| c.is_alphanumeric() || c == '_' || c == '$' || c == '#')
+        {
+            return None;
+        }
+
+        let params: Vec<String> = Self::split_top_level(&sig[open + 1..close], ',')
+            .into_iter()
+            .filter_map(Self::neutral_param)
+            .collect();
+
+        let rest = sig[close + 1..].trim();
+        let rest = rest.split(" where ").next().unwrap_or(rest);
+        let rest = rest.trim_end_matches(['{', ';', ' ']);
+        let returns = rest
+            .strip_prefix("->")
+            .or_else(|| rest.strip_prefix(':'))
+            .map(Self::neutral_type)
+            .filter(|ret| ret != "void" && !ret.is_empty());
+
+        let mut out = format!("{}({})", Self::snake_case(name), params.join(", "));
+        if let Some(ret) = returns {
+            out.push_str(" -> ");
+            out.push_str(&ret);
+        }
+        Some(out)
+    }
+
+    /// One parameter as `name: type` (`None` for receivers)
+    fn neutral_param(param: &str) -> Option<String> {
+        // Decorators (`@Body() dto: Dto`) and modifiers come before the name
+        let mut param = param.trim();
+        while let Some(rest) = param.strip_prefix('@') {
+            // `@Body()`, `@Query('a b')` end at their closing paren, `@Req` at a space
+            let end = match rest.find(['(', ' '])? {
+                paren if rest[paren..].starts_with('(') => {
+                    paren + Self::matching_paren(&rest[paren..])? + 1
+                }
+                space => space,
+            };
+            param = rest[end..].trim_start();
+        }
+        for modifier in [
+            "public ",
+            "private ",
+            "protected ",
+            "readonly ",
+            "override ",
+            "mut ",
+        ] {
+            param = param.strip_prefix(modifier).unwrap_or(param).trim_start();
+        }
+        let param = param.trim_start_matches("...");
+
+        let (name, ty) = match Self::top_level_position(param, ':') {
+            Some(colon) => (&param[..colon], Some(&param[colon + 1..])),
+            None => (param, None),
+        };
+        // Default values (`limit = 10`, `limit: number = 10`)
+        let name = name.split('=').next().unwrap_or(name).trim();
+        let ty = ty.map(|ty| {
+            Self::top_level_position(ty, '=')
+                .map_or(ty, |eq| &ty[..eq])
+                .trim()
+        });
+
+        let (name, optional) = match name.strip_suffix('?') {
+            Some(name) => (name, true),
+            None => (name, false),
+        };
+        let receiver = matches!(name, "self" | "&self" | "&mut self" | "this")
+            || name.starts_with("&'") && name.ends_with(" self");
+        if receiver || name.is_empty() {
+            return None;
+        }
+
+        let name = Self::snake_case(name);
+        Some(match ty {
+            Some(ty) => {
+                let ty = Self::neutral_type(ty);
+                if optional && !ty.ends_with('?') {
+                    format!("{}: {}?", name, ty)
+                } else {
+                    format!("{}: {}", name, ty)
+                }
+            }
+            None => name,
+        })
+    }
+
+    /// Maps a TS or Rust type to the neutral vocabulary: `int`, `float`,
+    /// `str`, `bool`, `list<T>`, `map<K, V>`, `any`, `void`
+    ///
+    /// `Promise`, `Result` (error dropped), `Box` / `Arc` / `Rc` and
+    /// references unwrap to their inner type; `Option<T>` and unions with
+    /// `null` / `undefined` become `T?`. TS `number` counts as `int`, the
+    /// common case (ids, counts). Other types keep their name, with their
+    /// type arguments mapped.
+    ///
+    /// # Examples
+    /// ```
+    /// use ycg_core::type_abbreviator::TypeAbbreviator;
+    ///
+    /// assert_eq!(TypeAbbreviator::neutral_type("Promise<string[]>"), "list<str>");
+    /// assert_eq!(TypeAbbreviator::neutral_type("Option<&Vec<u32>>"), "list<int>?");
+    /// assert_eq!(TypeAbbreviator::neutral_type("Record<string, number>"), "map<str, int>");
+    /// ```
+    pub fn neutral_type(ty: &str) -> String {
+        let ty = ty.trim();
+        let mut ty = ty.strip_prefix("readonly ").unwrap_or(ty).trim();
+        // References, lifetimes and trait-object markers
+        loop {
+            let stripped = if let Some(rest) = ty.strip_prefix('&') {
+                let rest = rest.trim_start();
+                match rest.strip_prefix('\'') {
+                    Some(lifetime) => lifetime.split_once(' ').map_or(lifetime, |(_, rest)| rest),
+                    None => rest,
+                }
+            } else if let Some(rest) = ty
+                .strip_prefix("mut ")
+                .or_else(|| ty.strip_prefix("dyn "))
+                .or_else(|| ty.strip_prefix("impl "))
+            {
+                rest
+            } else {
+                break;
+            };
+            ty = stripped.trim_start();
+        }
+
+        let union = Self::split_top_level(ty, '|');
+        if union.len() > 1 {
+            let optional = union
+                .iter()
+                .any(|part| matches!(part.trim(), "null" | "undefined"));
+            let parts: Vec<String> = union
+                .iter()
+                .filter(|part| !matches!(part.trim(), "null" | "undefined"))
+                .map(|part| Self::neutral_type(part))
+                .collect();
+            let joined = parts.join(" | ");
+            return if optional {
+                format!("{}?", joined)
+            } else {
+                joined
+            };
+        }
+
+        if let Some(base) = ty.strip_suffix("[]") {
+            return format!("list<{}>", Self::neutral_type(base));
+        }
+        if let Some(inner) = ty.strip_prefix('[').and_then(|t| t.strip_suffix(']')) {
+            // Rust `[T]` / `[T; N]` slices and arrays; TS tuples
+            let items = Self::split_top_level(inner, ',');
+            return if items.len() > 1 {
+                let items: Vec<String> = items.iter().map(|i| Self::neutral_type(i)).collect();
+                format!("({})", items.join(", "))
+            } else {
+                let item = inner.split(';').next().unwrap_or(inner);
+                format!("list<{}>", Self::neutral_type(item))
+            };
+        }
+
+        if let Some(lt) = Self::top_level_position(ty, '<')
+            && let Some(params) = ty[lt + 1..].strip_suffix('>')
+        {
+            let base = ty[..lt].trim();
+            let base = base.rsplit("::").next().unwrap_or(base);
+            let params: Vec<String> = Self::split_top_level(params, ',')
+                .iter()
+                .map(|param| Self::neutral_type(param))
+                .collect();
+            let first = params.first().cloned().unwrap_or_default();
+            return match base {
+                "Promise" | "PromiseLike" | "Result" | "Box" | "Arc" | "Rc" | "Cow" | "RefCell"
+                | "Readonly" => first,
+                "Option" => format!("{}?", first),
+                "Array" | "ReadonlyArray" | "Vec" | "VecDeque" | "Set" | "ReadonlySet"
+                | "HashSet" | "BTreeSet" => format!("list<{}>", first),
+                "Map" | "ReadonlyMap" | "Record" | "HashMap" | "BTreeMap" | "IndexMap" => {
+                    format!("map<{}>", params.join(", "))
+                }
+                _ => format!("{}<{}>", base, params.join(", ")),
+            };
+        }
+
+        match ty {
+            "number" | "bigint" | "i8" | "i16" | "i32" | "i64" | "i128" | "isize" | "u8"
+            | "u16" | "u32" | "u64" | "u128" | "usize" => "int".to_string(),
+            "f32" | "f64" => "float".to_string(),
+            "string" | "String" | "str" | "char" => "str".to_string(),
+            "boolean" | "bool" => "bool".to_string(),
+            "void" | "undefined" | "never" | "()" | "!" => "void".to_string(),
+            "any" | "unknown" | "object" | "Object" => "any".to_string(),
+            other => other.to_string(),
+        }
+    }
+
+    /// `findOne` -> `find_one`, `HTTPServer` -> `http_server`
+    fn snake_case(name: &str) -> String {
+        let chars: Vec<char> = name.chars().collect();
+        let mut out = String::with_capacity(name.len() + 4);
+        for (i, &c) in chars.iter().enumerate() {
+            if c.is_uppercase() {
+                let prev = i.checked_sub(1).map(|p| chars[p]);
+                let next = chars.get(i + 1);
+                let boundary = prev.is_some_and(|p| p.is_lowercase() || p.is_ascii_digit())
+                    || (prev.is_some_and(char::is_uppercase)
+                        && next.is_some_and(|n| n.is_lowercase()));
+                if boundary && !out.ends_with('_') {
+                    out.push('_');
+                }
+                out.extend(c.to_lowercase());
+            } else {
+                out.push(c);
+            }
+        }
+        out
+    }
+
+    /// Splits at `sep` outside of `<>`, `()`, `[]` and `{}` (`->` is no bracket)
+    fn split_top_level(s: &str, sep: char) -> Vec<&str> {
+        let mut parts = Vec::new();
+        let mut depth = 0i32;
+        let mut start = 0;
+        let mut prev = ' ';
+        for (i, c) in s.char_indices() {
+            match c {
+                '<' | '(' | '[' | '{' => depth += 1,
+                '>' if prev == '-' || prev == '=' => {}
+                '>' | ')' | ']' | '}' => depth -= 1,
+                _ if c == sep && depth == 0 => {
+                    parts.push(&s[start..i]);
+                    start = i + c.len_utf8();
+                }
+                _ => {}
+            }
+            prev = c;
+        }
+        parts.push(&s[start..]);
+        parts.retain(|part| !part.trim().is_empty());
+        parts
+    }
+
+    /// First `target` outside of brackets (the opening bracket itself counts)
+    fn top_level_position(s: &str, target: char) -> Option<usize> {
+        let mut depth = 0i32;
+        let mut prev = ' ';
+        for (i, c) in s.char_indices() {
+            if c == target && depth == 0 {
+                return Some(i);
+            }
+            match c {
+                '<' | '(' | '[' | '{' => depth += 1,
+                '>' if prev == '-' || prev == '=' => {}
+                '>' | ')' | ']' | '}' => depth -= 1,
+                _ => {}
+            }
+            prev = c;
+        }
+        None
+    }
+
+    /// Index of the `)` closing the `(` that starts `s`
+    fn matching_paren(s: &str) -> Option<usize> {
+        let mut depth = 0;
+        for (i, c) in s.char_indices() {
+            match c {
+                '(' => depth += 1,
+                ')' => {
+                    depth -= 1;
+                    if depth == 0 {
+                        return Some(i);
+                    }
+                }
+                _ => {}
+            }
+        }
+        None
+    }
+
     /// Abbreviate a simple (non-generic, non-array) type
     ///
     /// **Standard Abbreviation Table:**
@@ -189,6 +582,45 @@ mod tests {
         assert_eq!(TypeAbbreviator::abbreviate("number?"), "num?");
     }
 
+    #[test]
+    fn test_abbreviate_promise_wrapper() {
+        assert_eq!(
+            TypeAbbreviator::abbreviate_wrappers("findOne(id:num):Promise<UserDto>", "~"),
+            "findOne(id:num):~UserDto"
+        );
+        assert_eq!(
+            TypeAbbreviator::abbreviate_wrappers("all():Promise<Map<str,User[]>>", "~"),
+            "all():~Map<str,User[]>"
+        );
+        // Configurable marker
+        assert_eq!(
+            TypeAbbreviator::abbreviate_wrappers("save(u:User):Promise<void>", "async "),
+            "save(u:User):async void"
+        );
+    }
+
+    #[test]
+    fn test_abbreviate_result_wrapper() {
+        assert_eq!(
+            TypeAbbreviator::abbreviate_wrappers("load(p:&Path):Result<Config,io::Error>", "~"),
+            "load(p:&Path):Config?"
+        );
+        assert_eq!(
+            TypeAbbreviator::abbreviate_wrappers("run():Result<Vec<Job>>", "~"),
+            "run():Vec<Job>?"
+        );
+        assert_eq!(
+            TypeAbbreviator::abbreviate_wrappers("get():Promise<Result<User,Error>>", "~"),
+            "get():~User?"
+        );
+    }
+
+    #[test]
+    fn test_abbreviate_wrappers_leaves_other_names_alone() {
+        let sig = "query(cb:Fn(Row)->bool):QueryResult<Row>|MyPromise<T>";
+        assert_eq!(TypeAbbreviator::abbreviate_wrappers(sig, "~"), sig);
+    }
+
     #[test]
     fn test_generic_types() {
         // Requirement 4.7: Preserve generic type parameters
@@ -314,4 +746,58 @@ mod tests {
             assert_eq!(abbreviated, re_abbreviated);
         }
     }
+
+    #[test]
+    fn test_neutral_signatures_match_across_languages() {
+        let pairs = [
+            (
+                "async findOne(id: number): Promise<User>",
+                "pub async fn find_one(id: i64) -> Result<User>",
+                "find_one(id: int) -> User",
+            ),
+            (
+                "listByTags(tags: string[], limit?: number): Promise<Map<string, User[]>>",
+                "pub fn list_by_tags(&self, tags: &[String], limit: Option<u32>) -> HashMap<String, Vec<User>>",
+                "list_by_tags(tags: list<str>, limit: int?) -> map<str, list<User>>",
+            ),
+            (
+                "public save(@Body() dto: UserDto, @Query('force flag') force: boolean = false): void",
+                "fn save<'a>(&'a mut self, dto: &'a UserDto, force: bool)",
+                "save(dto: UserDto, force: bool)",
+            ),
+            (
+                "ratio(a: number, b: number | undefined): number",
+                "fn ratio(a: i32, b: Option<i32>) -> i32 where Self: Sized",
+                "ratio(a: int, b: int?) -> int",
+            ),
+        ];
+        for (ts, rust, neutral) in pairs {
+            assert_eq!(
+                TypeAbbreviator::neutral_signature(ts).as_deref(),
+                Some(neutral),
+                "{}",
+                ts
+            );
+            assert_eq!(
+                TypeAbbreviator::neutral_signature(rust).as_deref(),
+                Some(neutral),
+                "{}",
+                rust
+            );
+        }
+
+        assert_eq!(TypeAbbreviator::neutral_type("f64"), "float");
+        assert_eq!(
+            TypeAbbreviator::neutral_type("Box<dyn Fn(i32) -> bool>"),
+            "Fn(i32) -> bool"
+        );
+        assert_eq!(
+            TypeAbbreviator::neutral_signature("class UserService"),
+            None
+        );
+        assert_eq!(
+            TypeAbbreviator::neutral_signature("const handler = (req: Request) => void"),
+            None
+        );
+    }
 }
@@ -0,0 +1,145 @@
+// crates/ycg_core/src/unused_imports.rs
+//! Imports never used in the importing file (`analyze --unused-imports`).
+//!
+//! An occurrence carrying the SCIP `Import` role is what becomes an
+//! `imports` edge from the file; any other non-definition occurrence of the
+//! same symbol in that file is what becomes a `calls` / `references` edge.
+//! An import without such a use is reported with the line it sits on.
+
+use crate::scip_proto;
+use std::collections::{BTreeSet, HashSet};
+use std::fmt;
+
+/// An import statement whose symbol is not used afterwards
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct UnusedImport {
+    /// Importing file (SCIP relative path)
+    pub file: String,
+    /// 1-based line of the import
+    pub line: u32,
+    /// Short name of the imported symbol
+    pub symbol: String,
+}
+
+/// Unused imports found in an index, sorted by file and line
+#[derive(Debug, Clone, Default)]
+pub struct UnusedImportReport {
+    pub unused: Vec<UnusedImport>,
+}
+
+impl UnusedImportReport {
+    pub fn from_index(index: &scip_proto::Index) -> Self {
+        let definition_role = scip_proto::SymbolRole::Definition as i32;
+        let import_role = scip_proto::SymbolRole::Import as i32;
+
+        let mut unused = BTreeSet::new();
+        for doc in &index.documents {
+            let occurrences: Vec<&scip_proto::Occurrence> = doc
+                .occurrences
+                .iter()
+                .filter(|occurrence| {
+                    !crate::is_malformed_symbol(&occurrence.symbol)
+                        && !crate::is_malformed_range(&occurrence.range)
+                })
+                .collect();
+            let used: HashSet<&str> = occurrences
+                .iter()
+                .filter(|occurrence| occurrence.symbol_roles & (definition_role | import_role) == 0)
+                .map(|occurrence| occurrence.symbol.as_str())
+                .collect();
+
+            for occurrence in &occurrences {
+                if occurrence.symbol_roles & import_role != 0
+                    && !used.contains(occurrence.symbol.as_str())
+                {
+                    unused.insert(UnusedImport {
+                        file: doc.relative_path.clone(),
+                        line: occurrence.range[0] as u32 + 1,
+                        symbol: crate::extract_name_from_uri(&occurrence.symbol),
+                    });
+                }
+            }
+        }
+        Self {
+            unused: unused.into_iter().collect(),
+        }
+    }
+}
+
+impl fmt::Display for UnusedImportReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "--- Unused imports: {} ---", self.unused.len())?;
+        for import in &self.unused {
+            writeln!(
+                f,
+                "  {}:{} imports {} (never used)",
+                import.file, import.line, import.symbol
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn occurrence(
+        symbol: &str,
+        line: i32,
+        roles: scip_proto::SymbolRole,
+    ) -> scip_proto::Occurrence {
+        scip_proto::Occurrence {
+            range: vec![line, 0, 10],
+            symbol: symbol.to_string(),
+            symbol_roles: roles as i32,
+            ..Default::default()
+        }
+    }
+
+    const USERS: &str = "scip-typescript npm pkg 1.0.0 src/`users.ts`/UserService#";
+    const ORDERS: &str = "scip-typescript npm pkg 1.0.0 src/`orders.ts`/OrderService#";
+    const APP: &str = "scip-typescript npm pkg 1.0.0 src/`app.ts`/App#";
+
+    #[test]
+    fn test_import_without_use_is_reported() {
+        use scip_proto::SymbolRole::{Definition, Import, ReadAccess};
+
+        let index = scip_proto::Index {
+            documents: vec![
+                scip_proto::Document {
+                    relative_path: "src/app.ts".to_string(),
+                    occurrences: vec![
+                        occurrence(USERS, 0, Import),
+                        occurrence(ORDERS, 1, Import),
+                        occurrence(APP, 3, Definition),
+                        occurrence(USERS, 4, ReadAccess),
+                    ],
+                    ..Default::default()
+                },
+                // Definitions elsewhere are not uses in app.ts
+                scip_proto::Document {
+                    relative_path: "src/orders.ts".to_string(),
+                    occurrences: vec![occurrence(ORDERS, 0, Definition)],
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        };
+
+        let report = UnusedImportReport::from_index(&index);
+        assert_eq!(
+            report.unused,
+            vec![UnusedImport {
+                file: "src/app.ts".to_string(),
+                line: 2,
+                symbol: "OrderService".to_string(),
+            }]
+        );
+        assert!(
+            report
+                .to_string()
+                .contains("src/app.ts:2 imports OrderService (never used)")
+        );
+    }
+}
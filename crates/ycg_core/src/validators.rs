@@ -5,8 +5,11 @@
 //! - YAML output conforms to YAML 1.2 specification
 //! - Ad-Hoc format has correct structure (3 pipe-separated fields)
 //! - Graph edges maintain referential integrity (all IDs exist)
+//! - Definitions have unique IDs and known parents
 
-use crate::model::{AdHocGranularity, YcgGraph, YcgGraphAdHoc, YcgGraphOptimized};
+use crate::model::{
+    AdHocGranularity, SymbolNode, YcgGraph, YcgGraphAdHoc, YcgGraphByType, YcgGraphOptimized,
+};
 use anyhow::{Context, Result, anyhow};
 use std::collections::HashSet;
 
@@ -432,6 +435,142 @@ pub fn validate_graph_integrity_optimized(graph: &YcgGraphOptimized) -> Result<(
     Ok(())
 }
 
+/// Validate that definition IDs are unique and every `parent_id` exists
+///
+/// # Arguments
+/// * `definitions` - The `_defs` section of a YcgGraph or YcgGraphOptimized
+///
+/// # Returns
+/// * `Ok(())` if all IDs are unique and all parents are defined
+/// * `Err` with details about duplicates and unknown parents
+pub fn validate_definitions(definitions: &[SymbolNode]) -> Result<()> {
+    let mut seen = HashSet::new();
+    let mut problems = Vec::new();
+
+    for node in definitions {
+        if !seen.insert(node.id.as_str()) {
+            problems.push(format!("  - Duplicate ID '{}'\n", node.id));
+        }
+    }
+
+    for node in definitions {
+        if let Some(parent) = &node.parent_id
+            && !seen.contains(parent.as_str())
+        {
+            problems.push(format!(
+                "  - Node '{}' has parent '{}' not found in definitions\n",
+                node.id, parent
+            ));
+        }
+    }
+
+    if !problems.is_empty() {
+        let mut error_msg = format!(
+            "Graph definition violation: {} problem(s) found\n",
+            problems.len()
+        );
+        for problem in problems.iter().take(5) {
+            error_msg.push_str(problem);
+        }
+        if problems.len() > 5 {
+            error_msg.push_str(&format!("  ... and {} more\n", problems.len() - 5));
+        }
+        return Err(anyhow!(error_msg));
+    }
+
+    Ok(())
+}
+
+/// Validate a YAML graph produced by YCG or written by hand/another tool
+///
+/// Deserializes `yaml` as a `YcgGraph` (flat edges) or, failing that, a
+/// `YcgGraphOptimized` (adjacency list), then runs [`validate_definitions`]
+/// and the matching referential integrity check. All problems are reported
+/// together, each with the line of the offending ID in `yaml`.
+///
+/// # Arguments
+/// * `yaml` - The YAML document to validate
+///
+/// # Returns
+/// * `Ok(())` if the graph conforms to the YCG model
+/// * `Err` listing parse errors or every integrity problem found
+pub fn validate_graph_yaml(yaml: &str) -> Result<()> {
+    let results = match serde_yaml::from_str::<YcgGraph>(yaml) {
+        Ok(graph) => vec![
+            validate_definitions(&graph.definitions),
+            validate_graph_integrity(&graph),
+        ],
+        Err(flat_err) => match serde_yaml::from_str::<YcgGraphOptimized>(yaml) {
+            Ok(graph) => vec![
+                validate_definitions(&graph.definitions),
+                validate_graph_integrity_optimized(&graph),
+            ],
+            Err(optimized_err) => match serde_yaml::from_str::<YcgGraphByType>(yaml) {
+                Ok(grouped) => {
+                    let graph = grouped.into_graph();
+                    vec![
+                        validate_definitions(&graph.definitions),
+                        validate_graph_integrity(&graph),
+                    ]
+                }
+                Err(_) => {
+                    return Err(anyhow!(
+                        "Not a YCG graph\n  - as flat graph: {}\n  - as compact graph: {}",
+                        flat_err,
+                        optimized_err
+                    ));
+                }
+            },
+        },
+    };
+
+    let errors: Vec<String> = results
+        .into_iter()
+        .filter_map(|result| result.err())
+        .map(|err| with_line_context(&err.to_string(), yaml))
+        .collect();
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(anyhow!(errors.join("")))
+    }
+}
+
+/// Appends `(line N)` to each `  - ...` detail line, locating the ID it is about
+fn with_line_context(message: &str, yaml: &str) -> String {
+    message
+        .lines()
+        .map(|line| {
+            let ids: Vec<&str> = line.split('\'').skip(1).step_by(2).collect();
+            // "source ID not found" is about the first ID; everything else about the last
+            let id = if line.contains("source ID") {
+                ids.first()
+            } else {
+                ids.last()
+            };
+            match id.and_then(|id| line_of(yaml, id)) {
+                Some(n) if line.starts_with("  - ") => format!("{} (line {})\n", line, n),
+                _ => format!("{}\n", line),
+            }
+        })
+        .collect()
+}
+
+/// 1-based line of the first whole-token occurrence of `id` in `yaml`
+fn line_of(yaml: &str, id: &str) -> Option<usize> {
+    let is_ident = |c: char| c.is_alphanumeric() || c == '_';
+    yaml.lines()
+        .position(|line| {
+            line.match_indices(id).any(|(start, _)| {
+                let before = line[..start].chars().next_back();
+                let after = line[start + id.len()..].chars().next();
+                !before.is_some_and(is_ident) && !after.is_some_and(is_ident)
+            })
+        })
+        .map(|idx| idx + 1)
+}
+
 /// Validate graph edge referential integrity for ad-hoc format
 ///
 /// Ensures all graph edges in the adjacency list reference valid symbol
@@ -546,15 +685,13 @@ mod tests {
             metadata: ProjectMetadata {
                 name: "test".to_string(),
                 version: "1.0".to_string(),
+                ..Default::default()
             },
             definitions: vec![SymbolNode {
                 id: "A_0001".to_string(),
                 name: "ClassA".to_string(),
                 kind: ScipSymbolKind::Class,
-                parent_id: None,
-                documentation: None,
-                signature: None,
-                logic: None,
+                ..Default::default()
             }],
             references: vec![],
         };
@@ -580,6 +717,7 @@ mod tests {
             metadata: ProjectMetadata {
                 name: "test".to_string(),
                 version: "1.0".to_string(),
+                ..Default::default()
             },
             definitions: vec![
                 "A_0001|ClassA|class".to_string(),
@@ -597,6 +735,7 @@ mod tests {
             metadata: ProjectMetadata {
                 name: "test".to_string(),
                 version: "1.0".to_string(),
+                ..Default::default()
             },
             definitions: vec![
                 "A_0001|ClassA|class".to_string(),
@@ -621,6 +760,7 @@ mod tests {
             metadata: ProjectMetadata {
                 name: "test".to_string(),
                 version: "1.0".to_string(),
+                ..Default::default()
             },
             definitions: vec![
                 r"A\|0001|Class\|A|class".to_string(), // Escaped pipes should not count
@@ -637,31 +777,27 @@ mod tests {
             metadata: ProjectMetadata {
                 name: "test".to_string(),
                 version: "1.0".to_string(),
+                ..Default::default()
             },
             definitions: vec![
                 SymbolNode {
                     id: "A_0001".to_string(),
                     name: "ClassA".to_string(),
                     kind: ScipSymbolKind::Class,
-                    parent_id: None,
-                    documentation: None,
-                    signature: None,
-                    logic: None,
+                    ..Default::default()
                 },
                 SymbolNode {
                     id: "B_0002".to_string(),
                     name: "methodB".to_string(),
                     kind: ScipSymbolKind::Method,
-                    parent_id: None,
-                    documentation: None,
-                    signature: None,
-                    logic: None,
+                    ..Default::default()
                 },
             ],
             references: vec![ReferenceEdge {
                 from: "B_0002".to_string(),
                 to: "A_0001".to_string(),
                 edge_type: EdgeType::Calls,
+                call_text: None,
             }],
         };
 
@@ -674,20 +810,19 @@ mod tests {
             metadata: ProjectMetadata {
                 name: "test".to_string(),
                 version: "1.0".to_string(),
+                ..Default::default()
             },
             definitions: vec![SymbolNode {
                 id: "A_0001".to_string(),
                 name: "ClassA".to_string(),
                 kind: ScipSymbolKind::Class,
-                parent_id: None,
-                documentation: None,
-                signature: None,
-                logic: None,
+                ..Default::default()
             }],
             references: vec![ReferenceEdge {
                 from: "INVALID_ID".to_string(),
                 to: "A_0001".to_string(),
                 edge_type: EdgeType::Calls,
+                call_text: None,
             }],
         };
 
@@ -707,20 +842,19 @@ mod tests {
             metadata: ProjectMetadata {
                 name: "test".to_string(),
                 version: "1.0".to_string(),
+                ..Default::default()
             },
             definitions: vec![SymbolNode {
                 id: "A_0001".to_string(),
                 name: "ClassA".to_string(),
                 kind: ScipSymbolKind::Class,
-                parent_id: None,
-                documentation: None,
-                signature: None,
-                logic: None,
+                ..Default::default()
             }],
             references: vec![ReferenceEdge {
                 from: "A_0001".to_string(),
                 to: "INVALID_TARGET".to_string(),
                 edge_type: EdgeType::Calls,
+                call_text: None,
             }],
         };
 
@@ -745,25 +879,20 @@ mod tests {
             metadata: ProjectMetadata {
                 name: "test".to_string(),
                 version: "1.0".to_string(),
+                ..Default::default()
             },
             definitions: vec![
                 SymbolNode {
                     id: "A_0001".to_string(),
                     name: "ClassA".to_string(),
                     kind: ScipSymbolKind::Class,
-                    parent_id: None,
-                    documentation: None,
-                    signature: None,
-                    logic: None,
+                    ..Default::default()
                 },
                 SymbolNode {
                     id: "B_0002".to_string(),
                     name: "methodB".to_string(),
                     kind: ScipSymbolKind::Method,
-                    parent_id: None,
-                    documentation: None,
-                    signature: None,
-                    logic: None,
+                    ..Default::default()
                 },
             ],
             adjacency,
@@ -783,15 +912,13 @@ mod tests {
             metadata: ProjectMetadata {
                 name: "test".to_string(),
                 version: "1.0".to_string(),
+                ..Default::default()
             },
             definitions: vec![SymbolNode {
                 id: "B_0002".to_string(),
                 name: "methodB".to_string(),
                 kind: ScipSymbolKind::Method,
-                parent_id: None,
-                documentation: None,
-                signature: None,
-                logic: None,
+                ..Default::default()
             }],
             adjacency,
         };
@@ -817,6 +944,7 @@ mod tests {
             metadata: ProjectMetadata {
                 name: "test".to_string(),
                 version: "1.0".to_string(),
+                ..Default::default()
             },
             definitions: vec![
                 "A_0001|ClassA|class".to_string(),
@@ -839,6 +967,7 @@ mod tests {
             metadata: ProjectMetadata {
                 name: "test".to_string(),
                 version: "1.0".to_string(),
+                ..Default::default()
             },
             definitions: vec!["B_0002|methodB|method".to_string()],
             adjacency,
@@ -874,6 +1003,7 @@ mod tests {
             metadata: ProjectMetadata {
                 name: "test".to_string(),
                 version: "1.0".to_string(),
+                ..Default::default()
             },
             definitions: vec!["A_0001|ClassA|class".to_string()],
             adjacency,
@@ -897,6 +1027,7 @@ mod tests {
             metadata: ProjectMetadata {
                 name: "test".to_string(),
                 version: "1.0".to_string(),
+                ..Default::default()
             },
             definitions: vec![
                 "A_0001|ClassA|class".to_string(),
@@ -915,6 +1046,7 @@ mod tests {
             metadata: ProjectMetadata {
                 name: "test".to_string(),
                 version: "1.0".to_string(),
+                ..Default::default()
             },
             definitions: vec![
                 "A_0001|ClassA|class".to_string(),
@@ -936,6 +1068,7 @@ mod tests {
             metadata: ProjectMetadata {
                 name: "test".to_string(),
                 version: "1.0".to_string(),
+                ..Default::default()
             },
             definitions: vec![
                 "A_0001|ClassA|class".to_string(),
@@ -954,6 +1087,7 @@ mod tests {
             metadata: ProjectMetadata {
                 name: "test".to_string(),
                 version: "1.0".to_string(),
+                ..Default::default()
             },
             definitions: vec![
                 "A_0001|ClassA|class".to_string(),
@@ -975,6 +1109,7 @@ mod tests {
             metadata: ProjectMetadata {
                 name: "test".to_string(),
                 version: "1.0".to_string(),
+                ..Default::default()
             },
             definitions: vec![
                 "A_0001|ClassA|class".to_string(),
@@ -993,6 +1128,7 @@ mod tests {
             metadata: ProjectMetadata {
                 name: "test".to_string(),
                 version: "1.0".to_string(),
+                ..Default::default()
             },
             definitions: vec![
                 "A_0001|ClassA|class".to_string(),
@@ -1011,6 +1147,7 @@ mod tests {
             metadata: ProjectMetadata {
                 name: "test".to_string(),
                 version: "1.0".to_string(),
+                ..Default::default()
             },
             definitions: vec![
                 "A_0001|ClassA|class".to_string(),
@@ -1032,6 +1169,7 @@ mod tests {
             metadata: ProjectMetadata {
                 name: "test".to_string(),
                 version: "1.0".to_string(),
+                ..Default::default()
             },
             definitions: vec![
                 "B_0002|methodB(param:str):bool|method|check(param);return(true)".to_string(), // Missing "logic:" prefix
@@ -1051,6 +1189,7 @@ mod tests {
             metadata: ProjectMetadata {
                 name: "test".to_string(),
                 version: "1.0".to_string(),
+                ..Default::default()
             },
             definitions: vec![
                 "B_0002|methodB|method|logic:check(x>0);action(save);get(data);match(x)?a:b;return(result)".to_string(),
@@ -1068,6 +1207,7 @@ mod tests {
             metadata: ProjectMetadata {
                 name: "test".to_string(),
                 version: "1.0".to_string(),
+                ..Default::default()
             },
             definitions: vec![
                 "B_0002|methodB|method|logic:check(x>0);invalid_keyword(data);return(result)"
@@ -1091,6 +1231,7 @@ mod tests {
             metadata: ProjectMetadata {
                 name: "test".to_string(),
                 version: "1.0".to_string(),
+                ..Default::default()
             },
             definitions: vec![
                 "B_0002|methodB|method|logic:check(x>0);action(save);get(data);match(x)?a:b;return(res...".to_string(),
@@ -1108,6 +1249,7 @@ mod tests {
             metadata: ProjectMetadata {
                 name: "test".to_string(),
                 version: "1.0".to_string(),
+                ..Default::default()
             },
             definitions: vec![
                 "A_0001|ClassA|class".to_string(),
@@ -1126,6 +1268,7 @@ mod tests {
             metadata: ProjectMetadata {
                 name: "test".to_string(),
                 version: "1.0".to_string(),
+                ..Default::default()
             },
             definitions: vec![
                 "A_0001|ClassA|class".to_string(),
@@ -1145,6 +1288,7 @@ mod tests {
             metadata: ProjectMetadata {
                 name: "test".to_string(),
                 version: "1.0".to_string(),
+                ..Default::default()
             },
             definitions: vec![
                 "A_0001|ClassA|class".to_string(),
@@ -1183,6 +1327,7 @@ mod tests {
             metadata: ProjectMetadata {
                 name: "test".to_string(),
                 version: "1.0".to_string(),
+                ..Default::default()
             },
             definitions: vec![
                 "B_0002|methodB|method|logic:check(x>0 && y<10);action(save);return(result)"
@@ -1202,6 +1347,7 @@ mod tests {
             metadata: ProjectMetadata {
                 name: "test".to_string(),
                 version: "1.0".to_string(),
+                ..Default::default()
             },
             definitions: vec!["B_0002|methodB|method|logic:check(x>0);;return(result)".to_string()],
             adjacency: BTreeMap::new(),
@@ -1210,4 +1356,74 @@ mod tests {
         let result = validate_adhoc_granularity(&graph, AdHocGranularity::InlineLogic);
         assert!(result.is_ok());
     }
+
+    const VALID_FLAT: &str = "_meta:
+  name: demo
+  version: 1.0.0
+_defs:
+- id: UserService_a1b2
+  n: UserService
+  t: class
+- id: findOne_c3d4
+  n: UserService.findOne
+  t: method
+  parent_id: UserService_a1b2
+graph:
+- from: findOne_c3d4
+  to: UserService_a1b2
+  type: references
+";
+
+    #[test]
+    fn test_validate_graph_yaml_accepts_flat_compact_and_by_type() {
+        assert!(validate_graph_yaml(VALID_FLAT).is_ok());
+
+        let compact = VALID_FLAT.replace(
+            "graph:\n- from: findOne_c3d4\n  to: UserService_a1b2\n  type: references\n",
+            "graph:\n  findOne_c3d4:\n    references:\n    - UserService_a1b2\n",
+        );
+        assert!(validate_graph_yaml(&compact).is_ok());
+
+        let by_type = VALID_FLAT.replace(
+            "graph:\n- from: findOne_c3d4\n  to: UserService_a1b2\n  type: references\n",
+            "graph:\n  references:\n  - from: findOne_c3d4\n    to: UserService_a1b2\n",
+        );
+        assert!(validate_graph_yaml(&by_type).is_ok());
+        let dangling = by_type.replace("to: UserService_a1b2", "to: Missing_ffff");
+        let err = validate_graph_yaml(&dangling).unwrap_err().to_string();
+        assert!(err.contains("target ID not found"), "{}", err);
+    }
+
+    #[test]
+    fn test_validate_graph_yaml_reports_dangling_edge_with_line() {
+        let yaml = VALID_FLAT.replace("  to: UserService_a1b2", "  to: Missing_ffff");
+        let err = validate_graph_yaml(&yaml).unwrap_err().to_string();
+        assert!(err.contains("target ID not found"), "{}", err);
+        assert!(err.contains("(line 14)"), "{}", err);
+    }
+
+    #[test]
+    fn test_validate_graph_yaml_reports_duplicate_id_and_unknown_parent() {
+        let yaml = VALID_FLAT
+            .replace("- id: findOne_c3d4", "- id: UserService_a1b2")
+            .replace("parent_id: UserService_a1b2", "parent_id: Gone_0000");
+        let err = validate_graph_yaml(&yaml).unwrap_err().to_string();
+        assert!(err.contains("Duplicate ID 'UserService_a1b2'"), "{}", err);
+        assert!(
+            err.contains("parent 'Gone_0000' not found in definitions (line 11)"),
+            "{}",
+            err
+        );
+        // The edge source no longer exists either; all problems are reported together
+        assert!(err.contains("source ID not found"), "{}", err);
+    }
+
+    #[test]
+    fn test_validate_graph_yaml_rejects_non_graph_yaml() {
+        let err = validate_graph_yaml("name: not a graph\n")
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("Not a YCG graph"), "{}", err);
+        assert!(err.contains("missing field `_meta`"), "{}", err);
+    }
 }
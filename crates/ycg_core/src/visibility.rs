@@ -0,0 +1,118 @@
+// crates/ycg_core/src/visibility.rs
+//! Member visibility detected from a definition's name and signature.
+//!
+//! - TypeScript: `private` / `protected` modifiers and `#private` names
+//! - Rust: `pub` before `fn`; restricted forms (`pub(crate)`) count as private
+//!
+//! Without a signature (e.g. `--lod 0`) a member is assumed public.
+
+use crate::model::SymbolNode;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Visibility {
+    Public,
+    Protected,
+    Private,
+}
+
+/// Visibility of `node`, see the module docs for the rules
+pub fn detect(node: &SymbolNode) -> Visibility {
+    // `Foo##secret`: the TS `#secret` private name
+    if node.name.contains("##") {
+        return Visibility::Private;
+    }
+
+    let Some(signature) = &node.signature else {
+        return Visibility::Public;
+    };
+
+    // Modifiers come before the name; decorators (`@Get()`) are skipped
+    let modifiers = signature
+        .split_whitespace()
+        .filter(|token| !token.starts_with('@'))
+        .take_while(|token| !token.contains('(') || token.starts_with("pub("));
+
+    let mut rust_fn = false;
+    let mut rust_pub = false;
+    for token in modifiers {
+        match token {
+            "private" => return Visibility::Private,
+            "protected" => return Visibility::Protected,
+            "pub" => rust_pub = true,
+            "fn" => rust_fn = true,
+            _ => {}
+        }
+        if rust_fn {
+            break;
+        }
+    }
+
+    if rust_fn && !rust_pub {
+        Visibility::Private
+    } else {
+        Visibility::Public
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::ScipSymbolKind;
+
+    fn method(name: &str, signature: Option<&str>) -> SymbolNode {
+        SymbolNode {
+            id: format!("{}_0001", name),
+            name: name.to_string(),
+            kind: ScipSymbolKind::Method,
+            signature: signature.map(|s| s.to_string()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_typescript_modifiers() {
+        let cases = [
+            ("findOne(id: number): User", Visibility::Public),
+            ("public async findOne(id: number)", Visibility::Public),
+            (
+                "@Get(':id') findOne(@Param('id') id: string)",
+                Visibility::Public,
+            ),
+            ("private hash(password: string)", Visibility::Private),
+            ("protected static validate(dto: Dto)", Visibility::Protected),
+        ];
+        for (sig, expected) in cases {
+            assert_eq!(
+                detect(&method("UserService#m", Some(sig))),
+                expected,
+                "{}",
+                sig
+            );
+        }
+        assert_eq!(
+            detect(&method("UserService##secret", None)),
+            Visibility::Private
+        );
+    }
+
+    #[test]
+    fn test_rust_pub() {
+        let cases = [
+            ("pub fn parse(input: &str) -> usize", Visibility::Public),
+            ("pub async fn load(&self)", Visibility::Public),
+            ("fn helper(&self)", Visibility::Private),
+            ("pub(crate) fn internal(&self)", Visibility::Private),
+        ];
+        for (sig, expected) in cases {
+            assert_eq!(detect(&method("Parser::m", Some(sig))), expected, "{}", sig);
+        }
+    }
+
+    #[test]
+    fn test_missing_signature_is_public() {
+        assert_eq!(
+            detect(&method("UserService#find", None)),
+            Visibility::Public
+        );
+    }
+}
@@ -26,9 +26,8 @@ fn test_adhoc_variable_with_none_signature() {
         name: "userId0:".to_string(), // SCIP name includes colon for destructured vars
         kind: ScipSymbolKind::Variable,
         parent_id: Some("UsersController_findOne_13b7".to_string()),
-        documentation: None,
         signature: None, // Signature was rejected by validation
-        logic: None,
+        ..Default::default()
     };
 
     // Create a method node for comparison
@@ -37,11 +36,10 @@ fn test_adhoc_variable_with_none_signature() {
         name: "findOne".to_string(),
         kind: ScipSymbolKind::Method,
         parent_id: Some("UsersController_4702".to_string()),
-        documentation: None,
         signature: Some(
             "findOne(@Param('id', ParseIntPipe) id: number): Promise<UserDto>".to_string(),
         ),
-        logic: None,
+        ..Default::default()
     };
 
     // Create a graph with both nodes
@@ -49,6 +47,7 @@ fn test_adhoc_variable_with_none_signature() {
         metadata: ProjectMetadata {
             name: "test".to_string(),
             version: "1.0.0".to_string(),
+            ..Default::default()
         },
         definitions: vec![method_node, variable_node],
         references: vec![],
@@ -108,15 +107,15 @@ fn test_adhoc_variable_with_valid_signature() {
         name: "userId".to_string(),
         kind: ScipSymbolKind::Variable,
         parent_id: Some("SomeClass_xyz".to_string()),
-        documentation: None,
         signature: Some("userId: number".to_string()), // Valid simple type
-        logic: None,
+        ..Default::default()
     };
 
     let graph = YcgGraph {
         metadata: ProjectMetadata {
             name: "test".to_string(),
             version: "1.0.0".to_string(),
+            ..Default::default()
         },
         definitions: vec![variable_node],
         references: vec![],
@@ -152,11 +151,10 @@ fn test_adhoc_multiple_variables_no_inheritance() {
         name: "login".to_string(),
         kind: ScipSymbolKind::Method,
         parent_id: Some("AuthController_6ba5".to_string()),
-        documentation: None,
         signature: Some(
             "async login(loginDto: LoginDto): Promise<{ access_token: string }>".to_string(),
         ),
-        logic: None,
+        ..Default::default()
     };
 
     // Create multiple variable nodes with None signatures (rejected by validation)
@@ -165,9 +163,8 @@ fn test_adhoc_multiple_variables_no_inheritance() {
         name: "access_token0:".to_string(),
         kind: ScipSymbolKind::Variable,
         parent_id: Some("AuthController_login_8ec4".to_string()),
-        documentation: None,
         signature: None, // Rejected by validation
-        logic: None,
+        ..Default::default()
     };
 
     let var2 = SymbolNode {
@@ -175,9 +172,8 @@ fn test_adhoc_multiple_variables_no_inheritance() {
         name: "user0:".to_string(),
         kind: ScipSymbolKind::Variable,
         parent_id: Some("AuthController_login_8ec4".to_string()),
-        documentation: None,
         signature: None, // Rejected by validation
-        logic: None,
+        ..Default::default()
     };
 
     let var3 = SymbolNode {
@@ -185,15 +181,15 @@ fn test_adhoc_multiple_variables_no_inheritance() {
         name: "message0:".to_string(),
         kind: ScipSymbolKind::Variable,
         parent_id: Some("AuthController_login_8ec4".to_string()),
-        documentation: None,
         signature: None, // Rejected by validation
-        logic: None,
+        ..Default::default()
     };
 
     let graph = YcgGraph {
         metadata: ProjectMetadata {
             name: "test".to_string(),
             version: "1.0.0".to_string(),
+            ..Default::default()
         },
         definitions: vec![method_node, var1, var2, var3],
         references: vec![],
@@ -265,15 +261,15 @@ fn test_adhoc_level_0_variable_with_none_signature() {
         name: "userId0:".to_string(),
         kind: ScipSymbolKind::Variable,
         parent_id: Some("method_id".to_string()),
-        documentation: None,
         signature: None, // Rejected by validation
-        logic: None,
+        ..Default::default()
     };
 
     let graph = YcgGraph {
         metadata: ProjectMetadata {
             name: "test".to_string(),
             version: "1.0.0".to_string(),
+            ..Default::default()
         },
         definitions: vec![variable_node],
         references: vec![],
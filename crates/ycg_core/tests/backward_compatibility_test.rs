@@ -39,6 +39,7 @@ fn test_simple_ts_medium_backward_compatibility() -> Result<()> {
             use_gitignore: false,
         },
         adhoc_granularity: AdHocGranularity::default(), // Default: Level 0
+        ..Default::default()
     };
 
     let current_output = run_scip_conversion(&scip_path, config)?;
@@ -127,6 +128,7 @@ fn test_all_baselines_backward_compatibility() -> Result<()> {
                 use_gitignore: false,
             },
             adhoc_granularity: AdHocGranularity::default(),
+            ..Default::default()
         };
 
         let current_output = match run_scip_conversion(&scip_path, config) {
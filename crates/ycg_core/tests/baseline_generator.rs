@@ -163,6 +163,7 @@ fn generate_baseline(test_case: &BaselineTestCase, baseline_dir: &Path) -> Resul
             use_gitignore: false, // Default: don't use gitignore for baseline
         },
         adhoc_granularity: test_case.granularity.unwrap_or_default(),
+        ..Default::default()
     };
 
     // Generate output
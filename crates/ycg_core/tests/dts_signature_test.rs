@@ -0,0 +1,64 @@
+// Integration tests for signatures extracted from TypeScript declaration files (.d.ts)
+
+use std::path::PathBuf;
+use ycg_core::enricher::TreeSitterEnricher;
+
+fn fixture() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/dts/http-client.d.ts")
+}
+
+/// Signature of the declaration starting at a zero-based line
+fn signature_at(line: usize) -> Option<String> {
+    TreeSitterEnricher::new()
+        .enrich(&fixture(), line)
+        .and_then(|result| result.signature)
+}
+
+#[test]
+fn test_dts_method_signature_is_atomic() {
+    assert_eq!(
+        signature_at(14).as_deref(),
+        Some("get<T>(path: string, options?: RequestOptions): Promise<T>")
+    );
+    assert_eq!(
+        signature_at(15).as_deref(),
+        Some("post<T, B = unknown>(path: string, body: B): Promise<T>")
+    );
+}
+
+#[test]
+fn test_dts_property_signatures() {
+    assert_eq!(
+        signature_at(2).as_deref(),
+        Some("headers?: Record<string, string>")
+    );
+    assert_eq!(signature_at(3).as_deref(), Some("timeout: number"));
+    assert_eq!(
+        signature_at(11).as_deref(),
+        Some("readonly baseUrl: string")
+    );
+}
+
+#[test]
+fn test_dts_multiline_member_is_collapsed() {
+    assert_eq!(
+        signature_at(4).as_deref(),
+        Some("retry(attempt: number, error: Error): boolean")
+    );
+}
+
+#[test]
+fn test_dts_containers_stop_at_body() {
+    assert_eq!(signature_at(1).as_deref(), Some("interface RequestOptions"));
+    assert_eq!(signature_at(10).as_deref(), Some("class HttpClient"));
+    assert_eq!(
+        signature_at(18).as_deref(),
+        Some("function createClient(baseUrl: string): HttpClient")
+    );
+}
+
+#[test]
+fn test_dts_member_keeps_documentation() {
+    let result = TreeSitterEnricher::new().enrich(&fixture(), 14).unwrap();
+    assert_eq!(result.documentation.as_deref(), Some("Sends a GET request"));
+}
@@ -0,0 +1,19 @@
+/** Options accepted by every request */
+export interface RequestOptions {
+    headers?: Record<string, string>;
+    timeout: number;
+    retry(attempt: number,
+          error: Error): boolean;
+}
+
+export type Method = "GET" | "POST";
+
+export declare class HttpClient {
+    readonly baseUrl: string;
+    constructor(baseUrl: string, options?: RequestOptions);
+    /** Sends a GET request */
+    get<T>(path: string, options?: RequestOptions): Promise<T>;
+    post<T, B = unknown>(path: string, body: B): Promise<T>;
+}
+
+export declare function createClient(baseUrl: string): HttpClient;
@@ -45,6 +45,7 @@ fn test_adhoc_default_matches_baseline_simple_ts() -> Result<()> {
             use_gitignore: false,
         },
         adhoc_granularity: AdHocGranularity::Default, // Explicit Level 0
+        ..Default::default()
     };
 
     let current_output = run_scip_conversion(&scip_path, config)?;
@@ -105,6 +106,7 @@ fn test_adhoc_default_matches_baseline_nestjs() -> Result<()> {
             use_gitignore: false,
         },
         adhoc_granularity: AdHocGranularity::Default,
+        ..Default::default()
     };
 
     let current_output = run_scip_conversion(&scip_path, config)?;
@@ -294,6 +296,7 @@ fn test_existing_adhoc_format_works() -> Result<()> {
             use_gitignore: false,
         },
         adhoc_granularity: AdHocGranularity::default(),
+        ..Default::default()
     };
 
     // Should not panic or error
@@ -342,6 +345,7 @@ fn test_all_granularity_levels_produce_valid_output() -> Result<()> {
                 use_gitignore: false,
             },
             adhoc_granularity: level,
+            ..Default::default()
         };
 
         // Should not panic or error
@@ -22,11 +22,13 @@ fn test_method_has_logic_metadata() {
         name: "findOne".to_string(),
         kind: ScipSymbolKind::Method,
         parent_id: Some("UsersController_4702".to_string()),
-        documentation: None,
         signature: Some("async findOne(id: number): Promise<UserDto>".to_string()),
         logic: Some(LogicMetadata {
             preconditions: vec!["must avoid: user".to_string()],
+            steps: Vec::new(),
+            delegates_to: None,
         }),
+        ..Default::default()
     };
 
     // Verify the method has logic metadata
@@ -54,12 +56,13 @@ fn test_function_has_logic_metadata() {
         id: "validateInput_abc123".to_string(),
         name: "validateInput".to_string(),
         kind: ScipSymbolKind::Function,
-        parent_id: None,
-        documentation: None,
         signature: Some("function validateInput(data: string): boolean".to_string()),
         logic: Some(LogicMetadata {
             preconditions: vec!["must check: data.length > 0".to_string()],
+            steps: Vec::new(),
+            delegates_to: None,
         }),
+        ..Default::default()
     };
 
     // Verify the function has logic metadata
@@ -88,9 +91,8 @@ fn test_variable_has_no_logic_metadata() {
         name: "userId0:".to_string(),
         kind: ScipSymbolKind::Variable,
         parent_id: Some("UsersController_findOne_13b7".to_string()),
-        documentation: None,
-        signature: None,
         logic: None, // Variables should never have logic
+        ..Default::default()
     };
 
     // Verify the variable does NOT have logic metadata
@@ -113,9 +115,8 @@ fn test_class_has_no_logic_metadata() {
         name: "UsersController".to_string(),
         kind: ScipSymbolKind::Class,
         parent_id: Some("file_abc".to_string()),
-        documentation: None,
-        signature: None,
         logic: None, // Classes should never have logic
+        ..Default::default()
     };
 
     // Verify the class does NOT have logic metadata
@@ -138,9 +139,8 @@ fn test_interface_has_no_logic_metadata() {
         name: "UserDto".to_string(),
         kind: ScipSymbolKind::Interface,
         parent_id: Some("file_abc".to_string()),
-        documentation: None,
-        signature: None,
         logic: None, // Interfaces should never have logic
+        ..Default::default()
     };
 
     // Verify the interface does NOT have logic metadata
@@ -163,6 +163,7 @@ fn test_graph_logic_metadata_restriction() {
         metadata: ProjectMetadata {
             name: "test".to_string(),
             version: "1.0.0".to_string(),
+            ..Default::default()
         },
         definitions: vec![
             // Class - no logic
@@ -171,9 +172,7 @@ fn test_graph_logic_metadata_restriction() {
                 name: "UsersController".to_string(),
                 kind: ScipSymbolKind::Class,
                 parent_id: Some("file_abc".to_string()),
-                documentation: None,
-                signature: None,
-                logic: None,
+                ..Default::default()
             },
             // Method - has logic
             SymbolNode {
@@ -181,11 +180,13 @@ fn test_graph_logic_metadata_restriction() {
                 name: "findOne".to_string(),
                 kind: ScipSymbolKind::Method,
                 parent_id: Some("UsersController_4702".to_string()),
-                documentation: None,
                 signature: Some("async findOne(id: number): Promise<UserDto>".to_string()),
                 logic: Some(LogicMetadata {
                     preconditions: vec!["must avoid: user".to_string()],
+                    steps: Vec::new(),
+                    delegates_to: None,
                 }),
+                ..Default::default()
             },
             // Variable - no logic
             SymbolNode {
@@ -193,9 +194,7 @@ fn test_graph_logic_metadata_restriction() {
                 name: "userId0:".to_string(),
                 kind: ScipSymbolKind::Variable,
                 parent_id: Some("UsersController_findOne_13b7".to_string()),
-                documentation: None,
-                signature: None,
-                logic: None,
+                ..Default::default()
             },
             // Variable - no logic
             SymbolNode {
@@ -203,21 +202,20 @@ fn test_graph_logic_metadata_restriction() {
                 name: "username0:".to_string(),
                 kind: ScipSymbolKind::Variable,
                 parent_id: Some("UsersController_findOne_13b7".to_string()),
-                documentation: None,
-                signature: None,
-                logic: None,
+                ..Default::default()
             },
             // Function - has logic
             SymbolNode {
                 id: "validateInput_abc123".to_string(),
                 name: "validateInput".to_string(),
                 kind: ScipSymbolKind::Function,
-                parent_id: None,
-                documentation: None,
                 signature: Some("function validateInput(data: string): boolean".to_string()),
                 logic: Some(LogicMetadata {
                     preconditions: vec!["must check: data.length > 0".to_string()],
+                    steps: Vec::new(),
+                    delegates_to: None,
                 }),
+                ..Default::default()
             },
         ],
         references: vec![],
@@ -272,14 +270,16 @@ fn test_variables_in_method_with_guard_clauses() {
         name: "findOne".to_string(),
         kind: ScipSymbolKind::Method,
         parent_id: Some("UsersController_4702".to_string()),
-        documentation: None,
         signature: Some("async findOne(id: number): Promise<UserDto>".to_string()),
         logic: Some(LogicMetadata {
             preconditions: vec![
                 "must avoid: user".to_string(),
                 "must check: id > 0".to_string(),
             ],
+            steps: Vec::new(),
+            delegates_to: None,
         }),
+        ..Default::default()
     };
 
     // Create variables within the method
@@ -288,9 +288,8 @@ fn test_variables_in_method_with_guard_clauses() {
         name: "userId0:".to_string(),
         kind: ScipSymbolKind::Variable,
         parent_id: Some("UsersController_findOne_13b7".to_string()),
-        documentation: None,
-        signature: None,
         logic: None, // Should NOT inherit method's logic
+        ..Default::default()
     };
 
     let var2 = SymbolNode {
@@ -298,9 +297,8 @@ fn test_variables_in_method_with_guard_clauses() {
         name: "username0:".to_string(),
         kind: ScipSymbolKind::Variable,
         parent_id: Some("UsersController_findOne_13b7".to_string()),
-        documentation: None,
-        signature: None,
         logic: None, // Should NOT inherit method's logic
+        ..Default::default()
     };
 
     // Verify method has logic
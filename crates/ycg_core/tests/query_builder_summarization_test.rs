@@ -1,7 +1,7 @@
 // Integration test for QueryBuilder signature summarization
 // Validates Requirements 5.1, 5.2, 5.3, 5.4, 5.6
 
-use ycg_core::model::{ScipSymbolKind, SymbolNode};
+use ycg_core::model::{ScipSymbolKind, SignatureStyle, SymbolNode};
 use ycg_core::signature_extractor::SignatureExtractor;
 
 #[test]
@@ -13,10 +13,8 @@ fn test_query_builder_summarization_integration() {
         id: "test_qb_var".to_string(),
         name: "activeUsers".to_string(),
         kind: ScipSymbolKind::Variable,
-        parent_id: None,
-        documentation: None,
         signature: Some(long_qb_signature.to_string()),
-        logic: None,
+        ..Default::default()
     };
 
     // Extract signature
@@ -52,10 +50,8 @@ fn test_query_builder_get_one_summarization() {
         id: "test_qb_single".to_string(),
         name: "user".to_string(),
         kind: ScipSymbolKind::Variable,
-        parent_id: None,
-        documentation: None,
         signature: Some(qb_signature.to_string()),
-        logic: None,
+        ..Default::default()
     };
 
     let result = SignatureExtractor::extract_signature(&node);
@@ -76,10 +72,8 @@ fn test_query_builder_with_joins() {
         id: "test_qb_joins".to_string(),
         name: "usersWithProfiles".to_string(),
         kind: ScipSymbolKind::Variable,
-        parent_id: None,
-        documentation: None,
         signature: Some(qb_signature.to_string()),
-        logic: None,
+        ..Default::default()
     };
 
     let result = SignatureExtractor::extract_signature(&node);
@@ -101,10 +95,8 @@ fn test_query_builder_fallback_to_query_result() {
         id: "test_qb_fallback".to_string(),
         name: "results".to_string(),
         kind: ScipSymbolKind::Variable,
-        parent_id: None,
-        documentation: None,
         signature: Some(qb_signature.to_string()),
-        logic: None,
+        ..Default::default()
     };
 
     let result = SignatureExtractor::extract_signature(&node);
@@ -126,10 +118,8 @@ fn test_non_query_builder_not_summarized() {
         id: "test_regular".to_string(),
         name: "fetchUserData".to_string(),
         kind: ScipSymbolKind::Method,
-        parent_id: None,
-        documentation: None,
         signature: Some(regular_signature.to_string()),
-        logic: None,
+        ..Default::default()
     };
 
     let result = SignatureExtractor::extract_signature(&node);
@@ -152,10 +142,8 @@ fn test_short_query_builder_not_summarized() {
         id: "test_short_qb".to_string(),
         name: "users".to_string(),
         kind: ScipSymbolKind::Variable,
-        parent_id: None,
-        documentation: None,
         signature: Some(short_qb.to_string()),
-        logic: None,
+        ..Default::default()
     };
 
     let result = SignatureExtractor::extract_signature(&node);
@@ -176,10 +164,8 @@ fn test_query_builder_with_complex_entity_name() {
         id: "test_complex_entity".to_string(),
         name: "entities".to_string(),
         kind: ScipSymbolKind::Variable,
-        parent_id: None,
-        documentation: None,
         signature: Some(qb_signature.to_string()),
-        logic: None,
+        ..Default::default()
     };
 
     let result = SignatureExtractor::extract_signature(&node);
@@ -190,3 +176,101 @@ fn test_query_builder_with_complex_entity_name() {
     // Should extract the full entity name
     assert_eq!(signature, "entities: UserProfileEntity[]");
 }
+
+fn variable(name: &str, signature: &str) -> SymbolNode {
+    SymbolNode {
+        id: format!("test_{}", name),
+        name: name.to_string(),
+        kind: ScipSymbolKind::Variable,
+        signature: Some(signature.to_string()),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_fluent_chains_summarized_above_threshold() {
+    let long_fetch = variable(
+        "profile",
+        "const profile = await fetch(`${API_URL}/users/${id}/profile`, { headers, credentials: 'include' }).then(check).then((res) => res.json())",
+    );
+    // Awaited: the value is the body, not the promise
+    assert_eq!(
+        SignatureExtractor::extract_signature(&long_fetch).as_deref(),
+        Some("profile: Json")
+    );
+
+    let short_fetch = variable(
+        "res",
+        "const res = fetch(url).then(check).then((r) => r.text())",
+    );
+    let intact = SignatureExtractor::extract_signature(&short_fetch).unwrap();
+    assert!(!intact.starts_with("res: Promise"), "got {}", intact);
+
+    // The threshold is configurable: the short chain is summarized below it
+    let with_threshold = |node: &SymbolNode, threshold: usize| {
+        SignatureExtractor::extract_signature_with_chain_threshold(
+            node,
+            SignatureStyle::Named,
+            false,
+            threshold,
+        )
+        .unwrap()
+    };
+    assert_eq!(with_threshold(&short_fetch, 20), "res: Promise<string>");
+    let above = with_threshold(&long_fetch, 500);
+    assert!(!above.starts_with("profile: Json"), "got {}", above);
+
+    let axios = variable(
+        "users",
+        "const users = await axios.get<User[]>('/api/users', { params: { active: true, page, limit } }).then((res) => res.data)",
+    );
+    assert_eq!(
+        SignatureExtractor::extract_signature(&axios).as_deref(),
+        Some("users: User[]")
+    );
+
+    let iterator = variable(
+        "names",
+        "let names = users.iter().filter(|user| user.active && !user.deleted).map(|user| user.name.to_uppercase()).collect::<Vec<String>>()",
+    );
+    assert_eq!(
+        SignatureExtractor::extract_signature(&iterator).as_deref(),
+        Some("names: Vec<String>")
+    );
+}
+
+#[test]
+fn test_fluent_chains_ending_in_opaque_callbacks_not_summarized() {
+    let chains = [
+        // `.then` after `.json()` reshapes the body
+        (
+            "profile",
+            "const profile = await fetch(`${API_URL}/users/${id}/profile`, { headers }).then((res) => res.json()).then((body) => body.data)",
+        ),
+        ("res", "const res = fetch(url).then(check).then(parse)"),
+        (
+            "users",
+            "const users = await axios.get<User[]>('/api/users', { params: { active: true } }).then((res) => res.data).catch(handleError)",
+        ),
+        (
+            "upper",
+            "let upper = users.iter().filter(|user| user.active && !user.deleted).map(|user| user.name.to_uppercase())",
+        ),
+    ];
+    for (name, signature) in chains {
+        let summary = SignatureExtractor::extract_signature_with_chain_threshold(
+            &variable(name, signature),
+            SignatureStyle::Named,
+            false,
+            20,
+        )
+        .unwrap();
+        for result_type in ["Promise<", "Json", "User[]", "Iterator"] {
+            assert!(
+                !summary.starts_with(&format!("{}: {}", name, result_type)),
+                "got {}",
+                summary
+            );
+        }
+    }
+}
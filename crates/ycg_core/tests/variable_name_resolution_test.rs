@@ -2,7 +2,7 @@
 // Validates Requirements 6.1, 6.2, 6.3, 6.4, 6.5
 
 use std::path::PathBuf;
-use ycg_core::enricher::TreeSitterEnricher;
+use ycg_core::enricher::{ColumnEncoding, TreeSitterEnricher};
 
 #[test]
 fn test_resolve_variable_name_typescript() {
@@ -73,6 +73,24 @@ fn test_resolve_variable_name_unsupported_extension() {
     assert!(result.is_none());
 }
 
+#[test]
+fn test_resolve_variable_name_after_non_ascii_prefix() {
+    let mut enricher = TreeSitterEnricher::new();
+
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let test_file = temp_dir.path().join("labels.ts");
+    std::fs::write(&test_file, "const s = \"🚀 café\"; const total = 1;\n").unwrap();
+
+    // `total` starts at UTF-16 unit 27 (scip-typescript), but byte 30 (tree-sitter)
+    let result =
+        enricher.resolve_variable_name_with_encoding(&test_file, 0, 27, ColumnEncoding::Utf16);
+    assert_eq!(result.as_deref(), Some("total"));
+
+    // Byte columns still resolve directly
+    let result = enricher.resolve_variable_name(&test_file, 0, 30);
+    assert_eq!(result.as_deref(), Some("total"));
+}
+
 // Note: is_generic_name is a private function in lib.rs
 // We test it indirectly through integration tests
 // Here are the expected behaviors: